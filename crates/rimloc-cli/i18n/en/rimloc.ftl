@@ -38,12 +38,17 @@ validate-clean = All clean, no errors found
 
 export-po-saved = PO saved to { $path }
 export-po-tm-coverage = TM prefill: { $filled } / { $total } ({ $pct }%)
+export-po-split-file = { $path } ({ $n } entries)
+export-po-split-saved = { $count } PO files saved to { $path }
+export-po-compact = Compact export: { $shared } keys share the text of another entry ({ $total } entries)
 export-po-missing-definj-suggested = English DefInjected files were not found; apply { $path } to bootstrap them (copy to Languages/{ $lang_dir }/DefInjected)
 export-po-missing-definj-learned = English DefInjected files were not found; use learned defs from { $path } to create them
 export-po-missing-definj-generate = English DefInjected folder is empty; run `rimloc-cli learn-defs --lang-dir { $lang_dir }` or apply suggested templates before exporting
 
 import-dry-run-header = DRY-RUN plan:
 import-total-keys = TOTAL: { $n } key(s)
+import-fuzzy-summary = Fuzzy entries: skipped={ $skipped }, imported={ $imported }
+import-duplicate-key = Key { $key } is given more than once for { $path }; the last value is used
 import-only-empty = PO contains only empty strings. Add --keep-empty if you want to import placeholders.
 import-nothing-to-do = Nothing to import (all strings are empty; add --keep-empty if placeholders are needed).
 import-done = Import completed to { $root }
@@ -73,6 +78,7 @@ validate-po-got = got: { $ph }
 validate-po-total-mismatches = Total mismatches: { $count }
 validate-po-report-line = { $ctxt } → { $reference }
 validate-po-summary = Total mismatches: { $count }
+validate-po-stats = Entries: { $total }, untranslated: { $untranslated }, fuzzy: { $fuzzy }
 learn-defs-summary = Learned from Defs: candidates={ $candidates }, accepted={ $accepted } → missing_keys.json={ $missing }, suggested.xml={ $suggested }
 
 # hints
@@ -95,7 +101,10 @@ schema-dumped = Schemas saved to { $path }
 # === warnings / errors ===
 ui-lang-unsupported = UI language code is not supported
 err-placeholder-mismatches = placeholder mismatches detected
-validate-po-error = placeholder mismatches detected
+validate-po-error = PO validation issues detected
+validate-strict-error = Validation found error-level problems
+po-other-mod = PO was exported from mod { $po }, but the target mod is { $expected }
+po-other-game-version = PO was exported for game version { $po }, but { $expected } is selected
 
 # === validation kinds (short labels used in reports) ===
 kind-duplicate = duplicate
@@ -148,26 +157,29 @@ help-validate-game-version = Game version folder to use (e.g., 1.6 or v1.6); def
 help-validate-include-all = Include all version subfolders (disable auto-pick of latest)
 
 # validate-po
-help-validatepo-about = Validate .po file placeholder consistency (msgid vs msgstr)
+help-validatepo-about = Lint a .po file: placeholders, duplicates, Language header, whitespace, XML safety
 help-validatepo-po = Path to .po file to validate
-help-validatepo-strict = Strict mode: return error (exit code 1) if mismatches are found
+help-validatepo-strict = Strict mode: return error (exit code 1) if any issue is found
 help-validatepo-format = Output format: "text" (default) or "json"
 
 # export-po
 help-exportpo-about = Export extracted strings into a single .po file
 help-exportpo-root = Path to RimWorld mod root containing extracted strings
-help-exportpo-out-po = Output .po file path
+help-exportpo-out-po = Output .po file path (output directory with --split)
 help-exportpo-lang = Target translation language code (e.g., ru, ja, de)
 help-exportpo-pot = Write POT template (empty Language header) instead of a localized PO
 help-exportpo-source-lang = Source language ISO code to export from (e.g., en, ru, ja)
 help-exportpo-source-lang-dir = Source language folder name (e.g., English). Overrides --source-lang
-help-exportpo-tm-root = Path(s) to translation memory roots (repeatable). Each can be Languages/<lang> or a mod root. Prefills msgstr and marks entries as fuzzy
+help-exportpo-tm-root = Path(s) to translation memory sources (repeatable): Languages/<lang>, a mod root, a .tmx file or a TM built by tm-build (.json). Matches by key and by similar source text; prefills msgstr, marks entries as fuzzy and lists candidates as '#. TM <score>%' comments
+help-exportpo-tm-min-score = Minimum similarity (0..1) for fuzzy TM suggestions (default 0.75)
 help-exportpo-game-version = Game version folder to scan (e.g., 1.6 or v1.6); defaults to latest under root
 help-exportpo-include-all = Include all version subfolders (may create duplicates)
+help-exportpo-compact = Emit one entry per unique source text; the other keys are listed in references and get the same translation on import
+help-exportpo-split = Write several .po files into the --out-po directory: file (one per source file), def-type (one per Keyed file and per DefInjected DefType) or version (one per version folder)
 
 # import-po
 help-importpo-about = Import .po  -  either into a single XML, or spread across existing mod structure
-help-importpo-po = Path to .po file to import
+help-importpo-po = Path to .po file to import, or a directory of .po files (e.g., a split export)
 help-importpo-out-xml = Output XML file path (single-file mode)
 help-importpo-mod-root = Mod root to update with imported strings (structure mode)
 help-importpo-lang = Target language code for import (e.g., ru)
@@ -182,10 +194,11 @@ help-importpo-report = Print a summary of created/updated/skipped files and tota
 help-importpo-incremental = Skip writing files whose content would be identical
 import-report-summary = Import summary: created={ $created }, updated={ $updated }, skipped={ $skipped }, keys={ $keys }
 help-importpo-only-diff = Write only changed/new keys per file (skip unchanged keys)
+help-importpo-fuzzy = How to treat #, fuzzy entries: skip (default), include, or mark (include with an XML comment)
 
 # build-mod
 help-buildmod-about = Build a standalone translation mod from a .po file
-help-buildmod-po = Path to .po file to build from
+help-buildmod-po = Path to .po file to build from, or a directory of .po files
 help-buildmod-out-mod = Output mod folder path
 help-buildmod-lang = Language code of the translation
 help-buildmod-from-root = Build from existing Languages/<lang> under this root instead of a .po
@@ -196,6 +209,7 @@ help-buildmod-rw-version = Target RimWorld version
 help-buildmod-lang-dir = Language folder name inside the mod (optional)
 help-buildmod-dry-run = Do not write files; only print the build plan
 help-buildmod-dedupe = Remove duplicate keys within one XML (last wins)
+help-buildmod-fuzzy = How to treat #, fuzzy entries: skip (default), include, or mark (include with an XML comment)
 
 # diff-xml
 help-diffxml-about = Diff source vs translation presence and detect changed source strings using a baseline PO
@@ -286,3 +300,124 @@ help-morph-cache-size = Provider cache size (default: 1024)
 help-morph-pym-url = Pymorphy2 service URL (overrides PYMORPHY_URL)
 morph-summary = Morph generated { $processed } entries for { $lang }
 morph-provider-morpher-stub = Morpher API provider is not implemented yet; falling back to dummy rules
+# po-update
+help-poupdate-about = Update an existing .po against current mod sources (msgmerge-style)
+help-poupdate-root = Path to RimWorld mod root
+help-poupdate-po = Existing translated .po to update
+help-poupdate-out-po = Write the updated .po here instead of overwriting --po
+help-poupdate-source-lang = Source language ISO code (e.g., en)
+help-poupdate-source-lang-dir = Source language folder name (e.g., English)
+help-poupdate-game-version = Game version subfolder to operate on
+help-poupdate-include-all = Include all version subfolders (disable auto-pick of latest)
+help-poupdate-min-similarity = Minimal similarity (0..1) to pre-fill new entries from existing translations (default: 0.75)
+help-poupdate-dry-run = Do not write files; only print summary
+help-poupdate-format = Output format: text or json
+po-update-saved = Updated PO saved to { $path }
+po-update-summary = PO update: kept={ $kept }, fuzzy={ $fuzzy }, new={ $added }, prefilled={ $prefilled }, obsolete={ $obsolete }, total={ $total }
+# po-merge
+help-pomerge-about = Merge several .po files (or directories of them) into one
+help-pomerge-po = Input .po files or directories (repeatable)
+help-pomerge-out-po = Output .po file path
+help-pomerge-format = Output format: text or json
+po-merge-saved = Merged PO saved to { $path }
+po-merge-summary = PO merge: files={ $files }, entries={ $entries }, duplicates={ $duplicates }
+# xliff
+help-exportxliff-about = Export extracted strings to XLIFF (2.0 or 1.2) for CAT tools such as memoQ and Trados
+help-exportxliff-root = Path to RimWorld mod root containing extracted strings
+help-exportxliff-out-xliff = Output .xlf file path
+help-exportxliff-lang = Target language ISO code (trgLang); existing translations under Languages/<lang> are written as targets
+help-exportxliff-version = XLIFF version: 2.0 (default) or 1.2
+export-xliff-saved = XLIFF saved to { $path } ({ $total } units, { $translated } already translated)
+help-importxliff-about = Import a translated XLIFF (1.2 or 2.0) into a single XML or into an existing mod's structure
+help-importxliff-xliff = Path to the translated .xlf/.xliff file
+help-importxliff-fuzzy = How to treat filled units still in state new/initial: skip (default), include, or mark with an XML comment
+
+# tmx
+help-exporttmx-about = Export source/target pairs of a translated mod to a TMX translation memory
+help-exporttmx-root = Path to the translated RimWorld mod root
+help-exporttmx-out-tmx = Output .tmx file path
+help-exporttmx-lang = Target language ISO code or folder name; pairs are taken from Languages/<lang>
+export-tmx-saved = TMX saved to { $path } ({ $exported } pairs from { $total } source strings)
+
+# tm-build
+help-tmbuild-about = Build a translation memory from all translation mods in a Mods or Steam workshop folder
+help-tmbuild-mods = Mods folder, Steam workshop content folder or single mod (repeatable)
+help-tmbuild-lang = Target language ISO code or folder name; Languages/<lang> trees (also "<lang> (…)") are harvested
+help-tmbuild-out-tm = Output TM file (.json), usable with export-po --tm-root
+help-tmbuild-format = Output format: text or json
+tm-build-pair = { $path }: { $paired } pairs from { $sources } ({ $unpaired } without source)
+tm-build-unpaired = { $path }: no source mod found for { $count } translations
+tm-build-saved = TM saved to { $path } ({ $entries } entries from { $translations } translations in { $mods } mods)
+
+# glossary
+help-validate-compare-rich-text = Compare rich-text tags (<b>, <color=…>) of --lang/--lang-dir with the source by key (kinds rich-text, rich-text-unknown)
+help-validate-compare-grammar = Compare grammar rules (rulesStrings) of --lang/--lang-dir with the source by def (kind grammar)
+help-validate-compare-literals = Compare numbers, percent values, URLs, hotkeys and \n escapes of --lang/--lang-dir with the source by key (kinds *-mismatch)
+help-validate-glossary = Glossary (CSV or TBX): translations under --lang/--lang-dir must use the approved target form of every term in their source text
+help-validate-strict = Strict mode: return error (exit code 1) if any error-level message is found (see severity)
+help-validatepo-glossary = Glossary (CSV or TBX): report translations missing an approved term or using a forbidden one (kind "glossary")
+help-exportpo-glossary = Glossary (CSV or TBX): terms found in a source text are listed as '#. Glossary:' comments
+
+# extract-terms
+help-extractterms-about = Extract recurring terms (def labels, capitalized names, frequent phrases) from a mod's source texts as a glossary skeleton
+help-extractterms-root = Path to the RimWorld mod root
+help-extractterms-min-count = Minimum number of uses of a term (default 2)
+help-extractterms-limit = Maximum number of terms, best ranked first (0 = all)
+help-extractterms-out = Write a glossary skeleton to fill in: .csv, .tsv or .tbx (usable with --glossary)
+help-extractterms-lang = Target language of the skeleton (ISO code or folder name); sets the TBX target language set
+help-extractterms-format = Output format: text or json
+extract-terms-row = { $term } ({ $kind }): { $count } uses in { $files } files; e.g. { $examples }
+extract-terms-none = No recurring terms found
+extract-terms-saved = Glossary skeleton with { $count } terms saved to { $path }
+
+# mt-fill
+help-mtfill-about = Pre-fill untranslated PO entries with machine translation; results are marked fuzzy with a '#. MT' comment
+help-mtfill-po = PO file to fill (updated in place unless --out-po is set)
+help-mtfill-out-po = Write the filled PO here instead
+help-mtfill-provider = Translation provider: libretranslate (default) or dummy (returns the source text, for trying the pipeline)
+help-mtfill-url = LibreTranslate-compatible server URL (default: [mt] url, LIBRETRANSLATE_URL, then http://localhost:5000)
+help-mtfill-api-key = API key for the server (default: LIBRETRANSLATE_API_KEY)
+help-mtfill-source-lang = Source language ISO code (default: X-RimLoc-Source-Lang header, then en)
+help-mtfill-lang = Target language ISO code (default: PO Language header)
+help-mtfill-batch-size = Strings sent per request (default 50)
+help-mtfill-requests-per-minute = Maximum requests per minute (default: no limit)
+help-mtfill-timeout-ms = HTTP timeout in milliseconds (default 30000)
+help-mtfill-no-cache = Do not read or write the translation cache
+help-mtfill-dry-run = Translate, but do not write the PO
+help-mtfill-format = Output format: text or json
+mt-fill-saved = { $translated } of { $untranslated } untranslated entries filled by { $provider } ({ $cached } from cache, { $requests } requests); saved to { $path }
+mt-fill-dry-run = DRY-RUN: { $translated } of { $untranslated } untranslated entries would be filled
+mt-fill-failed = { $count } translations dropped: placeholders or tags were lost
+
+# csv
+help-exportcsv-about = Export extracted strings to a bilingual CSV (key, kind, file, source, translation, status, notes) for spreadsheets
+help-exportcsv-root = Path to RimWorld mod root containing extracted strings
+help-exportcsv-out-csv = Output .csv file path
+help-exportcsv-lang = Target language ISO code or folder name; current translations under Languages/<lang> fill the translation column
+help-exportcsv-delimiter = Field delimiter: a single character such as , or ; or the word tab
+help-exportcsv-bom = Start the file with a UTF-8 BOM so Excel opens it with the right encoding
+export-csv-saved = CSV saved to { $path } ({ $total } rows, { $translated } already translated)
+help-importcsv-about = Import a translated bilingual CSV into a single XML or into an existing mod's structure
+help-importcsv-csv = Path to the translated .csv file
+help-importcsv-delimiter = Field delimiter: a single character or tab (default: guessed from the header line)
+help-importcsv-fuzzy = How to treat rows with status fuzzy: skip (default), include, or mark with an XML comment
+csv-bad-delimiter = Invalid CSV delimiter '{ $value }': use a single character or tab
+
+# xlsx
+help-exportxlsx-about = Export extracted strings to an XLSX workbook with one sheet per Keyed file or DefInjected def type; only the translation column is editable
+help-exportxlsx-root = Path to RimWorld mod root containing extracted strings
+help-exportxlsx-out-xlsx = Output .xlsx file path
+help-exportxlsx-lang = Target language ISO code or folder name; current translations under Languages/<lang> fill the translation column
+export-xlsx-saved = Workbook saved to { $path } ({ $total } rows on { $sheets } sheets, { $translated } already translated)
+help-importxlsx-about = Import a translated XLSX workbook into a single XML or into an existing mod's structure; fails if keys or source texts were edited
+help-importxlsx-xlsx = Path to the translated .xlsx file made by export-xlsx
+
+# json
+help-exportjson-about = Export extracted strings as key/value JSON per language, mirroring Languages/<lang>/Keyed|DefInjected, for Weblate, Crowdin and similar platforms
+help-exportjson-root = Path to RimWorld mod root containing extracted strings
+help-exportjson-out-dir = Output folder; files are written to <out-dir>/<LangDir>/Keyed|DefInjected/….json
+help-exportjson-lang = Target language ISO code or folder name; writes target files with current translations from Languages/<lang> (empty when missing)
+help-exportjson-nested = Write dotted keys (Beer.label) as nested objects instead of flat keys
+export-json-saved = JSON saved to { $path } ({ $files } files, { $keys } keys, { $translated } already translated)
+help-importjson-about = Import translated key/value JSON (flat or nested) into a single XML or into an existing mod's structure
+help-importjson-json = JSON file or language folder (e.g. out/Russian) made by export-json
@@ -35,11 +35,16 @@ scan-json-saved = JSON сохранён в { $path }
 validate-clean = Всё чисто, ошибок не найдено
 export-po-saved = PO сохранён в { $path }
 export-po-tm-coverage = TM автозаполнение: { $filled } / { $total } ({ $pct }%)
+export-po-split-file = { $path } (записей: { $n })
+export-po-split-saved = Сохранено PO файлов: { $count } в { $path }
+export-po-compact = Компактный экспорт: { $shared } ключей используют текст другой записи (записей: { $total })
 export-po-missing-definj-suggested = Не найдено English/DefInjected; примените { $path }, скопировав в Languages/{ $lang_dir }/DefInjected
 export-po-missing-definj-learned = Не найдено English/DefInjected; воспользуйтесь обученными данными из { $path }
 export-po-missing-definj-generate = Папка Languages/{ $lang_dir }/DefInjected пуста; запустите «rimloc-cli learn-defs --lang-dir { $lang_dir }» или добавьте шаблоны перед экспортом
 import-dry-run-header = DRY-RUN план:
 import-total-keys = ИТОГО: { $n } ключ(ей)
+import-fuzzy-summary = Fuzzy-записи: пропущено={ $skipped }, импортировано={ $imported }
+import-duplicate-key = Ключ { $key } встречается несколько раз для { $path }; используется последнее значение
 import-only-empty = PO содержит только пустые строки. Добавьте --keep-empty, если хотите импортировать заглушки.
 import-nothing-to-do = Нечего импортировать (все строки пустые; добавьте --keep-empty, если нужны заглушки).
 import-done = Импорт выполнен в { $root }
@@ -62,6 +67,7 @@ validate-po-got = получено: { $ph }
 validate-po-total-mismatches = Всего несовпадений: { $count }
 validate-po-report-line = { $ctxt } → { $reference }
 validate-po-summary = Итого несовпадений: { $count }
+validate-po-stats = Записей: { $total }, без перевода: { $untranslated }, fuzzy: { $fuzzy }
 learn-defs-summary = Обучение по Defs: кандидатов={ $candidates }, принято={ $accepted } → missing_keys.json={ $missing }, suggested.xml={ $suggested }
 import-need-target = Ошибка: нужно указать либо --out-xml, либо --mod-root
 import-dry-run-line = { $path }  ({ $n } ключ(ей))
@@ -75,7 +81,10 @@ build-summary = ИТОГО: { $n } ключей будет записано
 schema-dumped = Схемы сохранены в { $path }
 ui-lang-unsupported = Неподдерживаемый код языка интерфейса
 err-placeholder-mismatches = обнаружены несовпадения плейсхолдеров
-validate-po-error = обнаружены несовпадения плейсхолдеров
+validate-po-error = обнаружены проблемы в PO
+validate-strict-error = проверка нашла проблемы уровня error
+po-other-mod = PO экспортирован из мода { $po }, а целевой мод — { $expected }
+po-other-game-version = PO экспортирован для версии игры { $po }, а выбрана { $expected }
 kind-duplicate = дубликат
 kind-empty = пустое
 kind-placeholder-check = проверка-плейсхолдеров
@@ -106,22 +115,25 @@ help-validate-source-lang-dir = Путь к директории исходно
 help-validate-format = Формат вывода: «text» (по умолчанию) или «json»
 help-validate-game-version = Папка версии игры (например, 1.6 или v1.6); по умолчанию выбирается самая новая
 help-validate-include-all = Включить все подпапки версий (отключить авто‑выбор последней)
-help-validatepo-about = Проверить согласованность плейсхолдеров в .po (msgid vs msgstr)
+help-validatepo-about = Проверить .po: плейсхолдеры, дубликаты, заголовок Language, пробелы, XML-безопасность
 help-validatepo-po = Путь к .po файлу для проверки
-help-validatepo-strict = Строгий режим: вернуть ошибку (код 1), если найдены несовпадения
+help-validatepo-strict = Строгий режим: вернуть ошибку (код 1), если найдена хотя бы одна проблема
 help-validatepo-format = Формат вывода: «text» (по умолчанию) или «json»
 help-exportpo-about = Экспортировать извлечённые строки в единый .po файл
 help-exportpo-root = Путь к корню мода RimWorld с извлечёнными строками
-help-exportpo-out-po = Путь к выходному .po файлу
+help-exportpo-out-po = Путь к выходному .po файлу (с --split — выходная папка)
 help-exportpo-lang = ISO-код языка перевода (например, ru, ja, de)
 help-exportpo-pot = Вместо локализованного PO записать POT-шаблон (пустой заголовок Language)
 help-exportpo-source-lang = ISO-код исходного языка для экспорта (например, en, ru, ja)
 help-exportpo-source-lang-dir = Имя папки исходного языка (например, English). Перекрывает --source-lang
-help-exportpo-tm-root = Путь(и) к базам переводов (флаг повторяемый). Каждая база: Languages/<язык> или корень мода. Автозаполняет msgstr и помечает fuzzy
+help-exportpo-tm-root = Путь(и) к памяти переводов (флаг повторяемый): Languages/<язык>, корень мода, файл .tmx или память из tm-build (.json). Совпадения ищутся по ключу и по похожему исходному тексту; автозаполняет msgstr, помечает fuzzy и перечисляет кандидатов в комментариях '#. TM <процент>%'
+help-exportpo-tm-min-score = Минимальная похожесть (0..1) для нечётких подсказок из TM (по умолчанию 0.75)
 help-exportpo-game-version = Папка версии игры для сканирования (например, 1.6 или v1.6); по умолчанию — самая новая
 help-exportpo-include-all = Включить все подпапки версий (может привести к дублям)
+help-exportpo-compact = Одна запись на каждый уникальный исходный текст; остальные ключи перечислены в ссылках и получают тот же перевод при импорте
+help-exportpo-split = Записать несколько .po файлов в папку --out-po: file (по исходному файлу), def-type (по файлу Keyed и по DefType в DefInjected) или version (по папке версии)
 help-importpo-about = Импорт .po  -  в один XML или по структуре существующего мода
-help-importpo-po = Путь к .po файлу для импорта
+help-importpo-po = Путь к .po файлу для импорта или к папке с .po файлами (например, после разбиения)
 help-importpo-out-xml = Путь выходного XML (режим одного файла)
 help-importpo-mod-root = Корень мода для обновления импортированными строками (структурный режим)
 help-importpo-lang = Код целевого языка для импорта (например, ru)
@@ -136,8 +148,9 @@ help-importpo-report = Показать сводку: создано/обнов
 help-importpo-incremental = Не перезаписывать файлы, если содержимое не изменилось
 import-report-summary = Сводка импорта: создано={ $created }, обновлено={ $updated }, пропущено={ $skipped }, ключей={ $keys }
 help-importpo-only-diff = Записывать только изменённые/новые ключи по файлам (пропускать неизменённые)
+help-importpo-fuzzy = Как обрабатывать записи #, fuzzy: skip (пропускать, по умолчанию), include (импортировать) или mark (импортировать с XML-комментарием)
 help-buildmod-about = Собрать отдельный мод‑перевод из .po файла
-help-buildmod-po = Путь к .po файлу для сборки
+help-buildmod-po = Путь к .po файлу для сборки или к папке с .po файлами
 help-buildmod-out-mod = Путь выходной папки мода
 help-buildmod-lang = Код языка перевода
 help-buildmod-from-root = Собрать из уже существующей структуры Languages/<язык> в этом корне вместо .po
@@ -148,6 +161,7 @@ help-buildmod-rw-version = Целевая версия RimWorld
 help-buildmod-lang-dir = Имя языковой папки внутри мода (необязательно)
 help-buildmod-dry-run = Ничего не записывать; только вывести план сборки
 help-buildmod-dedupe = Удалять дублирующиеся ключи в одном XML (последний имеет приоритет)
+help-buildmod-fuzzy = Как обрабатывать записи #, fuzzy: skip (пропускать, по умолчанию), include (импортировать) или mark (импортировать с XML-комментарием)
 help-diffxml-about = Сравнить присутствие ключей в исходнике и переводе; при наличии baseline PO найти изменившиеся исходные строки
 help-diffxml-root = Путь к корню мода RimWorld для анализа
 help-diffxml-source-lang = ISO-код исходного языка (соответствует папке RimWorld)
@@ -223,3 +237,109 @@ help-morph-cache-size = Размер кэша провайдера (по умо
 help-morph-pym-url = URL сервиса Pymorphy2 (перекрывает PYMORPHY_URL)
 morph-summary = Сгенерировано форм: { $processed } для { $lang }
 morph-provider-morpher-stub = Провайдер Morpher API пока не реализован; применяется dummy-логика
+help-poupdate-about = Обновить существующий .po по текущим исходникам мода (как msgmerge)
+help-poupdate-root = Путь к корню мода RimWorld
+help-poupdate-po = Существующий переведённый .po для обновления
+help-poupdate-out-po = Записать обновлённый .po сюда вместо перезаписи --po
+help-poupdate-source-lang = ISO-код исходного языка (например, en)
+help-poupdate-source-lang-dir = Имя папки исходного языка (например, English)
+help-poupdate-game-version = Подпапка версии игры
+help-poupdate-include-all = Учитывать все подпапки версий (отключить авто-выбор последней)
+help-poupdate-min-similarity = Минимальная похожесть (0..1) для предзаполнения новых записей из существующих переводов (по умолчанию 0.75)
+help-poupdate-dry-run = Ничего не записывать; только вывести сводку
+help-poupdate-format = Формат вывода: text или json
+po-update-saved = Обновлённый PO сохранён в { $path }
+po-update-summary = Обновление PO: сохранено={ $kept }, fuzzy={ $fuzzy }, новых={ $added }, предзаполнено={ $prefilled }, устарело={ $obsolete }, всего={ $total }
+help-pomerge-about = Объединить несколько .po файлов (или папок с ними) в один
+help-pomerge-po = Входные .po файлы или папки (флаг повторяемый)
+help-pomerge-out-po = Путь к выходному .po файлу
+help-pomerge-format = Формат вывода: text или json
+po-merge-saved = Объединённый PO сохранён в { $path }
+po-merge-summary = Объединение PO: файлов={ $files }, записей={ $entries }, дублей={ $duplicates }
+help-exportxliff-about = Экспортировать извлечённые строки в XLIFF (2.0 или 1.2) для CAT-инструментов вроде memoQ и Trados
+help-exportxliff-root = Путь к корню мода RimWorld с извлечёнными строками
+help-exportxliff-out-xliff = Путь к выходному .xlf файлу
+help-exportxliff-lang = ISO-код языка перевода (trgLang); существующие переводы из Languages/<язык> записываются как target
+help-exportxliff-version = Версия XLIFF: 2.0 (по умолчанию) или 1.2
+export-xliff-saved = XLIFF сохранён в { $path } (юнитов: { $total }, уже переведено: { $translated })
+help-importxliff-about = Импортировать переведённый XLIFF (1.2 или 2.0) в один XML или в структуру существующего мода
+help-importxliff-xliff = Путь к переведённому .xlf/.xliff файлу
+help-importxliff-fuzzy = Как обрабатывать заполненные юниты в состоянии new/initial: skip (по умолчанию), include или mark (XML-комментарий)
+help-exporttmx-about = Экспортировать пары исходник/перевод из переведённого мода в память переводов TMX
+help-exporttmx-root = Путь к корню переведённого мода RimWorld
+help-exporttmx-out-tmx = Путь к выходному .tmx файлу
+help-exporttmx-lang = ISO-код или имя папки языка перевода; пары берутся из Languages/<язык>
+export-tmx-saved = TMX сохранён в { $path } (пар: { $exported } из { $total } исходных строк)
+
+help-tmbuild-about = Собрать память переводов из всех модов-переводов в папке Mods или Steam Workshop
+help-tmbuild-mods = Папка Mods, папка контента Steam Workshop или отдельный мод (можно повторять)
+help-tmbuild-lang = ISO-код или имя папки целевого языка; собираются деревья Languages/<lang> (а также "<lang> (…)")
+help-tmbuild-out-tm = Файл памяти переводов (.json) для export-po --tm-root
+help-tmbuild-format = Формат вывода: text или json
+tm-build-pair = { $path }: { $paired } пар из { $sources } ({ $unpaired } без исходника)
+tm-build-unpaired = { $path }: не найден исходный мод для { $count } переводов
+tm-build-saved = Память переводов сохранена в { $path } ({ $entries } записей из { $translations } переводов в { $mods } модах)
+
+help-validate-compare-rich-text = Сверить rich-text теги (<b>, <color=…>) перевода --lang/--lang-dir с исходником по ключу (виды rich-text, rich-text-unknown)
+help-validate-compare-grammar = Сверить правила грамматики (rulesStrings) перевода --lang/--lang-dir с исходником по дефу (вид grammar)
+help-validate-compare-literals = Сверить числа, проценты, URL, горячие клавиши и экранированные \n перевода --lang/--lang-dir с исходником по ключу (виды *-mismatch)
+help-validate-glossary = Глоссарий (CSV или TBX): переводы в --lang/--lang-dir должны использовать утверждённый перевод каждого термина из исходного текста
+help-validate-strict = Строгий режим: вернуть ошибку (код 1), если есть сообщения уровня error (см. severity)
+help-validatepo-glossary = Глоссарий (CSV или TBX): сообщать о переводах без утверждённого термина или с запрещённым вариантом (тип "glossary")
+help-exportpo-glossary = Глоссарий (CSV или TBX): термины из исходного текста перечисляются в комментариях '#. Glossary:'
+
+help-extractterms-about = Извлечь повторяющиеся термины (метки дефов, имена с заглавной буквы, частые словосочетания) из исходных текстов мода в заготовку глоссария
+help-extractterms-root = Путь к корню мода RimWorld
+help-extractterms-min-count = Минимальное число употреблений термина (по умолчанию 2)
+help-extractterms-limit = Максимальное число терминов, лучшие первыми (0 = все)
+help-extractterms-out = Записать заготовку глоссария для заполнения: .csv, .tsv или .tbx (подходит для --glossary)
+help-extractterms-lang = Целевой язык заготовки (ISO-код или имя папки); задаёт языковой набор TBX
+help-extractterms-format = Формат вывода: text или json
+extract-terms-row = { $term } ({ $kind }): употреблений — { $count }, файлов — { $files }; например, { $examples }
+extract-terms-none = Повторяющиеся термины не найдены
+extract-terms-saved = Заготовка глоссария ({ $count } терминов) сохранена в { $path }
+
+help-mtfill-about = Предзаполнить непереведённые записи PO машинным переводом; результаты помечаются fuzzy и комментарием '#. MT'
+help-mtfill-po = PO-файл для заполнения (изменяется на месте, если не задан --out-po)
+help-mtfill-out-po = Записать заполненный PO сюда
+help-mtfill-provider = Провайдер перевода: libretranslate (по умолчанию) или dummy (возвращает исходный текст, для проверки конвейера)
+help-mtfill-url = URL сервера, совместимого с LibreTranslate (по умолчанию: [mt] url, LIBRETRANSLATE_URL, затем http://localhost:5000)
+help-mtfill-api-key = API-ключ сервера (по умолчанию: LIBRETRANSLATE_API_KEY)
+help-mtfill-source-lang = ISO-код исходного языка (по умолчанию: заголовок X-RimLoc-Source-Lang, затем en)
+help-mtfill-lang = ISO-код целевого языка (по умолчанию: заголовок Language в PO)
+help-mtfill-batch-size = Строк в одном запросе (по умолчанию 50)
+help-mtfill-requests-per-minute = Максимум запросов в минуту (по умолчанию без ограничения)
+help-mtfill-timeout-ms = Таймаут HTTP в миллисекундах (по умолчанию 30000)
+help-mtfill-no-cache = Не читать и не записывать кеш переводов
+help-mtfill-dry-run = Перевести, но не записывать PO
+help-mtfill-format = Формат вывода: text или json
+mt-fill-saved = Заполнено { $translated } из { $untranslated } непереведённых записей провайдером { $provider } (из кеша: { $cached }, запросов: { $requests }); сохранено в { $path }
+mt-fill-dry-run = DRY-RUN: было бы заполнено { $translated } из { $untranslated } непереведённых записей
+mt-fill-failed = Отброшено переводов: { $count } — потеряны плейсхолдеры или теги
+help-exportcsv-about = Экспортировать извлечённые строки в двуязычный CSV (key, kind, file, source, translation, status, notes) для электронных таблиц
+help-exportcsv-root = Путь к корню мода RimWorld с извлечёнными строками
+help-exportcsv-out-csv = Путь к выходному .csv файлу
+help-exportcsv-lang = ISO-код или имя папки языка перевода; текущие переводы из Languages/<язык> заполняют колонку translation
+help-exportcsv-delimiter = Разделитель полей: один символ, например , или ;, либо слово tab
+help-exportcsv-bom = Записать в начало файла UTF-8 BOM, чтобы Excel открыл его в правильной кодировке
+export-csv-saved = CSV сохранён в { $path } (строк: { $total }, уже переведено: { $translated })
+help-importcsv-about = Импортировать переведённый двуязычный CSV в один XML или в структуру существующего мода
+help-importcsv-csv = Путь к переведённому .csv файлу
+help-importcsv-delimiter = Разделитель полей: один символ или tab (по умолчанию определяется по строке заголовка)
+help-importcsv-fuzzy = Как обрабатывать строки со статусом fuzzy: skip (по умолчанию), include или mark (XML-комментарий)
+csv-bad-delimiter = Недопустимый разделитель CSV '{ $value }': укажите один символ или tab
+help-exportxlsx-about = Экспортировать извлечённые строки в книгу XLSX: по листу на файл Keyed или тип дефа DefInjected; редактируется только колонка перевода
+help-exportxlsx-root = Путь к корню мода RimWorld с извлечёнными строками
+help-exportxlsx-out-xlsx = Путь к выходному .xlsx файлу
+help-exportxlsx-lang = ISO-код или имя папки языка перевода; текущие переводы из Languages/<язык> заполняют колонку translation
+export-xlsx-saved = Книга сохранена в { $path } (строк: { $total } на листах: { $sheets }, уже переведено: { $translated })
+help-importxlsx-about = Импортировать переведённую книгу XLSX в один XML или в структуру существующего мода; завершается ошибкой, если ключи или исходные тексты были изменены
+help-importxlsx-xlsx = Путь к переведённому .xlsx файлу, созданному export-xlsx
+help-exportjson-about = Экспортировать извлечённые строки в JSON «ключ — значение» по языкам с раскладкой как в Languages/<язык>/Keyed|DefInjected, для Weblate, Crowdin и похожих платформ
+help-exportjson-root = Путь к корню мода RimWorld с извлечёнными строками
+help-exportjson-out-dir = Выходная папка; файлы записываются в <out-dir>/<ПапкаЯзыка>/Keyed|DefInjected/….json
+help-exportjson-lang = ISO-код или имя папки языка перевода; записывает целевые файлы с текущими переводами из Languages/<язык> (пустыми, если перевода нет)
+help-exportjson-nested = Записывать ключи с точками (Beer.label) вложенными объектами, а не плоскими ключами
+export-json-saved = JSON сохранён в { $path } (файлов: { $files }, ключей: { $keys }, уже переведено: { $translated })
+help-importjson-about = Импортировать переведённый JSON «ключ — значение» (плоский или вложенный) в один XML или в структуру существующего мода
+help-importjson-json = JSON-файл или папка языка (например, out/Russian), созданные export-json
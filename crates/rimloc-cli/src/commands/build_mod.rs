@@ -12,6 +12,7 @@ pub fn run_build_mod(
     lang_dir: Option<String>,
     dry_run: bool,
     dedupe: bool,
+    fuzzy: Option<String>,
 ) -> color_eyre::Result<()> {
     tracing::debug!(event = "build_mod_args", po = ?po, out_mod = ?out_mod, lang = %lang, from_root = ?from_root, from_game_version = ?from_game_version, name = %name, package_id = %package_id, rw_version = %rw_version, lang_dir = ?lang_dir, dry_run = dry_run);
     let cfg = rimloc_config::load_config().unwrap_or_default();
@@ -39,6 +40,11 @@ pub fn run_build_mod(
         rw_version
     };
     let dedupe = dedupe || cfg_build.dedupe.unwrap_or(false);
+    let fuzzy: rimloc_services::FuzzyPolicy = fuzzy
+        .as_deref()
+        .or(cfg_build.fuzzy.as_deref())
+        .unwrap_or("skip")
+        .parse()?;
     let from_game_version = if from_game_version.is_none() {
         cfg_build.from_root_versions
     } else {
@@ -96,6 +102,7 @@ pub fn run_build_mod(
             &package_id,
             &rw_version,
             dedupe,
+            fuzzy,
         )?;
         ui_out!("build-dry-run-header");
         ui_out!("build-name", value = plan.mod_name);
@@ -116,6 +123,13 @@ pub fn run_build_mod(
         }
         ui_out!("build-divider");
         ui_out!("build-summary", n = plan.total_keys);
+        if plan.fuzzy_skipped + plan.fuzzy_imported > 0 {
+            ui_info!(
+                "import-fuzzy-summary",
+                skipped = plan.fuzzy_skipped,
+                imported = plan.fuzzy_imported
+            );
+        }
     } else {
         rimloc_services::build_from_po_execute(
             &po,
@@ -125,6 +139,7 @@ pub fn run_build_mod(
             &package_id,
             &rw_version,
             dedupe,
+            fuzzy,
         )?;
         ui_ok!("build-done", out = out_mod.display().to_string());
     }
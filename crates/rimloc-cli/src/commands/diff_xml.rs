@@ -1,5 +1,4 @@
 use crate::version::resolve_game_version_root;
-use rimloc_services::apply_diff_flags;
 
 #[allow(clippy::too_many_arguments)]
 pub fn run_diff_xml(
@@ -114,6 +113,10 @@ pub fn run_diff_xml(
     }
     let merged = rimloc_parsers_xml::merge_defs_dicts(&dicts);
 
+    if let Some(po) = baseline_po.as_deref() {
+        crate::commands::import_po::warn_po_identity(po, Some(&root), selected_version.as_deref());
+    }
+
     let diff = rimloc_services::diff_xml_with_defs_and_dict(
         &scan_root,
         &src_dir,
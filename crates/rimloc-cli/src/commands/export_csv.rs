@@ -0,0 +1,48 @@
+use crate::version::resolve_game_version_root;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_export_csv(
+    root: std::path::PathBuf,
+    out_csv: std::path::PathBuf,
+    lang: Option<String>,
+    source_lang: Option<String>,
+    source_lang_dir: Option<String>,
+    game_version: Option<String>,
+    include_all_versions: bool,
+    delimiter: String,
+    bom: bool,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "export_csv_args", root = ?root, out_csv = ?out_csv, lang = ?lang, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, game_version = ?game_version, include_all_versions = include_all_versions, delimiter = %delimiter, bom = bom);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+    let Some(delimiter) = rimloc_services::parse_csv_delimiter(&delimiter) else {
+        color_eyre::eyre::bail!(tr!("csv-bad-delimiter", value = delimiter));
+    };
+
+    let effective_version = game_version.or(cfg.game_version.clone());
+    let (scan_root, selected_version) = if include_all_versions {
+        (root.clone(), None)
+    } else {
+        resolve_game_version_root(&root, effective_version.as_deref())?
+    };
+    if let Some(ver) = selected_version.as_deref() {
+        tracing::info!(event = "export_version_resolved", version = ver, path = %scan_root.display());
+    }
+
+    let effective_source_lang = source_lang.or(cfg.source_lang.clone());
+    let effective_lang = lang.or(cfg.target_lang.clone());
+    let stats = rimloc_services::export_csv(
+        &scan_root,
+        &out_csv,
+        effective_lang.as_deref(),
+        effective_source_lang.as_deref(),
+        source_lang_dir.as_deref(),
+        rimloc_services::CsvOptions { delimiter, bom },
+    )?;
+    ui_ok!(
+        "export-csv-saved",
+        path = out_csv.display().to_string(),
+        total = stats.total,
+        translated = stats.translated
+    );
+    Ok(())
+}
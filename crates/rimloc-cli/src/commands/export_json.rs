@@ -0,0 +1,45 @@
+use crate::version::resolve_game_version_root;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_export_json(
+    root: std::path::PathBuf,
+    out_dir: std::path::PathBuf,
+    lang: Option<String>,
+    source_lang: Option<String>,
+    source_lang_dir: Option<String>,
+    game_version: Option<String>,
+    include_all_versions: bool,
+    nested: bool,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "export_json_args", root = ?root, out_dir = ?out_dir, lang = ?lang, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, game_version = ?game_version, include_all_versions = include_all_versions, nested = nested);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+
+    let effective_version = game_version.or(cfg.game_version.clone());
+    let (scan_root, selected_version) = if include_all_versions {
+        (root.clone(), None)
+    } else {
+        resolve_game_version_root(&root, effective_version.as_deref())?
+    };
+    if let Some(ver) = selected_version.as_deref() {
+        tracing::info!(event = "export_version_resolved", version = ver, path = %scan_root.display());
+    }
+
+    let effective_source_lang = source_lang.or(cfg.source_lang.clone());
+    let effective_lang = lang.or(cfg.target_lang.clone());
+    let stats = rimloc_services::export_json(
+        &scan_root,
+        &out_dir,
+        effective_lang.as_deref(),
+        effective_source_lang.as_deref(),
+        source_lang_dir.as_deref(),
+        nested,
+    )?;
+    ui_ok!(
+        "export-json-saved",
+        path = out_dir.display().to_string(),
+        files = stats.files,
+        keys = stats.keys,
+        translated = stats.translated
+    );
+    Ok(())
+}
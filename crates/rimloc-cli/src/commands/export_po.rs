@@ -28,14 +28,28 @@ pub fn run_export_po(
     source_lang: Option<String>,
     source_lang_dir: Option<String>,
     mut tm_roots: Vec<std::path::PathBuf>,
+    tm_min_score: Option<f64>,
     game_version: Option<String>,
     include_all_versions: bool,
+    split: Option<String>,
+    compact: bool,
+    glossary: Option<std::path::PathBuf>,
 ) -> color_eyre::Result<()> {
-    tracing::debug!(event = "export_po_args", root = ?root, out_po = ?out_po, lang = ?lang, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, tm_roots = ?tm_roots, game_version = ?game_version, include_all_versions = include_all_versions);
+    tracing::debug!(event = "export_po_args", root = ?root, out_po = ?out_po, lang = ?lang, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, tm_roots = ?tm_roots, tm_min_score = ?tm_min_score, game_version = ?game_version, include_all_versions = include_all_versions, split = ?split, compact = compact, glossary = ?glossary);
     let cfg = rimloc_config::load_config().unwrap_or_default();
+    let split: Option<rimloc_services::PoSplit> = split.as_deref().map(str::parse).transpose()?;
+    let compact = compact
+        || cfg
+            .export
+            .as_ref()
+            .and_then(|e| e.compact)
+            .unwrap_or(false);
 
     let effective_version = game_version.or(cfg.game_version.clone());
-    let (scan_root, selected_version) = if include_all_versions {
+    // split by version needs every version folder
+    let all_versions =
+        include_all_versions || split == Some(rimloc_services::PoSplit::Version);
+    let (scan_root, selected_version) = if all_versions {
         (root.clone(), None)
     } else {
         resolve_game_version_root(&root, effective_version.as_deref())?
@@ -58,19 +72,71 @@ pub fn run_export_po(
     let auto = rimloc_services::autodiscover_defs_context(&scan_root)?;
 
     let effective_source_lang = source_lang.clone().or(cfg.source_lang.clone());
-    let stats = rimloc_services::export_po_with_tm(
-        &scan_root,
-        &out_po,
-        lang.as_deref(),
-        effective_source_lang.as_deref(),
-        source_lang_dir.as_deref(),
-        if tm_roots.is_empty() {
-            None
-        } else {
-            Some(&tm_roots)
-        },
-    )?;
-    ui_ok!("export-po-saved", path = out_po.display().to_string());
+    let export_cfg = cfg.export.clone().unwrap_or_default();
+    let tm_cache_dir = export_cfg
+        .tm_cache_dir
+        .map(std::path::PathBuf::from)
+        .or_else(|| rimloc_config::cache_dir().map(|d| d.join("tm")));
+    let tm_opts = rimloc_services::TmOptions {
+        roots: &tm_roots,
+        min_score: tm_min_score
+            .or(export_cfg.tm_min_score)
+            .unwrap_or(rimloc_services::DEFAULT_TM_MIN_SCORE)
+            .clamp(0.0, 1.0),
+        cache_dir: tm_cache_dir.as_deref(),
+    };
+    let tm = if tm_roots.is_empty() {
+        None
+    } else {
+        Some(&tm_opts)
+    };
+    let glossary = glossary
+        .or(cfg.glossary.clone().map(std::path::PathBuf::from))
+        .map(|p| {
+            let code = lang.as_deref().map(rimloc_import_po::rimworld_lang_code);
+            rimloc_services::Glossary::load(&p, code.as_deref())
+        })
+        .transpose()?;
+    let opts = rimloc_services::ExportPoOptions {
+        lang: lang.as_deref(),
+        source_lang: effective_source_lang.as_deref(),
+        source_lang_dir: source_lang_dir.as_deref(),
+        tm,
+        glossary: glossary.as_ref(),
+        game_version: selected_version.as_deref(),
+        compact,
+    };
+    let stats = if let Some(split) = split {
+        let written = rimloc_services::export_po_split(&scan_root, &out_po, split, &opts)?;
+        let mut total = rimloc_services::ExportPoStats::default();
+        for (path, st) in &written {
+            ui_out!(
+                "export-po-split-file",
+                path = path.display().to_string(),
+                n = st.total
+            );
+            total.total += st.total;
+            total.tm_filled += st.tm_filled;
+            total.shared += st.shared;
+        }
+        ui_ok!(
+            "export-po-split-saved",
+            count = written.len(),
+            path = out_po.display().to_string()
+        );
+        total
+    } else {
+        let stats = rimloc_services::export_po_with_tm(&scan_root, &out_po, &opts)?;
+        ui_ok!("export-po-saved", path = out_po.display().to_string());
+        stats
+    };
+    if stats.shared > 0 {
+        ui_info!(
+            "export-po-compact",
+            shared = stats.shared,
+            total = stats.total
+        );
+    }
     if !tm_roots.is_empty() {
         let pct: u32 = if stats.total == 0 {
             0
@@ -0,0 +1,41 @@
+use crate::version::resolve_game_version_root;
+
+pub fn run_export_tmx(
+    root: std::path::PathBuf,
+    out_tmx: std::path::PathBuf,
+    lang: String,
+    source_lang: Option<String>,
+    source_lang_dir: Option<String>,
+    game_version: Option<String>,
+    include_all_versions: bool,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "export_tmx_args", root = ?root, out_tmx = ?out_tmx, lang = %lang, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, game_version = ?game_version, include_all_versions = include_all_versions);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+
+    let effective_version = game_version.or(cfg.game_version.clone());
+    let (scan_root, selected_version) = if include_all_versions {
+        (root.clone(), None)
+    } else {
+        resolve_game_version_root(&root, effective_version.as_deref())?
+    };
+    if let Some(ver) = selected_version.as_deref() {
+        tracing::info!(event = "export_version_resolved", version = ver, path = %scan_root.display());
+    }
+
+    let effective_source_lang = source_lang.or(cfg.source_lang.clone());
+    let stats = rimloc_services::export_tmx(
+        &scan_root,
+        &out_tmx,
+        &lang,
+        effective_source_lang.as_deref(),
+        source_lang_dir.as_deref(),
+        selected_version.as_deref().or(effective_version.as_deref()),
+    )?;
+    ui_ok!(
+        "export-tmx-saved",
+        path = out_tmx.display().to_string(),
+        exported = stats.exported,
+        total = stats.total
+    );
+    Ok(())
+}
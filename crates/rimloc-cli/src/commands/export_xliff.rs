@@ -0,0 +1,44 @@
+use crate::version::resolve_game_version_root;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_export_xliff(
+    root: std::path::PathBuf,
+    out_xliff: std::path::PathBuf,
+    lang: Option<String>,
+    source_lang: Option<String>,
+    source_lang_dir: Option<String>,
+    game_version: Option<String>,
+    include_all_versions: bool,
+    xliff_version: String,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "export_xliff_args", root = ?root, out_xliff = ?out_xliff, lang = ?lang, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, game_version = ?game_version, include_all_versions = include_all_versions, xliff_version = %xliff_version);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+    let version: rimloc_services::XliffVersion = xliff_version.parse()?;
+
+    let effective_version = game_version.or(cfg.game_version.clone());
+    let (scan_root, selected_version) = if include_all_versions {
+        (root.clone(), None)
+    } else {
+        resolve_game_version_root(&root, effective_version.as_deref())?
+    };
+    if let Some(ver) = selected_version.as_deref() {
+        tracing::info!(event = "export_version_resolved", version = ver, path = %scan_root.display());
+    }
+
+    let effective_source_lang = source_lang.or(cfg.source_lang.clone());
+    let stats = rimloc_services::export_xliff(
+        &scan_root,
+        &out_xliff,
+        version,
+        lang.as_deref(),
+        effective_source_lang.as_deref(),
+        source_lang_dir.as_deref(),
+    )?;
+    ui_ok!(
+        "export-xliff-saved",
+        path = out_xliff.display().to_string(),
+        total = stats.total,
+        translated = stats.translated
+    );
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::version::resolve_game_version_root;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_extract_terms(
+    root: std::path::PathBuf,
+    source_lang: Option<String>,
+    source_lang_dir: Option<String>,
+    game_version: Option<String>,
+    include_all_versions: bool,
+    min_count: usize,
+    limit: usize,
+    out: Option<std::path::PathBuf>,
+    lang: Option<String>,
+    format: String,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "extract_terms_args", root = ?root, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, game_version = ?game_version, include_all_versions = include_all_versions, min_count = min_count, limit = limit, out = ?out, lang = ?lang);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+
+    let effective_version = game_version.or(cfg.game_version.clone());
+    let (scan_root, selected_version) = if include_all_versions {
+        (root.clone(), None)
+    } else {
+        resolve_game_version_root(&root, effective_version.as_deref())?
+    };
+    if let Some(ver) = selected_version.as_deref() {
+        tracing::info!(event = "extract_terms_version_resolved", version = ver, path = %scan_root.display());
+    }
+
+    let effective_source_lang = source_lang.or(cfg.source_lang.clone());
+    let opts = rimloc_services::TermExtractOptions {
+        min_count,
+        // 0 keeps every term
+        limit: (limit > 0).then_some(limit),
+        ..Default::default()
+    };
+    let terms = rimloc_services::extract_terms_under_root(
+        &scan_root,
+        effective_source_lang.as_deref(),
+        source_lang_dir.as_deref(),
+        &opts,
+    )?;
+
+    if let Some(out) = &out {
+        let source_code = rimloc_import_po::rimworld_lang_code(
+            source_lang_dir
+                .as_deref()
+                .or(effective_source_lang.as_deref())
+                .unwrap_or("en"),
+        );
+        let target_code = lang
+            .or(cfg.target_lang.clone())
+            .map(|l| rimloc_import_po::rimworld_lang_code(&l));
+        let glossary =
+            rimloc_services::terms_to_glossary(&terms, &source_code, target_code.as_deref());
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        glossary.save(out)?;
+    }
+
+    if format == "json" {
+        serde_json::to_writer(std::io::stdout().lock(), &terms)?;
+        return Ok(());
+    }
+    if terms.is_empty() {
+        ui_info!("extract-terms-none");
+    }
+    for term in &terms {
+        ui_out!(
+            "extract-terms-row",
+            term = term.term.clone(),
+            kind = term.kind.clone(),
+            count = term.count,
+            files = term.files,
+            examples = term.examples.join(", ")
+        );
+    }
+    if let Some(out) = &out {
+        ui_ok!(
+            "extract-terms-saved",
+            path = out.display().to_string(),
+            count = terms.len()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use super::import_po::{run_import, ImportArgs, ImportInput};
+
+pub fn run_import_csv(
+    csv: std::path::PathBuf,
+    delimiter: Option<String>,
+    args: ImportArgs,
+    fuzzy: Option<String>,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "import_csv_args", csv = ?csv, delimiter = ?delimiter, args = ?args);
+    // без --delimiter разделитель определяется по строке заголовка
+    let delimiter = match delimiter {
+        Some(d) => match rimloc_services::parse_csv_delimiter(&d) {
+            Some(b) => Some(b),
+            None => color_eyre::eyre::bail!(tr!("csv-bad-delimiter", value = d)),
+        },
+        None => None,
+    };
+    run_import(ImportInput::Csv(csv, delimiter), args, fuzzy)
+}
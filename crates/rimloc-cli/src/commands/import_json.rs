@@ -0,0 +1,7 @@
+use super::import_po::{run_import, ImportArgs, ImportInput};
+
+pub fn run_import_json(json: std::path::PathBuf, args: ImportArgs) -> color_eyre::Result<()> {
+    tracing::debug!(event = "import_json_args", json = ?json, args = ?args);
+    // файл или папка языка; размещение по Keyed/DefInjected в пути к JSON
+    run_import(ImportInput::Json(json), args, None)
+}
@@ -1,30 +1,95 @@
 use crate::version::resolve_game_version_root;
+use std::io::IsTerminal;
+
+/// Placement and write options shared by every `import-*` command.
+#[derive(clap::Args, Debug)]
+pub(crate) struct ImportArgs {
+    #[arg(long, conflicts_with = "mod_root")]
+    pub out_xml: Option<std::path::PathBuf>,
+    #[arg(long, conflicts_with = "out_xml")]
+    pub mod_root: Option<std::path::PathBuf>,
+    #[arg(long)]
+    pub lang: Option<String>,
+    #[arg(long)]
+    pub lang_dir: Option<String>,
+    #[arg(long, default_value_t = false)]
+    pub keep_empty: bool,
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    #[arg(long, default_value_t = false)]
+    pub backup: bool,
+    #[arg(long, default_value_t = false)]
+    pub single_file: bool,
+    /// Game version folder to operate on (e.g., 1.6 or v1.6).
+    #[arg(long)]
+    pub game_version: Option<String>,
+    /// Output format for reports or dry-run: "text" (default) or "json".
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    pub format: String,
+    /// Print a summary of created/updated/skipped files and total keys written.
+    #[arg(long, default_value_t = false)]
+    pub report: bool,
+    /// Skip writing files whose content would be identical.
+    #[arg(long, default_value_t = false)]
+    pub incremental: bool,
+    /// Only write changed/new keys for each file (skip unchanged keys)
+    #[arg(long, default_value_t = false)]
+    pub only_diff: bool,
+}
+
+/// Translation file read by [`run_import`].
+pub(crate) enum ImportInput {
+    Po(std::path::PathBuf),
+    Xliff(std::path::PathBuf),
+    Csv(std::path::PathBuf, Option<u8>),
+    Xlsx(std::path::PathBuf),
+    Json(std::path::PathBuf),
+}
+
+impl ImportInput {
+    fn read_entries(&self) -> color_eyre::Result<Vec<rimloc_core::PoEntry>> {
+        match self {
+            ImportInput::Po(po) => rimloc_import_po::read_po_entries(po),
+            ImportInput::Xliff(xliff) => rimloc_services::read_xliff_entries(xliff),
+            ImportInput::Csv(csv, delimiter) => rimloc_services::read_csv_entries(csv, *delimiter),
+            ImportInput::Xlsx(xlsx) => rimloc_services::read_xlsx_entries(xlsx),
+            ImportInput::Json(json) => rimloc_services::read_json_entries(json),
+        }
+    }
+}
 
 #[allow(dead_code)]
-#[allow(clippy::too_many_arguments)]
 pub fn run_import_po(
     po: std::path::PathBuf,
-    out_xml: Option<std::path::PathBuf>,
-    mod_root: Option<std::path::PathBuf>,
-    lang: Option<String>,
-    lang_dir: Option<String>,
-    keep_empty: bool,
-    dry_run: bool,
-    backup: bool,
-    single_file: bool,
-    game_version: Option<String>,
-    format: String,
-    // New behavior flags
-    // If true, print a summary of created/updated/skipped files and total keys written
-    // (text only; JSON can be added later if needed)
-    report: bool,
-    // If true, skip writing files whose content would be identical (compare bytes)
-    incremental: bool,
-    only_diff: bool,
+    args: ImportArgs,
+    fuzzy: Option<String>,
 ) -> color_eyre::Result<()> {
-    tracing::debug!(event = "import_po_args", po = ?po, out_xml = ?out_xml, mod_root = ?mod_root, lang = ?lang, lang_dir = ?lang_dir, keep_empty = keep_empty, dry_run = dry_run, backup = backup, single_file = single_file, game_version = ?game_version);
-    // no local fs imports needed
+    tracing::debug!(event = "import_po_args", po = ?po, args = ?args);
+    run_import(ImportInput::Po(po), args, fuzzy)
+}
 
+/// Shared body of `import-po` and the `import-xliff`/`-csv`/`-xlsx`/`-json` variants.
+pub(crate) fn run_import(
+    input: ImportInput,
+    args: ImportArgs,
+    // skip | include | mark (falls back to config, then "skip")
+    fuzzy: Option<String>,
+) -> color_eyre::Result<()> {
+    let ImportArgs {
+        out_xml,
+        mod_root,
+        lang,
+        lang_dir,
+        keep_empty,
+        dry_run,
+        backup,
+        single_file,
+        game_version,
+        format,
+        report,
+        incremental,
+        only_diff,
+    } = args;
     let cfg_all = rimloc_config::load_config().unwrap_or_default();
     let cfg_imp = cfg_all.import.unwrap_or_default();
     let eff_keep_empty = keep_empty || cfg_imp.keep_empty.unwrap_or(false);
@@ -33,9 +98,27 @@ pub fn run_import_po(
     let eff_incremental = incremental || cfg_imp.incremental.unwrap_or(false);
     let eff_only_diff = only_diff || cfg_imp.only_diff.unwrap_or(false);
     let eff_report = report || cfg_imp.report.unwrap_or(false);
+    let eff_fuzzy: rimloc_services::FuzzyPolicy = fuzzy
+        .as_deref()
+        .or(cfg_imp.fuzzy.as_deref())
+        .unwrap_or("skip")
+        .parse()?;
     if let Some(out) = out_xml {
         let summary =
-            rimloc_services::import_po_to_file(&po, &out, eff_keep_empty, dry_run, eff_backup)?;
+            rimloc_services::import_entries_to_file(
+                input.read_entries()?,
+                &out,
+                eff_keep_empty,
+                eff_fuzzy,
+                dry_run,
+                eff_backup,
+            )?;
+        print_duplicate_keys(
+            summary
+                .duplicates
+                .iter()
+                .map(|(k, path)| (k.as_str(), path.clone())),
+        );
         if dry_run {
             if format == "json" {
                 #[derive(serde::Serialize)]
@@ -43,6 +126,8 @@ pub fn run_import_po(
                     mode: &'a str,
                     files: Vec<(String, usize)>,
                     total_keys: usize,
+                    fuzzy_skipped: usize,
+                    fuzzy_imported: usize,
                 }
                 let p = Plan {
                     mode: "dry_run",
@@ -52,6 +137,8 @@ pub fn run_import_po(
                         .map(|f| (f.path.clone(), f.keys))
                         .collect(),
                     total_keys: summary.keys,
+                    fuzzy_skipped: summary.fuzzy_skipped,
+                    fuzzy_imported: summary.fuzzy_imported,
                 };
                 serde_json::to_writer(std::io::stdout().lock(), &p)?;
             } else {
@@ -62,10 +149,14 @@ pub fn run_import_po(
                         path = f.path.as_str()
                     );
                 }
+                print_fuzzy_summary(summary.fuzzy_skipped, summary.fuzzy_imported);
             }
             return Ok(());
         }
         ui_ok!("xml-saved", path = out.display().to_string());
+        if format != "json" {
+            print_fuzzy_summary(summary.fuzzy_skipped, summary.fuzzy_imported);
+        }
         if report && format == "json" {
             #[derive(serde::Serialize)]
             struct Out<'a> {
@@ -75,6 +166,8 @@ pub fn run_import_po(
                 skipped: usize,
                 keys: usize,
                 files: Vec<(String, usize)>,
+                fuzzy_skipped: usize,
+                fuzzy_imported: usize,
             }
             let files = summary
                 .files
@@ -88,6 +181,8 @@ pub fn run_import_po(
                 skipped: summary.skipped,
                 keys: summary.keys,
                 files,
+                fuzzy_skipped: summary.fuzzy_skipped,
+                fuzzy_imported: summary.fuzzy_imported,
             };
             serde_json::to_writer(std::io::stdout().lock(), &stats)?;
         }
@@ -103,6 +198,9 @@ pub fn run_import_po(
     if let Some(ver) = selected_version.as_deref() {
         tracing::info!(event = "import_version_resolved", version = ver, path = %root.display());
     }
+    if let ImportInput::Po(po) = &input {
+        warn_po_identity(po, Some(&base_root), selected_version.as_deref());
+    }
 
     let lang_folder = if let Some(dir) = lang_dir.or(cfg_imp.lang_dir) {
         dir
@@ -114,11 +212,12 @@ pub fn run_import_po(
     tracing::debug!(event = "resolved_lang_folder", lang_folder = %lang_folder);
 
     // Delegate to services for grouping/writing/statistics
-    let (plan, summary) = rimloc_services::import_po_to_mod_tree(
-        &po,
+    let (plan, summary) = rimloc_services::import_entries_to_mod_tree(
+        input.read_entries()?,
         &root,
         &lang_folder,
         eff_keep_empty,
+        eff_fuzzy,
         dry_run,
         eff_backup,
         eff_single_file,
@@ -127,6 +226,11 @@ pub fn run_import_po(
         eff_report,
     )?;
     if let Some(p) = plan {
+        print_duplicate_keys(
+            p.duplicates
+                .iter()
+                .map(|(k, path)| (k.as_str(), path.display().to_string())),
+        );
         ui_out!("import-dry-run-header");
         if format == "json" {
             #[derive(serde::Serialize)]
@@ -134,6 +238,8 @@ pub fn run_import_po(
                 mode: &'a str,
                 total_keys: usize,
                 files: Vec<(String, usize)>,
+                fuzzy_skipped: usize,
+                fuzzy_imported: usize,
             }
             let files = p
                 .files
@@ -144,6 +250,8 @@ pub fn run_import_po(
                 mode: "dry_run",
                 total_keys: p.total_keys,
                 files,
+                fuzzy_skipped: p.fuzzy_skipped,
+                fuzzy_imported: p.fuzzy_imported,
             };
             serde_json::to_writer(std::io::stdout().lock(), &json)?;
         } else {
@@ -155,12 +263,21 @@ pub fn run_import_po(
                 );
             }
             ui_out!("import-total-keys", n = p.total_keys);
+            print_fuzzy_summary(p.fuzzy_skipped, p.fuzzy_imported);
         }
         return Ok(());
     }
 
     if let Some(sum) = summary {
+        print_duplicate_keys(
+            sum.duplicates
+                .iter()
+                .map(|(k, path)| (k.as_str(), path.clone())),
+        );
         ui_ok!("import-done", root = root.display().to_string());
+        if format != "json" {
+            print_fuzzy_summary(sum.fuzzy_skipped, sum.fuzzy_imported);
+        }
         if report {
             if format == "json" {
                 #[derive(serde::Serialize)]
@@ -179,6 +296,8 @@ pub fn run_import_po(
                     skipped: usize,
                     keys: usize,
                     files: Vec<FileStat>,
+                    fuzzy_skipped: usize,
+                    fuzzy_imported: usize,
                 }
                 let files: Vec<FileStat> = sum
                     .files
@@ -198,6 +317,8 @@ pub fn run_import_po(
                     skipped: sum.skipped,
                     keys: sum.keys,
                     files,
+                    fuzzy_skipped: sum.fuzzy_skipped,
+                    fuzzy_imported: sum.fuzzy_imported,
                 };
                 serde_json::to_writer(std::io::stdout().lock(), &out)?;
             } else {
@@ -215,3 +336,54 @@ pub fn run_import_po(
     Ok(())
 }
 // helper moved to rimloc-services
+
+/// Warn when the PO header names another mod (packageId) or game version.
+pub(crate) fn warn_po_identity(
+    po: &std::path::Path,
+    mod_root: Option<&std::path::Path>,
+    game_version: Option<&str>,
+) {
+    let files = match rimloc_import_po::collect_po_files(po) {
+        Ok(files) => files,
+        Err(e) => {
+            tracing::debug!(event = "po_identity_check_failed", error = %e);
+            return;
+        }
+    };
+    for file in files {
+        warn_po_file_identity(&file, mod_root, game_version);
+    }
+}
+
+fn warn_po_file_identity(
+    po: &std::path::Path,
+    mod_root: Option<&std::path::Path>,
+    game_version: Option<&str>,
+) {
+    match rimloc_services::check_po_identity(po, mod_root, game_version) {
+        Ok(found) => {
+            for m in found {
+                let (po, expected) = (m.po.as_str(), m.expected.as_str());
+                if m.field == "package_id" {
+                    ui_warn!("po-other-mod", po = po, expected = expected);
+                } else {
+                    ui_warn!("po-other-game-version", po = po, expected = expected);
+                }
+            }
+        }
+        Err(e) => tracing::debug!(event = "po_identity_check_failed", error = %e),
+    }
+}
+
+fn print_fuzzy_summary(skipped: usize, imported: usize) {
+    if skipped + imported > 0 {
+        ui_info!("import-fuzzy-summary", skipped = skipped, imported = imported);
+    }
+}
+
+/// Keys given twice for one file: the last value is written.
+fn print_duplicate_keys<'a>(duplicates: impl IntoIterator<Item = (&'a str, String)>) {
+    for (key, path) in duplicates {
+        ui_warn!("import-duplicate-key", key = key, path = path);
+    }
+}
@@ -0,0 +1,11 @@
+use super::import_po::{run_import, ImportArgs, ImportInput};
+
+pub fn run_import_xliff(
+    xliff: std::path::PathBuf,
+    args: ImportArgs,
+    fuzzy: Option<String>,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "import_xliff_args", xliff = ?xliff, args = ?args);
+    // размещение по файлам — то же, что у import-po
+    run_import(ImportInput::Xliff(xliff), args, fuzzy)
+}
@@ -0,0 +1,7 @@
+use super::import_po::{run_import, ImportArgs, ImportInput};
+
+pub fn run_import_xlsx(xlsx: std::path::PathBuf, args: ImportArgs) -> color_eyre::Result<()> {
+    tracing::debug!(event = "import_xlsx_args", xlsx = ?xlsx, args = ?args);
+    // строки с изменённым ключом или исходником отклоняются до записи XML
+    run_import(ImportInput::Xlsx(xlsx), args, None)
+}
@@ -1,15 +1,30 @@
 pub mod annotate;
 pub mod build_mod;
 pub mod diff_xml;
+pub mod export_csv;
+pub mod export_json;
 pub mod export_po;
+pub mod export_tmx;
+pub mod export_xliff;
+pub mod export_xlsx;
+pub mod extract_terms;
+pub mod import_csv;
+pub mod import_json;
 pub mod import_po;
+pub mod import_xliff;
+pub mod import_xlsx;
 pub mod init;
 pub mod lang_update;
 pub mod learn_defs;
 pub mod learn_patches;
 pub mod morph;
+pub mod mt_fill;
+pub mod po_merge;
+pub mod po_update;
 pub mod scan;
 pub mod schema;
+pub mod tm_build;
 pub mod validate;
+pub mod validate_po;
 pub mod xml_health;
 // re-export commonly used helpers if needed later
@@ -0,0 +1,97 @@
+use std::io::IsTerminal;
+
+/// Local LibreTranslate server started with its defaults.
+const DEFAULT_MT_URL: &str = "http://localhost:5000";
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_mt_fill(
+    po: std::path::PathBuf,
+    out_po: Option<std::path::PathBuf>,
+    provider: Option<String>,
+    url: Option<String>,
+    api_key: Option<String>,
+    source_lang: Option<String>,
+    lang: Option<String>,
+    batch_size: Option<usize>,
+    requests_per_minute: Option<u32>,
+    timeout_ms: Option<u64>,
+    no_cache: bool,
+    dry_run: bool,
+    format: String,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "mt_fill_args", po = ?po, out_po = ?out_po, provider = ?provider, url = ?url, source_lang = ?source_lang, lang = ?lang, batch_size = ?batch_size, requests_per_minute = ?requests_per_minute, timeout_ms = ?timeout_ms, no_cache = no_cache, dry_run = dry_run);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+    let mt_cfg = cfg.mt.clone().unwrap_or_default();
+
+    let provider_name = provider
+        .or(mt_cfg.provider.clone())
+        .unwrap_or_else(|| "libretranslate".to_string());
+    let mut provider: Box<dyn rimloc_services::TranslationProvider> =
+        match provider_name.to_lowercase().as_str() {
+            "dummy" => Box::new(rimloc_services::DummyTranslator),
+            _ => {
+                let url = url
+                    .or(mt_cfg.url.clone())
+                    .or_else(|| std::env::var("LIBRETRANSLATE_URL").ok())
+                    .unwrap_or_else(|| DEFAULT_MT_URL.to_string());
+                let api_key = api_key
+                    .or_else(|| std::env::var("LIBRETRANSLATE_API_KEY").ok())
+                    .filter(|k| !k.is_empty());
+                Box::new(rimloc_services::LibreTranslate::new(
+                    &url,
+                    api_key,
+                    timeout_ms.or(mt_cfg.timeout_ms).unwrap_or(30_000),
+                )?)
+            }
+        };
+
+    let cache_dir = if no_cache {
+        None
+    } else {
+        mt_cfg
+            .cache_dir
+            .clone()
+            .map(std::path::PathBuf::from)
+            .or_else(|| rimloc_config::cache_dir().map(|d| d.join("mt")))
+    };
+    let target_lang = lang.or(cfg.target_lang.clone());
+    let source_lang = source_lang.or(cfg.source_lang.clone());
+    let opts = rimloc_services::MtOptions {
+        source_lang: source_lang.as_deref(),
+        target_lang: target_lang.as_deref(),
+        batch_size: batch_size
+            .or(mt_cfg.batch_size)
+            .unwrap_or(rimloc_services::DEFAULT_MT_BATCH_SIZE),
+        requests_per_minute: requests_per_minute.or(mt_cfg.requests_per_minute),
+        cache_dir: cache_dir.as_deref(),
+    };
+    let summary =
+        rimloc_services::mt_fill(&po, out_po.as_deref(), provider.as_mut(), &opts, dry_run)?;
+
+    if format == "json" {
+        serde_json::to_writer(std::io::stdout().lock(), &summary)?;
+        return Ok(());
+    }
+    if summary.failed > 0 {
+        ui_warn!("mt-fill-failed", count = summary.failed);
+    }
+    let path = out_po.as_ref().unwrap_or(&po).display().to_string();
+    if dry_run {
+        ui_info!(
+            "mt-fill-dry-run",
+            translated = summary.translated,
+            untranslated = summary.untranslated
+        );
+    } else {
+        ui_ok!(
+            "mt-fill-saved",
+            path = path,
+            translated = summary.translated,
+            untranslated = summary.untranslated,
+            cached = summary.cached,
+            requests = summary.requests,
+            provider = summary.provider.clone()
+        );
+    }
+    Ok(())
+}
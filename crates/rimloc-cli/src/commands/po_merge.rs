@@ -0,0 +1,22 @@
+pub fn run_po_merge(
+    po: Vec<std::path::PathBuf>,
+    out_po: std::path::PathBuf,
+    format: String,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "po_merge_args", po = ?po, out_po = ?out_po);
+
+    let summary = rimloc_services::po_merge(&po, &out_po)?;
+
+    if format == "json" {
+        serde_json::to_writer(std::io::stdout().lock(), &summary)?;
+        return Ok(());
+    }
+    ui_ok!("po-merge-saved", path = out_po.display().to_string());
+    ui_out!(
+        "po-merge-summary",
+        files = summary.files,
+        entries = summary.entries,
+        duplicates = summary.duplicates
+    );
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::version::resolve_game_version_root;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_po_update(
+    root: std::path::PathBuf,
+    po: std::path::PathBuf,
+    out_po: Option<std::path::PathBuf>,
+    source_lang: Option<String>,
+    source_lang_dir: Option<String>,
+    game_version: Option<String>,
+    include_all_versions: bool,
+    min_similarity: f64,
+    dry_run: bool,
+    format: String,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "po_update_args", root = ?root, po = ?po, out_po = ?out_po, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir, game_version = ?game_version, include_all_versions = include_all_versions, min_similarity = min_similarity, dry_run = dry_run);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+
+    let effective_version = game_version.or(cfg.game_version.clone());
+    let (scan_root, selected_version) = if include_all_versions {
+        (root.clone(), None)
+    } else {
+        resolve_game_version_root(&root, effective_version.as_deref())?
+    };
+    if let Some(ver) = selected_version.as_deref() {
+        tracing::info!(event = "po_update_version_resolved", version = ver, path = %scan_root.display());
+    }
+
+    let effective_source_lang = source_lang.or(cfg.source_lang.clone());
+    let summary = rimloc_services::po_update(
+        &scan_root,
+        &po,
+        out_po.as_deref(),
+        effective_source_lang.as_deref(),
+        source_lang_dir.as_deref(),
+        min_similarity.clamp(0.0, 1.0),
+        dry_run,
+    )?;
+
+    if format == "json" {
+        serde_json::to_writer(std::io::stdout().lock(), &summary)?;
+        return Ok(());
+    }
+    if !dry_run {
+        let target = out_po.as_ref().unwrap_or(&po);
+        ui_ok!("po-update-saved", path = target.display().to_string());
+    }
+    ui_out!(
+        "po-update-summary",
+        kept = summary.kept,
+        fuzzy = summary.fuzzy,
+        added = summary.added,
+        prefilled = summary.prefilled,
+        obsolete = summary.obsolete,
+        total = summary.total
+    );
+    Ok(())
+}
@@ -250,22 +250,21 @@ pub fn run_scan(
             }
         }
         "json" => {
-            #[derive(serde::Serialize)]
-            struct JsonUnit<'a> {
-                schema_version: u32,
-                path: String,
-                line: Option<usize>,
-                key: &'a str,
-                value: Option<&'a str>,
-            }
-            let items: Vec<JsonUnit<'_>> = units
+            let items: Vec<rimloc_domain::ScanUnit> = units
                 .iter()
-                .map(|u| JsonUnit {
+                .map(|u| rimloc_domain::ScanUnit {
                     schema_version: crate::OUTPUT_SCHEMA_VERSION,
                     path: u.path.display().to_string(),
                     line: u.line,
-                    key: u.key.as_str(),
-                    value: u.source.as_deref(),
+                    key: u.key.clone(),
+                    value: u.source.clone(),
+                    kind: u.kind.as_str().to_string(),
+                    def_type: u.def_type.clone(),
+                    def_name: u.def_name.clone(),
+                    field_path: u.field_path.clone(),
+                    origin_path: u.origin_path.as_ref().map(|p| p.display().to_string()),
+                    origin_line: u.origin_line,
+                    comments: u.comments.clone(),
                 })
                 .collect();
 
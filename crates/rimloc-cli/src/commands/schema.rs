@@ -26,6 +26,12 @@ pub fn run_schema(out_dir: std::path::PathBuf) -> color_eyre::Result<()> {
     dump!(rimloc_domain::DiffOutput, "diff_output.schema.json");
     dump!(rimloc_domain::HealthReport, "health_report.schema.json");
     dump!(rimloc_domain::AnnotatePlan, "annotate_plan.schema.json");
+    dump!(rimloc_domain::PoUpdateSummary, "po_update_summary.schema.json");
+    dump!(rimloc_domain::PoIssue, "po_issue.schema.json");
+    dump!(rimloc_domain::PoMergeSummary, "po_merge_summary.schema.json");
+    dump!(rimloc_domain::TmBuildSummary, "tm_build_summary.schema.json");
+    dump!(rimloc_domain::TermCandidate, "term_candidate.schema.json");
+    dump!(rimloc_domain::MtFillSummary, "mt_fill_summary.schema.json");
     crate::ui_ok!("schema-dumped", path = out_dir.display().to_string());
     Ok(())
 }
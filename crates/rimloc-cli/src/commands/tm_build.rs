@@ -0,0 +1,54 @@
+use std::io::IsTerminal;
+
+pub fn run_tm_build(
+    mods: Vec<std::path::PathBuf>,
+    lang: String,
+    out_tm: std::path::PathBuf,
+    source_lang: Option<String>,
+    source_lang_dir: Option<String>,
+    format: String,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "tm_build_args", mods = ?mods, lang = %lang, out_tm = ?out_tm, source_lang = ?source_lang, source_lang_dir = ?source_lang_dir);
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+
+    let source_dir = match (source_lang_dir, source_lang.or(cfg.source_lang.clone())) {
+        (Some(dir), _) => dir,
+        (None, Some(code)) => rimloc_import_po::rimworld_lang_dir(&code),
+        (None, None) => "English".to_string(),
+    };
+    let (index, summary) = rimloc_services::build_tm(&mods, &lang, &source_dir)?;
+    if let Some(parent) = out_tm.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    index.save(&out_tm)?;
+
+    if format == "json" {
+        serde_json::to_writer(std::io::stdout().lock(), &summary)?;
+        return Ok(());
+    }
+    for pair in &summary.translations {
+        if pair.paired == 0 {
+            ui_warn!(
+                "tm-build-unpaired",
+                path = pair.translation.clone(),
+                count = pair.unpaired
+            );
+        } else {
+            ui_out!(
+                "tm-build-pair",
+                path = pair.translation.clone(),
+                sources = pair.sources.join(", "),
+                paired = pair.paired,
+                unpaired = pair.unpaired
+            );
+        }
+    }
+    ui_ok!(
+        "tm-build-saved",
+        path = out_tm.display().to_string(),
+        entries = summary.entries,
+        translations = summary.translations.len(),
+        mods = summary.mods
+    );
+    Ok(())
+}
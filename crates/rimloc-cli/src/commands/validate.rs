@@ -1,5 +1,4 @@
 use crate::version::resolve_game_version_root;
-use rimloc_services::validate_placeholders_cross_language;
 
 #[allow(dead_code)]
 #[allow(clippy::too_many_arguments)]
@@ -15,8 +14,13 @@ pub fn run_validate(
     game_version: Option<String>,
     include_all_versions: bool,
     compare_placeholders: bool,
+    compare_rich_text: bool,
+    compare_grammar: bool,
+    compare_literals: bool,
     target_lang: Option<String>,
     target_lang_dir: Option<String>,
+    glossary: Option<std::path::PathBuf>,
+    strict: bool,
     use_color: bool,
 ) -> color_eyre::Result<()> {
     tracing::debug!(event = "validate_args", root = ?root, game_version = ?game_version, include_all_versions = include_all_versions);
@@ -110,7 +114,27 @@ pub fn run_validate(
         &cli_defs_field,
     )?;
 
-    if compare_placeholders {
+    if selected_version.is_some() {
+        // the mod root's Languages loads together with the picked version folder
+        let extra = rimloc_services::validate_keyed_duplicates_with_root(
+            &root,
+            &scan_root,
+            source_lang.as_deref().or(cfg.source_lang.as_deref()),
+            source_lang_dir.as_deref(),
+        )?;
+        msgs.retain(|m| {
+            m.kind != "duplicate"
+                || !extra
+                    .iter()
+                    .any(|e| e.key == m.key && e.path == m.path && e.line == m.line)
+        });
+        msgs.extend(extra);
+    }
+
+    let glossary = glossary.or(cfg.glossary.clone().map(std::path::PathBuf::from));
+    let compare_any =
+        compare_placeholders || compare_rich_text || compare_grammar || compare_literals;
+    if compare_any || glossary.is_some() {
         // Resolve source and target dirs
         let src_dir = if let Some(dir) = source_lang_dir.clone() {
             dir
@@ -126,37 +150,79 @@ pub fn run_validate(
         } else {
             "Russian".to_string()
         };
-        if let Ok(mut extra) = rimloc_services::validate_placeholders_cross_language(
-            &scan_root,
-            &src_dir,
-            &trg_dir,
-            defs_abs.as_deref(),
-        ) {
-            msgs.append(&mut extra);
+        if compare_placeholders {
+            if let Ok(mut extra) = rimloc_services::validate_placeholders_cross_language(
+                &scan_root,
+                &src_dir,
+                &trg_dir,
+                defs_abs.as_deref(),
+            ) {
+                msgs.append(&mut extra);
+            }
+        }
+        if compare_rich_text {
+            msgs.extend(rimloc_services::validate_rich_text_cross_language(
+                &scan_root,
+                &src_dir,
+                &trg_dir,
+                defs_abs.as_deref(),
+            )?);
+        }
+        if compare_grammar {
+            msgs.extend(rimloc_services::validate_grammar_cross_language(
+                &scan_root,
+                &src_dir,
+                &trg_dir,
+                defs_abs.as_deref(),
+            )?);
+        }
+        if compare_literals {
+            msgs.extend(rimloc_services::validate_literals_cross_language(
+                &scan_root,
+                &src_dir,
+                &trg_dir,
+                defs_abs.as_deref(),
+            )?);
+        }
+        if let Some(path) = glossary.as_deref() {
+            // the glossary language picks the stemmer and the TBX language set
+            let trg_lang = rimloc_import_po::rimworld_lang_code(
+                target_lang
+                    .as_deref()
+                    .or(cfg.target_lang.as_deref())
+                    .unwrap_or(&trg_dir),
+            );
+            let glossary = rimloc_services::Glossary::load(path, Some(&trg_lang))?;
+            msgs.extend(rimloc_services::validate_glossary_cross_language(
+                &scan_root,
+                &src_dir,
+                &trg_dir,
+                defs_abs.as_deref(),
+                &glossary,
+            )?);
         }
     }
+    // --strict fails on error-level kinds only; warnings and info are reported
+    let has_errors = msgs
+        .iter()
+        .any(|m| m.severity() == rimloc_validate::Severity::Error);
     if format == "json" {
-        #[derive(serde::Serialize)]
-        struct JsonMsg<'a> {
-            schema_version: u32,
-            kind: &'a str,
-            key: &'a str,
-            path: &'a str,
-            line: Option<usize>,
-            message: &'a str,
-        }
-        let items: Vec<JsonMsg> = msgs
+        let items: Vec<rimloc_domain::ValidationMsg> = msgs
             .iter()
-            .map(|m| JsonMsg {
+            .map(|m| rimloc_domain::ValidationMsg {
                 schema_version: crate::OUTPUT_SCHEMA_VERSION,
-                kind: m.kind.as_str(),
-                key: m.key.as_str(),
-                path: m.path.as_str(),
+                kind: m.kind.clone(),
+                severity: m.severity().as_str().to_string(),
+                key: m.key.clone(),
+                path: m.path.clone(),
                 line: m.line,
-                message: m.message.as_str(),
+                message: m.message.clone(),
             })
             .collect();
         serde_json::to_writer(std::io::stdout().lock(), &items)?;
+        if strict && has_errors {
+            color_eyre::eyre::bail!(tr!("validate-strict-error"));
+        }
         return Ok(());
     }
     if msgs.is_empty() {
@@ -168,9 +234,11 @@ pub fn run_validate(
         }
     } else {
         for m in msgs {
+            let severity = m.severity();
             if !use_color {
                 println!(
-                    "[{}] {} ({}:{}) — {}",
+                    "{} [{}] {} ({}:{}) — {}",
+                    severity.as_str(),
                     m.kind,
                     m.key,
                     m.path,
@@ -183,12 +251,23 @@ pub fn run_validate(
                     "duplicate" => "⚠",
                     "empty" => "✖",
                     "placeholder-check" => "ℹ",
-                    _ => "•",
+                    "glossary" => "✎",
+                    _ => match severity {
+                        rimloc_validate::Severity::Error => "✖",
+                        rimloc_validate::Severity::Warning => "⚠",
+                        rimloc_validate::Severity::Info => "ℹ",
+                    },
+                };
+                let severity_token = match severity {
+                    rimloc_validate::Severity::Error => severity.as_str().red().to_string(),
+                    rimloc_validate::Severity::Warning => severity.as_str().yellow().to_string(),
+                    rimloc_validate::Severity::Info => severity.as_str().cyan().to_string(),
                 };
                 let plain_kind_token = m.kind.as_str();
                 println!(
-                    "{} [{}] {} ({}:{}) — {}",
+                    "{} {} [{}] {} ({}:{}) — {}",
                     tag,
+                    severity_token,
                     plain_kind_token,
                     m.key.green(),
                     m.path.blue(),
@@ -201,5 +280,8 @@ pub fn run_validate(
             }
         }
     }
+    if strict && has_errors {
+        color_eyre::eyre::bail!(tr!("validate-strict-error"));
+    }
     Ok(())
 }
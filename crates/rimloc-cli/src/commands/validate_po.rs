@@ -0,0 +1,108 @@
+pub fn run_validate_po(
+    po: std::path::PathBuf,
+    strict: bool,
+    format: String,
+    glossary: Option<std::path::PathBuf>,
+    use_color: bool,
+) -> color_eyre::Result<()> {
+    tracing::debug!(event = "validate_po_args", po = ?po, strict = strict, glossary = ?glossary);
+
+    let cfg = rimloc_config::load_config().unwrap_or_default();
+    let report = match glossary.or(cfg.glossary.map(std::path::PathBuf::from)) {
+        Some(g) => rimloc_services::lint_po_with_glossary(&po, &g)?,
+        None => rimloc_services::lint_po(&po)?,
+    };
+
+    if format == "json" {
+        // same array shape as before the lint checks, one item per issue; counts go to stderr
+        serde_json::to_writer(std::io::stdout().lock(), &report.issues)?;
+        ui_info!(
+            "validate-po-stats",
+            total = report.total,
+            untranslated = report.untranslated,
+            fuzzy = report.fuzzy
+        );
+        if strict && !report.issues.is_empty() {
+            color_eyre::eyre::bail!(tr!("validate-po-error"));
+        }
+        return Ok(());
+    }
+
+    let print_stats = || {
+        ui_out!(
+            "validate-po-stats",
+            total = report.total,
+            untranslated = report.untranslated,
+            fuzzy = report.fuzzy
+        )
+    };
+    if report.issues.is_empty() {
+        print_stats();
+        if use_color {
+            use owo_colors::OwoColorize;
+            println!(
+                "{} {}",
+                "✔".green(),
+                tr!("validate-po-ok", count = report.checked)
+            );
+        } else {
+            println!("✔ {}", tr!("validate-po-ok", count = report.checked));
+        }
+        return Ok(());
+    }
+
+    let mark = if use_color {
+        use owo_colors::OwoColorize;
+        "✖".red().to_string()
+    } else {
+        "✖".to_string()
+    };
+    for it in &report.issues {
+        let ctxt_s = it.context.as_deref().unwrap_or("");
+        let ref_s = it.reference.as_deref().unwrap_or("");
+        if it.kind == "placeholder" {
+            println!(
+                "{mark} {}",
+                tr!(
+                    "validate-po-mismatch",
+                    ctxt = ctxt_s.to_string(),
+                    reference = ref_s.to_string()
+                )
+            );
+            println!(
+                "    {}",
+                tr!("validate-po-msgid", value = it.msgid.as_str())
+            );
+            println!(
+                "    {}",
+                tr!("validate-po-msgstr", value = it.msgstr.as_str())
+            );
+            println!(
+                "{}",
+                tr!(
+                    "validate-po-expected",
+                    ph = format!("{:?}", it.expected_placeholders)
+                )
+            );
+            println!(
+                "{}",
+                tr!("validate-po-got", ph = format!("{:?}", it.got_placeholders))
+            );
+        } else {
+            println!(
+                "{mark} [{}] {} ({}) — {}",
+                it.kind, ctxt_s, ref_s, it.message
+            );
+        }
+    }
+    print_stats();
+    println!(
+        "{mark} {}",
+        tr!("validate-po-total-mismatches", count = report.issues.len())
+    );
+
+    if strict {
+        color_eyre::eyre::bail!(tr!("validate-po-error"));
+    }
+    Ok(())
+}
@@ -2,8 +2,6 @@
 
 // use rimloc_validate::validate; // moved into commands
 include!(concat!(env!("OUT_DIR"), "/supported_locales.rs"));
-use crate::placeholders::extract_placeholders;
-use crate::po::parse_po_basic;
 use clap::{Command as ClapCommand, Parser, Subcommand};
 use color_eyre::eyre::Result;
 use i18n_embed::fluent::FluentLanguageLoader;
@@ -13,7 +11,7 @@ use once_cell::sync::OnceCell;
 use rust_embed::RustEmbed;
 use std::io::IsTerminal;
 use std::path::PathBuf;
-use tracing::{debug, error, info};
+use tracing::{error, info};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::Layer;
@@ -32,8 +30,6 @@ static LANG_LOADER: OnceCell<FluentLanguageLoader> = OnceCell::new();
 mod ui;
 
 mod commands;
-mod placeholders;
-mod po;
 mod version;
 
 fn init_i18n() {
@@ -114,6 +110,25 @@ fn pre_scan_quiet() -> bool {
 }
 
 /// Apply localized texts (about/help) to the clap Command using tr!()
+/// Help for the placement args every `import-*` command shares (see `ImportArgs`).
+fn localize_import_args(cmd: ClapCommand) -> ClapCommand {
+    cmd.mut_arg("out_xml", |a| a.help(tr!("help-importpo-out-xml")))
+        .mut_arg("mod_root", |a| a.help(tr!("help-importpo-mod-root")))
+        .mut_arg("lang", |a| a.help(tr!("help-importpo-lang")))
+        .mut_arg("lang_dir", |a| a.help(tr!("help-importpo-lang-dir")))
+        .mut_arg("keep_empty", |a| a.help(tr!("help-importpo-keep-empty")))
+        .mut_arg("dry_run", |a| a.help(tr!("help-importpo-dry-run")))
+        .mut_arg("backup", |a| a.help(tr!("help-importpo-backup")))
+        .mut_arg("single_file", |a| a.help(tr!("help-importpo-single-file")))
+        .mut_arg("game_version", |a| {
+            a.help(tr!("help-importpo-game-version"))
+        })
+        .mut_arg("format", |a| a.help(tr!("help-importpo-format")))
+        .mut_arg("report", |a| a.help(tr!("help-importpo-report")))
+        .mut_arg("incremental", |a| a.help(tr!("help-importpo-incremental")))
+        .mut_arg("only_diff", |a| a.help(tr!("help-importpo-only-diff")))
+}
+
 fn localize_command(mut cmd: ClapCommand) -> ClapCommand {
     // Top-level about
     // Expect FTL key: help-about
@@ -160,6 +175,17 @@ fn localize_command(mut cmd: ClapCommand) -> ClapCommand {
                 owned = owned.mut_arg("include_all_versions", |a| {
                     a.help(tr!("help-validate-include-all"))
                 });
+                owned = owned.mut_arg("compare_rich_text", |a| {
+                    a.help(tr!("help-validate-compare-rich-text"))
+                });
+                owned = owned.mut_arg("compare_grammar", |a| {
+                    a.help(tr!("help-validate-compare-grammar"))
+                });
+                owned = owned.mut_arg("compare_literals", |a| {
+                    a.help(tr!("help-validate-compare-literals"))
+                });
+                owned = owned.mut_arg("glossary", |a| a.help(tr!("help-validate-glossary")));
+                owned = owned.mut_arg("strict", |a| a.help(tr!("help-validate-strict")));
                 *sc = owned;
             }
             "validate-po" => {
@@ -168,6 +194,7 @@ fn localize_command(mut cmd: ClapCommand) -> ClapCommand {
                 owned = owned.mut_arg("po", |a| a.help(tr!("help-validatepo-po")));
                 owned = owned.mut_arg("strict", |a| a.help(tr!("help-validatepo-strict")));
                 owned = owned.mut_arg("format", |a| a.help(tr!("help-validatepo-format")));
+                owned = owned.mut_arg("glossary", |a| a.help(tr!("help-validatepo-glossary")));
                 *sc = owned;
             }
             "diff-xml" => {
@@ -199,33 +226,238 @@ fn localize_command(mut cmd: ClapCommand) -> ClapCommand {
                     a.help(tr!("help-exportpo-source-lang-dir"))
                 });
                 owned = owned.mut_arg("tm_root", |a| a.help(tr!("help-exportpo-tm-root")));
+                owned = owned.mut_arg("tm_min_score", |a| {
+                    a.help(tr!("help-exportpo-tm-min-score"))
+                });
                 owned = owned.mut_arg("game_version", |a| {
                     a.help(tr!("help-exportpo-game-version"))
                 });
                 owned = owned.mut_arg("include_all_versions", |a| {
                     a.help(tr!("help-exportpo-include-all"))
                 });
+                owned = owned.mut_arg("split", |a| a.help(tr!("help-exportpo-split")));
+                owned = owned.mut_arg("compact", |a| a.help(tr!("help-exportpo-compact")));
+                owned = owned.mut_arg("glossary", |a| a.help(tr!("help-exportpo-glossary")));
                 *sc = owned;
             }
             "import-po" => {
                 let mut owned = std::mem::take(sc);
                 owned = owned.about(tr!("help-importpo-about"));
                 owned = owned.mut_arg("po", |a| a.help(tr!("help-importpo-po")));
-                owned = owned.mut_arg("out_xml", |a| a.help(tr!("help-importpo-out-xml")));
-                owned = owned.mut_arg("mod_root", |a| a.help(tr!("help-importpo-mod-root")));
-                owned = owned.mut_arg("lang", |a| a.help(tr!("help-importpo-lang")));
-                owned = owned.mut_arg("lang_dir", |a| a.help(tr!("help-importpo-lang-dir")));
-                owned = owned.mut_arg("keep_empty", |a| a.help(tr!("help-importpo-keep-empty")));
-                owned = owned.mut_arg("dry_run", |a| a.help(tr!("help-importpo-dry-run")));
-                owned = owned.mut_arg("backup", |a| a.help(tr!("help-importpo-backup")));
-                owned = owned.mut_arg("single_file", |a| a.help(tr!("help-importpo-single-file")));
+                owned = localize_import_args(owned);
+                owned = owned.mut_arg("fuzzy", |a| a.help(tr!("help-importpo-fuzzy")));
+                *sc = owned;
+            }
+            "po-update" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-poupdate-about"));
+                owned = owned.mut_arg("root", |a| a.help(tr!("help-poupdate-root")));
+                owned = owned.mut_arg("po", |a| a.help(tr!("help-poupdate-po")));
+                owned = owned.mut_arg("out_po", |a| a.help(tr!("help-poupdate-out-po")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-poupdate-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-poupdate-source-lang-dir"))
+                });
+                owned = owned.mut_arg("game_version", |a| {
+                    a.help(tr!("help-poupdate-game-version"))
+                });
+                owned = owned.mut_arg("include_all_versions", |a| {
+                    a.help(tr!("help-poupdate-include-all"))
+                });
+                owned = owned.mut_arg("min_similarity", |a| {
+                    a.help(tr!("help-poupdate-min-similarity"))
+                });
+                owned = owned.mut_arg("dry_run", |a| a.help(tr!("help-poupdate-dry-run")));
+                owned = owned.mut_arg("format", |a| a.help(tr!("help-poupdate-format")));
+                *sc = owned;
+            }
+            "po-merge" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-pomerge-about"));
+                owned = owned.mut_arg("po", |a| a.help(tr!("help-pomerge-po")));
+                owned = owned.mut_arg("out_po", |a| a.help(tr!("help-pomerge-out-po")));
+                owned = owned.mut_arg("format", |a| a.help(tr!("help-pomerge-format")));
+                *sc = owned;
+            }
+            "export-xliff" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-exportxliff-about"));
+                owned = owned.mut_arg("root", |a| a.help(tr!("help-exportxliff-root")));
+                owned = owned.mut_arg("out_xliff", |a| a.help(tr!("help-exportxliff-out-xliff")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-exportxliff-lang")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-exportpo-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-exportpo-source-lang-dir"))
+                });
+                owned = owned.mut_arg("game_version", |a| {
+                    a.help(tr!("help-exportpo-game-version"))
+                });
+                owned = owned.mut_arg("include_all_versions", |a| {
+                    a.help(tr!("help-exportpo-include-all"))
+                });
+                owned = owned.mut_arg("xliff_version", |a| {
+                    a.help(tr!("help-exportxliff-version"))
+                });
+                *sc = owned;
+            }
+            "import-xliff" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-importxliff-about"));
+                owned = owned.mut_arg("xliff", |a| a.help(tr!("help-importxliff-xliff")));
+                owned = localize_import_args(owned);
+                owned = owned.mut_arg("fuzzy", |a| a.help(tr!("help-importxliff-fuzzy")));
+                *sc = owned;
+            }
+            "export-tmx" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-exporttmx-about"));
+                owned = owned.mut_arg("root", |a| a.help(tr!("help-exporttmx-root")));
+                owned = owned.mut_arg("out_tmx", |a| a.help(tr!("help-exporttmx-out-tmx")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-exporttmx-lang")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-exportpo-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-exportpo-source-lang-dir"))
+                });
+                owned = owned.mut_arg("game_version", |a| {
+                    a.help(tr!("help-exportpo-game-version"))
+                });
+                owned = owned.mut_arg("include_all_versions", |a| {
+                    a.help(tr!("help-exportpo-include-all"))
+                });
+                *sc = owned;
+            }
+            "tm-build" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-tmbuild-about"));
+                owned = owned.mut_arg("mods", |a| a.help(tr!("help-tmbuild-mods")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-tmbuild-lang")));
+                owned = owned.mut_arg("out_tm", |a| a.help(tr!("help-tmbuild-out-tm")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-exportpo-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-exportpo-source-lang-dir"))
+                });
+                owned = owned.mut_arg("format", |a| a.help(tr!("help-tmbuild-format")));
+                *sc = owned;
+            }
+            "extract-terms" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-extractterms-about"));
+                owned = owned.mut_arg("root", |a| a.help(tr!("help-extractterms-root")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-exportpo-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-exportpo-source-lang-dir"))
+                });
+                owned = owned.mut_arg("game_version", |a| {
+                    a.help(tr!("help-exportpo-game-version"))
+                });
+                owned = owned.mut_arg("include_all_versions", |a| {
+                    a.help(tr!("help-exportpo-include-all"))
+                });
+                owned = owned.mut_arg("min_count", |a| a.help(tr!("help-extractterms-min-count")));
+                owned = owned.mut_arg("limit", |a| a.help(tr!("help-extractterms-limit")));
+                owned = owned.mut_arg("out", |a| a.help(tr!("help-extractterms-out")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-extractterms-lang")));
+                owned = owned.mut_arg("format", |a| a.help(tr!("help-extractterms-format")));
+                *sc = owned;
+            }
+            "mt-fill" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-mtfill-about"));
+                owned = owned.mut_arg("po", |a| a.help(tr!("help-mtfill-po")));
+                owned = owned.mut_arg("out_po", |a| a.help(tr!("help-mtfill-out-po")));
+                owned = owned.mut_arg("provider", |a| a.help(tr!("help-mtfill-provider")));
+                owned = owned.mut_arg("url", |a| a.help(tr!("help-mtfill-url")));
+                owned = owned.mut_arg("api_key", |a| a.help(tr!("help-mtfill-api-key")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-mtfill-source-lang")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-mtfill-lang")));
+                owned = owned.mut_arg("batch_size", |a| a.help(tr!("help-mtfill-batch-size")));
+                owned = owned.mut_arg("requests_per_minute", |a| {
+                    a.help(tr!("help-mtfill-requests-per-minute"))
+                });
+                owned = owned.mut_arg("timeout_ms", |a| a.help(tr!("help-mtfill-timeout-ms")));
+                owned = owned.mut_arg("no_cache", |a| a.help(tr!("help-mtfill-no-cache")));
+                owned = owned.mut_arg("dry_run", |a| a.help(tr!("help-mtfill-dry-run")));
+                owned = owned.mut_arg("format", |a| a.help(tr!("help-mtfill-format")));
+                *sc = owned;
+            }
+            "export-csv" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-exportcsv-about"));
+                owned = owned.mut_arg("root", |a| a.help(tr!("help-exportcsv-root")));
+                owned = owned.mut_arg("out_csv", |a| a.help(tr!("help-exportcsv-out-csv")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-exportcsv-lang")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-exportpo-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-exportpo-source-lang-dir"))
+                });
+                owned = owned.mut_arg("game_version", |a| {
+                    a.help(tr!("help-exportpo-game-version"))
+                });
+                owned = owned.mut_arg("include_all_versions", |a| {
+                    a.help(tr!("help-exportpo-include-all"))
+                });
+                owned = owned.mut_arg("delimiter", |a| a.help(tr!("help-exportcsv-delimiter")));
+                owned = owned.mut_arg("bom", |a| a.help(tr!("help-exportcsv-bom")));
+                *sc = owned;
+            }
+            "import-csv" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-importcsv-about"));
+                owned = owned.mut_arg("csv", |a| a.help(tr!("help-importcsv-csv")));
+                owned = owned.mut_arg("delimiter", |a| a.help(tr!("help-importcsv-delimiter")));
+                owned = localize_import_args(owned);
+                owned = owned.mut_arg("fuzzy", |a| a.help(tr!("help-importcsv-fuzzy")));
+                *sc = owned;
+            }
+            "export-xlsx" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-exportxlsx-about"));
+                owned = owned.mut_arg("root", |a| a.help(tr!("help-exportxlsx-root")));
+                owned = owned.mut_arg("out_xlsx", |a| a.help(tr!("help-exportxlsx-out-xlsx")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-exportxlsx-lang")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-exportpo-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-exportpo-source-lang-dir"))
+                });
+                owned = owned.mut_arg("game_version", |a| {
+                    a.help(tr!("help-exportpo-game-version"))
+                });
+                owned = owned.mut_arg("include_all_versions", |a| {
+                    a.help(tr!("help-exportpo-include-all"))
+                });
+                *sc = owned;
+            }
+            "import-xlsx" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-importxlsx-about"));
+                owned = owned.mut_arg("xlsx", |a| a.help(tr!("help-importxlsx-xlsx")));
+                owned = localize_import_args(owned);
+                *sc = owned;
+            }
+            "export-json" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-exportjson-about"));
+                owned = owned.mut_arg("root", |a| a.help(tr!("help-exportjson-root")));
+                owned = owned.mut_arg("out_dir", |a| a.help(tr!("help-exportjson-out-dir")));
+                owned = owned.mut_arg("lang", |a| a.help(tr!("help-exportjson-lang")));
+                owned = owned.mut_arg("source_lang", |a| a.help(tr!("help-exportpo-source-lang")));
+                owned = owned.mut_arg("source_lang_dir", |a| {
+                    a.help(tr!("help-exportpo-source-lang-dir"))
+                });
                 owned = owned.mut_arg("game_version", |a| {
-                    a.help(tr!("help-importpo-game-version"))
+                    a.help(tr!("help-exportpo-game-version"))
                 });
-                owned = owned.mut_arg("format", |a| a.help(tr!("help-importpo-format")));
-                owned = owned.mut_arg("report", |a| a.help(tr!("help-importpo-report")));
-                owned = owned.mut_arg("incremental", |a| a.help(tr!("help-importpo-incremental")));
-                owned = owned.mut_arg("only_diff", |a| a.help(tr!("help-importpo-only-diff")));
+                owned = owned.mut_arg("include_all_versions", |a| {
+                    a.help(tr!("help-exportpo-include-all"))
+                });
+                owned = owned.mut_arg("nested", |a| a.help(tr!("help-exportjson-nested")));
+                *sc = owned;
+            }
+            "import-json" => {
+                let mut owned = std::mem::take(sc);
+                owned = owned.about(tr!("help-importjson-about"));
+                owned = owned.mut_arg("json", |a| a.help(tr!("help-importjson-json")));
+                owned = localize_import_args(owned);
                 *sc = owned;
             }
             "build-mod" => {
@@ -244,6 +476,7 @@ fn localize_command(mut cmd: ClapCommand) -> ClapCommand {
                 owned = owned.mut_arg("lang_dir", |a| a.help(tr!("help-buildmod-lang-dir")));
                 owned = owned.mut_arg("dry_run", |a| a.help(tr!("help-buildmod-dry-run")));
                 owned = owned.mut_arg("dedupe", |a| a.help(tr!("help-buildmod-dedupe")));
+                owned = owned.mut_arg("fuzzy", |a| a.help(tr!("help-buildmod-fuzzy")));
                 *sc = owned;
             }
             "annotate" => {
@@ -457,25 +690,43 @@ enum Commands {
         /// Compare placeholders between source and target language by key
         #[arg(long, default_value_t = false)]
         compare_placeholders: bool,
+        /// Compare rich-text tags between source and target language by key
+        #[arg(long, default_value_t = false)]
+        compare_rich_text: bool,
+        /// Compare grammar rules (rulesStrings) between source and target language
+        #[arg(long, default_value_t = false)]
+        compare_grammar: bool,
+        /// Compare numbers, percents, URLs, hotkeys and \n escapes between source and target language
+        #[arg(long, default_value_t = false)]
+        compare_literals: bool,
         /// Target translation language ISO code
         #[arg(long)]
         lang: Option<String>,
         /// Target translation folder name (e.g., "Russian")
         #[arg(long)]
         lang_dir: Option<String>,
+        /// Glossary (CSV or TBX) to check translations of the target language against.
+        #[arg(long, value_name = "PATH")]
+        glossary: Option<PathBuf>,
+        /// Strict mode: return non-zero exit if any error-level message is found.
+        #[arg(long, default_value_t = false)]
+        strict: bool,
     },
 
-    /// Validate .po placeholder consistency (msgid vs msgstr); help via FTL.
+    /// Lint a .po file (placeholders, duplicates, header, whitespace, XML safety); help via FTL.
     ValidatePo {
         /// Path to .po file.
         #[arg(long)]
         po: PathBuf,
-        /// Strict mode: return non-zero exit if mismatches are found.
+        /// Strict mode: return non-zero exit if any issue is found.
         #[arg(long, default_value_t = false)]
         strict: bool,
         /// Output format for results: "text" (default) or "json".
         #[arg(long, default_value = "text", value_parser = ["text", "json"])]
         format: String,
+        /// Glossary (CSV or TBX) to check translations against.
+        #[arg(long, value_name = "PATH")]
+        glossary: Option<PathBuf>,
     },
 
     /// Diff source vs translation presence, optionally against a baseline PO to detect changed source strings.
@@ -817,49 +1068,360 @@ enum Commands {
         /// Path(s) to translation memory roots (repeatable). Each can be Languages/<lang> or a mod root.
         #[arg(long, value_name = "PATH", num_args = 0.., value_delimiter = None)]
         tm_root: Vec<PathBuf>,
+        /// Minimum similarity (0..1) for fuzzy TM suggestions (default 0.75).
+        #[arg(long, value_name = "SCORE")]
+        tm_min_score: Option<f64>,
         /// Game version folder to operate on (e.g., 1.6 or v1.6).
         #[arg(long)]
         game_version: Option<String>,
         /// Include all version subfolders (disable auto-pick of latest).
         #[arg(long, default_value_t = false)]
         include_all_versions: bool,
+        /// Split the export into several .po files under --out-po (a directory): file, def-type or version.
+        #[arg(long, value_parser = ["file", "def-type", "version"])]
+        split: Option<String>,
+        /// Emit one entry per unique source text; import fans it out to every covered key.
+        #[arg(long, default_value_t = false)]
+        compact: bool,
+        /// Glossary (CSV or TBX); terms found in a source text are listed as PO comments.
+        #[arg(long, value_name = "PATH")]
+        glossary: Option<PathBuf>,
     },
 
     /// Import .po into a single XML or into an existing mod's structure (help via FTL).
     ImportPo {
         #[arg(long)]
         po: PathBuf,
-        #[arg(long, conflicts_with = "mod_root")]
-        out_xml: Option<PathBuf>,
-        #[arg(long, conflicts_with = "out_xml")]
-        mod_root: Option<PathBuf>,
+        #[command(flatten)]
+        args: commands::import_po::ImportArgs,
+        /// How to treat `#, fuzzy` entries: skip (default), include, or mark with an XML comment.
+        #[arg(long, value_parser = ["skip", "include", "mark"])]
+        fuzzy: Option<String>,
+    },
+
+    /// Update an existing .po against current mod sources, msgmerge-style (help via FTL).
+    PoUpdate {
+        /// Path to mod root.
+        #[arg(short, long)]
+        root: PathBuf,
+        /// Existing translated .po to update.
         #[arg(long)]
-        lang: Option<String>,
+        po: PathBuf,
+        /// Write the updated .po here instead of overwriting --po.
         #[arg(long)]
-        lang_dir: Option<String>,
+        out_po: Option<PathBuf>,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
+        /// Game version folder to operate on (e.g., 1.6 or v1.6).
+        #[arg(long)]
+        game_version: Option<String>,
+        /// Include all version subfolders (disable auto-pick of latest).
         #[arg(long, default_value_t = false)]
-        keep_empty: bool,
+        include_all_versions: bool,
+        /// Minimal similarity (0..1) to pre-fill new entries from existing translations.
+        #[arg(long, default_value_t = rimloc_services::DEFAULT_MIN_SIMILARITY)]
+        min_similarity: f64,
+        /// Dry-run: do not write files, print summary only.
         #[arg(long, default_value_t = false)]
         dry_run: bool,
+        /// Output format: "text" (default) or "json".
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Merge several .po files (or directories of them) into one (help via FTL).
+    PoMerge {
+        /// Input .po files or directories (repeatable).
+        #[arg(long, required = true, num_args = 1..)]
+        po: Vec<PathBuf>,
+        /// Output .po path.
+        #[arg(long)]
+        out_po: PathBuf,
+        /// Output format: "text" (default) or "json".
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Export extracted strings to XLIFF for CAT tools (help via FTL).
+    ExportXliff {
+        /// Path to mod root.
+        #[arg(short, long)]
+        root: PathBuf,
+        /// Output .xlf path.
+        #[arg(long)]
+        out_xliff: PathBuf,
+        /// Target language ISO code (trgLang; existing translations become targets).
+        #[arg(long)]
+        lang: Option<String>,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
+        /// Game version folder to operate on (e.g., 1.6 or v1.6).
+        #[arg(long)]
+        game_version: Option<String>,
+        /// Include all version subfolders (disable auto-pick of latest).
         #[arg(long, default_value_t = false)]
-        backup: bool,
+        include_all_versions: bool,
+        /// XLIFF version to write: 2.0 (default) or 1.2.
+        #[arg(long, default_value = "2.0", value_parser = ["1.2", "2.0"])]
+        xliff_version: String,
+    },
+
+    /// Import a translated XLIFF into a single XML or into an existing mod's structure (help via FTL).
+    ImportXliff {
+        #[arg(long)]
+        xliff: PathBuf,
+        #[command(flatten)]
+        args: commands::import_po::ImportArgs,
+        /// How to treat filled units still in state new/initial: skip (default), include, or mark.
+        #[arg(long, value_parser = ["skip", "include", "mark"])]
+        fuzzy: Option<String>,
+    },
+
+    /// Export source/target pairs of a translated mod to a TMX translation memory (help via FTL).
+    ExportTmx {
+        /// Path to mod root.
+        #[arg(short, long)]
+        root: PathBuf,
+        /// Output .tmx path.
+        #[arg(long)]
+        out_tmx: PathBuf,
+        /// Target language ISO code or folder name (translations under Languages/<lang>).
+        #[arg(long)]
+        lang: String,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
+        /// Game version folder to operate on (e.g., 1.6 or v1.6).
+        #[arg(long)]
+        game_version: Option<String>,
+        /// Include all version subfolders (disable auto-pick of latest).
         #[arg(long, default_value_t = false)]
-        single_file: bool,
+        include_all_versions: bool,
+    },
+
+    /// Build a translation memory from translation mods in a Mods/workshop folder (help via FTL).
+    TmBuild {
+        /// Mods folder, Steam workshop content folder or single mod (repeatable).
+        #[arg(long, required = true, num_args = 1..)]
+        mods: Vec<PathBuf>,
+        /// Target language ISO code or folder name.
+        #[arg(long)]
+        lang: String,
+        /// Output TM file (.json).
+        #[arg(long)]
+        out_tm: PathBuf,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
+        /// Output format: "text" (default) or "json".
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Extract recurring terms from a mod's source texts into a glossary skeleton (help via FTL).
+    ExtractTerms {
+        /// Path to mod root.
+        #[arg(short, long)]
+        root: PathBuf,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
         /// Game version folder to operate on (e.g., 1.6 or v1.6).
         #[arg(long)]
         game_version: Option<String>,
-        /// Output format for reports or dry-run: "text" (default) or "json".
+        /// Include all version subfolders (disable auto-pick of latest).
+        #[arg(long, default_value_t = false)]
+        include_all_versions: bool,
+        /// Minimum number of uses.
+        #[arg(long, default_value_t = 2)]
+        min_count: usize,
+        /// Maximum number of terms (0 = all).
+        #[arg(long, default_value_t = 200)]
+        limit: usize,
+        /// Write a glossary skeleton (.csv, .tsv or .tbx).
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        /// Target language of the skeleton (ISO code or folder name).
+        #[arg(long)]
+        lang: Option<String>,
+        /// Output format: "text" (default) or "json".
         #[arg(long, default_value = "text", value_parser = ["text", "json"])]
         format: String,
-        /// Print a summary of created/updated/skipped files and total keys written.
+    },
+
+    /// Pre-fill untranslated PO entries with machine translation, marked fuzzy (help via FTL).
+    MtFill {
+        /// PO file to fill.
+        #[arg(long)]
+        po: PathBuf,
+        /// Write the result here instead of updating --po in place.
+        #[arg(long)]
+        out_po: Option<PathBuf>,
+        /// Provider: libretranslate (default) or dummy.
+        #[arg(long, value_parser = ["libretranslate", "dummy"])]
+        provider: Option<String>,
+        /// LibreTranslate-compatible server URL.
+        #[arg(long)]
+        url: Option<String>,
+        /// API key for the server (or LIBRETRANSLATE_API_KEY).
+        #[arg(long)]
+        api_key: Option<String>,
+        /// Source language ISO code (default: PO header, then en).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Target language ISO code (default: PO Language header).
+        #[arg(long)]
+        lang: Option<String>,
+        /// Strings per request.
+        #[arg(long)]
+        batch_size: Option<usize>,
+        /// Maximum requests per minute.
+        #[arg(long)]
+        requests_per_minute: Option<u32>,
+        /// HTTP timeout, ms.
+        #[arg(long)]
+        timeout_ms: Option<u64>,
+        /// Do not read or write the translation cache.
+        #[arg(long, default_value_t = false)]
+        no_cache: bool,
+        /// Translate but do not write the PO.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        /// Output format: "text" (default) or "json".
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Export extracted strings to a bilingual CSV for spreadsheets (help via FTL).
+    ExportCsv {
+        /// Path to mod root.
+        #[arg(short, long)]
+        root: PathBuf,
+        /// Output .csv path.
+        #[arg(long)]
+        out_csv: PathBuf,
+        /// Target language ISO code or folder name (current translations from Languages/<lang>).
+        #[arg(long)]
+        lang: Option<String>,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
+        /// Game version folder to operate on (e.g., 1.6 or v1.6).
+        #[arg(long)]
+        game_version: Option<String>,
+        /// Include all version subfolders (disable auto-pick of latest).
+        #[arg(long, default_value_t = false)]
+        include_all_versions: bool,
+        /// Field delimiter: a single character or "tab".
+        #[arg(long, default_value = ",")]
+        delimiter: String,
+        /// Start the file with a UTF-8 BOM (for Excel).
         #[arg(long, default_value_t = false)]
-        report: bool,
-        /// Skip writing files whose content would be identical.
+        bom: bool,
+    },
+
+    /// Import a translated bilingual CSV into a single XML or into an existing mod's structure (help via FTL).
+    ImportCsv {
+        #[arg(long)]
+        csv: PathBuf,
+        /// Field delimiter: a single character or "tab" (default: guessed from the header).
+        #[arg(long)]
+        delimiter: Option<String>,
+        #[command(flatten)]
+        args: commands::import_po::ImportArgs,
+        /// How to treat rows with status "fuzzy": skip (default), include, or mark.
+        #[arg(long, value_parser = ["skip", "include", "mark"])]
+        fuzzy: Option<String>,
+    },
+
+    /// Export extracted strings to an XLSX workbook, one sheet per file or def type (help via FTL).
+    ExportXlsx {
+        /// Path to mod root.
+        #[arg(short, long)]
+        root: PathBuf,
+        /// Output .xlsx path.
+        #[arg(long)]
+        out_xlsx: PathBuf,
+        /// Target language ISO code or folder name (current translations from Languages/<lang>).
+        #[arg(long)]
+        lang: Option<String>,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
+        /// Game version folder to operate on (e.g., 1.6 or v1.6).
+        #[arg(long)]
+        game_version: Option<String>,
+        /// Include all version subfolders (disable auto-pick of latest).
+        #[arg(long, default_value_t = false)]
+        include_all_versions: bool,
+    },
+
+    /// Import a translated XLSX workbook into a single XML or into an existing mod's structure (help via FTL).
+    ImportXlsx {
+        #[arg(long)]
+        xlsx: PathBuf,
+        #[command(flatten)]
+        args: commands::import_po::ImportArgs,
+    },
+
+    /// Export extracted strings as key/value JSON per language for Weblate/Crowdin (help via FTL).
+    ExportJson {
+        /// Path to mod root.
+        #[arg(short, long)]
+        root: PathBuf,
+        /// Output folder; files go to <out-dir>/<LangDir>/Keyed|DefInjected/….json.
+        #[arg(long)]
+        out_dir: PathBuf,
+        /// Target language ISO code or folder name (current translations from Languages/<lang>).
+        #[arg(long)]
+        lang: Option<String>,
+        /// Source language by ISO code (mapped via rimworld_lang_dir).
+        #[arg(long)]
+        source_lang: Option<String>,
+        /// Source language folder name (e.g., "English"). Takes precedence over --source-lang.
+        #[arg(long)]
+        source_lang_dir: Option<String>,
+        /// Game version folder to operate on (e.g., 1.6 or v1.6).
+        #[arg(long)]
+        game_version: Option<String>,
+        /// Include all version subfolders (disable auto-pick of latest).
         #[arg(long, default_value_t = false)]
-        incremental: bool,
-        /// Only write changed/new keys for each file (skip unchanged keys)
+        include_all_versions: bool,
+        /// Write dotted keys as nested objects.
         #[arg(long, default_value_t = false)]
-        only_diff: bool,
+        nested: bool,
+    },
+
+    /// Import translated key/value JSON into a single XML or into an existing mod's structure (help via FTL).
+    ImportJson {
+        /// JSON file or language folder made by export-json.
+        #[arg(long)]
+        json: PathBuf,
+        #[command(flatten)]
+        args: commands::import_po::ImportArgs,
     },
 
     /// Build a standalone translation mod from a .po file (help via FTL).
@@ -889,6 +1451,9 @@ enum Commands {
         /// Remove duplicate keys within a single XML file (last wins)
         #[arg(long, default_value_t = false)]
         dedupe: bool,
+        /// How to treat `#, fuzzy` entries: skip (default), include, or mark with an XML comment.
+        #[arg(long, value_parser = ["skip", "include", "mark"])]
+        fuzzy: Option<String>,
     },
 }
 
@@ -974,8 +1539,13 @@ impl Runnable for Commands {
                 game_version,
                 include_all_versions,
                 compare_placeholders,
+                compare_rich_text,
+                compare_grammar,
+                compare_literals,
                 lang,
                 lang_dir,
+                glossary,
+                strict,
             } => commands::validate::run_validate(
                 root,
                 source_lang,
@@ -988,133 +1558,22 @@ impl Runnable for Commands {
                 game_version,
                 include_all_versions,
                 compare_placeholders,
+                compare_rich_text,
+                compare_grammar,
+                compare_literals,
                 lang,
                 lang_dir,
+                glossary,
+                strict,
                 use_color,
             ),
 
-            Commands::ValidatePo { po, strict, format } => {
-                debug!(event = "validate_po_args", po = ?po, strict = strict);
-
-                let entries = parse_po_basic(&po)?;
-                let mut mismatches = Vec::new();
-                let mut checked = 0usize;
-
-                for (ctx, msgid, msgstr, reference) in entries {
-                    // пропускаем заголовок PO (msgid "") и пустые переводы
-                    if msgid.is_empty() {
-                        continue;
-                    }
-                    if msgstr.trim().is_empty() {
-                        continue;
-                    }
-                    checked += 1;
-
-                    let src_ph = extract_placeholders(&msgid);
-                    let dst_ph = extract_placeholders(&msgstr);
-
-                    if src_ph != dst_ph {
-                        mismatches.push((ctx, reference, msgid, msgstr, src_ph, dst_ph));
-                    }
-                }
-
-                if format == "json" {
-                    #[derive(serde::Serialize)]
-                    struct PoMismatch<'a> {
-                        context: Option<&'a str>,
-                        reference: Option<&'a str>,
-                        msgid: &'a str,
-                        msgstr: &'a str,
-                        expected_placeholders: Vec<String>,
-                        got_placeholders: Vec<String>,
-                    }
-                    let items: Vec<PoMismatch> = mismatches
-                        .iter()
-                        .map(|(ctx, reference, id, strv, src_ph, dst_ph)| PoMismatch {
-                            context: ctx.as_deref(),
-                            reference: reference.as_deref(),
-                            msgid: id.as_str(),
-                            msgstr: strv.as_str(),
-                            expected_placeholders: src_ph.iter().cloned().collect(),
-                            got_placeholders: dst_ph.iter().cloned().collect(),
-                        })
-                        .collect();
-                    serde_json::to_writer(std::io::stdout().lock(), &items)?;
-                    if strict && !items.is_empty() {
-                        color_eyre::eyre::bail!(tr!("validate-po-error"));
-                    }
-                    return Ok(());
-                }
-                if mismatches.is_empty() {
-                    if use_color {
-                        use owo_colors::OwoColorize;
-                        println!("{} {}", "✔".green(), tr!("validate-po-ok", count = checked));
-                    } else {
-                        println!("✔ {}", tr!("validate-po-ok", count = checked));
-                    }
-                    Ok(())
-                } else {
-                    for (ctx, reference, id, strv, src_ph, dst_ph) in &mismatches {
-                        let ctxt_s = ctx.as_deref().unwrap_or("");
-                        let ref_s = reference.as_deref().unwrap_or("");
-
-                        if use_color {
-                            use owo_colors::OwoColorize;
-                            println!(
-                                "{} {}",
-                                "✖".red(),
-                                tr!(
-                                    "validate-po-mismatch",
-                                    ctxt = ctxt_s.to_string(),
-                                    reference = ref_s.to_string()
-                                )
-                            );
-                            println!("    {}", tr!("validate-po-msgid", value = id));
-                            println!("    {}", tr!("validate-po-msgstr", value = strv));
-                            println!(
-                                "{}",
-                                tr!("validate-po-expected", ph = format!("{:?}", src_ph))
-                            );
-                            println!("{}", tr!("validate-po-got", ph = format!("{:?}", dst_ph)));
-                        } else {
-                            println!(
-                                "✖ {}",
-                                tr!(
-                                    "validate-po-mismatch",
-                                    ctxt = ctxt_s.to_string(),
-                                    reference = ref_s.to_string()
-                                )
-                            );
-                            println!("    {}", tr!("validate-po-msgid", value = id));
-                            println!("    {}", tr!("validate-po-msgstr", value = strv));
-                            println!(
-                                "{}",
-                                tr!("validate-po-expected", ph = format!("{:?}", src_ph))
-                            );
-                            println!("{}", tr!("validate-po-got", ph = format!("{:?}", dst_ph)));
-                        }
-                    }
-                    if use_color {
-                        use owo_colors::OwoColorize;
-                        println!(
-                            "{} {}",
-                            "✖".red(),
-                            tr!("validate-po-total-mismatches", count = mismatches.len())
-                        );
-                    } else {
-                        println!(
-                            "✖ {}",
-                            tr!("validate-po-total-mismatches", count = mismatches.len())
-                        );
-                    }
-
-                    if strict {
-                        color_eyre::eyre::bail!(tr!("validate-po-error"));
-                    } else {
-                        Ok(())
-                    }
-                }
-            }
+            Commands::ValidatePo {
+                po,
+                strict,
+                format,
+                glossary,
+            } => commands::validate_po::run_validate_po(po, strict, format, glossary, use_color),
 
             Commands::DiffXml {
                 apply_flags,
@@ -1182,8 +1641,12 @@ impl Runnable for Commands {
                 source_lang,
                 source_lang_dir,
                 tm_root,
+                tm_min_score,
                 game_version,
                 include_all_versions,
+                split,
+                compact,
+                glossary,
             } => commands::export_po::run_export_po(
                 root,
                 out_po,
@@ -1191,42 +1654,233 @@ impl Runnable for Commands {
                 source_lang,
                 source_lang_dir,
                 tm_root,
+                tm_min_score,
                 game_version,
                 include_all_versions,
+                split,
+                compact,
+                glossary,
             ),
 
-            Commands::ImportPo {
+            Commands::ImportPo { po, args, fuzzy } => {
+                commands::import_po::run_import_po(po, args, fuzzy)
+            }
+
+            Commands::PoUpdate {
+                root,
                 po,
-                out_xml,
-                mod_root,
-                lang,
-                lang_dir,
-                keep_empty,
+                out_po,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                min_similarity,
                 dry_run,
-                backup,
-                single_file,
+                format,
+            } => commands::po_update::run_po_update(
+                root,
+                po,
+                out_po,
+                source_lang,
+                source_lang_dir,
                 game_version,
+                include_all_versions,
+                min_similarity,
+                dry_run,
                 format,
-                report,
-                incremental,
-                only_diff,
-            } => commands::import_po::run_import_po(
+            ),
+
+            Commands::PoMerge { po, out_po, format } => {
+                commands::po_merge::run_po_merge(po, out_po, format)
+            }
+
+            Commands::ExportXliff {
+                root,
+                out_xliff,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                xliff_version,
+            } => commands::export_xliff::run_export_xliff(
+                root,
+                out_xliff,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                xliff_version,
+            ),
+
+            Commands::ImportXliff { xliff, args, fuzzy } => {
+                commands::import_xliff::run_import_xliff(xliff, args, fuzzy)
+            }
+
+            Commands::ExportTmx {
+                root,
+                out_tmx,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+            } => commands::export_tmx::run_export_tmx(
+                root,
+                out_tmx,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+            ),
+
+            Commands::TmBuild {
+                mods,
+                lang,
+                out_tm,
+                source_lang,
+                source_lang_dir,
+                format,
+            } => commands::tm_build::run_tm_build(
+                mods,
+                lang,
+                out_tm,
+                source_lang,
+                source_lang_dir,
+                format,
+            ),
+
+            Commands::ExtractTerms {
+                root,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                min_count,
+                limit,
+                out,
+                lang,
+                format,
+            } => commands::extract_terms::run_extract_terms(
+                root,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                min_count,
+                limit,
+                out,
+                lang,
+                format,
+            ),
+
+            Commands::MtFill {
                 po,
-                out_xml,
-                mod_root,
+                out_po,
+                provider,
+                url,
+                api_key,
+                source_lang,
                 lang,
-                lang_dir,
-                keep_empty,
+                batch_size,
+                requests_per_minute,
+                timeout_ms,
+                no_cache,
                 dry_run,
-                backup,
-                single_file,
-                game_version,
                 format,
-                report,
-                incremental,
-                only_diff,
+            } => commands::mt_fill::run_mt_fill(
+                po,
+                out_po,
+                provider,
+                url,
+                api_key,
+                source_lang,
+                lang,
+                batch_size,
+                requests_per_minute,
+                timeout_ms,
+                no_cache,
+                dry_run,
+                format,
+            ),
+
+            Commands::ExportCsv {
+                root,
+                out_csv,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                delimiter,
+                bom,
+            } => commands::export_csv::run_export_csv(
+                root,
+                out_csv,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                delimiter,
+                bom,
             ),
 
+            Commands::ImportCsv {
+                csv,
+                delimiter,
+                args,
+                fuzzy,
+            } => commands::import_csv::run_import_csv(csv, delimiter, args, fuzzy),
+
+            Commands::ExportXlsx {
+                root,
+                out_xlsx,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+            } => commands::export_xlsx::run_export_xlsx(
+                root,
+                out_xlsx,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+            ),
+
+            Commands::ImportXlsx { xlsx, args } => {
+                commands::import_xlsx::run_import_xlsx(xlsx, args)
+            }
+
+            Commands::ExportJson {
+                root,
+                out_dir,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                nested,
+            } => commands::export_json::run_export_json(
+                root,
+                out_dir,
+                lang,
+                source_lang,
+                source_lang_dir,
+                game_version,
+                include_all_versions,
+                nested,
+            ),
+
+            Commands::ImportJson { json, args } => {
+                commands::import_json::run_import_json(json, args)
+            }
+
             Commands::BuildMod {
                 po,
                 out_mod,
@@ -1239,6 +1893,7 @@ impl Runnable for Commands {
                 lang_dir,
                 dry_run,
                 dedupe,
+                fuzzy,
             } => commands::build_mod::run_build_mod(
                 po,
                 out_mod,
@@ -1251,6 +1906,7 @@ impl Runnable for Commands {
                 lang_dir,
                 dry_run,
                 dedupe,
+                fuzzy,
             ),
 
             Commands::XmlHealth {
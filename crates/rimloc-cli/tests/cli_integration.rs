@@ -1,6 +1,6 @@
 use assert_cmd::prelude::*;
 use predicates::prelude::*;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::{fs, path::PathBuf, process::Command};
 
 include!(concat!(env!("OUT_DIR"), "/supported_locales.rs"));
@@ -639,6 +639,176 @@ fn validate_detects_issues_in_bad_xml() {
     );
 }
 
+#[test]
+fn validate_strict_fails_only_on_error_level_messages() {
+    // duplicates, empty values and placeholder checks are warnings
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "validate", "--strict", "--root"])
+        .arg(fixture("test/TestMod"));
+    let assert = cmd.assert().success();
+    let out = String::from_utf8_lossy(assert.get_output().stdout.as_ref()).to_string();
+    assert!(out.contains("warning [duplicate]"), "{out}");
+
+    let tmp = tempfile::tempdir().expect(&ti18n!("test-tempdir"));
+    write_keyed(tmp.path(), "English", "  <Bold>&lt;b&gt;open</Bold>");
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "validate", "--strict", "--root"])
+        .arg(tmp.path());
+    let assert = cmd.assert().failure();
+    let out = String::from_utf8_lossy(assert.get_output().stdout.as_ref()).to_string();
+    assert!(out.contains("error [rich-text] Bold"), "{out}");
+}
+
+#[test]
+fn validate_reports_each_check_kind() {
+    const KEYED: &str = "Keyed/Sample.xml";
+    // (file, key, English, Russian or "" to leave untranslated, kind, severity, message)
+    let cases: &[(&str, &str, &str, &str, &str, &str, &str)] = &[
+        (
+            KEYED,
+            "Broken",
+            "&lt;b&gt;Never closed",
+            "",
+            "rich-text",
+            "error",
+            "Unclosed tag <b>",
+        ),
+        (
+            KEYED,
+            "Bold",
+            "&lt;b&gt;Bold&lt;/b&gt;",
+            "&lt;i&gt;Жирный&lt;/i&gt;",
+            "rich-text",
+            "error",
+            "Rich-text tags differ from source: expected [<b> </b>], got [<i> </i>]",
+        ),
+        (
+            KEYED,
+            "Blink",
+            "Press &lt;Enter&gt;",
+            "Нажмите &lt;Enter&gt; &lt;blink&gt;сейчас&lt;/blink&gt;",
+            "rich-text-unknown",
+            "info",
+            "Unknown rich-text tag <blink> is not in source",
+        ),
+        (
+            KEYED,
+            "Switch",
+            "{0_nameDef} gave {1}.",
+            "{0_nameDef} отдал{0_gender ? him} {1}.",
+            "placeholder-check",
+            "warning",
+            "Gender switch {0_gender ? him} needs 2 or 3 options separated by ':'",
+        ),
+        (
+            KEYED,
+            "Gave",
+            "{PAWN_labelShort} gave {1} to {2}.",
+            "{PAWN_gender ? Он : Она} отдал {1}.",
+            "placeholder-check",
+            "warning",
+            "Placeholder mismatch vs source; missing {2}",
+        ),
+        (
+            "DefInjected/RulePackDef/Rules.xml",
+            "Hit.rulePack.rulesStrings",
+            "<li>r_logentry->[INITIATOR_definite] [hitverb].</li><li>hitverb->smacked</li>",
+            "<li>r_logentry->[INITIATOR_definite] врезал.</li><li>hitverb->врезал</li>",
+            "grammar",
+            "error",
+            "Symbol [hitverb] from source is not used",
+        ),
+        (
+            KEYED,
+            "Damage",
+            "Deals x2 damage.",
+            "Наносит x3 урона.",
+            "number-mismatch",
+            "warning",
+            "Numbers differ from source; missing 2; not in source 3",
+        ),
+        (
+            KEYED,
+            "Chance",
+            "25% chance",
+            "шанс 20 %",
+            "percent-mismatch",
+            "warning",
+            "Percent values differ from source; missing 25%; not in source 20%",
+        ),
+        (
+            KEYED,
+            "Wiki",
+            "See https://example.com/wiki.",
+            "См. https://example.org/wiki",
+            "url-mismatch",
+            "error",
+            "URLs differ from source; missing https://example.com/wiki; not in source https://example.org/wiki",
+        ),
+        (
+            KEYED,
+            "Lines",
+            "One\\nTwo",
+            "Один Два",
+            "newline-mismatch",
+            "info",
+            "\\n escapes differ from source: 1 in source, 0 in translation",
+        ),
+        (
+            KEYED,
+            "Save",
+            "Ctrl+S to save",
+            "Ctrl+C — сохранить",
+            "hotkey-mismatch",
+            "warning",
+            "Hotkeys differ from source; missing ctrl+s; not in source ctrl+c",
+        ),
+    ];
+
+    let tmp = tempfile::tempdir().expect(&ti18n!("test-tempdir"));
+    let mut files: BTreeMap<(&str, &str), String> = BTreeMap::new();
+    for (file, key, en, ru, ..) in cases {
+        for (lang, text) in [("English", en), ("Russian", ru)] {
+            if !text.is_empty() {
+                let body = files.entry((lang, file)).or_default();
+                body.push_str(&format!("  <{key}>{text}</{key}>\n"));
+            }
+        }
+    }
+    for ((lang, file), body) in &files {
+        write_file(
+            &tmp.path().join("Languages").join(lang).join(file),
+            &format!("<LanguageData>\n{body}</LanguageData>\n"),
+        );
+    }
+
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "validate", "--format", "json", "--root"])
+        .arg(tmp.path())
+        .args(["--compare-placeholders", "--compare-rich-text"])
+        .args(["--compare-grammar", "--compare-literals", "--lang", "ru"]);
+    let assert = cmd.assert().success();
+    let msgs: Vec<serde_json::Value> =
+        serde_json::from_slice(&assert.get_output().stdout).expect("json");
+    let found: BTreeSet<(&str, &str, &str, &str)> = msgs
+        .iter()
+        .map(|m| {
+            (
+                m["key"].as_str().unwrap(),
+                m["kind"].as_str().unwrap(),
+                m["severity"].as_str().unwrap(),
+                m["message"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    for (_, key, _, _, kind, severity, message) in cases {
+        assert!(
+            found.contains(&(*key, *kind, *severity, *message)),
+            "{found:#?}"
+        );
+    }
+}
+
 #[test]
 fn import_po_requires_target() {
     let mut cmd = bin_cmd();
@@ -758,6 +928,220 @@ fn validate_po_strict_mismatch() {
     );
 }
 
+#[test]
+fn validate_po_json_reports_lint_issues() {
+    let tmp = tempfile::tempdir().expect(&ti18n!("test-tempdir"));
+    let po = tmp.path().join("lint.po");
+    std::fs::write(
+        &po,
+        concat!(
+            "msgid \"\"\nmsgstr \"\"\n\n",
+            "msgctxt \"Greeting|Keyed/A.xml\"\nmsgid \"Hello\\n\"\nmsgstr \" Привет\"\n\n",
+            "msgctxt \"Greeting|Keyed/A.xml\"\nmsgid \"Hi\"\nmsgstr \"\"\n\n",
+            "#, fuzzy\nmsgctxt \"Bad Key|Keyed/A.xml\"\nmsgid \"Bye\"\nmsgstr \"Пока\"\n",
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "--ui-lang", "en", "validate-po", "--po"])
+        .arg(&po)
+        .args(["--format", "json", "--strict"]);
+    let assert = cmd.assert().failure();
+    let out = String::from_utf8_lossy(assert.get_output().stdout.as_ref()).to_string();
+    let issues: Vec<serde_json::Value> = serde_json::from_str(out.trim()).expect("json array");
+    for it in &issues {
+        for field in ["context", "reference", "msgid", "msgstr"] {
+            assert!(it.get(field).is_some(), "{field} missing in {it}");
+        }
+        assert!(it["expected_placeholders"].is_array(), "{it}");
+        assert!(it["got_placeholders"].is_array(), "{it}");
+    }
+    // Fluent wraps arguments in Unicode isolation marks
+    let err = String::from_utf8_lossy(assert.get_output().stderr.as_ref())
+        .replace(['\u{2068}', '\u{2069}'], "");
+    assert!(
+        err.contains("Entries: 3, untranslated: 1, fuzzy: 1"),
+        "{err}"
+    );
+    let kinds: Vec<&str> = issues.iter().map(|i| i["kind"].as_str().unwrap()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "language-header",
+            "whitespace",
+            "newlines",
+            "duplicate-msgctxt",
+            "invalid-key"
+        ]
+    );
+}
+
+#[test]
+fn export_po_split_then_merge_and_import_dir() {
+    let tmp = tempfile::tempdir().expect(&ti18n!("test-tempdir"));
+    let split_dir = tmp.path().join("split");
+
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "export-po", "--root"])
+        .arg(fixture("test/TestMod"))
+        .args(["--out-po"])
+        .arg(&split_dir)
+        .args(["--lang", "ru", "--include-all-versions", "--split", "def-type"]);
+    cmd.assert().success();
+    assert!(split_dir.join("Keyed/Bad.po").is_file());
+    assert!(split_dir.join("DefInjected/ThingDef.po").is_file());
+
+    let merged = tmp.path().join("merged.po");
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "po-merge", "--po"])
+        .arg(&split_dir)
+        .args(["--out-po"])
+        .arg(&merged)
+        .args(["--format", "json"]);
+    let assert = cmd.assert().success();
+    let out = String::from_utf8_lossy(assert.get_output().stdout.as_ref()).to_string();
+    let summary: serde_json::Value = serde_json::from_str(out.trim()).expect("valid json summary");
+    assert_eq!(summary["files"], 2);
+    assert_eq!(summary["duplicates"], 0);
+    let merged_text = std::fs::read_to_string(&merged).unwrap();
+    assert_eq!(
+        merged_text.matches("msgctxt ").count(),
+        summary["entries"].as_u64().unwrap() as usize
+    );
+
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "import-po", "--po"])
+        .arg(&split_dir)
+        .args(["--out-xml"])
+        .arg(tmp.path().join("out.xml"))
+        .args(["--keep-empty", "--dry-run"]);
+    cmd.assert().success();
+}
+
+#[test]
+fn export_po_split_by_version_round_trips_into_version_folders() {
+    let tmp = tempfile::tempdir().expect(&ti18n!("test-tempdir"));
+    let mod_root = tmp.path().join("Mod");
+    for ver in ["1.4", "1.5"] {
+        let keyed = mod_root.join(ver).join("Languages/English/Keyed");
+        fs::create_dir_all(&keyed).unwrap();
+        fs::write(
+            keyed.join("A.xml"),
+            "<LanguageData>\n  <Hi>Hello</Hi>\n</LanguageData>\n",
+        )
+        .unwrap();
+    }
+
+    let split_dir = tmp.path().join("split");
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "export-po", "--root"])
+        .arg(&mod_root)
+        .args(["--out-po"])
+        .arg(&split_dir)
+        .args(["--lang", "ru", "--include-all-versions", "--split", "version"]);
+    cmd.assert().success();
+    for ver in ["1.4", "1.5"] {
+        let po = split_dir.join(format!("{ver}.po"));
+        let text = fs::read_to_string(&po).unwrap();
+        assert!(text.contains(&format!("#: {ver}/Languages/English/Keyed/A.xml")), "{text}");
+        fs::write(
+            &po,
+            text.replace(
+                "msgid \"Hello\"\nmsgstr \"\"",
+                &format!("msgid \"Hello\"\nmsgstr \"Привет {ver}\""),
+            ),
+        )
+        .unwrap();
+    }
+
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "import-po", "--po"])
+        .arg(&split_dir)
+        .args(["--mod-root"])
+        .arg(&mod_root)
+        .args(["--lang", "ru"]);
+    cmd.assert().success();
+    for ver in ["1.4", "1.5"] {
+        let xml =
+            fs::read_to_string(mod_root.join(ver).join("Languages/Russian/Keyed/A.xml")).unwrap();
+        assert_eq!(xml.matches("<Hi>").count(), 1, "{xml}");
+        assert!(xml.contains(&format!("<Hi>Привет {ver}</Hi>")), "{xml}");
+    }
+}
+
+#[test]
+fn import_po_keeps_last_value_of_repeated_key() {
+    let tmp = tempfile::tempdir().expect(&ti18n!("test-tempdir"));
+    let mod_root = tmp.path().join("Mod");
+    let po = tmp.path().join("dup.po");
+    fs::write(
+        &po,
+        concat!(
+            "#: Languages/English/Keyed/A.xml:2\n",
+            "msgctxt \"Hi|Keyed/A.xml\"\nmsgid \"Hello\"\nmsgstr \"Привет\"\n\n",
+            "#: Languages/English/Keyed/A.xml:2\n",
+            "msgctxt \"Hi|Keyed/A.xml:2\"\nmsgid \"Hello\"\nmsgstr \"Здравствуй\"\n",
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = bin_cmd();
+    cmd.args(["import-po", "--po"])
+        .arg(&po)
+        .args(["--mod-root"])
+        .arg(&mod_root)
+        .args(["--lang", "ru"]);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Hi"));
+    let xml = fs::read_to_string(mod_root.join("Languages/Russian/Keyed/A.xml")).unwrap();
+    assert_eq!(xml.matches("<Hi>").count(), 1, "{xml}");
+    assert!(xml.contains("<Hi>Здравствуй</Hi>"), "{xml}");
+}
+
+#[test]
+fn compact_export_fans_out_on_import() {
+    let tmp = tempfile::tempdir().expect(&ti18n!("test-tempdir"));
+    let mod_root = tmp.path().join("Mod");
+    let keyed = mod_root.join("Languages/English/Keyed");
+    fs::create_dir_all(&keyed).unwrap();
+    fs::write(
+        keyed.join("A.xml"),
+        "<LanguageData>\n  <ToggleA>Toggle</ToggleA>\n  <ToggleB>Toggle</ToggleB>\n  <Other>Other</Other>\n</LanguageData>\n",
+    )
+    .unwrap();
+
+    let po = tmp.path().join("compact.po");
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "export-po", "--root"])
+        .arg(&mod_root)
+        .args(["--out-po"])
+        .arg(&po)
+        .args(["--lang", "ru", "--compact"]);
+    cmd.assert().success();
+    let text = fs::read_to_string(&po).unwrap();
+    assert_eq!(text.matches("msgctxt ").count(), 2);
+    assert!(text.contains("#. rimloc-key: ToggleB|Keyed/A.xml"));
+
+    let translated = text
+        .replacen("msgid \"Toggle\"\nmsgstr \"\"", "msgid \"Toggle\"\nmsgstr \"Переключить\"", 1)
+        .replacen("msgid \"Other\"\nmsgstr \"\"", "msgid \"Other\"\nmsgstr \"Другое\"", 1);
+    fs::write(&po, translated).unwrap();
+
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "import-po", "--po"])
+        .arg(&po)
+        .args(["--mod-root"])
+        .arg(&mod_root)
+        .args(["--lang-dir", "Russian"]);
+    cmd.assert().success();
+    let xml = fs::read_to_string(mod_root.join("Languages/Russian/Keyed/A.xml")).unwrap();
+    assert!(xml.contains("<ToggleA>Переключить</ToggleA>"));
+    assert!(xml.contains("<ToggleB>Переключить</ToggleB>"));
+    assert!(xml.contains("<Other>Другое</Other>"));
+}
+
 #[test]
 fn import_single_file_dry_run_path() {
     let mut cmd = bin_cmd();
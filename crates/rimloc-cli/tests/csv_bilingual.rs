@@ -0,0 +1,80 @@
+use assert_cmd::prelude::*;
+use std::{fs, path::Path};
+
+mod helpers;
+use helpers::*;
+
+fn write_fixture(root: &Path) {
+    write_mod(
+        root,
+        "  <Greeting>Hello, {0}!</Greeting>\n  <Letter>Line one;\n\"Line\" two</Letter>\n  <Count>%d items</Count>\n  <Bye>Bye</Bye>",
+    );
+}
+
+#[test]
+fn csv_round_trip_through_mod_tree() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_fixture(&root);
+    let csv = tmp.path().join("mod.csv");
+
+    bin_cmd()
+        .args(["--quiet", "export-csv", "--root"])
+        .arg(&root)
+        .arg("--out-csv")
+        .arg(&csv)
+        .args(["--lang", "ru", "--delimiter", ";", "--bom"])
+        .assert()
+        .success();
+    let text = fs::read_to_string(&csv).unwrap();
+    assert!(text.starts_with("\u{feff}key;kind;file;source;translation;status;notes\n"));
+    assert!(text.contains(
+        "Greeting;keyed;Languages/English/Keyed/Sample.xml;Hello, {0}!;;new;Placeholders: {0}\n"
+    ));
+    assert!(text.contains(
+        "Letter;keyed;Languages/English/Keyed/Sample.xml;\"Line one;\n\"\"Line\"\" two\";;new;"
+    ));
+    assert!(text.contains("Bye;keyed;Languages/English/Keyed/Sample.xml;Bye;Пока;translated;"));
+
+    // таблицу сохранили с CRLF; статус fuzzy по умолчанию не импортируется
+    let translated = text
+        .replace("Hello, {0}!;;new", "Hello, {0}!;Привет, {0}!;new")
+        .replace(
+            "\"\"Line\"\" two\";;new",
+            "\"\"Line\"\" two\";\"Строка один;\n\"\"Строка\"\" два\";translated",
+        )
+        .replace("%d items;;new", "%d items;%d шт.;fuzzy")
+        .replace('\n', "\r\n");
+    fs::write(&csv, translated).unwrap();
+
+    bin_cmd()
+        .args(["--quiet", "import-csv", "--csv"])
+        .arg(&csv)
+        .arg("--mod-root")
+        .arg(&root)
+        .args(["--lang", "ru"])
+        .assert()
+        .success();
+    let xml = fs::read_to_string(root.join("Languages/Russian/Keyed/Sample.xml")).unwrap();
+    assert!(xml.contains("<Greeting>Привет, {0}!</Greeting>"), "{xml}");
+    assert!(
+        xml.contains("<Letter>Строка один;\n&quot;Строка&quot; два</Letter>"),
+        "{xml}"
+    );
+    assert!(xml.contains("<Bye>Пока</Bye>"), "{xml}");
+    assert!(!xml.contains("<Count>"), "{xml}");
+}
+
+#[test]
+fn import_csv_rejects_bad_delimiter() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let csv = tmp.path().join("mod.csv");
+    fs::write(&csv, "key,translation\nA,Б\n").unwrap();
+    bin_cmd()
+        .args(["--quiet", "import-csv", "--csv"])
+        .arg(&csv)
+        .args(["--delimiter", "||", "--out-xml"])
+        .arg(tmp.path().join("out.xml"))
+        .assert()
+        .failure();
+}
@@ -0,0 +1,81 @@
+use assert_cmd::prelude::*;
+use std::fs;
+
+mod helpers;
+use helpers::*;
+
+#[test]
+fn extract_terms_ranks_terms_and_writes_glossary_skeleton() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_file(
+        &root.join("Defs/Things.xml"),
+        r#"<Defs>
+  <ThingDef><defName>Plasteel</defName><label>plasteel</label><description>A light alloy.</description></ThingDef>
+  <ThingDef><defName>Wall</defName><label>plasteel wall</label><description>A wall made of plasteel.</description></ThingDef>
+</Defs>
+"#,
+    );
+    write_file(
+        &root.join("Languages/English/Keyed/Events.xml"),
+        r#"<LanguageData>
+  <RaidStarted>The Mech Hive sends {0} mechanoids.</RaidStarted>
+  <RaidEnded>The Mech Hive retreats; the mechanoid raid is over.</RaidEnded>
+  <Trade>Sell plasteel to traders.</Trade>
+</LanguageData>
+"#,
+    );
+
+    let out = bin_cmd()
+        .args(["--quiet", "extract-terms", "--root"])
+        .arg(&root)
+        .args(["--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let terms: serde_json::Value = serde_json::from_slice(&out).expect("json terms");
+    let terms = terms.as_array().expect("array");
+    let find = |t: &str| terms.iter().find(|c| c["term"] == t).cloned();
+    let plasteel = find("plasteel").expect("plasteel");
+    assert_eq!(plasteel["kind"], "def-label");
+    assert_eq!(plasteel["def"], "ThingDef/Plasteel");
+    assert_eq!(
+        (plasteel["count"].as_u64(), plasteel["files"].as_u64()),
+        (Some(3), Some(2))
+    );
+    assert_eq!(find("Mech Hive").expect("Mech Hive")["kind"], "name");
+    assert_eq!(find("mechanoid").expect("mechanoid")["count"], 2);
+
+    let csv = tmp.path().join("glossary.csv");
+    bin_cmd()
+        .args(["--quiet", "extract-terms", "--root"])
+        .arg(&root)
+        .args(["--lang", "ru", "--limit", "2", "--out"])
+        .arg(&csv)
+        .assert()
+        .success();
+    let text = fs::read_to_string(&csv).unwrap();
+    let mut lines = text.lines();
+    assert_eq!(lines.next(), Some("source,target,forbidden,note"));
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("plasteel,,,\"def-label: 3 uses in 2 files;"));
+    assert_eq!(lines.count(), 1);
+
+    // the skeleton is a glossary validate-po can read
+    let po = tmp.path().join("ru.po");
+    write_file(
+        &po,
+        "msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n\nmsgctxt \"Trade\"\nmsgid \"Sell plasteel to traders.\"\nmsgstr \"Продайте пласталь торговцам.\"\n",
+    );
+    bin_cmd()
+        .args(["--quiet", "validate-po", "--po"])
+        .arg(&po)
+        .arg("--glossary")
+        .arg(&csv)
+        .assert()
+        .success();
+}
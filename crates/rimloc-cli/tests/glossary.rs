@@ -0,0 +1,83 @@
+use assert_cmd::prelude::*;
+use std::fs;
+
+mod helpers;
+use helpers::*;
+
+#[test]
+fn glossary_is_enforced_by_validate_and_validate_po_and_exported_to_po() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_keyed(
+        &root,
+        "English",
+        "  <Arrived>Two colonists arrived</Arrived>\n  <Left>A colonist left</Left>",
+    );
+    write_keyed(
+        &root,
+        "Russian",
+        "  <Arrived>Прибыли два колониста</Arrived>\n  <Left>Поселенец ушёл</Left>",
+    );
+    let glossary = tmp.path().join("terms.csv");
+    fs::write(
+        &glossary,
+        "source;target;forbidden;note\nColonist;Колонист;Поселен*;member of the colony\n",
+    )
+    .unwrap();
+
+    let out = bin_cmd()
+        .args(["--quiet", "validate", "--format", "json", "--root"])
+        .arg(&root)
+        .args(["--lang", "ru", "--glossary"])
+        .arg(&glossary)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let msgs: Vec<serde_json::Value> = serde_json::from_slice(&out).expect("json");
+    let glossary_msgs: Vec<_> = msgs.iter().filter(|m| m["kind"] == "glossary").collect();
+    assert_eq!(glossary_msgs.len(), 1, "{msgs:?}");
+    assert_eq!(glossary_msgs[0]["key"], "Left");
+    assert!(glossary_msgs[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("forbidden \"Поселен*\""));
+
+    let po = tmp.path().join("mod.ru.po");
+    bin_cmd()
+        .args(["--quiet", "export-po", "--lang", "ru", "--root"])
+        .arg(&root)
+        .arg("--out-po")
+        .arg(&po)
+        .arg("--glossary")
+        .arg(&glossary)
+        .assert()
+        .success();
+    let text = fs::read_to_string(&po).unwrap();
+    assert!(
+        text.contains("#. Glossary: Colonist → Колонист (not: Поселен*) — member of the colony\n"),
+        "{text}"
+    );
+
+    let translated = text.replacen(
+        "msgid \"A colonist left\"\nmsgstr \"\"",
+        "msgid \"A colonist left\"\nmsgstr \"Поселенец ушёл\"",
+        1,
+    );
+    assert_ne!(translated, text);
+    fs::write(&po, translated).unwrap();
+    let out = bin_cmd()
+        .args(["--quiet", "validate-po", "--format", "json", "--po"])
+        .arg(&po)
+        .arg("--glossary")
+        .arg(&glossary)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let issues: Vec<serde_json::Value> = serde_json::from_slice(&out).expect("json");
+    let kinds: Vec<_> = issues.iter().map(|i| i["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, vec!["glossary"]);
+}
@@ -1,6 +1,11 @@
+// Shared by several test crates; each uses only part of it.
+#![allow(dead_code)]
+
+use assert_cmd::prelude::*;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::process::Command;
 
 pub const CTX_NONE: &str = "n/a";
 
@@ -807,3 +812,28 @@ pub fn assert_dir_contains_xml(dir: &std::path::Path, context_msg: &str, ctx_key
         "<no xml files>"
     );
 }
+
+/// Fresh `rimloc-cli` invocation.
+pub fn bin_cmd() -> Command {
+    Command::cargo_bin("rimloc-cli").expect("rimloc-cli built")
+}
+
+/// Write `body` to `path`, creating parent directories.
+pub fn write_file(path: &Path, body: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, body).unwrap();
+}
+
+/// Write `Languages/<lang>/Keyed/Sample.xml` with `body` inside `<LanguageData>`.
+pub fn write_keyed(root: &Path, lang: &str, body: &str) {
+    write_file(
+        &root.join("Languages").join(lang).join("Keyed/Sample.xml"),
+        &format!("<LanguageData>\n{body}\n</LanguageData>\n"),
+    );
+}
+
+/// Mod with English `Sample.xml` holding `english` and a Russian one translating only `Bye`.
+pub fn write_mod(root: &Path, english: &str) {
+    write_keyed(root, "English", english);
+    write_keyed(root, "Russian", "  <Bye>Пока</Bye>");
+}
@@ -0,0 +1,95 @@
+use assert_cmd::prelude::*;
+use serde::Deserialize;
+use std::{fs, path::PathBuf};
+
+mod helpers;
+use helpers::*;
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Summary {
+    keys: usize,
+    fuzzy_skipped: usize,
+    fuzzy_imported: usize,
+}
+
+const PO: &str = r#"msgid ""
+msgstr ""
+"Language: ru\n"
+
+#: Languages/English/Keyed/Sample.xml:3
+msgctxt "Reviewed|Keyed/Sample.xml"
+msgid "Hello"
+msgstr "Привет"
+
+#: Languages/English/Keyed/Sample.xml:4
+#, fuzzy
+msgctxt "Prefilled|Keyed/Sample.xml"
+msgid "Hello there"
+msgstr "Привет"
+
+#~ msgctxt "Removed|Keyed/Sample.xml"
+#~ msgid "Gone"
+#~ msgstr "Нет"
+"#;
+
+fn import(fuzzy: Option<&str>) -> (Summary, String) {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root: PathBuf = tmp.path().join("Mod");
+    let po_path = tmp.path().join("ru.po");
+    fs::write(&po_path, PO).unwrap();
+
+    let mut cmd = bin_cmd();
+    cmd.args(["--quiet", "import-po", "--po"])
+        .arg(&po_path)
+        .arg("--mod-root")
+        .arg(&root)
+        .args(["--lang", "ru", "--report", "--format", "json"]);
+    if let Some(policy) = fuzzy {
+        cmd.args(["--fuzzy", policy]);
+    }
+    let assert = cmd.assert().success();
+    let out = String::from_utf8_lossy(assert.get_output().stdout.as_ref()).to_string();
+    // stdout: last non-empty line is JSON summary
+    let json_line = out
+        .lines()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .expect("have json line");
+    let summary: Summary = serde_json::from_str(json_line).expect("json summary");
+    let xml = fs::read_to_string(
+        root.join("Languages")
+            .join("Russian")
+            .join("Keyed")
+            .join("Sample.xml"),
+    )
+    .expect("imported file");
+    (summary, xml)
+}
+
+#[test]
+fn import_skips_fuzzy_by_default() {
+    let (summary, xml) = import(None);
+    assert_eq!(summary.keys, 1);
+    assert_eq!(summary.fuzzy_skipped, 1);
+    assert_eq!(summary.fuzzy_imported, 0);
+    assert!(xml.contains("<Reviewed>"));
+    assert!(!xml.contains("<Prefilled>"));
+    assert!(
+        !xml.contains("<Removed>"),
+        "obsolete entries must not be imported"
+    );
+}
+
+#[test]
+fn import_marks_fuzzy_with_comment() {
+    let (summary, xml) = import(Some("mark"));
+    assert_eq!(summary.keys, 2);
+    assert_eq!(summary.fuzzy_imported, 1);
+    assert!(xml.contains("<!-- fuzzy -->"));
+    assert!(!xml.contains("<Removed>"));
+
+    let (summary, xml) = import(Some("include"));
+    assert_eq!((summary.keys, summary.fuzzy_imported), (2, 1));
+    assert!(xml.contains("<Prefilled>") && !xml.contains("<!--"));
+}
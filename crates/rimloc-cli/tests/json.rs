@@ -0,0 +1,74 @@
+use assert_cmd::prelude::*;
+use std::{fs, path::Path};
+
+mod helpers;
+use helpers::*;
+
+fn write_fixture(root: &Path) {
+    write_mod(root, "  <Greeting>Hello, {0}!</Greeting>\n  <Bye>Bye</Bye>");
+    write_file(
+        &root.join("Defs/Things.xml"),
+        "<Defs><ThingDef><defName>Beer</defName><label>beer</label><description>Tasty.</description></ThingDef></Defs>",
+    );
+}
+
+#[test]
+fn json_round_trip_through_mod_tree() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_fixture(&root);
+    let out = tmp.path().join("json");
+
+    bin_cmd()
+        .args(["--quiet", "export-json", "--root"])
+        .arg(&root)
+        .arg("--out-dir")
+        .arg(&out)
+        .args(["--lang", "ru", "--nested"])
+        .assert()
+        .success();
+
+    let source: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out.join("English/Keyed/Sample.json")).unwrap())
+            .unwrap();
+    assert_eq!(source["Greeting"], "Hello, {0}!");
+    let defs_path = out.join("Russian/DefInjected/ThingDef/Things.json");
+    let defs: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&defs_path).unwrap()).unwrap();
+    assert_eq!(defs["Beer"]["label"], "");
+    let keyed_path = out.join("Russian/Keyed/Sample.json");
+    let keyed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&keyed_path).unwrap()).unwrap();
+    assert_eq!(keyed["Bye"], "Пока");
+
+    // как после выгрузки из платформы: часть ключей переведена
+    fs::write(
+        &keyed_path,
+        r#"{ "Greeting": "Привет, {0}!", "Bye": "Пока" }"#,
+    )
+    .unwrap();
+    fs::write(
+        &defs_path,
+        r#"{ "Beer": { "label": "пиво", "description": "" } }"#,
+    )
+    .unwrap();
+
+    bin_cmd()
+        .args(["--quiet", "import-json", "--json"])
+        .arg(out.join("Russian"))
+        .arg("--mod-root")
+        .arg(&root)
+        .args(["--lang", "ru"])
+        .assert()
+        .success();
+    let xml = fs::read_to_string(root.join("Languages/Russian/Keyed/Sample.xml")).unwrap();
+    assert!(xml.contains("<Greeting>Привет, {0}!</Greeting>"), "{xml}");
+    assert!(xml.contains("<Bye>Пока</Bye>"), "{xml}");
+    let defs_xml =
+        fs::read_to_string(root.join("Languages/Russian/DefInjected/ThingDef/Things.xml")).unwrap();
+    assert!(
+        defs_xml.contains("<Beer.label>пиво</Beer.label>"),
+        "{defs_xml}"
+    );
+    assert!(!defs_xml.contains("Beer.description"), "{defs_xml}");
+}
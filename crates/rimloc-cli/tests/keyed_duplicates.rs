@@ -0,0 +1,122 @@
+use std::path::Path;
+
+mod helpers;
+use helpers::*;
+
+fn write_keyed_file(dir: &Path, name: &str, body: &str) {
+    write_file(
+        &dir.join(name),
+        &format!("<LanguageData>\n{body}</LanguageData>\n"),
+    );
+}
+
+fn validate_json(root: &Path, extra: &[&str]) -> Vec<serde_json::Value> {
+    let out = bin_cmd()
+        .args(["--quiet", "validate", "--root"])
+        .arg(root)
+        .args(["--format", "json"])
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let all: Vec<serde_json::Value> = serde_json::from_slice(&out.stdout).unwrap();
+    all.into_iter()
+        .filter(|m| m["kind"] == "duplicate")
+        .collect()
+}
+
+#[test]
+fn duplicates_across_keyed_files_follow_load_order() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    let en = root.join("Languages/English/Keyed");
+    write_keyed_file(
+        &en,
+        "A.xml",
+        "  <Hello>Hello root</Hello>\n  <Shared>a</Shared>\n",
+    );
+    write_keyed_file(&en, "B.xml", "  <Shared>b</Shared>\n");
+    write_keyed_file(
+        &root.join("1.4/Languages/English/Keyed"),
+        "A.xml",
+        "  <Hello>Hello 1.4</Hello>\n",
+    );
+    write_keyed_file(
+        &root.join("1.5/Languages/English/Keyed"),
+        "A.xml",
+        "  <Hello>Hello 1.5</Hello>\n",
+    );
+    // другой язык — не дубль
+    write_keyed_file(
+        &root.join("Languages/Russian/Keyed"),
+        "A.xml",
+        "  <Shared>р</Shared>\n",
+    );
+
+    let msgs = validate_json(&root, &["--game-version", "1.5"]);
+    let shared: Vec<_> = msgs.iter().filter(|m| m["key"] == "Shared").collect();
+    assert_eq!(shared.len(), 2, "{msgs:?}");
+    assert!(shared
+        .iter()
+        .all(|m| !m["path"].as_str().unwrap().contains("Russian")));
+    let kept_b = shared
+        .iter()
+        .find(|m| m["path"].as_str().unwrap().ends_with("B.xml"))
+        .unwrap();
+    assert_eq!(kept_b["line"], 2);
+    assert!(
+        kept_b["message"]
+            .as_str()
+            .unwrap()
+            .contains("A.xml:3 \"a\""),
+        "{kept_b}"
+    );
+
+    let hello: Vec<_> = msgs.iter().filter(|m| m["key"] == "Hello").collect();
+    assert_eq!(hello.len(), 2, "{msgs:?}");
+    let root_hello = hello
+        .iter()
+        .find(|m| !m["path"].as_str().unwrap().contains("1.5"))
+        .unwrap();
+    assert!(root_hello["message"]
+        .as_str()
+        .unwrap()
+        .contains("\"Hello 1.5\""));
+    assert!(!msgs
+        .iter()
+        .any(|m| m["path"].as_str().unwrap().contains("1.4")));
+
+    // все версии сразу: 1.4 и 1.5 конфликтуют только с корнем
+    let msgs = validate_json(&root, &["--include-all-versions"]);
+    let hello: Vec<_> = msgs.iter().filter(|m| m["key"] == "Hello").collect();
+    assert_eq!(hello.len(), 4, "{msgs:?}");
+    assert!(hello
+        .iter()
+        .any(|m| m["message"].as_str().unwrap().contains("\"Hello 1.4\"")));
+}
+
+#[test]
+fn key_repeated_in_one_file_and_another_is_reported_once_per_place() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    let en = root.join("Languages/English/Keyed");
+    write_keyed_file(
+        &en,
+        "A.xml",
+        "  <Shared>a</Shared>\n  <Shared>a2</Shared>\n  <Local>x</Local>\n  <Local>y</Local>\n",
+    );
+    write_keyed_file(&en, "B.xml", "  <Shared>b</Shared>\n");
+
+    let msgs = validate_json(&root, &[]);
+    let shared: Vec<_> = msgs.iter().filter(|m| m["key"] == "Shared").collect();
+    assert_eq!(shared.len(), 3, "{msgs:?}");
+    assert!(
+        shared
+            .iter()
+            .all(|m| m["message"].as_str().unwrap().contains("in 3 places")),
+        "{msgs:?}"
+    );
+    let local: Vec<_> = msgs.iter().filter(|m| m["key"] == "Local").collect();
+    assert_eq!(local.len(), 1, "{msgs:?}");
+    assert_eq!(local[0]["message"], "Duplicate key in file");
+}
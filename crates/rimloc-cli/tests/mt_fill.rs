@@ -0,0 +1,131 @@
+use assert_cmd::prelude::*;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::{fs, path::Path};
+
+mod helpers;
+use helpers::*;
+
+/// Minimal LibreTranslate stand-in: answers `POST /translate` with "ru: <text>"
+/// for every `q`, recording the request bodies.
+fn serve_translate() -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.expect("connection");
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut len = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    len = v.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body).unwrap();
+            let req: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let out: Vec<String> = req["q"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|q| format!("ru: {}", q.as_str().unwrap()))
+                .collect();
+            log.lock().unwrap().push(req);
+            let resp = serde_json::json!({ "translatedText": out }).to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{resp}",
+                resp.len()
+            )
+            .unwrap();
+        }
+    });
+    (url, seen)
+}
+
+fn write_po(path: &Path) {
+    fs::write(
+        path,
+        "msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n\"X-RimLoc-Source-Lang: English\\n\"\n\n\
+         msgctxt \"Arrived\"\nmsgid \"<color=red>{PAWN_label}</color> & [THING_label] arrived\"\nmsgstr \"\"\n\n\
+         msgctxt \"Hello\"\nmsgid \"Hello\"\nmsgstr \"Привет\"\n\n\
+         msgctxt \"Raid\"\nmsgid \"Raid\"\nmsgstr \"\"\n\n\
+         msgctxt \"Count\"\nmsgid \"{0}/{1}\"\nmsgstr \"\"\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn mt_fill_prefills_fuzzy_entries_through_libretranslate_api() {
+    let (url, seen) = serve_translate();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let po = tmp.path().join("ru.po");
+    write_po(&po);
+    let cfg_dir = tmp.path().join("cfg");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    fs::write(
+        cfg_dir.join("rimloc.toml"),
+        format!(
+            "[mt]\ncache_dir = \"{}\"\n",
+            tmp.path().join("cache").display()
+        ),
+    )
+    .unwrap();
+
+    let out = bin_cmd()
+        .current_dir(&cfg_dir)
+        .args(["--quiet", "mt-fill", "--po"])
+        .arg(&po)
+        .args(["--url", &url, "--batch-size", "1", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let summary: serde_json::Value = serde_json::from_slice(&out).expect("json summary");
+    assert_eq!(summary["provider"], "libretranslate");
+    assert_eq!(summary["untranslated"], 3);
+    assert_eq!(summary["translated"], 2);
+    assert_eq!(summary["requests"], 2);
+
+    let reqs = seen.lock().unwrap().clone();
+    assert_eq!(reqs[0]["source"], "en");
+    assert_eq!(reqs[0]["target"], "ru");
+    assert_eq!(reqs[0]["format"], "html");
+    assert_eq!(
+        reqs[0]["q"][0],
+        "<x id=\"0\"/><x id=\"1\"/><x id=\"2\"/> &amp; <x id=\"3\"/> arrived"
+    );
+
+    let text = fs::read_to_string(&po).unwrap();
+    assert!(text.contains(
+        "#. MT libretranslate\n#, fuzzy\nmsgctxt \"Arrived\"\nmsgid \"<color=red>{PAWN_label}</color> & [THING_label] arrived\"\nmsgstr \"ru: <color=red>{PAWN_label}</color> & [THING_label] arrived\""
+    ));
+    assert!(text.contains("msgid \"Hello\"\nmsgstr \"Привет\""));
+    assert!(text.contains("msgid \"Raid\"\nmsgstr \"ru: Raid\""));
+    // nothing but placeholders: left empty
+    assert!(text.contains("msgid \"{0}/{1}\"\nmsgstr \"\""));
+
+    // a second PO with the same strings is served from the cache
+    let other = tmp.path().join("other.po");
+    write_po(&other);
+    bin_cmd()
+        .current_dir(&cfg_dir)
+        .args(["--quiet", "mt-fill", "--po"])
+        .arg(&other)
+        .args(["--url", &url])
+        .assert()
+        .success();
+    assert_eq!(seen.lock().unwrap().len(), 2);
+    assert!(fs::read_to_string(&other)
+        .unwrap()
+        .contains("msgstr \"ru: Raid\""));
+}
@@ -0,0 +1,51 @@
+use assert_cmd::prelude::*;
+use std::{fs, path::PathBuf};
+
+mod helpers;
+use helpers::*;
+
+fn fixture(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(rel)
+}
+
+#[test]
+fn export_stamps_mod_identity_and_import_warns_on_other_mod() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let po = tmp.path().join("ru.po");
+    bin_cmd()
+        .args(["--quiet", "export-po", "--root"])
+        .arg(fixture("test/TestMod"))
+        .arg("--out-po")
+        .arg(&po)
+        .args(["--lang", "ru", "--include-all-versions"])
+        .assert()
+        .success();
+    let text = fs::read_to_string(&po).expect("po written");
+    assert!(text.contains("\"X-RimLoc-Package-Id: test.mod\\n\""));
+    assert!(text.contains("\"Project-Id-Version: Test Mod\\n\""));
+    assert!(text.contains("\"X-RimLoc-Source-Fingerprint: sha256:"));
+    assert!(!text.contains("\"POT-Creation-Date: \\n\""));
+
+    let other = tmp.path().join("Other");
+    fs::create_dir_all(other.join("About")).unwrap();
+    fs::write(
+        other.join("About/About.xml"),
+        "<ModMetaData><packageId>other.mod</packageId></ModMetaData>",
+    )
+    .unwrap();
+    let assert = bin_cmd()
+        .args(["--quiet", "import-po", "--po"])
+        .arg(&po)
+        .arg("--mod-root")
+        .arg(&other)
+        .args(["--lang", "ru", "--dry-run"])
+        .assert()
+        .success();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    assert!(
+        stderr.contains("test.mod") && stderr.contains("other.mod"),
+        "expected mod mismatch warning, got: {stderr}"
+    );
+}
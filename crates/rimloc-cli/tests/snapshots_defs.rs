@@ -27,10 +27,12 @@ fn sanitize_json_units(mut v: Value) -> Value {
             (ak, ap).cmp(&(bk, bp))
         });
         for obj in arr.iter_mut() {
-            if let Some(p) = obj.get_mut("path") {
-                if let Some(s) = p.as_str() {
-                    let norm = s.replace(&ws, "<WS>");
-                    *p = Value::String(norm);
+            for field in ["path", "origin_path"] {
+                if let Some(p) = obj.get_mut(field) {
+                    if let Some(s) = p.as_str() {
+                        let norm = s.replace(&ws, "<WS>");
+                        *p = Value::String(norm);
+                    }
                 }
             }
         }
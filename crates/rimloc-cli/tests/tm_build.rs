@@ -0,0 +1,69 @@
+use assert_cmd::prelude::*;
+use std::{fs, path::Path};
+
+mod helpers;
+use helpers::*;
+
+fn write_mod(root: &Path, about: &str, lang: &str, body: &str) {
+    write_file(
+        &root.join("About/About.xml"),
+        &format!("<ModMetaData>{about}</ModMetaData>\n"),
+    );
+    write_file(
+        &root.join("Languages").join(lang).join("Keyed/Sample.xml"),
+        &format!("<LanguageData>\n{body}\n</LanguageData>\n"),
+    );
+}
+
+#[test]
+fn tm_build_harvests_workshop_translations_for_export_po() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let workshop = tmp.path().join("workshop/content/294100");
+    write_mod(
+        &workshop.join("1001"),
+        "<packageId>author.doors</packageId>",
+        "English",
+        "  <Door>Open the heavy door</Door>\n  <Lock>Lock the door</Lock>",
+    );
+    write_mod(
+        &workshop.join("2002"),
+        "<packageId>fan.doors.ru</packageId><loadAfter><li>author.doors</li></loadAfter>",
+        "Russian (Русский)",
+        "  <Door>Откройте тяжёлую дверь</Door>\n  <Lock>Заприте дверь</Lock>",
+    );
+
+    let tm = tmp.path().join("tm/ru.json");
+    let out = bin_cmd()
+        .args(["--quiet", "tm-build", "--mods"])
+        .arg(tmp.path().join("workshop/content"))
+        .args(["--lang", "ru", "--format", "json", "--out-tm"])
+        .arg(&tm)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let summary: serde_json::Value = serde_json::from_slice(&out).expect("json summary");
+    assert_eq!(summary["mods"], 2);
+    assert_eq!(summary["entries"], 2);
+    assert_eq!(summary["translations"][0]["matched_by"], "package-id");
+    assert_eq!(summary["translations"][0]["sources"][0], "author.doors");
+
+    let new = tmp.path().join("NewMod");
+    write_file(
+        &new.join("Languages/English/Keyed/Sample.xml"),
+        "<LanguageData>\n  <HeavyDoor>Open the heavy door</HeavyDoor>\n</LanguageData>\n",
+    );
+    let po = tmp.path().join("new.po");
+    bin_cmd()
+        .args(["--quiet", "export-po", "--root"])
+        .arg(&new)
+        .arg("--out-po")
+        .arg(&po)
+        .args(["--lang", "ru", "--tm-root"])
+        .arg(&tm)
+        .assert()
+        .success();
+    let text = fs::read_to_string(&po).unwrap();
+    assert!(text.contains("msgstr \"Откройте тяжёлую дверь\""), "{text}");
+}
@@ -0,0 +1,114 @@
+use assert_cmd::prelude::*;
+use std::fs;
+
+mod helpers;
+use helpers::*;
+
+#[test]
+fn fuzzy_tm_suggests_edited_sentences_and_renamed_keys() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let old = tmp.path().join("OldMod");
+    write_keyed(
+        &old,
+        "English",
+        "  <Door>Open the heavy door now</Door>\n  <Greeting>Hello, {0}!</Greeting>",
+    );
+    write_keyed(
+        &old,
+        "Russian",
+        "  <Door>Откройте тяжёлую дверь</Door>\n  <Greeting>Привет, {0}!</Greeting>",
+    );
+    let new = tmp.path().join("NewMod");
+    write_keyed(
+        &new,
+        "English",
+        "  <Door>Open the heavy door</Door>\n  <Welcome>Hello, {0}!</Welcome>",
+    );
+    let po = tmp.path().join("new.po");
+    let export = |min_score: &str| {
+        bin_cmd()
+            .env("XDG_CACHE_HOME", tmp.path().join("cache"))
+            .args(["--quiet", "export-po", "--root"])
+            .arg(&new)
+            .arg("--out-po")
+            .arg(&po)
+            .args(["--lang", "ru", "--tm-min-score", min_score, "--tm-root"])
+            .arg(old.join("Languages/Russian"))
+            .assert()
+            .success();
+        fs::read_to_string(&po).unwrap()
+    };
+
+    let text = export("0.75");
+    assert!(
+        text.contains("#. TM 80% [key Door]: Откройте тяжёлую дверь\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("#| msgid \"Open the heavy door now\"\n"),
+        "{text}"
+    );
+    assert!(text.contains("msgstr \"Откройте тяжёлую дверь\""), "{text}");
+    assert!(
+        text.contains("#. TM 100% [source Greeting]: Привет, {0}!\n"),
+        "{text}"
+    );
+    assert!(text.contains("msgstr \"Привет, {0}!\""), "{text}");
+    assert!(
+        tmp.path()
+            .join("cache/rimloc/tm")
+            .read_dir()
+            .unwrap()
+            .count()
+            > 0,
+        "scanned TM root is cached"
+    );
+
+    let text = export("0.9");
+    assert!(!text.contains("Откройте"), "{text}");
+    assert!(text.contains("msgstr \"Привет, {0}!\""), "{text}");
+}
+
+#[test]
+fn multi_line_tm_target_keeps_po_valid() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let old = tmp.path().join("OldMod");
+    write_keyed(
+        &old,
+        "English",
+        "  <Letter>Dear colonist,\nwelcome</Letter>",
+    );
+    write_keyed(
+        &old,
+        "Russian",
+        "  <Letter>Дорогой колонист,\nдобро пожаловать</Letter>",
+    );
+    let new = tmp.path().join("NewMod");
+    write_keyed(
+        &new,
+        "English",
+        "  <Letter>Dear colonist,\nwelcome</Letter>",
+    );
+    let po = tmp.path().join("new.po");
+    bin_cmd()
+        .env("XDG_CACHE_HOME", tmp.path().join("cache"))
+        .args(["--quiet", "export-po", "--root"])
+        .arg(&new)
+        .arg("--out-po")
+        .arg(&po)
+        .args(["--lang", "ru", "--tm-root"])
+        .arg(old.join("Languages/Russian"))
+        .assert()
+        .success();
+
+    let text = fs::read_to_string(&po).unwrap();
+    assert!(
+        text.contains("#. TM 100% [exact Letter]: Дорогой колонист,\\nдобро пожаловать\n"),
+        "{text}"
+    );
+    bin_cmd()
+        .args(["--quiet", "validate-po", "--po"])
+        .arg(&po)
+        .assert()
+        .success();
+}
@@ -0,0 +1,62 @@
+use assert_cmd::prelude::*;
+use std::fs;
+
+mod helpers;
+use helpers::*;
+
+#[test]
+fn tmx_export_prefills_another_mod() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let done = tmp.path().join("Done");
+    fs::create_dir_all(done.join("About")).unwrap();
+    fs::write(
+        done.join("About/About.xml"),
+        "<ModMetaData><packageId>team.done</packageId></ModMetaData>",
+    )
+    .unwrap();
+    write_keyed(
+        &done.join("1.5"),
+        "English",
+        "  <Greeting>Hello, {0}!</Greeting>\n  <Bye>Bye</Bye>",
+    );
+    write_keyed(
+        &done.join("1.5"),
+        "Russian",
+        "  <Greeting>Привет, {0}!</Greeting>",
+    );
+    let tmx = tmp.path().join("done.tmx");
+
+    bin_cmd()
+        .args(["--quiet", "export-tmx", "--root"])
+        .arg(&done)
+        .arg("--out-tmx")
+        .arg(&tmx)
+        .args(["--lang", "ru", "--game-version", "1.5"])
+        .assert()
+        .success();
+    let text = fs::read_to_string(&tmx).unwrap();
+    assert!(text.contains(r#"<prop type="x-rimloc-key">Greeting</prop>"#));
+    assert!(text.contains(r#"<prop type="x-rimloc-package-id">team.done</prop>"#));
+    assert!(text.contains(r#"<prop type="x-rimloc-game-version">1.5</prop>"#));
+    assert!(text.contains(r#"<tuv xml:lang="ru"><seg>Привет, {0}!</seg></tuv>"#));
+    assert!(!text.contains("Bye"), "untranslated keys are not exported");
+
+    // другой мод с тем же текстом под другим ключом
+    let fresh = tmp.path().join("Fresh");
+    write_keyed(&fresh, "English", "  <Welcome>Hello, {0}!</Welcome>");
+    let po = tmp.path().join("fresh.po");
+    bin_cmd()
+        .args(["--quiet", "export-po", "--root"])
+        .arg(&fresh)
+        .arg("--out-po")
+        .arg(&po)
+        .args(["--lang", "ru", "--tm-root"])
+        .arg(&tmx)
+        .assert()
+        .success();
+    let po_text = fs::read_to_string(&po).unwrap();
+    assert!(
+        po_text.contains("#, fuzzy\nmsgctxt \"Welcome|Keyed/Sample.xml\"\nmsgid \"Hello, {0}!\"\nmsgstr \"Привет, {0}!\""),
+        "{po_text}"
+    );
+}
@@ -0,0 +1,86 @@
+use assert_cmd::prelude::*;
+use std::{fs, path::Path};
+
+mod helpers;
+use helpers::*;
+
+fn write_fixture(root: &Path) {
+    write_mod(
+        root,
+        "  <Greeting>Hello, {0}!</Greeting>\n  <Count>%d items</Count>\n  <Bye>Bye</Bye>",
+    );
+}
+
+#[test]
+fn xliff_round_trip_through_mod_tree() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_fixture(&root);
+    let xlf = tmp.path().join("mod.xlf");
+
+    bin_cmd()
+        .args(["--quiet", "export-xliff", "--root"])
+        .arg(&root)
+        .arg("--out-xliff")
+        .arg(&xlf)
+        .args(["--lang", "ru"])
+        .assert()
+        .success();
+    let text = fs::read_to_string(&xlf).unwrap();
+    assert!(text.contains(r#"srcLang="en" trgLang="ru""#));
+    assert!(text.contains(r#"original="Languages/English/Keyed/Sample.xml""#));
+    assert!(text
+        .contains(r#"<source>Hello, <ph id="1" dataRef="d1" equiv="{0}" disp="{0}"/>!</source>"#));
+    assert!(text.contains("<target>Пока</target>"));
+
+    // переводчик заполнил target в CAT-инструменте, плейсхолдер остался кодом
+    let translated = text
+        .replacen(
+            r#"<source>Hello, <ph id="1" dataRef="d1" equiv="{0}" disp="{0}"/>!</source>"#,
+            r#"<source>Hello, <ph id="1" dataRef="d1" equiv="{0}" disp="{0}"/>!</source><target>Привет, <ph id="1" dataRef="d1"/>!</target>"#,
+            1,
+        )
+        .replacen(r#"<segment state="initial">"#, r#"<segment state="final">"#, 1);
+    let translated = translated.replacen(
+        r#"<source><ph id="1" dataRef="d1" equiv="%d" disp="%d"/> items</source>"#,
+        r#"<source><ph id="1" dataRef="d1" equiv="%d" disp="%d"/> items</source><target><ph id="1" dataRef="d1"/> шт.</target>"#,
+        1,
+    );
+    fs::write(&xlf, translated).unwrap();
+
+    bin_cmd()
+        .args(["--quiet", "import-xliff", "--xliff"])
+        .arg(&xlf)
+        .arg("--mod-root")
+        .arg(&root)
+        .args(["--lang", "ru"])
+        .assert()
+        .success();
+    let xml = fs::read_to_string(root.join("Languages/Russian/Keyed/Sample.xml")).unwrap();
+    assert!(xml.contains("<Greeting>Привет, {0}!</Greeting>"), "{xml}");
+    assert!(xml.contains("<Bye>Пока</Bye>"), "{xml}");
+    // target в состоянии initial считается непроверенным и по умолчанию пропускается
+    assert!(!xml.contains("<Count>"), "{xml}");
+}
+
+#[test]
+fn xliff_1_2_export_uses_trans_units() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_fixture(&root);
+    let xlf = tmp.path().join("mod.xlf");
+
+    bin_cmd()
+        .args(["--quiet", "export-xliff", "--root"])
+        .arg(&root)
+        .arg("--out-xliff")
+        .arg(&xlf)
+        .args(["--lang", "ru", "--xliff-version", "1.2"])
+        .assert()
+        .success();
+    let text = fs::read_to_string(&xlf).unwrap();
+    assert!(text.contains(r#"version="1.2""#));
+    assert!(text.contains(r#"<trans-unit id="u2" resname="Count">"#));
+    assert!(text.contains(r#"<source><ph id="1">%d</ph> items</source>"#));
+    assert!(text.contains(r#"<target state="translated">Пока</target>"#));
+}
@@ -0,0 +1,94 @@
+use assert_cmd::prelude::*;
+use rimloc_xlsx::XlsxBook;
+use std::{fs, path::Path};
+
+mod helpers;
+use helpers::*;
+
+fn write_fixture(root: &Path) {
+    write_mod(root, "  <Greeting>Hello, {0}!</Greeting>\n  <Bye>Bye</Bye>");
+    write_file(
+        &root.join("Defs/Things.xml"),
+        "<Defs><ThingDef><defName>Beer</defName><label>beer</label></ThingDef></Defs>",
+    );
+}
+
+fn export(root: &Path, xlsx: &Path) {
+    bin_cmd()
+        .args(["--quiet", "export-xlsx", "--root"])
+        .arg(root)
+        .arg("--out-xlsx")
+        .arg(xlsx)
+        .args(["--lang", "ru"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn xlsx_round_trip_through_mod_tree() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_fixture(&root);
+    let xlsx = tmp.path().join("mod.xlsx");
+    export(&root, &xlsx);
+
+    let mut book = XlsxBook::read(&xlsx).unwrap();
+    let names: Vec<_> = book.sheets.iter().map(|s| s.name.clone()).collect();
+    assert_eq!(names, ["ThingDef", "Sample"]);
+    for row in book.sheets.iter_mut().flat_map(|s| s.rows.iter_mut()) {
+        match row.key.as_str() {
+            "Greeting" => row.translation = "Привет, {0}!".into(),
+            "Beer.label" => row.translation = "пиво".into(),
+            "Bye" => assert_eq!(row.translation, "Пока"),
+            other => panic!("unexpected key {other}"),
+        }
+    }
+    book.save(&xlsx).unwrap();
+
+    bin_cmd()
+        .args(["--quiet", "import-xlsx", "--xlsx"])
+        .arg(&xlsx)
+        .arg("--mod-root")
+        .arg(&root)
+        .args(["--lang", "ru"])
+        .assert()
+        .success();
+    let xml = fs::read_to_string(root.join("Languages/Russian/Keyed/Sample.xml")).unwrap();
+    assert!(xml.contains("<Greeting>Привет, {0}!</Greeting>"), "{xml}");
+    assert!(xml.contains("<Bye>Пока</Bye>"), "{xml}");
+    let defs =
+        fs::read_to_string(root.join("Languages/Russian/DefInjected/ThingDef/Things.xml")).unwrap();
+    assert!(defs.contains("<Beer.label>пиво</Beer.label>"), "{defs}");
+}
+
+#[test]
+fn import_xlsx_rejects_edited_keys() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("Mod");
+    write_fixture(&root);
+    let xlsx = tmp.path().join("mod.xlsx");
+    export(&root, &xlsx);
+
+    let mut book = XlsxBook::read(&xlsx).unwrap();
+    let row = book
+        .sheets
+        .iter_mut()
+        .flat_map(|s| s.rows.iter_mut())
+        .find(|r| r.key == "Greeting")
+        .unwrap();
+    row.key = "Greeting2".into();
+    row.translation = "Привет".into();
+    book.save(&xlsx).unwrap();
+
+    bin_cmd()
+        .args(["--quiet", "import-xlsx", "--xlsx"])
+        .arg(&xlsx)
+        .arg("--mod-root")
+        .arg(&root)
+        .args(["--lang", "ru"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("'Sample'!A2 (Greeting2)"));
+    let xml = fs::read_to_string(root.join("Languages/Russian/Keyed/Sample.xml")).unwrap();
+    assert!(!xml.contains("Привет"), "{xml}");
+}
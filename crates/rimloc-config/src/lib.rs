@@ -4,6 +4,7 @@ use serde::Deserialize;
 pub struct RimLocConfig {
     pub source_lang: Option<String>,
     pub target_lang: Option<String>,
+    pub glossary: Option<String>, // CSV/TBX used by validate, validate-po and export-po
     pub game_version: Option<String>,
     pub list_limit: Option<usize>,
     pub export: Option<ExportCfg>,
@@ -15,6 +16,7 @@ pub struct RimLocConfig {
     pub init: Option<InitCfg>,
     pub schema: Option<SchemaCfg>,
     pub scan: Option<ScanCfg>,
+    pub mt: Option<MtCfg>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -22,6 +24,9 @@ pub struct ExportCfg {
     pub source_lang_dir: Option<String>,
     pub include_all_versions: Option<bool>,
     pub tm_root: Option<String>,
+    pub tm_min_score: Option<f64>, // 0..1, fuzzy TM threshold
+    pub tm_cache_dir: Option<String>,
+    pub compact: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -33,6 +38,7 @@ pub struct ImportCfg {
     pub only_diff: Option<bool>,
     pub report: Option<bool>,
     pub lang_dir: Option<String>,
+    pub fuzzy: Option<String>, // skip | include | mark
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -43,6 +49,7 @@ pub struct BuildCfg {
     pub lang_dir: Option<String>,
     pub dedupe: Option<bool>,
     pub from_root_versions: Option<Vec<String>>,
+    pub fuzzy: Option<String>, // skip | include | mark
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -104,6 +111,11 @@ pub fn load_config() -> Result<RimLocConfig, ConfigError> {
     Ok(merged)
 }
 
+/// Per-user cache folder for RimLoc (e.g. `~/.cache/rimloc`).
+pub fn cache_dir() -> Option<std::path::PathBuf> {
+    dirs::cache_dir().map(|d| d.join("rimloc"))
+}
+
 fn merge(mut a: RimLocConfig, b: RimLocConfig) -> RimLocConfig {
     if a.source_lang.is_none() {
         a.source_lang = b.source_lang;
@@ -111,6 +123,9 @@ fn merge(mut a: RimLocConfig, b: RimLocConfig) -> RimLocConfig {
     if a.target_lang.is_none() {
         a.target_lang = b.target_lang;
     }
+    if a.glossary.is_none() {
+        a.glossary = b.glossary;
+    }
     if a.game_version.is_none() {
         a.game_version = b.game_version;
     }
@@ -126,6 +141,7 @@ fn merge(mut a: RimLocConfig, b: RimLocConfig) -> RimLocConfig {
     a.init = merge_opt(a.init, b.init, merge_init);
     a.schema = merge_opt(a.schema, b.schema, merge_schema);
     a.scan = merge_opt(a.scan, b.scan, merge_scan);
+    a.mt = merge_opt(a.mt, b.mt, merge_mt);
     a
 }
 
@@ -148,6 +164,15 @@ fn merge_export(mut a: ExportCfg, b: ExportCfg) -> ExportCfg {
     if a.tm_root.is_none() {
         a.tm_root = b.tm_root;
     }
+    if a.tm_min_score.is_none() {
+        a.tm_min_score = b.tm_min_score;
+    }
+    if a.tm_cache_dir.is_none() {
+        a.tm_cache_dir = b.tm_cache_dir;
+    }
+    if a.compact.is_none() {
+        a.compact = b.compact;
+    }
     a
 }
 fn merge_import(mut a: ImportCfg, b: ImportCfg) -> ImportCfg {
@@ -172,6 +197,9 @@ fn merge_import(mut a: ImportCfg, b: ImportCfg) -> ImportCfg {
     if a.lang_dir.is_none() {
         a.lang_dir = b.lang_dir;
     }
+    if a.fuzzy.is_none() {
+        a.fuzzy = b.fuzzy;
+    }
     a
 }
 fn merge_build(mut a: BuildCfg, b: BuildCfg) -> BuildCfg {
@@ -193,6 +221,9 @@ fn merge_build(mut a: BuildCfg, b: BuildCfg) -> BuildCfg {
     if a.from_root_versions.is_none() {
         a.from_root_versions = b.from_root_versions;
     }
+    if a.fuzzy.is_none() {
+        a.fuzzy = b.fuzzy;
+    }
     a
 }
 fn merge_diff(mut a: DiffCfg, b: DiffCfg) -> DiffCfg {
@@ -267,3 +298,35 @@ fn merge_scan(mut a: ScanCfg, b: ScanCfg) -> ScanCfg {
     }
     a
 }
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MtCfg {
+    pub provider: Option<String>, // libretranslate | dummy
+    pub url: Option<String>,      // LibreTranslate-compatible server
+    pub batch_size: Option<usize>,
+    pub requests_per_minute: Option<u32>,
+    pub timeout_ms: Option<u64>,
+    pub cache_dir: Option<String>,
+}
+
+fn merge_mt(mut a: MtCfg, b: MtCfg) -> MtCfg {
+    if a.provider.is_none() {
+        a.provider = b.provider;
+    }
+    if a.url.is_none() {
+        a.url = b.url;
+    }
+    if a.batch_size.is_none() {
+        a.batch_size = b.batch_size;
+    }
+    if a.requests_per_minute.is_none() {
+        a.requests_per_minute = b.requests_per_minute;
+    }
+    if a.timeout_ms.is_none() {
+        a.timeout_ms = b.timeout_ms;
+    }
+    if a.cache_dir.is_none() {
+        a.cache_dir = b.cache_dir;
+    }
+    a
+}
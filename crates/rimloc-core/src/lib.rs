@@ -1,4 +1,3 @@
-use color_eyre::eyre::eyre;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
@@ -10,7 +9,69 @@ pub type Result<T> = color_eyre::eyre::Result<T>;
 /// Schema version for RimLoc data outputs (JSON/PO headers).
 pub const RIMLOC_SCHEMA_VERSION: u32 = 1;
 
+/// Game version folder name such as `1.5` or `v1.4`.
+pub fn is_version_folder(name: &str) -> bool {
+    let digits = name.strip_prefix(['v', 'V']).unwrap_or(name);
+    match digits.split_once('.') {
+        Some((major, minor)) => [major, minor]
+            .iter()
+            .all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit())),
+        None => false,
+    }
+}
+
 pub mod placeholders {
+    use regex::Regex;
+    use std::collections::BTreeSet;
+    use std::sync::OnceLock;
+
+    static RE_PCT: OnceLock<Regex> = OnceLock::new();
+    static RE_BRACE: OnceLock<Regex> = OnceLock::new();
+
+    fn re_pct() -> &'static Regex {
+        RE_PCT.get_or_init(|| Regex::new(r"%(\d+\$)?0?\d*[sdif]").unwrap())
+    }
+
+    fn re_brace() -> &'static Regex {
+        RE_BRACE.get_or_init(|| Regex::new(r"\{\s*([^{}\s]+)\s*\}").unwrap())
+    }
+
+    /// Collect printf-style (`%s`, `%1$d`) and brace (`{0}`, `{PAWN_label}`) placeholders.
+    /// Brace placeholders are normalized by trimming inner whitespace.
+    pub fn extract(text: &str) -> BTreeSet<String> {
+        let (re_pct, re_brace) = (re_pct(), re_brace());
+        let mut out = BTreeSet::new();
+        for m in re_pct.find_iter(text) {
+            out.insert(m.as_str().to_string());
+        }
+        for cap in re_brace.captures_iter(text) {
+            if let Some(name) = cap.get(1) {
+                out.insert(format!("{{{}}}", name.as_str()));
+            }
+        }
+        out
+    }
+
+    /// Byte ranges of the placeholders in `text` (as matched by [`extract`]),
+    /// sorted and non-overlapping.
+    pub fn spans(text: &str) -> Vec<std::ops::Range<usize>> {
+        let mut out: Vec<std::ops::Range<usize>> = re_pct()
+            .find_iter(text)
+            .chain(re_brace().find_iter(text))
+            .map(|m| m.range())
+            .collect();
+        out.sort_by_key(|r| r.start);
+        let mut last_end = 0;
+        out.retain(|r| {
+            let keep = r.start >= last_end;
+            if keep {
+                last_end = r.end;
+            }
+            keep
+        });
+        out
+    }
+
     /// Return true if a percent placeholder looks suspicious (e.g., single '%' not matching printf pattern).
     pub fn is_bad_percent(text: &str) -> bool {
         let bytes = text.as_bytes();
@@ -48,9 +109,55 @@ pub mod placeholders {
     }
 }
 
+/// Where a translation unit was discovered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitKind {
+    /// `Languages/<lang>/Keyed/*.xml`
+    #[default]
+    Keyed,
+    /// `Languages/<lang>/DefInjected/<DefType>/*.xml`
+    DefInjected,
+    /// Implicit English text taken from `Defs/*.xml`
+    Defs,
+    /// `Languages/<lang>/Strings/**/*.txt`
+    Strings,
+    /// Produced by a scan plugin
+    Plugin,
+}
+
+impl UnitKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UnitKind::Keyed => "keyed",
+            UnitKind::DefInjected => "def_injected",
+            UnitKind::Defs => "defs",
+            UnitKind::Strings => "strings",
+            UnitKind::Plugin => "plugin",
+        }
+    }
+
+    /// Classify a file under `Languages/<lang>/` by its folder (Keyed/DefInjected/Strings).
+    /// Returns `None` for paths outside those folders.
+    pub fn from_languages_path(path: &std::path::Path) -> Option<Self> {
+        path.components().find_map(|c| {
+            let s = c.as_os_str().to_string_lossy();
+            if s.eq_ignore_ascii_case("Keyed") {
+                Some(UnitKind::Keyed)
+            } else if s.eq_ignore_ascii_case("DefInjected") {
+                Some(UnitKind::DefInjected)
+            } else if s.eq_ignore_ascii_case("Strings") {
+                Some(UnitKind::Strings)
+            } else {
+                None
+            }
+        })
+    }
+}
+
 /// Minimal unit used across crates to represent a single translation entry
 /// scanned from RimWorld XML (Keyed/DefInjected) or produced by tools.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransUnit {
     pub key: String,
     /// Source string (may be missing for keys detected without text)
@@ -59,6 +166,42 @@ pub struct TransUnit {
     pub path: PathBuf,
     /// 1-based line number if available
     pub line: Option<usize>,
+    /// Kind of source the unit was discovered in
+    #[serde(default)]
+    pub kind: UnitKind,
+    /// Def type (e.g. `ThingDef`) for Defs/DefInjected units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub def_type: Option<String>,
+    /// `defName` for Defs/DefInjected units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub def_name: Option<String>,
+    /// Field path inside the Def (e.g. `label`, `stages.0.label`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_path: Option<String>,
+    /// File the text was actually read from when `path` points elsewhere
+    /// (e.g. the Defs XML behind a DefInjected target path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_path: Option<PathBuf>,
+    /// 1-based line in `origin_path` if available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_line: Option<usize>,
+    /// Context for translators (def type, owning def label, placeholders);
+    /// written as `#.` extracted comments in PO
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
+}
+
+impl TransUnit {
+    /// Point the unit at `path` (e.g. a DefInjected target file), keeping the
+    /// current file/line as origin unless an origin is already recorded.
+    pub fn retarget(&mut self, path: PathBuf) {
+        let old_path = std::mem::replace(&mut self.path, path);
+        let old_line = self.line.take();
+        if self.origin_path.is_none() {
+            self.origin_path = Some(old_path);
+            self.origin_line = old_line;
+        }
+    }
 }
 
 /// Simple PO entry used by import/export utilities and tests.
@@ -69,6 +212,9 @@ pub struct PoEntry {
     /// Optional reference like
     /// "…/Languages/English/Keyed/Some.xml:42" used to reconstruct paths.
     pub reference: Option<String>,
+    /// Entry is flagged `#, fuzzy` (translation needs review).
+    #[serde(default)]
+    pub fuzzy: bool,
 }
 
 /// Keep a lightweight error type for crates that still import it.
@@ -78,58 +224,18 @@ pub enum RimLocError {
     Other(String),
 }
 
-/// Parse a minimal subset of PO syntax used across the workspace.
-/// Supports single-line `msgid`/`msgstr` pairs and optional reference lines (`#: ...`).
+/// Parse PO text into simple `msgid`/`msgstr` pairs.
+/// Thin adapter over `rimloc_po`: the header and obsolete entries are skipped,
+/// the first `#:` reference is kept.
 pub fn parse_simple_po(input: &str) -> Result<Vec<PoEntry>> {
-    let mut entries = Vec::new();
-    let mut cur_ref: Option<String> = None;
-    let mut cur_id: Option<String> = None;
-
-    fn unquote(raw: &str) -> String {
-        let trimmed = raw.trim();
-        if trimmed.starts_with('"') && trimmed.ends_with('"') && trimmed.len() >= 2 {
-            trimmed[1..trimmed.len() - 1].to_string()
-        } else {
-            trimmed.to_string()
-        }
-    }
-
-    for line in input.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        if let Some(rest) = trimmed.strip_prefix("#:") {
-            cur_ref = Some(rest.trim().to_string());
-            continue;
-        }
-        if let Some(rest) = trimmed.strip_prefix("msgid") {
-            let eq = rest
-                .trim_start()
-                .strip_prefix(' ')
-                .unwrap_or(rest)
-                .trim_start_matches('=');
-            cur_id = Some(unquote(eq.trim()));
-            continue;
-        }
-        if let Some(rest) = trimmed.strip_prefix("msgstr") {
-            let eq = rest
-                .trim_start()
-                .strip_prefix(' ')
-                .unwrap_or(rest)
-                .trim_start_matches('=');
-            let val = unquote(eq.trim());
-            if let Some(id) = cur_id.take() {
-                entries.push(PoEntry {
-                    key: id,
-                    value: val,
-                    reference: cur_ref.take(),
-                });
-            } else {
-                return Err(eyre!("Malformed PO entry: msgstr without msgid"));
-            }
-        }
-    }
-
-    Ok(entries)
+    let po = rimloc_po::PoFile::parse(input)?;
+    Ok(po
+        .active()
+        .map(|m| PoEntry {
+            key: m.msgid.clone(),
+            value: m.translation().to_string(),
+            reference: m.references.first().cloned(),
+            fuzzy: m.is_fuzzy(),
+        })
+        .collect())
 }
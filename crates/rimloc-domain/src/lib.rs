@@ -10,12 +10,32 @@ pub struct ScanUnit {
     pub line: Option<usize>,
     pub key: String,
     pub value: Option<String>,
+    /// keyed | def_injected | defs | strings | plugin
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub def_type: Option<String>,
+    #[serde(default)]
+    pub def_name: Option<String>,
+    #[serde(default)]
+    pub field_path: Option<String>,
+    /// Defs file the value was read from when `path` is a DefInjected target
+    #[serde(default)]
+    pub origin_path: Option<String>,
+    #[serde(default)]
+    pub origin_line: Option<usize>,
+    /// Translator context (def type, owning def label, placeholders)
+    #[serde(default)]
+    pub comments: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationMsg {
     pub schema_version: u32,
     pub kind: String,
+    /// error | warning | info (fixed per kind)
+    #[serde(default)]
+    pub severity: String,
     pub key: String,
     pub path: String,
     pub line: Option<usize>,
@@ -39,6 +59,15 @@ pub struct ImportSummary {
     pub skipped: usize,
     pub keys: usize,
     pub files: Vec<ImportFileStat>,
+    /// Fuzzy entries left out by the fuzzy policy
+    #[serde(default)]
+    pub fuzzy_skipped: usize,
+    /// Fuzzy entries written (policy `include` or `mark`)
+    #[serde(default)]
+    pub fuzzy_imported: usize,
+    /// Keys given more than once for the same file (`[key, file]`); the last value is written
+    #[serde(default)]
+    pub duplicates: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -75,3 +104,127 @@ pub struct AnnotatePlan {
     pub total_strip: usize,
     pub processed: usize,
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PoUpdateSummary {
+    /// Entries whose source text is unchanged (translation kept as is)
+    pub kept: usize,
+    /// Entries whose source text changed: translation kept, marked fuzzy with `#|` previous msgid
+    pub fuzzy: usize,
+    /// Entries left untranslated (new, or changed without a previous translation)
+    pub added: usize,
+    /// New entries pre-filled from a similar source string (marked fuzzy)
+    pub prefilled: usize,
+    /// Entries no longer present in the source, commented out as `#~`
+    pub obsolete: usize,
+    /// Active entries in the updated PO
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PoIssue {
+    /// placeholder | duplicate-msgctxt | language-header | whitespace | newlines | rich-text | grammar | xml-unsafe | invalid-key | glossary
+    pub kind: String,
+    pub context: Option<String>,
+    pub reference: Option<String>,
+    pub msgid: String,
+    pub msgstr: String,
+    pub message: String,
+    /// Placeholders in msgid (placeholder issues only)
+    #[serde(default)]
+    pub expected_placeholders: Vec<String>,
+    /// Placeholders in msgstr (placeholder issues only)
+    #[serde(default)]
+    pub got_placeholders: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PoLintReport {
+    pub path: String,
+    /// Value of the `Language` header, if any
+    pub language: Option<String>,
+    /// Active entries (header and obsolete `#~` entries excluded)
+    pub total: usize,
+    /// Entries with an empty msgstr
+    pub untranslated: usize,
+    /// Entries flagged `#, fuzzy`
+    pub fuzzy: usize,
+    /// Non-empty translations compared against their msgid
+    pub checked: usize,
+    pub issues: Vec<PoIssue>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PoMergeSummary {
+    /// Input PO files that were merged
+    pub files: usize,
+    /// Entries in the merged PO (obsolete `#~` entries included)
+    pub entries: usize,
+    /// Entries dropped because the same msgctxt/msgid was already present
+    pub duplicates: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TmBuildPair {
+    /// Mod folder holding the `Languages/<lang>` tree
+    pub translation: String,
+    pub package_id: Option<String>,
+    /// Mods the translations were paired with, the translation mod itself included
+    /// (packageId, or folder when it has none)
+    pub sources: Vec<String>,
+    /// self | package-id | key-overlap (comma-separated when several applied)
+    pub matched_by: String,
+    /// Translations paired with a source text
+    pub paired: usize,
+    /// Translations whose key was not found in any source mod
+    pub unpaired: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TmBuildSummary {
+    /// Mod folders found under the scanned roots
+    pub mods: usize,
+    /// One record per `Languages/<lang>` tree found
+    pub translations: Vec<TmBuildPair>,
+    /// Entries in the written TM
+    pub entries: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TermCandidate {
+    /// Most frequent spelling of the term
+    pub term: String,
+    /// def-label | name | phrase
+    pub kind: String,
+    /// Def the label belongs to (`ThingDef/Steel`) for def-label terms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub def: Option<String>,
+    /// Occurrences in source texts (for def labels, outside the label itself)
+    pub count: usize,
+    /// Files with at least one occurrence
+    pub files: usize,
+    /// Ranking score: occurrences weighted by spread across files and kind
+    pub score: f64,
+    /// Keys of a few strings using the term
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MtFillSummary {
+    /// Translation provider name (also written to the `#. MT` comments)
+    pub provider: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    /// Entries in the PO (obsolete entries excluded)
+    pub total: usize,
+    /// Entries with an empty msgstr before the run
+    pub untranslated: usize,
+    /// Entries filled with a machine translation (marked fuzzy)
+    pub translated: usize,
+    /// Filled entries whose translation came from the cache
+    pub cached: usize,
+    /// Translations dropped because they lost a placeholder or tag, or came back empty
+    pub failed: usize,
+    /// Requests sent to the provider
+    pub requests: usize,
+}
@@ -1,30 +1,247 @@
 use rimloc_core::{Result, TransUnit};
-use std::io::Write;
+use std::io::{Read, Write};
+
+/// Columns of a bilingual CSV, in the order they are written.
+pub const BILINGUAL_HEADER: [&str; 7] = [
+    "key",
+    "kind",
+    "file",
+    "source",
+    "translation",
+    "status",
+    "notes",
+];
+
+/// Translation status of a bilingual CSV row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RowStatus {
+    /// Not translated yet.
+    #[default]
+    New,
+    /// Translation needs review; imported according to the fuzzy policy.
+    Fuzzy,
+    Translated,
+}
+
+impl RowStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RowStatus::New => "new",
+            RowStatus::Fuzzy => "fuzzy",
+            RowStatus::Translated => "translated",
+        }
+    }
+
+    /// Lenient parse of a status cell edited by hand; unknown values are `None`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "new" | "untranslated" => Some(RowStatus::New),
+            "fuzzy" | "review" | "needs-review" => Some(RowStatus::Fuzzy),
+            "translated" | "done" | "ok" => Some(RowStatus::Translated),
+            _ => None,
+        }
+    }
+}
+
+/// One row of a bilingual CSV: a source string next to its translation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BilingualRow {
+    pub key: String,
+    pub kind: String,
+    /// Source file relative to the mod root; used to place the key on import.
+    pub file: String,
+    pub source: String,
+    pub translation: String,
+    pub status: RowStatus,
+    pub notes: String,
+}
+
+/// Layout options of a bilingual CSV.
+#[derive(Debug, Clone, Copy)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    /// Start the file with a UTF-8 BOM so Excel detects the encoding.
+    pub bom: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            bom: false,
+        }
+    }
+}
+
+/// Parse a delimiter given on the command line: a single character, `tab` or `\t`.
+pub fn parse_delimiter(s: &str) -> Option<u8> {
+    match s {
+        "tab" | "\\t" | "\t" => Some(b'\t'),
+        _ if s.len() == 1 && s.is_ascii() => Some(s.as_bytes()[0]),
+        _ => None,
+    }
+}
 
 pub fn write_csv<W: Write>(writer: W, units: &[TransUnit], lang: Option<&str>) -> Result<()> {
     let mut wtr = csv::Writer::from_writer(writer);
 
-    // Заголовок: с колонкой lang, если указан язык
-    match lang {
-        Some(_) => wtr.write_record(["lang", "key", "source", "path", "line"])?,
-        None => wtr.write_record(["key", "source", "path", "line"])?,
+    // Заголовок: с колонкой lang, если указан язык; метаданные юнита — в конце
+    const META: [&str; 7] = [
+        "kind",
+        "def_type",
+        "def_name",
+        "field_path",
+        "origin_path",
+        "origin_line",
+        "comments",
+    ];
+    let mut header: Vec<&str> = Vec::with_capacity(12);
+    if lang.is_some() {
+        header.push("lang");
     }
+    header.extend(["key", "source", "path", "line"]);
+    header.extend(META);
+    wtr.write_record(&header)?;
 
     for u in units {
         let line_str = u.line.map(|l| l.to_string()).unwrap_or_default();
         let path_str = u.path.to_string_lossy();
         let source = u.source.as_deref().unwrap_or("");
+        let origin_path = u
+            .origin_path
+            .as_ref()
+            .map(|p| p.to_string_lossy())
+            .unwrap_or_default();
+        let origin_line = u.origin_line.map(|l| l.to_string()).unwrap_or_default();
+        let comments = u.comments.join("; ");
 
-        match lang {
-            Some(l) => wtr.write_record([l, &u.key, source, &path_str, &line_str])?,
-            None => wtr.write_record([&u.key, source, &path_str, &line_str])?,
+        let mut record: Vec<&str> = Vec::with_capacity(header.len());
+        if let Some(l) = lang {
+            record.push(l);
         }
+        record.extend([u.key.as_str(), source, &path_str, &line_str]);
+        record.extend([
+            u.kind.as_str(),
+            u.def_type.as_deref().unwrap_or(""),
+            u.def_name.as_deref().unwrap_or(""),
+            u.field_path.as_deref().unwrap_or(""),
+            &origin_path,
+            &origin_line,
+            &comments,
+        ]);
+        wtr.write_record(&record)?;
     }
 
     wtr.flush()?;
     Ok(())
 }
 
+/// Write a bilingual CSV (see [`BILINGUAL_HEADER`]). Fields with the delimiter,
+/// quotes or line breaks are quoted, so multi-line texts survive a round trip.
+pub fn write_bilingual_csv<W: Write>(
+    mut writer: W,
+    rows: &[BilingualRow],
+    opts: CsvOptions,
+) -> Result<()> {
+    if opts.bom {
+        writer.write_all("\u{feff}".as_bytes())?;
+    }
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(opts.delimiter)
+        .from_writer(writer);
+    wtr.write_record(BILINGUAL_HEADER)?;
+    for r in rows {
+        wtr.write_record([
+            r.key.as_str(),
+            r.kind.as_str(),
+            r.file.as_str(),
+            r.source.as_str(),
+            r.translation.as_str(),
+            r.status.as_str(),
+            r.notes.as_str(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Read a bilingual CSV. Columns are matched by header name (case-insensitive), so
+/// they may be reordered and extra columns are ignored; `key` and `translation` are
+/// required. Without `delimiter` it is guessed from the header line (`,`, `;` or tab).
+/// A leading UTF-8 BOM is skipped and CRLF inside cells becomes LF.
+pub fn read_bilingual_csv<R: Read>(
+    mut reader: R,
+    delimiter: Option<u8>,
+) -> Result<Vec<BilingualRow>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+    let delimiter = delimiter.unwrap_or_else(|| sniff_delimiter(text));
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let header: Vec<String> = rdr
+        .headers()?
+        .iter()
+        .map(|h| h.trim().to_ascii_lowercase())
+        .collect();
+    let col = |name: &str| header.iter().position(|h| h == name);
+    let (Some(key_col), Some(tr_col)) = (col("key"), col("translation")) else {
+        color_eyre::eyre::bail!(
+            "CSV header must have 'key' and 'translation' columns, found: {}",
+            header.join(", ")
+        );
+    };
+    let cols = [
+        col("kind"),
+        col("file"),
+        col("source"),
+        col("status"),
+        col("notes"),
+    ];
+
+    let mut rows = Vec::new();
+    for record in rdr.records() {
+        let record = record?;
+        let cell = |i: Option<usize>| {
+            i.and_then(|i| record.get(i))
+                .unwrap_or("")
+                .replace("\r\n", "\n")
+        };
+        let key = cell(Some(key_col)).trim().to_string();
+        if key.is_empty() {
+            continue;
+        }
+        let translation = cell(Some(tr_col));
+        let status = RowStatus::parse(&cell(cols[3])).unwrap_or(if translation.is_empty() {
+            RowStatus::New
+        } else {
+            RowStatus::Translated
+        });
+        rows.push(BilingualRow {
+            key,
+            kind: cell(cols[0]),
+            file: cell(cols[1]),
+            source: cell(cols[2]),
+            translation,
+            status,
+            notes: cell(cols[4]),
+        });
+    }
+    Ok(rows)
+}
+
+fn sniff_delimiter(text: &str) -> u8 {
+    let first = text.lines().next().unwrap_or("");
+    [b',', b';', b'\t']
+        .into_iter()
+        .max_by_key(|d| first.bytes().filter(|b| b == d).count())
+        .filter(|d| first.as_bytes().contains(d))
+        .unwrap_or(b',')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,6 +253,7 @@ mod tests {
             source: Some(src.into()),
             path: PathBuf::from("/mod/Languages/English/Keyed/X.xml"),
             line: Some(42),
+            ..Default::default()
         }
     }
 
@@ -68,4 +286,82 @@ mod tests {
             .starts_with("lang,key,source,path,line"));
         assert!(s.contains("ru,A,Hello,/mod/Languages/English/Keyed/X.xml,42"));
     }
+
+    #[test]
+    fn csv_includes_def_metadata_and_origin() {
+        let units = vec![TransUnit {
+            key: "Beer.label".into(),
+            source: Some("beer".into()),
+            path: PathBuf::from("/mod/Languages/English/DefInjected/ThingDef/Drinks.xml"),
+            line: None,
+            kind: rimloc_core::UnitKind::Defs,
+            def_type: Some("ThingDef".into()),
+            def_name: Some("Beer".into()),
+            field_path: Some("label".into()),
+            origin_path: Some(PathBuf::from("/mod/Defs/Drinks.xml")),
+            origin_line: Some(7),
+            comments: vec!["ThingDef: Beer".into()],
+        }];
+        let mut buf: Vec<u8> = Vec::new();
+        write_csv(&mut buf, &units, None).unwrap();
+        let s = String::from_utf8(buf).unwrap();
+
+        assert_eq!(
+            s.lines().next().unwrap(),
+            "key,source,path,line,kind,def_type,def_name,field_path,origin_path,origin_line,comments"
+        );
+        assert!(s.contains(
+            "Beer.label,beer,/mod/Languages/English/DefInjected/ThingDef/Drinks.xml,,defs,ThingDef,Beer,label,/mod/Defs/Drinks.xml,7,ThingDef: Beer"
+        ));
+    }
+
+    #[test]
+    fn bilingual_round_trip_keeps_multiline_and_quotes() {
+        let rows = vec![
+            BilingualRow {
+                key: "Letter.text".into(),
+                kind: "keyed".into(),
+                file: "Languages/English/Keyed/A.xml".into(),
+                source: "Line one\n\"Two\"; three".into(),
+                translation: "Строка один\n\"Два\"; три".into(),
+                status: RowStatus::Translated,
+                notes: "placeholders: none".into(),
+            },
+            BilingualRow {
+                key: "Empty".into(),
+                source: "Hi".into(),
+                ..Default::default()
+            },
+        ];
+        let mut buf = Vec::new();
+        write_bilingual_csv(
+            &mut buf,
+            &rows,
+            CsvOptions {
+                delimiter: b';',
+                bom: true,
+            },
+        )
+        .unwrap();
+        assert!(buf.starts_with("\u{feff}key;kind;file".as_bytes()));
+        assert_eq!(read_bilingual_csv(buf.as_slice(), None).unwrap(), rows);
+    }
+
+    #[test]
+    fn bilingual_reader_matches_columns_by_name() {
+        let csv =
+            "Translation,Extra,KEY,status\r\n\"a\r\nb\",x,K1,fuzzy\r\n,x,K2,\r\nfilled,x,K3,\r\n";
+        let rows = read_bilingual_csv(csv.as_bytes(), None).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            (rows[0].key.as_str(), rows[0].translation.as_str()),
+            ("K1", "a\nb")
+        );
+        assert_eq!(rows[0].status, RowStatus::Fuzzy);
+        assert_eq!(rows[1].status, RowStatus::New);
+        assert_eq!(rows[2].status, RowStatus::Translated);
+
+        let err = read_bilingual_csv("key,source\nA,B\n".as_bytes(), None).unwrap_err();
+        assert!(err.to_string().contains("translation"));
+    }
 }
@@ -1,28 +1,12 @@
 use regex::Regex;
 use rimloc_core::Result;
 use rimloc_core::TransUnit;
+use rimloc_po::{make_msgctxt, PoFile, PoMessage, SHARED_KEY_COMMENT};
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::sync::OnceLock;
 
-fn escape_po(s: &str) -> String {
-    // Простейшее экранирование для PO-строк (достаточно для MVP):
-    // \ -> \\, " -> \", \n -> \n, \r -> \r, \t -> \t
-    let mut out = String::with_capacity(s.len() + 8);
-    for ch in s.chars() {
-        match ch {
-            '\\' => out.push_str("\\\\"),
-            '"' => out.push_str("\\\""),
-            '\n' => out.push_str("\\n"),
-            '\r' => out.push_str("\\r"),
-            '\t' => out.push_str("\\t"),
-            _ => out.push(ch),
-        }
-    }
-    out
-}
-
 fn rel_from_languages(path_str: &str) -> Option<String> {
     // Вырезаем подстроку после .../Languages/<locale>/  (кроссплатформенно)
     // Поддерживает и '/' и '\', а также отсутствие префикса каталога.
@@ -34,15 +18,160 @@ fn rel_from_languages(path_str: &str) -> Option<String> {
         .map(|m| m.as_str().to_string())
 }
 
-/// Записать единый .po файл с заголовком и всеми TransUnit.
-/// msgctxt = "<key>|<relative_path_from_Languages>:<line>" (уникальный контекст для Poedit)
-/// msgid   = исходный текст (source), msgstr = "" (пусто, готово к переводу)
-/// В комментарии `#:` пишем ссылку `path:line` (для импорта/раскладки по файлам).
+/// Путь `#:` относительно корня мода (с '/' в качестве разделителя).
+/// Если путь лежит вне корня (или корень не задан) — оставляем как есть.
+fn reference_path(path: &Path, mod_root: Option<&Path>) -> String {
+    match mod_root.and_then(|root| path.strip_prefix(root).ok()) {
+        Some(rel) => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        None => path.display().to_string(),
+    }
+}
+
 /// Stats for PO writing (useful when TM is applied)
 #[derive(Debug, Clone, Copy, Default)]
 pub struct PoStats {
     pub total: usize,
     pub tm_filled: usize,
+    /// Keys folded into another entry with the same source text (compact mode)
+    pub shared: usize,
+}
+
+/// Prefix of the `#.` comments listing TM candidates (`TM 92% [fuzzy Key]: text`)
+pub const TM_MATCH_COMMENT: &str = "TM";
+
+/// Prefix of the `#.` comments listing glossary terms of the msgid
+/// (`Glossary: Colonist → Колонист (not: Поселенец)`)
+pub const GLOSSARY_COMMENT: &str = "Glossary:";
+
+/// Custom header: packageId of the mod the PO was exported from
+pub const HEADER_PACKAGE_ID: &str = "X-RimLoc-Package-Id";
+/// Custom header: mod name from About.xml
+pub const HEADER_MOD_NAME: &str = "X-RimLoc-Mod-Name";
+/// Custom header: game version folder the sources were scanned from
+pub const HEADER_GAME_VERSION: &str = "X-RimLoc-Game-Version";
+/// Custom header: source language folder (e.g. `English`)
+pub const HEADER_SOURCE_LANG: &str = "X-RimLoc-Source-Lang";
+/// Custom header: fingerprint of exported source keys/strings
+pub const HEADER_SOURCE_FINGERPRINT: &str = "X-RimLoc-Source-Fingerprint";
+
+/// Mod identity and provenance written into the PO header.
+#[derive(Debug, Clone, Default)]
+pub struct PoHeaderMeta {
+    pub package_id: Option<String>,
+    pub mod_name: Option<String>,
+    pub mod_version: Option<String>,
+    pub game_version: Option<String>,
+    pub source_lang: Option<String>,
+    pub source_fingerprint: Option<String>,
+    /// Value for `POT-Creation-Date`/`PO-Revision-Date` (`YYYY-MM-DD HH:MM+ZZZZ`)
+    pub date: Option<String>,
+}
+
+/// Options for [`write_po_opts`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoWriteOptions<'a> {
+    /// Value of the `Language` header (empty for POT-like output)
+    pub lang: Option<&'a str>,
+    /// Translation memory (key -> translation) used to prefill msgstr
+    pub tm: Option<&'a std::collections::HashMap<String, String>>,
+    /// Fuzzy translation memory, consulted when `tm` has no hit; the best candidate
+    /// prefills msgstr and every candidate is listed in a `#. TM <score>%` comment
+    pub tm_index: Option<&'a rimloc_tm::TmIndex>,
+    /// Minimum fuzzy score for `tm_index` candidates (default [`rimloc_tm::DEFAULT_MIN_SCORE`])
+    pub tm_min_score: Option<f64>,
+    /// Mod root: `#:` references are written relative to it
+    pub mod_root: Option<&'a Path>,
+    /// Mod identity/provenance for the header
+    pub meta: Option<&'a PoHeaderMeta>,
+    /// One entry per unique source text; the other keys are listed as `#:` references
+    /// and `#. rimloc-key:` comments (see [`rimloc_po::PoMessage::covered_contexts`])
+    pub compact: bool,
+    /// Terms found in a msgid are listed in `#. Glossary:` comments
+    pub glossary: Option<&'a rimloc_glossary::Glossary>,
+}
+
+/// How to split an export into several PO files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoSplit {
+    /// One PO per source file (`Keyed/A.po`, `DefInjected/ThingDef/Drinks.po`)
+    File,
+    /// One PO per Keyed file and one per DefInjected DefType (`DefInjected/ThingDef.po`)
+    DefType,
+    /// One PO per game version folder (`1.5.po`); files outside version folders go to `Common.po`
+    Version,
+}
+
+impl PoSplit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PoSplit::File => "file",
+            PoSplit::DefType => "def-type",
+            PoSplit::Version => "version",
+        }
+    }
+
+    /// Output path (relative, `/`-separated) of the PO file that receives `unit`.
+    pub fn group_of(self, unit: &TransUnit, mod_root: Option<&Path>) -> String {
+        let by_file = || {
+            let rel = rel_from_languages(&unit.path.to_string_lossy()).unwrap_or_else(|| {
+                unit.path
+                    .file_name()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "Unknown.xml".to_string())
+            });
+            Path::new(&rel.replace('\\', "/"))
+                .with_extension("po")
+                .to_string_lossy()
+                .into_owned()
+        };
+        match self {
+            PoSplit::File => by_file(),
+            PoSplit::DefType => {
+                let file = by_file();
+                let mut parts = file.split('/');
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(folder), Some(def_type), Some(_))
+                        if folder.eq_ignore_ascii_case("DefInjected") =>
+                    {
+                        format!("{folder}/{def_type}.po")
+                    }
+                    _ => file,
+                }
+            }
+            PoSplit::Version => {
+                // версия определяется по файлу, из которого реально взят текст
+                let src = unit.origin_path.as_deref().unwrap_or(&unit.path);
+                let rel = mod_root
+                    .and_then(|root| src.strip_prefix(root).ok())
+                    .unwrap_or(src);
+                rel.components()
+                    .next()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .filter(|c| rimloc_core::is_version_folder(c))
+                    .map(|v| format!("{v}.po"))
+                    .unwrap_or_else(|| "Common.po".to_string())
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for PoSplit {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "file" => Ok(PoSplit::File),
+            "def-type" | "deftype" => Ok(PoSplit::DefType),
+            "version" => Ok(PoSplit::Version),
+            other => Err(color_eyre::eyre::eyre!(
+                "unknown split mode '{other}' (expected file, def-type or version)"
+            )),
+        }
+    }
 }
 
 /// Backward-compatible entry: write PO without TM
@@ -58,49 +187,91 @@ pub fn write_po_with_tm(
     lang: Option<&str>,
     tm: Option<&std::collections::HashMap<String, String>>,
 ) -> Result<PoStats> {
-    let file = File::create(path)?;
-    let mut w = BufWriter::new(file);
+    write_po_opts(
+        path,
+        units,
+        &PoWriteOptions {
+            lang,
+            tm,
+            ..Default::default()
+        },
+    )
+}
 
+/// Записать единый .po файл с заголовком и всеми TransUnit.
+/// msgctxt = "<key>|<relative_path_from_Languages>" (уникальный и стабильный контекст для Poedit,
+/// без номера строки — правки исходного XML не меняют контекст)
+/// msgid   = исходный текст (source), msgstr = "" (пусто, готово к переводу)
+/// В комментарии `#:` пишем ссылку `path:line` относительно корня мода (для импорта/раскладки по файлам).
+pub fn write_po_opts(path: &Path, units: &[TransUnit], opts: &PoWriteOptions) -> Result<PoStats> {
+    let (po, stats) = build_po(units, opts);
+    let mut w = BufWriter::new(File::create(path)?);
+    write!(w, "{po}")?;
+    w.flush()?;
+    Ok(stats)
+}
+
+/// Собрать PO в памяти (без записи на диск) — для слияния с уже переведённым PO.
+pub fn build_po(units: &[TransUnit], opts: &PoWriteOptions) -> (PoFile, PoStats) {
+    let PoWriteOptions {
+        lang,
+        tm,
+        tm_index,
+        tm_min_score,
+        mod_root,
+        meta,
+        compact,
+        glossary,
+    } = *opts;
+    let meta = meta.cloned().unwrap_or_default();
+    // Project-Id-Version: "<name or packageId> <version>" из About.xml
+    let project = match (
+        meta.mod_name.as_deref().or(meta.package_id.as_deref()),
+        meta.mod_version.as_deref(),
+    ) {
+        (Some(name), Some(ver)) => format!("{name} {ver}"),
+        (Some(name), None) => name.to_string(),
+        (None, _) => "rimloc 0.1".to_string(),
+    };
+    let date = meta.date.as_deref().unwrap_or("");
     // --- Header ---
-    writeln!(w, "msgid \"\"")?;
-    writeln!(w, "msgstr \"\"")?;
-    writeln!(w, "\"Project-Id-Version: rimloc 0.1\\n\"")?;
-    writeln!(w, "\"POT-Creation-Date: \\n\"")?;
-    writeln!(w, "\"PO-Revision-Date: \\n\"")?;
-    writeln!(w, "\"Last-Translator: \\n\"")?;
-    writeln!(w, "\"Language-Team: \\n\"")?;
-    if let Some(l) = lang {
-        writeln!(w, "\"Language: {}\\n\"", l)?;
-    } else {
-        writeln!(w, "\"Language: \\n\"")?;
-    }
-    writeln!(w, "\"MIME-Version: 1.0\\n\"")?;
-    writeln!(w, "\"Content-Type: text/plain; charset=UTF-8\\n\"")?;
-    writeln!(w, "\"Content-Transfer-Encoding: 8bit\\n\"")?;
+    let mut po = PoFile::default();
+    po.set_header_field("Project-Id-Version", &project);
+    po.set_header_field("POT-Creation-Date", date);
+    po.set_header_field("PO-Revision-Date", date);
+    po.set_header_field("Last-Translator", "");
+    po.set_header_field("Language-Team", "");
+    po.set_header_field("Language", lang.unwrap_or(""));
+    po.set_header_field("MIME-Version", "1.0");
+    po.set_header_field("Content-Type", "text/plain; charset=UTF-8");
+    po.set_header_field("Content-Transfer-Encoding", "8bit");
     // Custom header with RimLoc schema version for tooling
-    writeln!(
-        w,
-        "\"X-RimLoc-Schema: {}\\n\"",
-        rimloc_core::RIMLOC_SCHEMA_VERSION
-    )?;
-    writeln!(w)?; // пустая строка
+    po.set_header_field(
+        "X-RimLoc-Schema",
+        &rimloc_core::RIMLOC_SCHEMA_VERSION.to_string(),
+    );
+    for (name, value) in [
+        (HEADER_PACKAGE_ID, &meta.package_id),
+        (HEADER_MOD_NAME, &meta.mod_name),
+        (HEADER_GAME_VERSION, &meta.game_version),
+        (HEADER_SOURCE_LANG, &meta.source_lang),
+        (HEADER_SOURCE_FINGERPRINT, &meta.source_fingerprint),
+    ] {
+        if let Some(v) = value {
+            po.set_header_field(name, v);
+        }
+    }
 
     let mut stats = PoStats::default();
 
     // --- Entries ---
+    // compact: номер уже записанной записи для каждого исходного текста
+    let mut by_source: std::collections::HashMap<&str, usize> = Default::default();
     for u in units {
-        stats.total += 1;
         let key = &u.key;
         let msgid = u.source.as_deref().unwrap_or("");
 
-        // #: абсолютный (или полный) путь + строка — как было
-        if let Some(line) = u.line {
-            writeln!(w, "#: {}:{}", u.path.display(), line)?;
-        } else {
-            writeln!(w, "#: {}", u.path.display())?;
-        }
-
-        // msgctxt: делаем уникальным: "<key>|<relative_path>:<line?>"
+        // msgctxt: делаем уникальным: "<key>|<relative_path>"
         let path_str = u.path.to_string_lossy();
         let rel = rel_from_languages(&path_str).unwrap_or_else(|| {
             u.path
@@ -109,27 +280,130 @@ pub fn write_po_with_tm(
                 .unwrap_or("Unknown.xml")
                 .to_string()
         });
+        let ctxt = make_msgctxt(key, &rel);
+        // #: путь относительно корня мода + строка
         let line_suffix = u.line.map(|l| format!(":{}", l)).unwrap_or_default();
-        let ctx = format!("{}|{}{}", key, rel, line_suffix);
+        let reference = format!("{}{}", reference_path(&u.path, mod_root), line_suffix);
+        let tm_hit = match tm.and_then(|m| m.get(key)) {
+            Some(val) => Some(TmSuggestion {
+                msgstr: val.clone(),
+                ..Default::default()
+            }),
+            None => tm_index.and_then(|idx| {
+                tm_suggestion(
+                    idx,
+                    key,
+                    msgid,
+                    tm_min_score.unwrap_or(rimloc_tm::DEFAULT_MIN_SCORE),
+                )
+            }),
+        };
 
-        // If TM provided and has value for this key, mark fuzzy and prefill msgstr
-        let tm_val = tm.and_then(|m| m.get(key));
-        if let Some(val) = tm_val {
-            writeln!(w, "#, fuzzy")?;
+        if let Some(&idx) = by_source.get(msgid) {
+            // тот же текст уже есть — добавляем ключ к общей записи
+            let msg = &mut po.messages[idx];
+            msg.references.push(reference);
+            msg.extracted_comments
+                .push(format!("{SHARED_KEY_COMMENT} {ctxt}"));
+            for c in &u.comments {
+                if !msg.extracted_comments.contains(c) {
+                    msg.extracted_comments.push(c.clone());
+                }
+            }
+            if let Some(hit) = tm_hit.filter(|_| !msg.has_translation()) {
+                hit.apply(msg);
+                stats.tm_filled += 1;
+            }
+            stats.shared += 1;
+            continue;
+        }
+
+        stats.total += 1;
+        let mut msg = PoMessage::new(Some(ctxt), msgid, "");
+        // #. контекст для переводчика (тип Def, label, плейсхолдеры)
+        msg.extracted_comments = u.comments.clone();
+        if let Some(g) = glossary {
+            msg.extracted_comments
+                .extend(g.find(msgid).into_iter().map(glossary_comment));
+        }
+        msg.references.push(reference);
+
+        // If TM provided and has value for this key (or source text), mark fuzzy and prefill msgstr
+        if let Some(hit) = tm_hit {
+            hit.apply(&mut msg);
             stats.tm_filled += 1;
-            writeln!(w, "msgctxt \"{}\"", escape_po(&ctx))?;
-            writeln!(w, "msgid \"{}\"", escape_po(msgid))?;
-            writeln!(w, "msgstr \"{}\"", escape_po(val))?;
-        } else {
-            writeln!(w, "msgctxt \"{}\"", escape_po(&ctx))?;
-            writeln!(w, "msgid \"{}\"", escape_po(msgid))?;
-            writeln!(w, "msgstr \"\"")?;
         }
-        writeln!(w)?;
+        if compact && !msgid.trim().is_empty() {
+            by_source.insert(msgid, po.messages.len());
+        }
+        po.messages.push(msg);
     }
+    (po, stats)
+}
 
-    w.flush()?;
-    Ok(stats)
+/// TM prefill for one entry.
+#[derive(Default)]
+struct TmSuggestion {
+    msgstr: String,
+    /// Source of the best candidate when it differs (shown as `#|` in Poedit)
+    previous_msgid: Option<String>,
+    comments: Vec<String>,
+}
+
+impl TmSuggestion {
+    fn apply(self, msg: &mut PoMessage) {
+        msg.set_fuzzy(true);
+        msg.msgstr = self.msgstr;
+        if msg.previous_msgid.is_none() {
+            msg.previous_msgid = self.previous_msgid;
+        }
+        msg.extracted_comments.extend(self.comments);
+    }
+}
+
+fn glossary_comment(term: &rimloc_glossary::Term) -> String {
+    let mut c = format!("{GLOSSARY_COMMENT} {}", term.source);
+    if !term.targets.is_empty() {
+        c.push_str(&format!(" → {}", term.targets.join(" | ")));
+    }
+    if !term.forbidden.is_empty() {
+        c.push_str(&format!(" (not: {})", term.forbidden.join(", ")));
+    }
+    if let Some(note) = &term.note {
+        c.push_str(&format!(" — {note}"));
+    }
+    c
+}
+
+fn tm_suggestion(
+    idx: &rimloc_tm::TmIndex,
+    key: &str,
+    msgid: &str,
+    min_score: f64,
+) -> Option<TmSuggestion> {
+    let matches = idx.lookup(Some(key), msgid, min_score, rimloc_tm::DEFAULT_CANDIDATES);
+    let best = matches.first()?;
+    let comments = matches
+        .iter()
+        .map(|m| {
+            let label = match m.entry.key.as_deref() {
+                Some(k) => format!("{} {k}", m.kind.as_str()),
+                None => m.kind.as_str().to_string(),
+            };
+            // multi-line targets stay on the comment line, with visible `\n`
+            format!(
+                "{TM_MATCH_COMMENT} {}% [{label}]: {}",
+                m.percent(),
+                m.entry.target.replace('\r', "").replace('\n', "\\n")
+            )
+        })
+        .collect();
+    Some(TmSuggestion {
+        msgstr: best.entry.target.clone(),
+        previous_msgid: Some(best.entry.source.clone())
+            .filter(|s| !s.is_empty() && s.as_str() != msgid.trim()),
+        comments,
+    })
 }
 
 #[cfg(test)]
@@ -146,6 +420,7 @@ mod tests {
             source: Some(src.into()),
             path: PathBuf::from("/Mod/Languages/English/Keyed/A.xml"),
             line: Some(line),
+            ..Default::default()
         }
     }
 
@@ -167,9 +442,209 @@ mod tests {
         );
         // #: ссылка
         assert!(s.contains(r#"#: /Mod/Languages/English/Keyed/A.xml:3"#));
-        // msgctxt с пайпом и относительным путём
-        assert!(s.contains(r#"msgctxt "Greeting|Keyed/A.xml:3""#));
+        // msgctxt с пайпом и относительным путём, без номера строки
+        assert!(s.contains(r#"msgctxt "Greeting|Keyed/A.xml""#));
+        assert!(!s.contains(r#"msgctxt "Greeting|Keyed/A.xml:3""#));
         // msgid
         assert!(s.contains(r#"msgid "Hello""#));
     }
+
+    #[test]
+    fn unit_comments_become_extracted_comments() {
+        let mut u = unit("Beer.description", "Drink {0}", 4);
+        u.comments = vec!["ThingDef: Beer".into(), "Def label: beer".into()];
+        let (po, _) = build_po(&[u], &PoWriteOptions::default());
+        let s = po.to_string();
+        assert!(s.contains("#. ThingDef: Beer\n#. Def label: beer\n#: "));
+    }
+
+    #[test]
+    fn header_carries_mod_identity_and_provenance() {
+        let meta = PoHeaderMeta {
+            package_id: Some("author.mymod".into()),
+            mod_name: Some("My Mod".into()),
+            mod_version: Some("1.2".into()),
+            game_version: Some("1.5".into()),
+            source_lang: Some("English".into()),
+            source_fingerprint: Some("sha256:abc".into()),
+            date: Some("2025-01-02 03:04+0000".into()),
+        };
+        let opts = PoWriteOptions {
+            lang: Some("ru"),
+            meta: Some(&meta),
+            ..Default::default()
+        };
+        let (po, _) = build_po(&[unit("Greeting", "Hello", 3)], &opts);
+        assert_eq!(po.header_field("Project-Id-Version"), Some("My Mod 1.2"));
+        assert_eq!(
+            po.header_field("POT-Creation-Date"),
+            Some("2025-01-02 03:04+0000")
+        );
+        assert_eq!(po.header_field(HEADER_PACKAGE_ID), Some("author.mymod"));
+        assert_eq!(po.header_field(HEADER_GAME_VERSION), Some("1.5"));
+        assert_eq!(po.header_field(HEADER_SOURCE_LANG), Some("English"));
+        assert_eq!(po.header_field(HEADER_SOURCE_FINGERPRINT), Some("sha256:abc"));
+
+        let (plain, _) = build_po(&[], &PoWriteOptions::default());
+        assert_eq!(plain.header_field("Project-Id-Version"), Some("rimloc 0.1"));
+        assert_eq!(plain.header_field(HEADER_PACKAGE_ID), None);
+    }
+
+    #[test]
+    fn compact_folds_keys_with_the_same_source() {
+        let mut units = vec![
+            unit("ToggleA", "Toggle", 3),
+            unit("Other", "Other", 4),
+            unit("ToggleB", "Toggle", 5),
+        ];
+        units[2].comments = vec!["ThingDef: Lamp".into()];
+        let tm: std::collections::HashMap<String, String> =
+            [("ToggleB".to_string(), "Переключить".to_string())].into();
+        let opts = PoWriteOptions {
+            tm: Some(&tm),
+            mod_root: Some(Path::new("/Mod")),
+            compact: true,
+            ..Default::default()
+        };
+        let (po, stats) = build_po(&units, &opts);
+        assert_eq!((stats.total, stats.shared, stats.tm_filled), (2, 1, 1));
+        let toggle = &po.messages[0];
+        assert_eq!(
+            toggle.covered_contexts(),
+            vec!["ToggleA|Keyed/A.xml", "ToggleB|Keyed/A.xml"]
+        );
+        assert_eq!(
+            toggle.references,
+            vec!["Languages/English/Keyed/A.xml:3", "Languages/English/Keyed/A.xml:5"]
+        );
+        assert!(toggle.extracted_comments.contains(&"ThingDef: Lamp".to_string()));
+        assert_eq!(toggle.msgstr, "Переключить");
+        assert!(toggle.is_fuzzy());
+
+        let (plain, stats) = build_po(&units, &PoWriteOptions::default());
+        assert_eq!((plain.messages.len(), stats.shared), (3, 0));
+    }
+
+    #[test]
+    fn fuzzy_tm_prefills_and_lists_candidates() {
+        let units = vec![
+            unit("Greeting", "Hello, {0}!", 1),
+            unit("Door", "Open the heavy door", 2),
+            unit("Bye", "Bye", 3),
+        ];
+        let tm: std::collections::HashMap<String, String> =
+            [("Bye".to_string(), "Пока".to_string())].into();
+        let idx: rimloc_tm::TmIndex = [
+            rimloc_tm::TmEntry {
+                key: Some("Welcome".into()),
+                source: "Hello, {0}!".into(),
+                target: "Привет, {0}!".into(),
+                origin: None,
+            },
+            rimloc_tm::TmEntry {
+                key: Some("OldDoor".into()),
+                source: "Open the heavy door now".into(),
+                target: "Откройте тяжёлую дверь".into(),
+                origin: None,
+            },
+        ]
+        .into_iter()
+        .collect();
+        let opts = PoWriteOptions {
+            tm: Some(&tm),
+            tm_index: Some(&idx),
+            ..Default::default()
+        };
+        let (po, stats) = build_po(&units, &opts);
+        assert_eq!(stats.tm_filled, 3);
+        let greeting = &po.messages[0];
+        assert_eq!(greeting.msgstr, "Привет, {0}!");
+        assert!(greeting.is_fuzzy());
+        assert_eq!(greeting.previous_msgid, None);
+        assert!(greeting
+            .extracted_comments
+            .contains(&"TM 100% [source Welcome]: Привет, {0}!".to_string()));
+        let door = &po.messages[1];
+        assert_eq!(door.msgstr, "Откройте тяжёлую дверь");
+        assert_eq!(door.previous_msgid.as_deref(), Some("Open the heavy door now"));
+        assert!(door
+            .extracted_comments
+            .contains(&"TM 80% [fuzzy OldDoor]: Откройте тяжёлую дверь".to_string()));
+        assert_eq!(po.messages[2].msgstr, "Пока");
+
+        let strict = PoWriteOptions {
+            tm_min_score: Some(0.9),
+            ..opts
+        };
+        let (po, _) = build_po(&units, &strict);
+        assert_eq!(po.messages[1].msgstr, "");
+    }
+
+    #[test]
+    fn split_groups_by_file_def_type_and_version() {
+        let keyed = unit("Greeting", "Hello", 3);
+        let mut defs = unit("Beer.label", "beer", 1);
+        defs.path = PathBuf::from("/Mod/Languages/English/DefInjected/ThingDef/Drinks.xml");
+        defs.origin_path = Some(PathBuf::from("/Mod/1.5/Defs/Drinks.xml"));
+        let root = Some(Path::new("/Mod"));
+
+        assert_eq!(PoSplit::File.group_of(&keyed, root), "Keyed/A.po");
+        assert_eq!(
+            PoSplit::File.group_of(&defs, root),
+            "DefInjected/ThingDef/Drinks.po"
+        );
+        assert_eq!(PoSplit::DefType.group_of(&keyed, root), "Keyed/A.po");
+        assert_eq!(
+            PoSplit::DefType.group_of(&defs, root),
+            "DefInjected/ThingDef.po"
+        );
+        assert_eq!(PoSplit::Version.group_of(&keyed, root), "Common.po");
+        assert_eq!(PoSplit::Version.group_of(&defs, root), "1.5.po");
+        assert_eq!("def-type".parse::<PoSplit>().unwrap(), PoSplit::DefType);
+        assert!("by-mood".parse::<PoSplit>().is_err());
+    }
+
+    #[test]
+    fn glossary_terms_become_comments() {
+        let glossary = rimloc_glossary::Glossary::new(
+            "en",
+            Some("ru"),
+            vec![rimloc_glossary::Term {
+                source: "Colonist".into(),
+                targets: vec!["Колонист".into()],
+                forbidden: vec!["Поселенец".into()],
+                note: None,
+            }],
+        );
+        let units = vec![
+            unit("Arrived", "Two colonists arrived", 1),
+            unit("Bye", "Bye", 2),
+        ];
+        let opts = PoWriteOptions {
+            glossary: Some(&glossary),
+            ..Default::default()
+        };
+        let (po, _) = build_po(&units, &opts);
+        assert_eq!(
+            po.messages[0].extracted_comments,
+            vec!["Glossary: Colonist → Колонист (not: Поселенец)"]
+        );
+        assert!(po.messages[1].extracted_comments.is_empty());
+    }
+
+    #[test]
+    fn references_are_relative_to_mod_root() {
+        let tmp = NamedTempFile::new().unwrap();
+        let units = vec![unit("Greeting", "Hello", 3)];
+        let opts = PoWriteOptions {
+            lang: Some("ru"),
+            mod_root: Some(Path::new("/Mod")),
+            ..Default::default()
+        };
+        write_po_opts(tmp.path(), &units, &opts).unwrap();
+
+        let s = fs::read_to_string(tmp.path()).unwrap();
+        assert!(s.contains("#: Languages/English/Keyed/A.xml:3\n"));
+        assert!(s.contains(r#"msgctxt "Greeting|Keyed/A.xml""#));
+    }
 }
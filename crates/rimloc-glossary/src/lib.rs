@@ -0,0 +1,614 @@
+//! Terminology glossary for RimLoc: approved (and forbidden) translations of terms,
+//! loaded from CSV or TBX, and checks of translated strings against it.
+//!
+//! Words are compared by Snowball stems of the glossary languages, so `Colonists`
+//! finds the term `Colonist` and `колонистов` counts as the approved `Колонист`.
+//! A form ending in `*` matches any word starting with the rest (`Поселен*`), for
+//! languages without a stemmer or stems that change (`поселенец` / `поселенца`).
+
+use color_eyre::eyre::{eyre, Result};
+use rust_stemmers::{Algorithm, Stemmer};
+use std::fmt;
+use std::path::Path;
+
+const XML_NS: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// One glossary entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Term {
+    pub source: String,
+    /// Approved target forms; a translation must use one of them
+    pub targets: Vec<String>,
+    /// Forms that must not be used for this term
+    pub forbidden: Vec<String>,
+    pub note: Option<String>,
+}
+
+/// Problem found by [`Glossary::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TermIssue<'a> {
+    /// The source uses the term but the translation has none of its approved forms.
+    Missing { term: &'a Term },
+    /// The translation uses a forbidden form of a term found in the source.
+    Forbidden { term: &'a Term, form: &'a str },
+}
+
+impl TermIssue<'_> {
+    pub fn term(&self) -> &Term {
+        match self {
+            TermIssue::Missing { term } | TermIssue::Forbidden { term, .. } => term,
+        }
+    }
+}
+
+impl fmt::Display for TermIssue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let approved = |t: &Term| {
+            t.targets
+                .iter()
+                .map(|s| format!("\"{s}\""))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        };
+        match self {
+            TermIssue::Missing { term } => write!(
+                f,
+                "Term \"{}\" should be translated as {}",
+                term.source,
+                approved(term)
+            ),
+            TermIssue::Forbidden { term, form } if term.targets.is_empty() => write!(
+                f,
+                "Term \"{}\" translated as forbidden \"{form}\"",
+                term.source
+            ),
+            TermIssue::Forbidden { term, form } => write!(
+                f,
+                "Term \"{}\" translated as forbidden \"{form}\" (use {})",
+                term.source,
+                approved(term)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Word {
+    Stem(String),
+    Prefix(String),
+}
+
+type Phrase = Vec<Word>;
+
+#[derive(Debug, Clone)]
+struct Prepared {
+    source: Phrase,
+    targets: Vec<Phrase>,
+    forbidden: Vec<Phrase>,
+}
+
+/// Terms of one source/target language pair.
+#[derive(Debug, Clone)]
+pub struct Glossary {
+    source_lang: String,
+    target_lang: Option<String>,
+    terms: Vec<Term>,
+    prepared: Vec<Prepared>,
+}
+
+impl Glossary {
+    /// Languages are BCP 47 tags or English names (`en`, `ru-RU`, `Russian`); they
+    /// select the stemmers. Without a target language target words are compared whole.
+    pub fn new(source_lang: &str, target_lang: Option<&str>, terms: Vec<Term>) -> Self {
+        let src = stemmer(source_lang);
+        let tgt = target_lang.and_then(stemmer);
+        let prepared = terms
+            .iter()
+            .map(|t| Prepared {
+                source: phrase(&t.source, src.as_ref()),
+                targets: t.targets.iter().map(|s| phrase(s, tgt.as_ref())).collect(),
+                forbidden: t
+                    .forbidden
+                    .iter()
+                    .map(|s| phrase(s, tgt.as_ref()))
+                    .collect(),
+            })
+            .collect();
+        Self {
+            source_lang: source_lang.to_string(),
+            target_lang: target_lang.map(str::to_string),
+            terms,
+            prepared,
+        }
+    }
+
+    pub fn source_lang(&self) -> &str {
+        &self.source_lang
+    }
+
+    pub fn target_lang(&self) -> Option<&str> {
+        self.target_lang.as_deref()
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Load a `.tbx` (or `.xml`) TermBase eXchange file, anything else as CSV/TSV.
+    /// `target_lang` picks the language set of multilingual TBX files and the stemmer.
+    pub fn load(path: &Path, target_lang: Option<&str>) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+        let parsed = match ext.as_deref() {
+            Some("tbx") | Some("xml") => Self::parse_tbx(&text, target_lang),
+            Some("tsv") => Self::parse_csv(&text, Some(b'\t'), target_lang),
+            _ => Self::parse_csv(&text, None, target_lang),
+        };
+        parsed.map_err(|e| eyre!("{}: {e}", path.display()))
+    }
+
+    /// CSV with a header row naming the columns `source` (or `term`), `target` (or
+    /// `translation`), optional `forbidden` and `note`; without a recognised header
+    /// the columns are taken in that order. Several forms in one cell are separated
+    /// by `|` or `;`. The delimiter is guessed (`,` or `;`) unless given.
+    pub fn parse_csv(text: &str, delimiter: Option<u8>, target_lang: Option<&str>) -> Result<Self> {
+        let text = text.trim_start_matches('\u{feff}');
+        let delimiter = delimiter.unwrap_or_else(|| {
+            let first = text.lines().next().unwrap_or("");
+            if first.contains(';') && !first.contains(',') {
+                b';'
+            } else {
+                b','
+            }
+        });
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+        let mut rows = reader.records();
+        let mut columns = [Some(0), Some(1), Some(2), Some(3)];
+        let mut pending = None;
+        if let Some(first) = rows.next() {
+            let first = first?;
+            let find = |names: &[&str]| {
+                first
+                    .iter()
+                    .position(|h| names.iter().any(|n| h.trim().eq_ignore_ascii_case(n)))
+            };
+            let source = find(&["source", "term", "en", "english"]);
+            if source.is_some() {
+                columns = [
+                    source,
+                    find(&["target", "translation"]),
+                    find(&["forbidden", "deprecated", "avoid"]),
+                    find(&["note", "notes", "comment"]),
+                ];
+            } else {
+                pending = Some(first);
+            }
+        }
+        let mut terms = Vec::new();
+        for record in pending.into_iter().map(Ok).chain(rows) {
+            let record = record?;
+            let cell = |i: Option<usize>| i.and_then(|i| record.get(i)).unwrap_or("").trim();
+            let source = cell(columns[0]);
+            if source.is_empty() || source.starts_with('#') {
+                continue;
+            }
+            terms.push(Term {
+                source: source.to_string(),
+                targets: split_forms(cell(columns[1])),
+                forbidden: split_forms(cell(columns[2])),
+                note: Some(cell(columns[3]))
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string),
+            });
+        }
+        Ok(Self::new("en", target_lang, terms))
+    }
+
+    /// TBX (2008 `martif` or TBX v3 `tbx`). The source language is the `xml:lang` of
+    /// the root (English when absent); the target is `target_lang` or the first other
+    /// language. Terms marked `deprecatedTerm`/`supersededTerm` become forbidden forms.
+    pub fn parse_tbx(text: &str, target_lang: Option<&str>) -> Result<Self> {
+        let xml = roxmltree::Document::parse(text)?;
+        let root = xml.root_element();
+        if !matches!(root.tag_name().name(), "martif" | "tbx") {
+            return Err(eyre!(
+                "root element is <{}>, expected <martif> or <tbx>",
+                root.tag_name().name()
+            ));
+        }
+        let source_lang = lang_of(root).unwrap_or("en").to_string();
+        let mut target = target_lang.map(str::to_string);
+        let mut terms = Vec::new();
+        for entry in root
+            .descendants()
+            .filter(|n| matches!(n.tag_name().name(), "termEntry" | "conceptEntry"))
+        {
+            let mut sources = Vec::new();
+            let mut term = Term {
+                note: entry
+                    .descendants()
+                    .find(|n| {
+                        n.tag_name().name() == "note"
+                            || n.tag_name().name() == "descrip"
+                                && n.attribute("type") == Some("definition")
+                    })
+                    .and_then(|n| n.text())
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty()),
+                ..Default::default()
+            };
+            for set in entry
+                .children()
+                .filter(|n| n.tag_name().name() == "langSet")
+            {
+                let Some(lang) = lang_of(set) else { continue };
+                let is_source = same_lang(lang, &source_lang);
+                if !is_source {
+                    match &target {
+                        Some(t) if !same_lang(lang, t) => continue,
+                        Some(_) => {}
+                        None => target = Some(lang.to_string()),
+                    }
+                }
+                for group in set
+                    .children()
+                    .filter(|n| matches!(n.tag_name().name(), "tig" | "ntig" | "termSec"))
+                {
+                    let Some(text) = group
+                        .descendants()
+                        .find(|n| n.tag_name().name() == "term")
+                        .and_then(|n| n.text())
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                    else {
+                        continue;
+                    };
+                    let deprecated = group.descendants().any(|n| {
+                        n.tag_name().name() == "termNote"
+                            && n.text().is_some_and(|v| {
+                                v.starts_with("deprecatedTerm") || v.starts_with("supersededTerm")
+                            })
+                    });
+                    match (is_source, deprecated) {
+                        (true, false) => sources.push(text.to_string()),
+                        (true, true) => {}
+                        (false, false) => term.targets.push(text.to_string()),
+                        (false, true) => term.forbidden.push(text.to_string()),
+                    }
+                }
+            }
+            for source in sources {
+                terms.push(Term {
+                    source,
+                    ..term.clone()
+                });
+            }
+        }
+        Ok(Self::new(&source_lang, target.as_deref(), terms))
+    }
+
+    /// Write as `.tbx` (or `.xml`), `.tsv` or CSV by the extension of `path`, in the
+    /// layout [`Glossary::load`] reads back.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+        let text = match ext.as_deref() {
+            Some("tbx") | Some("xml") => self.to_tbx(),
+            Some("tsv") => self.to_csv(b'\t')?,
+            _ => self.to_csv(b',')?,
+        };
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// CSV with the columns `source,target,forbidden,note`; several forms are joined by `|`.
+    pub fn to_csv(&self, delimiter: u8) -> Result<String> {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .from_writer(Vec::new());
+        writer.write_record(["source", "target", "forbidden", "note"])?;
+        for t in &self.terms {
+            writer.write_record([
+                t.source.as_str(),
+                &t.targets.join("|"),
+                &t.forbidden.join("|"),
+                t.note.as_deref().unwrap_or(""),
+            ])?;
+        }
+        let bytes = writer.into_inner().map_err(|e| eyre!("{e}"))?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// TBX 2008 (`martif`) with one `termEntry` per term. With a target language every
+    /// entry gets its `langSet`, holding an empty `term` when there is no approved form
+    /// yet; forbidden forms are marked `deprecatedTerm-admn-sts`.
+    pub fn to_tbx(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<martif type=\"TBX\" xml:lang=\"{}\">\n  <text>\n    <body>\n",
+            escape(&self.source_lang)
+        ));
+        for (i, t) in self.terms.iter().enumerate() {
+            out.push_str(&format!("      <termEntry id=\"t{}\">\n", i + 1));
+            if let Some(note) = &t.note {
+                out.push_str(&format!("        <note>{}</note>\n", escape(note)));
+            }
+            out.push_str(&format!(
+                "        <langSet xml:lang=\"{}\"><tig><term>{}</term></tig></langSet>\n",
+                escape(&self.source_lang),
+                escape(&t.source)
+            ));
+            if let Some(lang) = &self.target_lang {
+                out.push_str(&format!(
+                    "        <langSet xml:lang=\"{}\">\n",
+                    escape(lang)
+                ));
+                let targets = if t.targets.is_empty() {
+                    vec![String::new()]
+                } else {
+                    t.targets.clone()
+                };
+                for form in &targets {
+                    out.push_str(&format!(
+                        "          <tig><term>{}</term></tig>\n",
+                        escape(form)
+                    ));
+                }
+                for form in &t.forbidden {
+                    out.push_str(&format!(
+                        "          <tig><term>{}</term><termNote type=\"administrativeStatus\">deprecatedTerm-admn-sts</termNote></tig>\n",
+                        escape(form)
+                    ));
+                }
+                out.push_str("        </langSet>\n");
+            }
+            out.push_str("      </termEntry>\n");
+        }
+        out.push_str("    </body>\n  </text>\n</martif>\n");
+        out
+    }
+
+    /// Terms whose source occurs in `text`, in glossary order.
+    pub fn find(&self, text: &str) -> Vec<&Term> {
+        let words = self.source_words(text);
+        self.terms
+            .iter()
+            .zip(&self.prepared)
+            .filter(|(_, p)| contains(&words, &p.source))
+            .map(|(t, _)| t)
+            .collect()
+    }
+
+    /// Check a translation: every term found in `source` must appear in `target` in
+    /// one of its approved forms and in none of its forbidden ones. Empty translations
+    /// are not checked.
+    pub fn check<'a>(&'a self, source: &str, target: &str) -> Vec<TermIssue<'a>> {
+        if target.trim().is_empty() {
+            return Vec::new();
+        }
+        let src_words = self.source_words(source);
+        let tgt_words = words(
+            target,
+            self.target_lang.as_deref().and_then(stemmer).as_ref(),
+        );
+        let mut issues = Vec::new();
+        for (term, p) in self.terms.iter().zip(&self.prepared) {
+            if !contains(&src_words, &p.source) {
+                continue;
+            }
+            if let Some(i) = p.forbidden.iter().position(|f| contains(&tgt_words, f)) {
+                issues.push(TermIssue::Forbidden {
+                    term,
+                    form: &term.forbidden[i],
+                });
+            } else if !p.targets.is_empty() && !p.targets.iter().any(|f| contains(&tgt_words, f)) {
+                issues.push(TermIssue::Missing { term });
+            }
+        }
+        issues
+    }
+
+    fn source_words(&self, text: &str) -> Vec<(String, String)> {
+        words(text, stemmer(&self.source_lang).as_ref())
+    }
+}
+
+/// Snowball stemmer for a language tag or English language name.
+fn stemmer(lang: &str) -> Option<Stemmer> {
+    let primary = lang
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(lang)
+        .to_ascii_lowercase();
+    let algorithm = match primary.as_str() {
+        "en" | "english" => Algorithm::English,
+        "ru" | "russian" => Algorithm::Russian,
+        "de" | "german" => Algorithm::German,
+        "fr" | "french" => Algorithm::French,
+        "es" | "spanish" => Algorithm::Spanish,
+        "pt" | "portuguese" | "portuguesebrazilian" => Algorithm::Portuguese,
+        "it" | "italian" => Algorithm::Italian,
+        "nl" | "dutch" => Algorithm::Dutch,
+        "sv" | "swedish" => Algorithm::Swedish,
+        "no" | "nb" | "norwegian" => Algorithm::Norwegian,
+        "da" | "danish" => Algorithm::Danish,
+        "fi" | "finnish" => Algorithm::Finnish,
+        "hu" | "hungarian" => Algorithm::Hungarian,
+        "ro" | "romanian" => Algorithm::Romanian,
+        "tr" | "turkish" => Algorithm::Turkish,
+        "el" | "greek" => Algorithm::Greek,
+        "ar" | "arabic" => Algorithm::Arabic,
+        _ => return None,
+    };
+    Some(Stemmer::create(algorithm))
+}
+
+/// Lowercased words of `text` with their stems.
+fn words(text: &str, stemmer: Option<&Stemmer>) -> Vec<(String, String)> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let lower = w.to_lowercase();
+            let stem = match stemmer {
+                Some(s) => s.stem(&lower).into_owned(),
+                None => lower.clone(),
+            };
+            (lower, stem)
+        })
+        .collect()
+}
+
+fn phrase(form: &str, stemmer: Option<&Stemmer>) -> Phrase {
+    form.split_whitespace()
+        .filter_map(|w| {
+            let lower = w.to_lowercase();
+            if let Some(prefix) = lower.strip_suffix('*') {
+                let prefix: String = prefix.chars().filter(|c| c.is_alphanumeric()).collect();
+                return (!prefix.is_empty()).then_some(Word::Prefix(prefix));
+            }
+            words(&lower, stemmer)
+                .into_iter()
+                .next()
+                .map(|(_, stem)| Word::Stem(stem))
+        })
+        .collect()
+}
+
+fn contains(words: &[(String, String)], phrase: &Phrase) -> bool {
+    !phrase.is_empty()
+        && words.windows(phrase.len()).any(|window| {
+            window.iter().zip(phrase).all(|((word, stem), p)| match p {
+                Word::Stem(s) => stem == s,
+                Word::Prefix(prefix) => word.starts_with(prefix.as_str()),
+            })
+        })
+}
+
+fn split_forms(cell: &str) -> Vec<String> {
+    cell.split(['|', ';'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn lang_of<'a>(node: roxmltree::Node<'a, '_>) -> Option<&'a str> {
+    node.attribute((XML_NS, "lang")).or(node.attribute("lang"))
+}
+
+fn same_lang(a: &str, b: &str) -> bool {
+    let primary = |s: &str| s.split(['-', '_']).next().unwrap_or(s).to_ascii_lowercase();
+    primary(a) == primary(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_inflected_terms_from_csv() {
+        let g = Glossary::parse_csv(
+            "source;target;forbidden;note\n\
+             Colonist;Колонист;Поселен*;people of the colony\n\
+             Steel;Сталь;;\n",
+            None,
+            Some("ru"),
+        )
+        .unwrap();
+        assert_eq!(g.len(), 2);
+        assert_eq!(g.terms()[0].note.as_deref(), Some("people of the colony"));
+
+        assert!(g
+            .check("Two colonists arrived", "Прибыли два колониста")
+            .is_empty());
+        let issues = g.check("The colonist's steel", "Сталь поселенца");
+        assert_eq!(
+            issues[0].to_string(),
+            "Term \"Colonist\" translated as forbidden \"Поселен*\" (use \"Колонист\")"
+        );
+        assert_eq!(issues.len(), 1);
+        let issues = g.check("Colonists need steel", "Людям нужна сталь");
+        assert!(matches!(issues[..], [TermIssue::Missing { .. }]));
+        // untranslated strings are not checked
+        assert!(g.check("Colonist", "").is_empty());
+        assert_eq!(g.find("Steel walls").len(), 1);
+    }
+
+    #[test]
+    fn reads_tbx_language_sets() {
+        let text = r#"<?xml version="1.0"?>
+<martif type="TBX" xml:lang="en">
+  <text><body>
+    <termEntry id="c1">
+      <descrip type="definition">A pawn of the player's faction</descrip>
+      <langSet xml:lang="en"><tig><term>Colonist</term></tig></langSet>
+      <langSet xml:lang="de"><tig><term>Kolonist</term></tig></langSet>
+      <langSet xml:lang="ru">
+        <tig><term>Колонист</term></tig>
+        <tig><term>Поселенец</term><termNote type="administrativeStatus">deprecatedTerm-admn-sts</termNote></tig>
+      </langSet>
+    </termEntry>
+  </body></text>
+</martif>"#;
+        let g = Glossary::parse_tbx(text, Some("ru-RU")).unwrap();
+        assert_eq!(
+            g.terms(),
+            &[Term {
+                source: "Colonist".into(),
+                targets: vec!["Колонист".into()],
+                forbidden: vec!["Поселенец".into()],
+                note: Some("A pawn of the player's faction".into()),
+            }]
+        );
+        assert!(matches!(
+            g.check("A colonist", "Поселенец")[..],
+            [TermIssue::Forbidden { .. }]
+        ));
+    }
+
+    #[test]
+    fn csv_and_tbx_round_trip() {
+        let g = Glossary::new(
+            "en",
+            Some("ru"),
+            vec![
+                Term {
+                    source: "Colonist".into(),
+                    targets: vec!["Колонист".into()],
+                    forbidden: vec!["Поселенец".into()],
+                    note: Some("pawns & \"people\"".into()),
+                },
+                Term {
+                    source: "Raid".into(),
+                    ..Default::default()
+                },
+            ],
+        );
+        let csv = Glossary::parse_csv(&g.to_csv(b',').unwrap(), None, Some("ru")).unwrap();
+        assert_eq!(csv.terms(), g.terms());
+        let tbx = Glossary::parse_tbx(&g.to_tbx(), Some("ru")).unwrap();
+        assert_eq!(tbx.terms(), g.terms());
+    }
+}
@@ -1,116 +1,194 @@
-use color_eyre::eyre::{eyre, Result};
+use color_eyre::eyre::Result;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Writer;
 use regex::Regex;
 use rimloc_core::PoEntry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader, BufWriter};
+use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-/// Очень простой парсер .po: собираем reference + msgctxt + msgstr.
-/// Поддерживает многострочный msgstr. Заголовки/комментарии (кроме "#:") игнорим.
-pub fn read_po_entries(po_path: &Path) -> Result<Vec<PoEntry>> {
-    let file = File::open(po_path)?;
-    let reader = BufReader::new(file);
-
+/// Список .po файлов: сам файл, либо все `*.po` внутри каталога (рекурсивно, по имени).
+pub fn collect_po_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
     let mut out = Vec::new();
-    let mut cur_ref: Option<String> = None;
-    let mut cur_ctxt: Option<String> = None;
-    let mut cur_str: Option<String> = None;
-    let mut in_msgstr = false;
-
-    for line in reader.lines() {
-        let l = line?;
-        let lt = l.trim();
-
-        if lt.starts_with("#:") {
-            if cur_ref.is_none() {
-                let r = lt.trim_start_matches("#:").trim().to_string();
-                cur_ref = Some(r);
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let p = entry?.path();
+            if p.is_dir() {
+                stack.push(p);
+            } else if p
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("po"))
+            {
+                out.push(p);
             }
-            continue;
-        }
-
-        if lt.starts_with("msgctxt") {
-            let raw = parse_po_string(lt.strip_prefix("msgctxt").unwrap_or(""))?;
-            // Если msgctxt имеет вид "Key|Path:Line", оставляем только "Key"
-            let key = raw
-                .split_once('|')
-                .map(|(k, _)| k.to_string())
-                .unwrap_or(raw);
-            cur_ctxt = Some(key);
-            in_msgstr = false;
-            continue;
         }
+    }
+    out.sort();
+    Ok(out)
+}
 
-        if lt.starts_with("msgstr") {
-            cur_str = Some(parse_po_string(lt.strip_prefix("msgstr").unwrap_or(""))?);
-            in_msgstr = true;
-            continue;
-        }
+/// Читаем .po через `rimloc_po`: ключ берём из msgctxt, значение — msgstr (у plural-записей `msgstr[0]`),
+/// ссылка — первая строка `#:`. Заголовок, устаревшие (`#~`) записи и записи без msgctxt пропускаем.
+/// `po_path` может быть каталогом: тогда читаются все `*.po` из [`collect_po_files`].
+///
+/// Общая запись компактного экспорта раздаётся всем ключам из `#. rimloc-key:`.
+/// Файл ключа берётся из его msgctxt (`Key|Path`), а не из номера строки `#:`:
+/// редакторы и `msgcat --sort-by-file` переставляют и сливают ссылки. Если переводчик
+/// вынес ключ в отдельную переведённую запись (msgctxt этого ключа), она имеет приоритет над общей.
+pub fn read_po_entries(po_path: &Path) -> Result<Vec<PoEntry>> {
+    let files = collect_po_files(po_path)?
+        .iter()
+        .map(|f| rimloc_po::PoFile::read(f))
+        .collect::<Result<Vec<_>>>()?;
+
+    // ключи с собственной переведённой записью
+    let overridden: HashSet<&str> = files
+        .iter()
+        .flat_map(|po| po.active())
+        .filter(|m| m.has_translation())
+        .filter_map(|m| m.msgctxt.as_deref())
+        .map(|ctxt| rimloc_po::split_msgctxt(ctxt).0)
+        .collect();
 
-        if lt.starts_with('"') && in_msgstr {
-            let val = parse_po_string(lt)?;
-            if let Some(ref mut s) = cur_str {
-                s.push_str(&val);
+    let mut out = Vec::new();
+    for m in files.iter().flat_map(|po| po.active()) {
+        for (i, ctxt) in m.covered_contexts().into_iter().enumerate() {
+            // msgctxt вида "Key|Path" (или старый "Key|Path:Line") — оставляем только "Key"
+            let (key, rel) = rimloc_po::split_msgctxt(ctxt);
+            if i > 0 && overridden.contains(key) {
+                continue;
             }
-            continue;
+            let reference = match rel {
+                Some(rel) => Some(reference_for_rel(&m.references, rel)),
+                None => m.references.first().cloned(),
+            };
+            out.push(PoEntry {
+                key: key.to_string(),
+                value: m.translation().to_string(),
+                reference,
+                fuzzy: m.is_fuzzy(),
+            });
         }
+    }
+    Ok(out)
+}
 
-        if lt.is_empty() {
-            if let (Some(k), Some(v)) = (&cur_ctxt, &cur_str) {
-                out.push(PoEntry {
-                    key: k.clone(),
-                    value: v.clone(),
-                    reference: cur_ref.clone(),
-                });
-            }
+/// Ссылка для файла `rel` (путь внутри `Languages/<lang>/` из msgctxt): строка `#:` на этот
+/// файл, чтобы сохранить папку версии, иначе `Languages/_/{rel}`. Если в msgctxt уже путь от
+/// корня мода (`Languages/<lang>/...`), берётся первая `#:` или сам этот путь.
+fn reference_for_rel(references: &[String], rel: &str) -> String {
+    if without_line(rel).replace('\\', "/").contains("Languages/") {
+        return references.first().cloned().unwrap_or_else(|| rel.to_string());
+    }
+    let suffix = format!("/{rel}");
+    references
+        .iter()
+        .find(|r| {
+            let path = without_line(r).replace('\\', "/");
+            path.contains("Languages/") && path.ends_with(&suffix)
+        })
+        .cloned()
+        .unwrap_or_else(|| format!("Languages/_/{rel}"))
+}
 
-            cur_ref = None;
-            cur_ctxt = None;
-            cur_str = None;
-            in_msgstr = false;
+/// Путь из ссылки `path:line` без номера строки.
+fn without_line(reference: &str) -> &str {
+    match reference.rsplit_once(':') {
+        Some((path, line)) if line.chars().all(|c| c.is_ascii_digit()) => path,
+        _ => reference,
+    }
+}
+
+/// Как поступать с записями `#, fuzzy` при импорте и сборке мода.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FuzzyPolicy {
+    /// Не импортировать fuzzy-записи (по умолчанию, как msgfmt).
+    #[default]
+    Skip,
+    /// Импортировать fuzzy-записи как обычные.
+    Include,
+    /// Импортировать и пометить XML-комментарием `<!-- fuzzy -->` перед элементом.
+    Mark,
+}
+
+impl FuzzyPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FuzzyPolicy::Skip => "skip",
+            FuzzyPolicy::Include => "include",
+            FuzzyPolicy::Mark => "mark",
         }
     }
+}
 
-    if let (Some(k), Some(v)) = (cur_ctxt, cur_str) {
-        out.push(PoEntry {
-            key: k,
-            value: v,
-            reference: cur_ref,
-        });
+impl std::str::FromStr for FuzzyPolicy {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "skip" => Ok(FuzzyPolicy::Skip),
+            "include" => Ok(FuzzyPolicy::Include),
+            "mark" => Ok(FuzzyPolicy::Mark),
+            other => Err(color_eyre::eyre::eyre!(
+                "unknown fuzzy policy '{other}' (expected skip, include or mark)"
+            )),
+        }
     }
+}
 
-    Ok(out)
+/// Счётчики fuzzy-записей после применения [`FuzzyPolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FuzzyStats {
+    pub skipped: usize,
+    pub imported: usize,
 }
 
-fn parse_po_string(s: &str) -> Result<String> {
-    let s = s.trim();
-    if !s.starts_with('"') || !s.ends_with('"') {
-        return Err(eyre!("invalid po string: {s}"));
-    }
-    let inner = &s[1..s.len() - 1];
-    let mut out = String::new();
-    let mut chars = inner.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            if let Some(next) = chars.next() {
-                match next {
-                    'n' => out.push('\n'),
-                    'r' => out.push('\r'),
-                    't' => out.push('\t'),
-                    '"' => out.push('"'),
-                    '\\' => out.push('\\'),
-                    other => out.push(other),
-                }
-            }
-        } else {
-            out.push(c);
+/// Текст XML-комментария, которым помечаются fuzzy-записи в режиме [`FuzzyPolicy::Mark`].
+pub const FUZZY_MARKER: &str = " fuzzy ";
+
+/// Отфильтровать записи по политике fuzzy и посчитать пропущенные/импортированные.
+pub fn apply_fuzzy_policy(entries: &mut Vec<PoEntry>, policy: FuzzyPolicy) -> FuzzyStats {
+    let fuzzy = entries.iter().filter(|e| e.fuzzy).count();
+    if policy == FuzzyPolicy::Skip {
+        entries.retain(|e| !e.fuzzy);
+        FuzzyStats {
+            skipped: fuzzy,
+            imported: 0,
+        }
+    } else {
+        FuzzyStats {
+            skipped: 0,
+            imported: fuzzy,
         }
     }
-    Ok(out)
+}
+
+/// Ключи, которые нужно пометить комментарием (только для [`FuzzyPolicy::Mark`]).
+pub fn fuzzy_marked_keys(entries: &[PoEntry], policy: FuzzyPolicy) -> HashSet<String> {
+    if policy != FuzzyPolicy::Mark {
+        return HashSet::new();
+    }
+    entries
+        .iter()
+        .filter(|e| e.fuzzy)
+        .map(|e| e.key.clone())
+        .collect()
+}
+
+/// Прочитать .po и сразу применить политику fuzzy.
+pub fn read_po_entries_with_policy(
+    po_path: &Path,
+    policy: FuzzyPolicy,
+) -> Result<(Vec<PoEntry>, FuzzyStats)> {
+    let mut entries = read_po_entries(po_path)?;
+    let stats = apply_fuzzy_policy(&mut entries, policy);
+    Ok((entries, stats))
 }
 
 /// Group PO entries by relative RimWorld path (e.g., Keyed/_Imported.xml)
@@ -157,6 +235,14 @@ fn dedupe_last_wins(items: &[(String, String)]) -> Vec<(String, String)> {
 
 /// Сгенерировать LanguageData XML из пар <key, value>.
 pub fn render_language_data_xml_bytes(entries: &[(String, String)]) -> Result<Vec<u8>> {
+    render_language_data_xml_bytes_marked(entries, &HashSet::new())
+}
+
+/// То же, но перед ключами из `fuzzy_keys` пишется комментарий [`FUZZY_MARKER`].
+pub fn render_language_data_xml_bytes_marked(
+    entries: &[(String, String)],
+    fuzzy_keys: &HashSet<String>,
+) -> Result<Vec<u8>> {
     let mut w = Writer::new_with_indent(Vec::new(), b' ', 2);
 
     w.write_event(Event::Decl(quick_xml::events::BytesDecl::new(
@@ -168,6 +254,11 @@ pub fn render_language_data_xml_bytes(entries: &[(String, String)]) -> Result<Ve
     w.write_event(Event::Start(BytesStart::new("LanguageData")))?;
 
     for (key, value) in entries {
+        if fuzzy_keys.contains(key) {
+            w.write_event(Event::Comment(quick_xml::events::BytesText::from_escaped(
+                FUZZY_MARKER,
+            )))?;
+        }
         let tag = BytesStart::new(key.as_str());
         w.write_event(Event::Start(tag))?;
         w.write_event(Event::Text(quick_xml::events::BytesText::new(value)))?;
@@ -179,10 +270,19 @@ pub fn render_language_data_xml_bytes(entries: &[(String, String)]) -> Result<Ve
 }
 
 pub fn write_language_data_xml(out_path: &Path, entries: &[(String, String)]) -> Result<()> {
+    write_language_data_xml_marked(out_path, entries, &HashSet::new())
+}
+
+/// Записать LanguageData XML, помечая ключи из `fuzzy_keys` комментарием.
+pub fn write_language_data_xml_marked(
+    out_path: &Path,
+    entries: &[(String, String)],
+    fuzzy_keys: &HashSet<String>,
+) -> Result<()> {
     if let Some(parent) = out_path.parent() {
         fs::create_dir_all(parent)?;
     }
-    let bytes = render_language_data_xml_bytes(entries)?;
+    let bytes = render_language_data_xml_bytes_marked(entries, fuzzy_keys)?;
     let mut w = BufWriter::new(File::create(out_path)?);
     use std::io::Write;
     w.write_all(&bytes)?;
@@ -266,6 +366,37 @@ pub fn rimworld_lang_dir(lang: &str) -> String {
     }
 }
 
+/// Обратное к [`rimworld_lang_dir`]: BCP-47 код для имени папки или кода
+/// (`Russian` → `ru`, `pt-br` → `pt-BR`). Неизвестные значения возвращаются как есть.
+pub fn rimworld_lang_code(lang: &str) -> String {
+    let code = match rimworld_lang_dir(lang).as_str() {
+        "English" => "en",
+        "Russian" => "ru",
+        "Japanese" => "ja",
+        "Korean" => "ko",
+        "French" => "fr",
+        "German" => "de",
+        "Spanish" => "es",
+        "SpanishLatin" => "es-419",
+        "Portuguese" => "pt",
+        "PortugueseBrazilian" => "pt-BR",
+        "Polish" => "pl",
+        "Italian" => "it",
+        "Turkish" => "tr",
+        "Ukrainian" => "uk",
+        "Czech" => "cs",
+        "Hungarian" => "hu",
+        "Dutch" => "nl",
+        "Romanian" => "ro",
+        "Thai" => "th",
+        "Greek" => "el",
+        "ChineseSimplified" => "zh-Hans",
+        "ChineseTraditional" => "zh-Hant",
+        _ => return lang.trim().to_string(),
+    };
+    code.to_string()
+}
+
 fn write_about_xml(
     about_xml: &std::path::Path,
     package_id: &str,
@@ -301,8 +432,8 @@ pub fn build_translation_mod(
     package_id: &str,
     rw_version: &str,
 ) -> Result<()> {
-    // 1) читаем po
-    let entries = read_po_entries(po_path)?;
+    // 1) читаем po (fuzzy-записи пропускаем)
+    let (entries, _) = read_po_entries_with_policy(po_path, FuzzyPolicy::Skip)?;
 
     // 2) группируем по относительным путям
     let grouped = group_entries_by_rel_path(entries);
@@ -334,8 +465,8 @@ pub fn build_translation_mod_with_langdir(
     package_id: &str,
     rw_version: &str,
 ) -> Result<()> {
-    // 1) читаем po
-    let entries = read_po_entries(po_path)?;
+    // 1) читаем po (fuzzy-записи пропускаем)
+    let (entries, _) = read_po_entries_with_policy(po_path, FuzzyPolicy::Skip)?;
 
     // 2) группируем по относительным путям
     let grouped = group_entries_by_rel_path(entries);
@@ -356,6 +487,7 @@ pub fn build_translation_mod_with_langdir(
 }
 
 /// Build translation mod with options
+#[allow(clippy::too_many_arguments)]
 pub fn build_translation_mod_with_langdir_opts(
     po_path: &Path,
     out_mod: &Path,
@@ -364,8 +496,10 @@ pub fn build_translation_mod_with_langdir_opts(
     package_id: &str,
     rw_version: &str,
     dedupe: bool,
+    fuzzy: FuzzyPolicy,
 ) -> Result<()> {
-    let entries = read_po_entries(po_path)?;
+    let (entries, _) = read_po_entries_with_policy(po_path, fuzzy)?;
+    let marked = fuzzy_marked_keys(&entries, fuzzy);
     let mut grouped = group_entries_by_rel_path(entries);
 
     let about_dir = out_mod.join("About");
@@ -378,7 +512,7 @@ pub fn build_translation_mod_with_langdir_opts(
             items = dedupe_last_wins(&items);
         }
         let out_path = out_mod.join("Languages").join(lang_dir).join(rel);
-        write_language_data_xml(&out_path, &items)?;
+        write_language_data_xml_marked(&out_path, &items, &marked)?;
     }
 
     Ok(())
@@ -394,6 +528,7 @@ pub struct DryRunPlan {
     pub lang_dir: String,
     pub files: Vec<(PathBuf, usize)>,
     pub total_keys: usize,
+    pub fuzzy: FuzzyStats,
 }
 
 /// Сухой прогон сборки мода перевода:
@@ -407,7 +542,7 @@ pub fn build_translation_mod_dry_run(
     package_id: &str,
     rw_version: &str,
 ) -> Result<DryRunPlan> {
-    let entries = read_po_entries(po_path)?;
+    let (entries, fuzzy) = read_po_entries_with_policy(po_path, FuzzyPolicy::Skip)?;
 
     let grouped = group_entries_by_rel_path(entries);
 
@@ -430,10 +565,12 @@ pub fn build_translation_mod_dry_run(
         lang_dir: lang_dir.to_string(),
         files,
         total_keys,
+        fuzzy,
     })
 }
 
 /// Dry-run variant with options
+#[allow(clippy::too_many_arguments)]
 pub fn build_translation_mod_dry_run_opts(
     po_path: &Path,
     out_mod: &Path,
@@ -442,8 +579,9 @@ pub fn build_translation_mod_dry_run_opts(
     package_id: &str,
     rw_version: &str,
     dedupe: bool,
+    fuzzy: FuzzyPolicy,
 ) -> Result<DryRunPlan> {
-    let entries = read_po_entries(po_path)?;
+    let (entries, fuzzy) = read_po_entries_with_policy(po_path, fuzzy)?;
     let grouped = group_entries_by_rel_path(entries);
 
     let mut total_keys = 0usize;
@@ -470,6 +608,7 @@ pub fn build_translation_mod_dry_run_opts(
         lang_dir: lang_dir.to_string(),
         files,
         total_keys,
+        fuzzy,
     })
 }
 
@@ -493,14 +632,144 @@ mod tests {
         assert_eq!(rimworld_lang_dir("pt-ao"), "PtAo");
     }
 
+    #[test]
+    fn maps_rimworld_dirs_to_codes() {
+        assert_eq!(rimworld_lang_code("Russian"), "ru");
+        assert_eq!(rimworld_lang_code("ru"), "ru");
+        assert_eq!(rimworld_lang_code("pt-br"), "pt-BR");
+        assert_eq!(rimworld_lang_code("ChineseTraditional"), "zh-Hant");
+        assert_eq!(rimworld_lang_code("tlh"), "tlh");
+    }
+
     #[test]
     fn parse_po_string_unescapes_sequences() {
         assert_eq!(
-            super::parse_po_string(r#""a\"b\\c\n\t\r""#).unwrap(),
+            rimloc_po::unquote(r#""a\"b\\c\n\t\r""#).unwrap(),
             "a\"b\\c\n\t\r"
         );
     }
 
+    #[test]
+    fn read_po_entries_accepts_stable_and_legacy_msgctxt() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        writeln!(tmp, r#"#: Languages/English/Keyed/A.xml:3"#).unwrap();
+        writeln!(tmp, r#"msgctxt "Greeting|Keyed/A.xml""#).unwrap();
+        writeln!(tmp, r#"msgid "Hello""#).unwrap();
+        writeln!(tmp, r#"msgstr "Привет""#).unwrap();
+        writeln!(tmp).unwrap();
+        writeln!(tmp, r#"msgctxt "Farewell|Keyed/A.xml:7""#).unwrap();
+        writeln!(tmp, r#"msgid "Bye""#).unwrap();
+        writeln!(tmp, r#"msgstr "Пока""#).unwrap();
+
+        let entries = read_po_entries(tmp.path()).unwrap();
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["Greeting", "Farewell"]);
+        assert_eq!(
+            entries[0].reference.as_deref(),
+            Some("Languages/English/Keyed/A.xml:3")
+        );
+    }
+
+    #[test]
+    fn read_po_entries_takes_first_plural_form() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        write!(
+            tmp,
+            concat!(
+                "msgctxt \"Apples|Keyed/A.xml\"\nmsgid \"apple\"\nmsgid_plural \"apples\"\n",
+                "msgstr[0] \"яблоко\"\nmsgstr[1] \"яблока\"\n",
+            )
+        )
+        .unwrap();
+
+        let entries = read_po_entries(tmp.path()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].value, "яблоко");
+    }
+
+    #[test]
+    fn read_po_entries_reads_every_po_in_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("DefInjected")).unwrap();
+        fs::write(
+            dir.path().join("Keyed.po"),
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"a\"\nmsgstr \"а\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("DefInjected/ThingDef.po"),
+            "msgctxt \"Beer.label|DefInjected/ThingDef/A.xml\"\nmsgid \"beer\"\nmsgstr \"пиво\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a po").unwrap();
+
+        let files = collect_po_files(dir.path()).unwrap();
+        assert_eq!(files.len(), 2);
+        let entries = read_po_entries(dir.path()).unwrap();
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["Beer.label", "A"]);
+    }
+
+    #[test]
+    fn shared_entries_fan_out_unless_overridden() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        write!(
+            tmp,
+            concat!(
+                "#. rimloc-key: B|Keyed/B.xml\n#. rimloc-key: C|Keyed/C.xml\n",
+                "#: Languages/English/Keyed/A.xml:1\n#: Languages/English/Keyed/B.xml:2\n",
+                "#: Languages/English/Keyed/C.xml:3\n",
+                "msgctxt \"A|Keyed/A.xml\"\nmsgid \"Toggle\"\nmsgstr \"Переключить\"\n\n",
+                "#: Languages/English/Keyed/C.xml:3\n",
+                "msgctxt \"C|Keyed/C.xml\"\nmsgid \"Toggle\"\nmsgstr \"Включить\"\n",
+            )
+        )
+        .unwrap();
+
+        let entries = read_po_entries(tmp.path()).unwrap();
+        let got: Vec<(&str, &str, Option<&str>)> = entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.value.as_str(), e.reference.as_deref()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("A", "Переключить", Some("Languages/English/Keyed/A.xml:1")),
+                ("B", "Переключить", Some("Languages/English/Keyed/B.xml:2")),
+                ("C", "Включить", Some("Languages/English/Keyed/C.xml:3")),
+            ]
+        );
+    }
+
+    #[test]
+    fn shared_keys_are_placed_by_msgctxt_not_reference_order() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        write!(
+            tmp,
+            concat!(
+                "#. rimloc-key: B|Keyed/B.xml\n#. rimloc-key: C|Keyed/C.xml\n",
+                // как после msgcat --sort-by-file: порядок и число ссылок другие
+                "#: 1.5/Languages/English/Keyed/C.xml:3 Languages/English/Keyed/A.xml:1\n",
+                "msgctxt \"A|Keyed/A.xml\"\nmsgid \"Toggle\"\nmsgstr \"Переключить\"\n",
+            )
+        )
+        .unwrap();
+
+        let entries = read_po_entries(tmp.path()).unwrap();
+        let got: Vec<(&str, Option<&str>)> = entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.reference.as_deref()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("A", Some("Languages/English/Keyed/A.xml:1")),
+                ("B", Some("Languages/_/Keyed/B.xml")),
+                ("C", Some("1.5/Languages/English/Keyed/C.xml:3")),
+            ]
+        );
+    }
+
     #[test]
     fn read_po_entries_parses_reference_ctxt_and_str() {
         // создаём временный .po с одной записью
@@ -520,4 +789,36 @@ mod tests {
             .unwrap()
             .contains("Languages/English/Keyed/A.xml:3"));
     }
+
+    #[test]
+    fn fuzzy_policy_skips_or_marks_and_never_imports_obsolete() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        writeln!(tmp, r#"msgctxt "Ok|Keyed/A.xml""#).unwrap();
+        writeln!(tmp, r#"msgid "Hello""#).unwrap();
+        writeln!(tmp, r#"msgstr "Привет""#).unwrap();
+        writeln!(tmp).unwrap();
+        writeln!(tmp, "#, fuzzy").unwrap();
+        writeln!(tmp, r#"msgctxt "Tm|Keyed/A.xml""#).unwrap();
+        writeln!(tmp, r#"msgid "Hello there""#).unwrap();
+        writeln!(tmp, r#"msgstr "Привет""#).unwrap();
+        writeln!(tmp).unwrap();
+        writeln!(tmp, r#"#~ msgctxt "Old|Keyed/A.xml""#).unwrap();
+        writeln!(tmp, r#"#~ msgid "Gone""#).unwrap();
+        writeln!(tmp, r#"#~ msgstr "Нет""#).unwrap();
+
+        let (skipped, stats) = read_po_entries_with_policy(tmp.path(), FuzzyPolicy::Skip).unwrap();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(stats, FuzzyStats { skipped: 1, imported: 0 });
+
+        let (marked, stats) = read_po_entries_with_policy(tmp.path(), FuzzyPolicy::Mark).unwrap();
+        assert_eq!(stats, FuzzyStats { skipped: 0, imported: 1 });
+        assert!(marked.iter().all(|e| e.key != "Old"));
+        let keys = fuzzy_marked_keys(&marked, FuzzyPolicy::Mark);
+        let pairs: Vec<(String, String)> =
+            marked.into_iter().map(|e| (e.key, e.value)).collect();
+        let xml = String::from_utf8(render_language_data_xml_bytes_marked(&pairs, &keys).unwrap())
+            .unwrap();
+        assert!(xml.contains("<!-- fuzzy -->\n  <Tm>"), "{xml}");
+        assert_eq!(xml.matches("<!--").count(), 1);
+    }
 }
@@ -2,7 +2,7 @@ pub use rimloc_core::parse_simple_po as parse_po_string;
 
 use quick_xml::events::Event;
 use quick_xml::Reader;
-use rimloc_core::{Result as CoreResult, TransUnit};
+use rimloc_core::{Result as CoreResult, TransUnit, UnitKind};
 use serde::Deserialize;
 use std::borrow::Cow;
 use std::convert::TryFrom;
@@ -133,28 +133,27 @@ pub fn scan_keyed_xml_with_options(root: &Path, opts: &KeyedScanOptions) -> Core
                 Ok(Event::End(_)) => {
                     if let Some(frame) = stack.pop() {
                         // Optional: emit nested dotted keys under LanguageData when enabled
-                        if opts.nested {
-                            if frame.has_text && !frame.name.is_empty() {
-                                // stack after pop contains ancestors; expect root[0] == LanguageData
-                                if stack
-                                    .first()
-                                    .map(|f| f.name.eq_ignore_ascii_case("LanguageData"))
-                                    .unwrap_or(false)
-                                    && stack.len() >= 2
-                                {
-                                    let mut parts: Vec<String> =
-                                        stack.iter().skip(1).map(|f| f.name.clone()).collect();
-                                    parts.push(frame.name.clone());
-                                    if !parts.iter().any(|p| p.eq_ignore_ascii_case("li")) {
-                                        let key = parts.join(".");
-                                        local.push(TransUnit {
-                                            key,
-                                            source: Some(frame.buffer.clone()),
-                                            path: p.clone(),
-                                            line: frame.line,
-                                        });
-                                        continue;
-                                    }
+                        if opts.nested && frame.has_text && !frame.name.is_empty() {
+                            // stack after pop contains ancestors; expect root[0] == LanguageData
+                            if stack
+                                .first()
+                                .map(|f| f.name.eq_ignore_ascii_case("LanguageData"))
+                                .unwrap_or(false)
+                                && stack.len() >= 2
+                            {
+                                let mut parts: Vec<String> =
+                                    stack.iter().skip(1).map(|f| f.name.clone()).collect();
+                                parts.push(frame.name.clone());
+                                if !parts.iter().any(|p| p.eq_ignore_ascii_case("li")) {
+                                    let key = parts.join(".");
+                                    local.push(TransUnit {
+                                        key,
+                                        source: Some(frame.buffer.clone()),
+                                        path: p.clone(),
+                                        line: frame.line,
+                                        ..Default::default()
+                                    });
+                                    continue;
                                 }
                             }
                         }
@@ -188,6 +187,7 @@ pub fn scan_keyed_xml_with_options(root: &Path, opts: &KeyedScanOptions) -> Core
                                     source: Some(source),
                                     path: p.clone(),
                                     line: frame.line,
+                                    ..Default::default()
                                 });
                             }
                         } else if opts.nested
@@ -209,6 +209,7 @@ pub fn scan_keyed_xml_with_options(root: &Path, opts: &KeyedScanOptions) -> Core
                                 source: Some(frame.buffer),
                                 path: p.clone(),
                                 line: frame.line,
+                                ..Default::default()
                             });
                         }
                     }
@@ -231,6 +232,7 @@ pub fn scan_keyed_xml_with_options(root: &Path, opts: &KeyedScanOptions) -> Core
                                 source: Some(String::new()),
                                 path: p.clone(),
                                 line,
+                                ..Default::default()
                             });
                         }
                     } else if stack.len() >= 2 {
@@ -267,6 +269,7 @@ pub fn scan_keyed_xml_with_options(root: &Path, opts: &KeyedScanOptions) -> Core
                                 source: Some(String::new()),
                                 path: p.clone(),
                                 line,
+                                ..Default::default()
                             });
                         }
                     }
@@ -305,6 +308,9 @@ pub fn scan_keyed_xml_with_options(root: &Path, opts: &KeyedScanOptions) -> Core
             buf.clear();
         }
 
+        for unit in &mut local {
+            annotate_language_unit(unit);
+        }
         local
     };
 
@@ -575,6 +581,11 @@ pub fn scan_defs_xml_under_with_fields(
                         source: Some(val),
                         path: p.to_path_buf(),
                         line,
+                        kind: UnitKind::Defs,
+                        def_type: Some(node.tag_name().name().to_string()),
+                        def_name: Some(def_name.to_string()),
+                        field_path: Some(field.clone()),
+                        ..Default::default()
                     });
                 }
             }
@@ -717,11 +728,32 @@ fn collect_values_by_path<'a>(
     }
 }
 
-fn inherit_enabled() -> bool {
-    match std::env::var("RIMLOC_INHERIT") {
-        Ok(val) if val.trim() == "0" => false,
-        _ => true,
+/// Fill kind and, for DefInjected files, def type/defName/field path of a unit
+/// read from `Languages/<lang>/...`.
+fn annotate_language_unit(unit: &mut TransUnit) {
+    unit.kind = UnitKind::from_languages_path(&unit.path).unwrap_or_default();
+    if unit.kind != UnitKind::DefInjected {
+        return;
     }
+    // Languages/<lang>/DefInjected/<DefType>/File.xml
+    let comps: Vec<_> = unit
+        .path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if let Some(i) = comps.iter().position(|c| c.eq_ignore_ascii_case("DefInjected")) {
+        if i + 2 < comps.len() {
+            unit.def_type = Some(comps[i + 1].clone());
+        }
+    }
+    if let Some((def_name, field)) = unit.key.split_once('.') {
+        unit.def_name = Some(def_name.to_string());
+        unit.field_path = Some(field.to_string());
+    }
+}
+
+fn inherit_enabled() -> bool {
+    !matches!(std::env::var("RIMLOC_INHERIT"), Ok(val) if val.trim() == "0")
 }
 
 // Helper for shallow-field inheritance across files by ParentName.
@@ -1063,6 +1095,11 @@ pub fn scan_defs_with_dict_meta(
                                             source: Some(v),
                                             path: p.to_path_buf(),
                                             line,
+                                            kind: UnitKind::Defs,
+                                            def_type: Some(def_type.clone()),
+                                            def_name: Some(def_name.clone()),
+                                            field_path: Some(path.clone()),
+                                            ..Default::default()
                                         },
                                         def_type: def_type.clone(),
                                         def_name: def_name.clone(),
@@ -1086,6 +1123,11 @@ pub fn scan_defs_with_dict_meta(
                                 source: Some(v.to_string()),
                                 path: p.to_path_buf(),
                                 line,
+                                kind: UnitKind::Defs,
+                                def_type: Some(def_type.clone()),
+                                def_name: Some(def_name.clone()),
+                                field_path: Some(display_path.clone()),
+                                ..Default::default()
                             },
                             def_type: def_type.clone(),
                             def_name: def_name.clone(),
@@ -1114,6 +1156,11 @@ pub fn scan_defs_with_dict_meta(
                                 source: Some(val.to_string()),
                                 path: p.to_path_buf(),
                                 line,
+                                kind: UnitKind::Defs,
+                                def_type: Some(def_type.clone()),
+                                def_name: Some(def_name.clone()),
+                                field_path: Some(f.clone()),
+                                ..Default::default()
                             },
                             def_type: def_type.clone(),
                             def_name: def_name.clone(),
@@ -1177,6 +1224,11 @@ pub fn scan_defs_with_dict_meta(
                                                 source: Some(val.to_string()),
                                                 path: p.to_path_buf(),
                                                 line,
+                                                kind: UnitKind::Defs,
+                                                def_type: Some(def_type.clone()),
+                                                def_name: Some(def_name.clone()),
+                                                field_path: Some(f.clone()),
+                                                ..Default::default()
                                             },
                                             def_type: def_type.clone(),
                                             def_name: def_name.clone(),
@@ -1218,6 +1270,11 @@ pub fn scan_defs_with_dict_meta(
                                     source: Some(val),
                                     path: p.to_path_buf(),
                                     line,
+                                    kind: UnitKind::Defs,
+                                    def_type: Some(def_type.clone()),
+                                    def_name: Some(def_name.clone()),
+                                    field_path: Some(f.clone()),
+                                    ..Default::default()
                                 },
                                 def_type: def_type.clone(),
                                 def_name: def_name.clone(),
@@ -1317,6 +1374,35 @@ mod defs_tests {
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn scan_keyed_xml_sets_kind_and_def_injected_metadata() -> CoreResult<()> {
+        let dir = tempdir()?;
+        let lang_dir = dir.path().join("Mods/TestMod/Languages/English");
+        fs::create_dir_all(lang_dir.join("Keyed"))?;
+        fs::create_dir_all(lang_dir.join("DefInjected/ThingDef"))?;
+        fs::write(
+            lang_dir.join("Keyed/Misc.xml"),
+            "<LanguageData><Greeting>Hi</Greeting></LanguageData>",
+        )?;
+        fs::write(
+            lang_dir.join("DefInjected/ThingDef/Items.xml"),
+            "<LanguageData><Beer.stages.0.label>drunk</Beer.stages.0.label></LanguageData>",
+        )?;
+
+        let units = scan_keyed_xml(dir.path())?;
+        let keyed = units.iter().find(|u| u.key == "Greeting").unwrap();
+        assert_eq!(keyed.kind, UnitKind::Keyed);
+        assert!(keyed.def_type.is_none() && keyed.def_name.is_none());
+
+        let injected = units.iter().find(|u| u.key == "Beer.stages.0.label").unwrap();
+        assert_eq!(injected.kind, UnitKind::DefInjected);
+        assert_eq!(injected.def_type.as_deref(), Some("ThingDef"));
+        assert_eq!(injected.def_name.as_deref(), Some("Beer"));
+        assert_eq!(injected.field_path.as_deref(), Some("stages.0.label"));
+        assert!(injected.origin_path.is_none());
+        Ok(())
+    }
+
     #[test]
     fn scan_defs_extracts_common_fields() -> CoreResult<()> {
         let dir = tempdir()?;
@@ -43,6 +43,8 @@ fn scan_ftl(root: &Path) -> Vec<Unit<'static>> {
     out
 }
 
+// C ABI entry point for the plugin loader; the signature is part of the plugin contract.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn rimloc_plugin_scan_json(root: *const c_char) -> *mut c_char {
     if root.is_null() {
@@ -0,0 +1,830 @@
+//! Gettext PO reader/writer shared by RimLoc crates.
+//!
+//! The model keeps every part of an entry (comments, references, flags,
+//! previous strings, obsolete marker, plurals) so that `parse` followed by
+//! `to_string` does not lose information. Writing uses the gettext layout
+//! without line wrapping: multi-line strings are split after each `\n`.
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Single PO entry (`msgid`/`msgstr` pair with all attached comments).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoMessage {
+    /// `# ...` lines (text after the `#` and one optional space)
+    pub translator_comments: Vec<String>,
+    /// `#. ...` lines
+    pub extracted_comments: Vec<String>,
+    /// `#: ...` references, one item per file reference
+    pub references: Vec<String>,
+    /// `#, ...` flags such as `fuzzy`
+    pub flags: Vec<String>,
+    /// `#| msgctxt` of the previous source string
+    pub previous_msgctxt: Option<String>,
+    /// `#| msgid` of the previous source string
+    pub previous_msgid: Option<String>,
+    /// `#| msgid_plural` of the previous source string
+    pub previous_msgid_plural: Option<String>,
+    pub msgctxt: Option<String>,
+    pub msgid: String,
+    pub msgid_plural: Option<String>,
+    /// Translation for singular entries (unused when `msgid_plural` is set)
+    pub msgstr: String,
+    /// `msgstr[n]` translations for plural entries
+    pub msgstr_plural: Vec<String>,
+    /// Entry is commented out with `#~`
+    pub obsolete: bool,
+}
+
+impl PoMessage {
+    /// Create a singular entry.
+    pub fn new(msgctxt: Option<String>, msgid: impl Into<String>, msgstr: impl Into<String>) -> Self {
+        Self {
+            msgctxt,
+            msgid: msgid.into(),
+            msgstr: msgstr.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    pub fn add_flag(&mut self, flag: &str) {
+        if !self.has_flag(flag) {
+            self.flags.push(flag.to_string());
+        }
+    }
+
+    pub fn remove_flag(&mut self, flag: &str) {
+        self.flags.retain(|f| f != flag);
+    }
+
+    pub fn is_fuzzy(&self) -> bool {
+        self.has_flag("fuzzy")
+    }
+
+    pub fn set_fuzzy(&mut self, fuzzy: bool) {
+        if fuzzy {
+            self.add_flag("fuzzy");
+        } else {
+            self.remove_flag("fuzzy");
+        }
+    }
+
+    pub fn is_plural(&self) -> bool {
+        self.msgid_plural.is_some()
+    }
+
+    /// True when every translation form is filled in.
+    pub fn has_translation(&self) -> bool {
+        if self.is_plural() {
+            !self.msgstr_plural.is_empty() && self.msgstr_plural.iter().all(|s| !s.is_empty())
+        } else {
+            !self.msgstr.is_empty()
+        }
+    }
+
+    /// Translation text: `msgstr`, or the first `msgstr[n]` form of a plural entry
+    /// (RimWorld strings have no plural forms, so `msgstr[0]` is what gets imported).
+    pub fn translation(&self) -> &str {
+        if self.is_plural() {
+            self.msgstr_plural.first().map(String::as_str).unwrap_or("")
+        } else {
+            &self.msgstr
+        }
+    }
+
+    /// Translated in the gettext sense: filled in, not fuzzy, not obsolete.
+    pub fn is_translated(&self) -> bool {
+        !self.obsolete && !self.is_fuzzy() && self.has_translation()
+    }
+
+    /// Header entry: empty `msgid` without context.
+    pub fn is_header(&self) -> bool {
+        !self.obsolete && self.msgctxt.is_none() && self.msgid.is_empty() && self.msgid_plural.is_none()
+    }
+
+    /// Contexts the entry applies to: its `msgctxt`, then every
+    /// `#. rimloc-key: <msgctxt>` line added by a compact export.
+    pub fn covered_contexts(&self) -> Vec<&str> {
+        self.msgctxt
+            .as_deref()
+            .into_iter()
+            .chain(self.extracted_comments.iter().filter_map(|c| {
+                c.strip_prefix(SHARED_KEY_COMMENT)
+                    .map(str::trim)
+                    .filter(|ctx| !ctx.is_empty())
+            }))
+            .collect()
+    }
+}
+
+/// Parsed PO file: optional header entry followed by regular (and obsolete) entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoFile {
+    pub header: Option<PoMessage>,
+    pub messages: Vec<PoMessage>,
+}
+
+impl PoFile {
+    /// Parse PO text. A leading UTF-8 BOM is ignored.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+        let mut p = Parser::default();
+        for (idx, raw) in input.lines().enumerate() {
+            p.line(idx + 1, raw.trim_end_matches('\r'))?;
+        }
+        p.finish();
+        Ok(p.out)
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| eyre!("{}: {e}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Entries that are not obsolete.
+    pub fn active(&self) -> impl Iterator<Item = &PoMessage> {
+        self.messages.iter().filter(|m| !m.obsolete)
+    }
+
+    /// Header fields in file order (`Name: value` lines of the header msgstr).
+    pub fn header_fields(&self) -> Vec<(String, String)> {
+        let Some(h) = &self.header else {
+            return Vec::new();
+        };
+        h.msgstr
+            .lines()
+            .filter_map(|l| l.split_once(':'))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect()
+    }
+
+    /// Header field value by name (case-insensitive).
+    pub fn header_field(&self, name: &str) -> Option<&str> {
+        let h = self.header.as_ref()?;
+        h.msgstr
+            .lines()
+            .filter_map(|l| l.split_once(':'))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.trim())
+    }
+
+    /// Set or append a header field, creating the header entry when missing.
+    pub fn set_header_field(&mut self, name: &str, value: &str) {
+        let h = self
+            .header
+            .get_or_insert_with(|| PoMessage::new(None, "", ""));
+        let mut replaced = false;
+        let mut out = String::with_capacity(h.msgstr.len() + name.len() + value.len() + 4);
+        for l in h.msgstr.lines() {
+            let is_target = l
+                .split_once(':')
+                .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case(name));
+            if is_target && !replaced {
+                out.push_str(&format!("{name}: {value}\n"));
+                replaced = true;
+            } else if !is_target {
+                out.push_str(l);
+                out.push('\n');
+            }
+        }
+        if !replaced {
+            out.push_str(&format!("{name}: {value}\n"));
+        }
+        h.msgstr = out;
+    }
+
+    /// Remove a header field (case-insensitive); no-op when absent.
+    pub fn remove_header_field(&mut self, name: &str) {
+        let Some(h) = self.header.as_mut() else {
+            return;
+        };
+        h.msgstr = h
+            .msgstr
+            .lines()
+            .filter(|l| {
+                !l.split_once(':')
+                    .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case(name))
+            })
+            .map(|l| format!("{l}\n"))
+            .collect();
+    }
+}
+
+impl fmt::Display for PoFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for m in self.header.iter().chain(self.messages.iter()) {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "{m}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PoMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in &self.translator_comments {
+            write_comment(f, "#", c)?;
+        }
+        for c in &self.extracted_comments {
+            write_comment(f, "#.", c)?;
+        }
+        for r in &self.references {
+            writeln!(f, "#: {r}")?;
+        }
+        if !self.flags.is_empty() {
+            writeln!(f, "#, {}", self.flags.join(", "))?;
+        }
+        let prev = if self.obsolete { "#~| " } else { "#| " };
+        if let Some(v) = &self.previous_msgctxt {
+            write_field(f, prev, "msgctxt", v)?;
+        }
+        if let Some(v) = &self.previous_msgid {
+            write_field(f, prev, "msgid", v)?;
+        }
+        if let Some(v) = &self.previous_msgid_plural {
+            write_field(f, prev, "msgid_plural", v)?;
+        }
+        let pfx = if self.obsolete { "#~ " } else { "" };
+        if let Some(v) = &self.msgctxt {
+            write_field(f, pfx, "msgctxt", v)?;
+        }
+        write_field(f, pfx, "msgid", &self.msgid)?;
+        if let Some(v) = &self.msgid_plural {
+            write_field(f, pfx, "msgid_plural", v)?;
+            for (i, s) in self.msgstr_plural.iter().enumerate() {
+                write_field(f, pfx, &format!("msgstr[{i}]"), s)?;
+            }
+            if self.msgstr_plural.is_empty() {
+                write_field(f, pfx, "msgstr[0]", "")?;
+            }
+        } else {
+            write_field(f, pfx, "msgstr", &self.msgstr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a comment with `marker` on every line, so a multi-line text cannot leave a bare line.
+fn write_comment(f: &mut fmt::Formatter<'_>, marker: &str, text: &str) -> fmt::Result {
+    for line in text.split('\n') {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            writeln!(f, "{marker}")?;
+        } else {
+            writeln!(f, "{marker} {line}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `keyword "value"`; values with inner newlines are split gettext-style.
+fn write_field(f: &mut fmt::Formatter<'_>, prefix: &str, keyword: &str, value: &str) -> fmt::Result {
+    let chunks: Vec<&str> = value.split_inclusive('\n').collect();
+    if chunks.len() > 1 {
+        writeln!(f, "{prefix}{keyword} \"\"")?;
+        for c in chunks {
+            writeln!(f, "{prefix}\"{}\"", escape(c))?;
+        }
+        Ok(())
+    } else {
+        writeln!(f, "{prefix}{keyword} \"{}\"", escape(value))
+    }
+}
+
+/// Build a RimLoc `msgctxt`: `<key>|<path under Languages/<lang>>`.
+/// No line numbers, so the context stays stable when the source XML is edited.
+pub fn make_msgctxt(key: &str, rel: &str) -> String {
+    format!("{key}|{}", rel.replace('\\', "/"))
+}
+
+/// Prefix of the `#.` comment naming another key that shares a compact entry.
+pub const SHARED_KEY_COMMENT: &str = "rimloc-key:";
+
+/// Split a RimLoc `msgctxt` into key and relative path.
+/// Also accepts the legacy `key|rel:line` form (the line suffix is dropped)
+/// and a bare `key`.
+pub fn split_msgctxt(ctx: &str) -> (&str, Option<&str>) {
+    let Some((key, rel)) = ctx.split_once('|') else {
+        return (ctx.trim(), None);
+    };
+    let rel = match rel.rsplit_once(':') {
+        Some((head, line)) if !line.is_empty() && line.bytes().all(|b| b.is_ascii_digit()) => head,
+        _ => rel,
+    };
+    (key.trim(), Some(rel).filter(|r| !r.is_empty()))
+}
+
+/// Escape a string for use inside PO double quotes.
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 8);
+    for ch in s.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{7}' => out.push_str("\\a"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{b}' => out.push_str("\\v"),
+            '\u{c}' => out.push_str("\\f"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Parse one quoted PO string literal (`"..."`) and resolve C escapes.
+pub fn unquote(s: &str) -> Result<String> {
+    let s = s.trim();
+    let inner = s
+        .strip_prefix('"')
+        .ok_or_else(|| eyre!("invalid po string: {s}"))?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                if chars.next().is_some() {
+                    return Err(eyre!("unexpected text after closing quote: {s}"));
+                }
+                return Ok(out);
+            }
+            '\\' => {
+                let Some(n) = chars.next() else { break };
+                match n {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'a' => out.push('\u{7}'),
+                    'b' => out.push('\u{8}'),
+                    'v' => out.push('\u{b}'),
+                    'f' => out.push('\u{c}'),
+                    '\\' | '"' | '\'' | '?' => out.push(n),
+                    '0'..='7' => {
+                        let mut v = n.to_digit(8).unwrap_or(0);
+                        for _ in 0..2 {
+                            match chars.peek().and_then(|d| d.to_digit(8)) {
+                                Some(d) => {
+                                    v = v * 8 + d;
+                                    chars.next();
+                                }
+                                None => break,
+                            }
+                        }
+                        out.push(char::from_u32(v).unwrap_or('\u{fffd}'));
+                    }
+                    'x' => {
+                        let mut v = 0u32;
+                        while let Some(d) = chars.peek().and_then(|d| d.to_digit(16)) {
+                            v = v.wrapping_mul(16).wrapping_add(d);
+                            chars.next();
+                        }
+                        out.push(char::from_u32(v).unwrap_or('\u{fffd}'));
+                    }
+                    other => {
+                        out.push('\\');
+                        out.push(other);
+                    }
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    Err(eyre!("unterminated po string: {s}"))
+}
+
+/// Split a `#:` line into references.
+///
+/// Whitespace separates references only after a `:<line>` suffix, so paths
+/// with spaces written one-per-line (as RimLoc does) stay intact. Unicode
+/// isolates (U+2068 … U+2069) used by newer gettext are honoured and stripped.
+fn split_references(s: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut in_isolate = false;
+    let mut token_start = 0usize;
+    let mut in_token = false;
+    let flush = |out: &mut Vec<String>, span: &str| {
+        let span = span.replace(['\u{2068}', '\u{2069}'], "");
+        if !span.is_empty() {
+            out.push(span);
+        }
+    };
+    let ends_with_line = |tok: &str| {
+        tok.rsplit_once(':')
+            .is_some_and(|(_, n)| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    };
+    for (i, ch) in s.char_indices() {
+        if ch.is_whitespace() && !in_isolate {
+            if in_token {
+                in_token = false;
+                let tok = &s[token_start..i];
+                let begin = *start.get_or_insert(token_start);
+                if ends_with_line(tok) || tok.ends_with('\u{2069}') {
+                    flush(&mut out, &s[begin..i]);
+                    start = None;
+                }
+            }
+            continue;
+        }
+        if !in_token {
+            in_token = true;
+            token_start = i;
+        }
+        if ch == '\u{2068}' {
+            in_isolate = true;
+        } else if ch == '\u{2069}' {
+            in_isolate = false;
+        }
+    }
+    if in_token {
+        start.get_or_insert(token_start);
+    }
+    if let Some(begin) = start {
+        flush(&mut out, s[begin..].trim_end());
+    }
+    out
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    None,
+    PrevCtxt,
+    PrevId,
+    PrevIdPlural,
+    Ctxt,
+    Id,
+    IdPlural,
+    Str,
+    StrPlural(usize),
+}
+
+#[derive(Default)]
+struct Parser {
+    out: PoFile,
+    cur: PoMessage,
+    field: Option<Field>,
+    has_ctxt: bool,
+    has_id: bool,
+    has_str: bool,
+}
+
+impl Parser {
+    fn started(&self) -> bool {
+        self.has_ctxt || self.has_id || self.has_str
+    }
+
+    fn finish(&mut self) {
+        if self.started() {
+            let msg = std::mem::take(&mut self.cur);
+            if self.out.header.is_none() && self.out.messages.is_empty() && msg.is_header() {
+                self.out.header = Some(msg);
+            } else {
+                self.out.messages.push(msg);
+            }
+        }
+        self.field = None;
+        self.has_ctxt = false;
+        self.has_id = false;
+        self.has_str = false;
+    }
+
+    fn line(&mut self, no: usize, raw: &str) -> Result<()> {
+        let t = raw.trim();
+        if t.is_empty() {
+            self.finish();
+            return Ok(());
+        }
+        if let Some(rest) = t.strip_prefix("#~") {
+            let rest = rest.trim_start();
+            if let Some(prev) = rest.strip_prefix('|') {
+                return self.previous(no, prev.trim_start(), true);
+            }
+            return self.keyword(no, rest, true);
+        }
+        if let Some(rest) = t.strip_prefix("#|") {
+            return self.previous(no, rest.trim_start(), false);
+        }
+        if let Some(rest) = t.strip_prefix('#') {
+            if self.started() {
+                self.finish();
+            }
+            self.field = None;
+            if let Some(v) = rest.strip_prefix('.') {
+                self.cur.extracted_comments.push(strip_one_space(v).to_string());
+            } else if let Some(v) = rest.strip_prefix(':') {
+                self.cur.references.extend(split_references(v));
+            } else if let Some(v) = rest.strip_prefix(',') {
+                self.cur.flags.extend(
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string),
+                );
+            } else {
+                self.cur.translator_comments.push(strip_one_space(rest).to_string());
+            }
+            return Ok(());
+        }
+        self.keyword(no, t, false)
+    }
+
+    fn previous(&mut self, no: usize, rest: &str, obsolete: bool) -> Result<()> {
+        if self.started() {
+            self.finish();
+        }
+        if obsolete {
+            self.cur.obsolete = true;
+        }
+        if rest.starts_with('"') {
+            return self.continuation(no, rest);
+        }
+        let (kw, value) = split_keyword(rest);
+        let value = unquote(value).map_err(|e| eyre!("line {no}: {e}"))?;
+        match kw {
+            "msgctxt" => {
+                self.cur.previous_msgctxt = Some(value);
+                self.field = Some(Field::PrevCtxt);
+            }
+            "msgid" => {
+                self.cur.previous_msgid = Some(value);
+                self.field = Some(Field::PrevId);
+            }
+            "msgid_plural" => {
+                self.cur.previous_msgid_plural = Some(value);
+                self.field = Some(Field::PrevIdPlural);
+            }
+            other => return Err(eyre!("line {no}: unexpected keyword after #|: {other}")),
+        }
+        Ok(())
+    }
+
+    fn keyword(&mut self, no: usize, t: &str, obsolete: bool) -> Result<()> {
+        if t.starts_with('"') {
+            return self.continuation(no, t);
+        }
+        let (kw, value) = split_keyword(t);
+        let value = unquote(value).map_err(|e| eyre!("line {no}: {e}"))?;
+        let starts_entry = matches!(kw, "msgctxt" | "msgid");
+        if starts_entry
+            && (self.has_str
+                || self.has_id
+                || (kw == "msgctxt" && self.has_ctxt)
+                || (self.started() && self.cur.obsolete != obsolete))
+        {
+            self.finish();
+        }
+        if obsolete {
+            self.cur.obsolete = true;
+        }
+        match kw {
+            "msgctxt" => {
+                self.cur.msgctxt = Some(value);
+                self.has_ctxt = true;
+                self.field = Some(Field::Ctxt);
+            }
+            "msgid" => {
+                self.cur.msgid = value;
+                self.has_id = true;
+                self.field = Some(Field::Id);
+            }
+            "msgid_plural" => {
+                if !self.has_id {
+                    return Err(eyre!("line {no}: msgid_plural without msgid"));
+                }
+                self.cur.msgid_plural = Some(value);
+                self.field = Some(Field::IdPlural);
+            }
+            "msgstr" => {
+                if self.has_str {
+                    return Err(eyre!("line {no}: duplicate msgstr"));
+                }
+                self.cur.msgstr = value;
+                self.has_str = true;
+                self.field = Some(Field::Str);
+            }
+            other => {
+                let idx = other
+                    .strip_prefix("msgstr[")
+                    .and_then(|r| r.strip_suffix(']'))
+                    .and_then(|n| n.trim().parse::<usize>().ok())
+                    .ok_or_else(|| eyre!("line {no}: unexpected keyword: {other}"))?;
+                if self.cur.msgstr_plural.len() <= idx {
+                    self.cur.msgstr_plural.resize(idx + 1, String::new());
+                }
+                self.cur.msgstr_plural[idx] = value;
+                self.has_str = true;
+                self.field = Some(Field::StrPlural(idx));
+            }
+        }
+        Ok(())
+    }
+
+    fn continuation(&mut self, no: usize, t: &str) -> Result<()> {
+        let chunk = unquote(t).map_err(|e| eyre!("line {no}: {e}"))?;
+        let target = match self.field.unwrap_or(Field::None) {
+            Field::None => return Err(eyre!("line {no}: string continuation without keyword")),
+            Field::PrevCtxt => self.cur.previous_msgctxt.get_or_insert_with(String::new),
+            Field::PrevId => self.cur.previous_msgid.get_or_insert_with(String::new),
+            Field::PrevIdPlural => self.cur.previous_msgid_plural.get_or_insert_with(String::new),
+            Field::Ctxt => self.cur.msgctxt.get_or_insert_with(String::new),
+            Field::Id => &mut self.cur.msgid,
+            Field::IdPlural => self.cur.msgid_plural.get_or_insert_with(String::new),
+            Field::Str => &mut self.cur.msgstr,
+            Field::StrPlural(i) => &mut self.cur.msgstr_plural[i],
+        };
+        target.push_str(&chunk);
+        Ok(())
+    }
+}
+
+fn split_keyword(t: &str) -> (&str, &str) {
+    match t.find(|c: char| c.is_whitespace() || c == '"') {
+        Some(pos) => (&t[..pos], &t[pos..]),
+        None => (t, ""),
+    }
+}
+
+fn strip_one_space(s: &str) -> &str {
+    s.strip_prefix(' ').unwrap_or(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"# Translator note
+#
+msgid ""
+msgstr ""
+"Project-Id-Version: Mod 1.0\n"
+"Language: ru\n"
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : 1);\n"
+
+# keep short
+#. ThingDef: description
+#: Languages/English/Keyed/A.xml:3
+#: C:\Program Files\Mods\A.xml:7
+#, fuzzy, c-format
+#| msgctxt "Greeting"
+#| msgid "Hi"
+msgctxt "Greeting"
+msgid "Hello\n"
+"world"
+msgstr "Привет\n"
+"мир"
+
+msgid "apple"
+msgid_plural "apples"
+msgstr[0] "яблоко"
+msgstr[1] "яблока"
+msgstr[2] "яблок"
+
+#~| msgid "Old"
+#~ msgctxt "Gone"
+#~ msgid "Removed"
+#~ msgstr "Удалено"
+"#;
+
+    #[test]
+    fn parses_all_entry_parts() {
+        let po = PoFile::parse(SAMPLE).unwrap();
+        let h = po.header.as_ref().unwrap();
+        assert_eq!(h.translator_comments, vec!["Translator note", ""]);
+        assert_eq!(po.header_field("language"), Some("ru"));
+        assert_eq!(po.header_fields().len(), 3);
+        assert_eq!(po.messages.len(), 3);
+
+        let m = &po.messages[0];
+        assert_eq!(m.translator_comments, vec!["keep short"]);
+        assert_eq!(m.extracted_comments, vec!["ThingDef: description"]);
+        assert_eq!(
+            m.references,
+            vec!["Languages/English/Keyed/A.xml:3", r"C:\Program Files\Mods\A.xml:7"]
+        );
+        assert!(m.is_fuzzy() && m.has_flag("c-format"));
+        assert_eq!(m.previous_msgctxt.as_deref(), Some("Greeting"));
+        assert_eq!(m.previous_msgid.as_deref(), Some("Hi"));
+        assert_eq!(m.msgid, "Hello\nworld");
+        assert_eq!(m.msgstr, "Привет\nмир");
+        assert!(!m.is_translated());
+
+        let p = &po.messages[1];
+        assert_eq!(p.msgid_plural.as_deref(), Some("apples"));
+        assert_eq!(p.msgstr_plural, vec!["яблоко", "яблока", "яблок"]);
+        assert_eq!(p.translation(), "яблоко");
+        assert!(p.is_translated());
+
+        let o = &po.messages[2];
+        assert!(o.obsolete);
+        assert_eq!(o.previous_msgid.as_deref(), Some("Old"));
+        assert_eq!(o.msgctxt.as_deref(), Some("Gone"));
+        assert_eq!(o.msgstr, "Удалено");
+        assert_eq!(po.active().count(), 2);
+    }
+
+    #[test]
+    fn write_then_parse_is_lossless() {
+        let po = PoFile::parse(SAMPLE).unwrap();
+        let text = po.to_string();
+        let again = PoFile::parse(&text).unwrap();
+        assert_eq!(po, again);
+        assert_eq!(text, again.to_string());
+    }
+
+    #[test]
+    fn multi_line_comments_stay_comments() {
+        let mut po = PoFile::parse(SAMPLE).unwrap();
+        let m = &mut po.messages[0];
+        m.translator_comments = vec!["first\n\nthird".into()];
+        m.extracted_comments = vec!["TM 90%: one\r\ntwo".into()];
+        let again = PoFile::parse(&po.to_string()).unwrap();
+        let m = again.active().next().unwrap();
+        assert_eq!(m.translator_comments, ["first", "", "third"]);
+        assert_eq!(m.extracted_comments, ["TM 90%: one", "two"]);
+    }
+
+    #[test]
+    fn header_field_can_be_replaced_or_added() {
+        let mut po = PoFile::parse(SAMPLE).unwrap();
+        po.set_header_field("Language", "uk");
+        po.set_header_field("X-RimLoc-Schema", "1");
+        assert_eq!(po.header_field("Language"), Some("uk"));
+        assert_eq!(po.header_field("X-RimLoc-Schema"), Some("1"));
+        assert_eq!(po.header_fields().len(), 4);
+        po.remove_header_field("x-rimloc-schema");
+        assert_eq!(po.header_field("X-RimLoc-Schema"), None);
+        assert_eq!(po.header_fields().len(), 3);
+    }
+
+    #[test]
+    fn unquote_resolves_escapes_and_rejects_garbage() {
+        assert_eq!(unquote(r#""a\"b\\c\n\t\r""#).unwrap(), "a\"b\\c\n\t\r");
+        assert_eq!(unquote(r#""\101\x42""#).unwrap(), "AB");
+        assert!(unquote(r#""open"#).is_err());
+        assert!(unquote(r#""a" b"#).is_err());
+        assert_eq!(escape("a\"b\\\n"), r#"a\"b\\\n"#);
+    }
+
+    #[test]
+    fn references_support_isolates_and_multiple_per_line() {
+        assert_eq!(split_references(" a.c:1 b.c:22"), vec!["a.c:1", "b.c:22"]);
+        assert_eq!(
+            split_references(" \u{2068}My Mod/A.xml\u{2069}:3 b.c"),
+            vec!["My Mod/A.xml:3", "b.c"]
+        );
+        assert_eq!(split_references(" \u{2068}My Mod/A.xml\u{2069}"), vec!["My Mod/A.xml"]);
+        assert_eq!(split_references(" /Mods/My Mod/A.xml"), vec!["/Mods/My Mod/A.xml"]);
+    }
+
+    #[test]
+    fn msgctxt_round_trips_and_accepts_legacy_lines() {
+        let ctx = make_msgctxt("Greeting", r"Keyed\A.xml");
+        assert_eq!(ctx, "Greeting|Keyed/A.xml");
+        assert_eq!(split_msgctxt(&ctx), ("Greeting", Some("Keyed/A.xml")));
+        assert_eq!(split_msgctxt("Greeting|Keyed/A.xml:3"), ("Greeting", Some("Keyed/A.xml")));
+        assert_eq!(split_msgctxt("Greeting"), ("Greeting", None));
+    }
+
+    #[test]
+    fn covered_contexts_include_shared_keys() {
+        let po = PoFile::parse(concat!(
+            "#. Def label: toggle\n#. rimloc-key: B|Keyed/B.xml\n#. rimloc-key: C|Keyed/C.xml\n",
+            "#: Keyed/A.xml:1\n#: Keyed/B.xml:2\n#: Keyed/C.xml:3\n",
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"Toggle\"\nmsgstr \"\"\n",
+        ))
+        .unwrap();
+        assert_eq!(
+            po.messages[0].covered_contexts(),
+            vec!["A|Keyed/A.xml", "B|Keyed/B.xml", "C|Keyed/C.xml"]
+        );
+        assert!(PoMessage::new(None, "x", "").covered_contexts().is_empty());
+    }
+
+    #[test]
+    fn lenient_with_missing_msgid_and_rejects_stray_strings() {
+        let po = PoFile::parse("msgctxt \"Greeting\"\nmsgstr \"Привет\"\n").unwrap();
+        assert!(po.header.is_none());
+        assert_eq!(po.messages[0].msgctxt.as_deref(), Some("Greeting"));
+        assert_eq!(po.messages[0].msgstr, "Привет");
+        assert!(PoFile::parse("\"dangling\"\n").is_err());
+        assert!(PoFile::parse("msgid \"a\"\nmsgstr \"b\"\nmsgstr \"c\"\n").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Result;
+use rimloc_import_po::FuzzyPolicy;
 use std::path::{Path, PathBuf};
 
 /// Build translation mod from an existing Languages/<lang> tree under `from_root`.
@@ -176,8 +177,11 @@ pub struct BuildPlan {
     pub lang_dir: String,
     pub files: Vec<(PathBuf, usize)>,
     pub total_keys: usize,
+    pub fuzzy_skipped: usize,
+    pub fuzzy_imported: usize,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_from_po_dry_run(
     po: &Path,
     out_mod: &Path,
@@ -186,6 +190,7 @@ pub fn build_from_po_dry_run(
     package_id: &str,
     rw_version: &str,
     dedupe: bool,
+    fuzzy: FuzzyPolicy,
 ) -> Result<BuildPlan> {
     let plan = rimloc_import_po::build_translation_mod_dry_run_opts(
         po,
@@ -195,6 +200,7 @@ pub fn build_from_po_dry_run(
         package_id,
         rw_version,
         dedupe,
+        fuzzy,
     )?;
     Ok(BuildPlan {
         mod_name: plan.mod_name,
@@ -204,9 +210,12 @@ pub fn build_from_po_dry_run(
         lang_dir: plan.lang_dir,
         files: plan.files,
         total_keys: plan.total_keys,
+        fuzzy_skipped: plan.fuzzy.skipped,
+        fuzzy_imported: plan.fuzzy.imported,
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_from_po_execute(
     po: &Path,
     out_mod: &Path,
@@ -215,6 +224,7 @@ pub fn build_from_po_execute(
     package_id: &str,
     rw_version: &str,
     dedupe: bool,
+    fuzzy: FuzzyPolicy,
 ) -> Result<()> {
     rimloc_import_po::build_translation_mod_with_langdir_opts(
         po,
@@ -224,6 +234,7 @@ pub fn build_from_po_execute(
         package_id,
         rw_version,
         dedupe,
+        fuzzy,
     )
 }
 
@@ -237,10 +248,12 @@ pub fn build_from_po_with_progress(
     package_id: &str,
     rw_version: &str,
     dedupe: bool,
+    fuzzy: FuzzyPolicy,
     mut progress: impl FnMut(usize, usize, &Path),
 ) -> Result<()> {
     // Read entries and group by relative path under Languages/
-    let entries = rimloc_import_po::read_po_entries(po)?;
+    let (entries, _) = rimloc_import_po::read_po_entries_with_policy(po, fuzzy)?;
+    let marked = rimloc_import_po::fuzzy_marked_keys(&entries, fuzzy);
     let re =
         regex::Regex::new(r"(?:^|[/\\])Languages[/\\][^/\\]+[/\\](?P<rel>.+?)(?::\d+)?$").unwrap();
     use std::collections::{BTreeMap, HashSet};
@@ -282,7 +295,7 @@ pub fn build_from_po_with_progress(
             items = outv;
         }
         let out_path = out_mod.join("Languages").join(lang_folder).join(&rel);
-        rimloc_import_po::write_language_data_xml(&out_path, &items)?;
+        rimloc_import_po::write_language_data_xml_marked(&out_path, &items, &marked)?;
         idx += 1;
         progress(idx, total, &out_path);
     }
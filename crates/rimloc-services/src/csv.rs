@@ -0,0 +1,107 @@
+use crate::{
+    export::{collect_export_units, collect_translations},
+    util::rel_slash_path,
+    Result,
+};
+use rimloc_core::PoEntry;
+use rimloc_export_csv::{BilingualRow, CsvOptions, RowStatus};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Stats for [`export_csv`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvExportStats {
+    pub total: usize,
+    /// Rows prefilled from existing `Languages/<lang>` translations
+    pub translated: usize,
+}
+
+/// Export source strings (same units as PO export) to a bilingual CSV, one row per key
+/// with the current translation from `Languages/<lang>` next to the source text.
+pub fn export_csv(
+    scan_root: &Path,
+    out: &Path,
+    lang: Option<&str>,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+    opts: CsvOptions,
+) -> Result<CsvExportStats> {
+    let units = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let existing: HashMap<String, String> = match lang {
+        Some(code) => collect_translations(scan_root, code)?,
+        None => HashMap::new(),
+    };
+
+    let mut stats = CsvExportStats::default();
+    let mut rows = Vec::with_capacity(units.len());
+    for u in units {
+        let file = rel_slash_path(&u.path, scan_root);
+        let translation = existing.get(&u.key).cloned().unwrap_or_default();
+        stats.total += 1;
+        let status = if translation.is_empty() {
+            RowStatus::New
+        } else {
+            stats.translated += 1;
+            RowStatus::Translated
+        };
+        rows.push(BilingualRow {
+            kind: u.kind.as_str().to_string(),
+            key: u.key,
+            file,
+            source: u.source.unwrap_or_default(),
+            translation,
+            status,
+            notes: u.comments.join("\n"),
+        });
+    }
+    let mut buf = Vec::new();
+    rimloc_export_csv::write_bilingual_csv(&mut buf, &rows, opts)?;
+    crate::util::write_atomic(out, &buf)?;
+    Ok(stats)
+}
+
+/// Read bilingual CSV rows as import entries: the `file` column is the reference used
+/// for placement, and rows with status `fuzzy` count as fuzzy.
+pub fn read_csv_entries(path: &Path, delimiter: Option<u8>) -> Result<Vec<PoEntry>> {
+    let rows = rimloc_export_csv::read_bilingual_csv(std::fs::File::open(path)?, delimiter)?;
+    Ok(rows
+        .into_iter()
+        .map(|r| PoEntry {
+            fuzzy: r.status == RowStatus::Fuzzy && !r.translation.is_empty(),
+            key: r.key,
+            value: r.translation,
+            reference: Some(r.file).filter(|f| !f.is_empty()),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_then_read_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        crate::util::write_greeting_mod(root);
+        let out = root.join("mod.csv");
+        let stats = export_csv(root, &out, Some("ru"), None, None, CsvOptions::default()).unwrap();
+        assert_eq!((stats.total, stats.translated), (2, 1));
+        let text = std::fs::read_to_string(&out).unwrap();
+        assert!(text.starts_with("key,kind,file,source,translation,status,notes\n"));
+        assert!(text.contains(
+            "Greeting,keyed,Languages/English/Keyed/A.xml,\"Hello, {0}\",\"Привет, {0}\",translated,"
+        ));
+
+        let entries = read_csv_entries(&out, None).unwrap();
+        let greeting = entries.iter().find(|e| e.key == "Greeting").unwrap();
+        assert_eq!(greeting.value, "Привет, {0}");
+        assert_eq!(
+            greeting.reference.as_deref(),
+            Some("Languages/English/Keyed/A.xml")
+        );
+        assert!(!greeting.fuzzy);
+        let bye = entries.iter().find(|e| e.key == "Bye").unwrap();
+        assert!(bye.value.is_empty());
+    }
+}
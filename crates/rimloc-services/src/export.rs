@@ -1,31 +1,203 @@
 use crate::{
-    autodiscover_defs_context, scan_defs_with_meta,
+    autodiscover_defs_context,
+    po_meta::{export_header_meta, source_fingerprint},
+    scan_defs_with_meta,
+    tm::{load_tm_index, TmOptions},
     util::{def_injected_target_path, is_under_languages_dir},
     ExportPoStats, Result,
 };
+use rimloc_export_po::PoSplit;
 use rimloc_parsers_xml::DefsMetaUnit;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-/// Export to PO with optional TM, filtering by source lang or explicit folder name.
+/// Options for [`export_po_with_tm`] and [`export_po_split`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportPoOptions<'a> {
+    /// Value of the `Language` header; also picks the TM target language
+    pub lang: Option<&'a str>,
+    /// Source language by ISO code (default: English)
+    pub source_lang: Option<&'a str>,
+    /// Source language folder name; takes precedence over `source_lang`
+    pub source_lang_dir: Option<&'a str>,
+    /// Fuzzy translation memory used to prefill msgstr
+    pub tm: Option<&'a TmOptions<'a>>,
+    /// Terms found in a source text are listed in `#. Glossary:` comments
+    pub glossary: Option<&'a rimloc_glossary::Glossary>,
+    /// Game version written to the header
+    pub game_version: Option<&'a str>,
+    /// Fold keys sharing a source text into one entry
+    pub compact: bool,
+}
+
+/// Export to PO with optional TM (fuzzy, see [`TmOptions`]), filtering by source lang or explicit folder name.
+/// The header carries mod identity from About.xml, dates, the game version and a source fingerprint.
 pub fn export_po_with_tm(
     scan_root: &Path,
     out_po: &Path,
-    lang: Option<&str>,
+    opts: &ExportPoOptions,
+) -> Result<ExportPoStats> {
+    let ExportPoOptions {
+        lang,
+        source_lang,
+        source_lang_dir,
+        tm,
+        glossary,
+        game_version,
+        compact,
+    } = *opts;
+    let filtered = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let tm_index = tm
+        .filter(|t| !t.roots.is_empty())
+        .map(|t| load_tm_index(t, lang, &source_dir(source_lang, source_lang_dir)))
+        .transpose()?;
+    let meta = export_header_meta(
+        scan_root,
+        &source_dir(source_lang, source_lang_dir),
+        game_version,
+        &filtered,
+    );
+
+    let stats = rimloc_export_po::write_po_opts(
+        out_po,
+        &filtered,
+        &rimloc_export_po::PoWriteOptions {
+            lang,
+            tm: None,
+            tm_index: tm_index.as_ref(),
+            tm_min_score: tm.map(|t| t.min_score),
+            mod_root: Some(scan_root),
+            meta: Some(&meta),
+            compact,
+            glossary,
+        },
+    )?;
+    Ok(stats)
+}
+
+/// Export to several PO files under `out_dir`, grouped by `split`
+/// (see [`PoSplit::group_of`]). Each file gets its own header fingerprint;
+/// with [`PoSplit::Version`] the header game version is the group's version folder.
+/// Returns the written files (sorted) with their stats.
+pub fn export_po_split(
+    scan_root: &Path,
+    out_dir: &Path,
+    split: PoSplit,
+    opts: &ExportPoOptions,
+) -> Result<Vec<(PathBuf, ExportPoStats)>> {
+    let ExportPoOptions {
+        lang,
+        source_lang,
+        source_lang_dir,
+        tm,
+        glossary,
+        game_version,
+        compact,
+    } = *opts;
+    let units = if split == PoSplit::Version {
+        collect_units_per_version(scan_root, source_lang, source_lang_dir)?
+    } else {
+        collect_export_units(scan_root, source_lang, source_lang_dir)?
+    };
+    let tm_index = tm
+        .filter(|t| !t.roots.is_empty())
+        .map(|t| load_tm_index(t, lang, &source_dir(source_lang, source_lang_dir)))
+        .transpose()?;
+    let base_meta = export_header_meta(
+        scan_root,
+        &source_dir(source_lang, source_lang_dir),
+        game_version,
+        &[],
+    );
+
+    let mut groups: BTreeMap<String, Vec<rimloc_core::TransUnit>> = BTreeMap::new();
+    for u in units {
+        groups
+            .entry(split.group_of(&u, Some(scan_root)))
+            .or_default()
+            .push(u);
+    }
+
+    let mut written = Vec::with_capacity(groups.len());
+    for (rel, part) in groups {
+        let mut meta = base_meta.clone();
+        meta.source_fingerprint = Some(source_fingerprint(&part));
+        if split == PoSplit::Version {
+            if let Some(ver) = rel.strip_suffix(".po").filter(|v| *v != "Common") {
+                meta.game_version = Some(ver.to_string());
+            }
+        }
+        let out_po = out_dir.join(&rel);
+        if let Some(parent) = out_po.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let stats = rimloc_export_po::write_po_opts(
+            &out_po,
+            &part,
+            &rimloc_export_po::PoWriteOptions {
+                lang,
+                tm: None,
+                tm_index: tm_index.as_ref(),
+                tm_min_score: tm.map(|t| t.min_score),
+                mod_root: Some(scan_root),
+                meta: Some(&meta),
+                compact,
+                glossary,
+            },
+        )?;
+        written.push((out_po, stats));
+    }
+    Ok(written)
+}
+
+/// Export units of every version folder scanned on its own (so the same key may
+/// appear once per version), plus units from outside version folders.
+fn collect_units_per_version(
+    scan_root: &Path,
     source_lang: Option<&str>,
     source_lang_dir: Option<&str>,
-    tm_roots: Option<&[std::path::PathBuf]>,
-) -> Result<ExportPoStats> {
-    let units = rimloc_parsers_xml::scan_keyed_xml(scan_root)?;
-    let auto = autodiscover_defs_context(scan_root)?;
+) -> Result<Vec<rimloc_core::TransUnit>> {
+    let mut out: Vec<rimloc_core::TransUnit> =
+        collect_export_units(scan_root, source_lang, source_lang_dir)?
+            .into_iter()
+            .filter(|u| PoSplit::Version.group_of(u, Some(scan_root)) == "Common.po")
+            .collect();
+    let mut version_dirs: Vec<PathBuf> = std::fs::read_dir(scan_root)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_dir()
+                && p.file_name()
+                    .is_some_and(|n| rimloc_core::is_version_folder(&n.to_string_lossy()))
+        })
+        .collect();
+    version_dirs.sort();
+    for dir in version_dirs {
+        out.extend(collect_export_units(&dir, source_lang, source_lang_dir)?);
+    }
+    Ok(out)
+}
 
-    let src_dir: String = if let Some(dir) = source_lang_dir {
+pub(crate) fn source_dir(source_lang: Option<&str>, source_lang_dir: Option<&str>) -> String {
+    if let Some(dir) = source_lang_dir {
         dir.to_string()
     } else if let Some(code) = source_lang {
         rimloc_import_po::rimworld_lang_dir(code)
     } else {
         "English".to_string()
-    };
+    }
+}
+
+/// Source units that go into a PO export: Keyed/DefInjected of the source language
+/// merged with Defs-derived units, sorted by path/line/key.
+pub fn collect_export_units(
+    scan_root: &Path,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+) -> Result<Vec<rimloc_core::TransUnit>> {
+    let units = rimloc_parsers_xml::scan_keyed_xml(scan_root)?;
+    let auto = autodiscover_defs_context(scan_root)?;
+
+    let src_dir = source_dir(source_lang, source_lang_dir);
     let mut english_map: HashMap<String, rimloc_core::TransUnit> = HashMap::new();
     for u in units
         .into_iter()
@@ -44,14 +216,11 @@ pub fn export_po_with_tm(
         }
         let target_path =
             def_injected_target_path(scan_root, &src_dir, &meta.def_type, &meta.unit.path);
-        let entry = english_map
-            .entry(key.clone())
-            .or_insert_with(|| rimloc_core::TransUnit {
-                key: key.clone(),
-                source: source.clone(),
-                path: target_path.clone(),
-                line: None,
-            });
+        let entry = english_map.entry(key).or_insert_with(|| {
+            let mut unit = meta.unit;
+            unit.retarget(target_path.clone());
+            unit
+        });
         if entry
             .source
             .as_ref()
@@ -63,12 +232,12 @@ pub fn export_po_with_tm(
         if !entry.path.to_string_lossy().contains("/DefInjected/")
             && !entry.path.to_string_lossy().contains("\\DefInjected\\")
         {
-            entry.path = target_path;
-            entry.line = None;
+            entry.retarget(target_path);
         }
     }
 
     let mut filtered: Vec<_> = english_map.into_values().collect();
+    crate::scan::annotate_context(&mut filtered);
     filtered.sort_by(|a, b| {
         (
             a.path.to_string_lossy(),
@@ -82,28 +251,21 @@ pub fn export_po_with_tm(
             ))
     });
 
-    let tm_map: Option<std::collections::HashMap<String, String>> = match tm_roots {
-        None => None,
-        Some([]) => None,
-        Some(roots) => {
-            let mut map = std::collections::HashMap::<String, String>::new();
-            for tm_path in roots {
-                if let Ok(units) = rimloc_parsers_xml::scan_keyed_xml(tm_path) {
-                    for u in units {
-                        if let Some(val) = u.source.as_deref() {
-                            let v = val.trim();
-                            if !v.is_empty() {
-                                // last wins across multiple TM roots
-                                map.insert(u.key, v.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-            Some(map)
-        }
-    };
+    Ok(filtered)
+}
 
-    let stats = rimloc_export_po::write_po_with_tm(out_po, &filtered, lang, tm_map.as_ref())?;
-    Ok(stats)
+/// Existing translations (key -> text) under `Languages/<lang>` of `scan_root`.
+pub(crate) fn collect_translations(
+    scan_root: &Path,
+    lang: &str,
+) -> Result<HashMap<String, String>> {
+    let target_dir = rimloc_import_po::rimworld_lang_dir(lang);
+    Ok(rimloc_parsers_xml::scan_keyed_xml(scan_root)?
+        .into_iter()
+        .filter(|u| is_under_languages_dir(&u.path, &target_dir))
+        .filter_map(|u| {
+            let v = u.source?;
+            (!v.trim().is_empty()).then_some((u.key, v))
+        })
+        .collect())
 }
@@ -7,6 +7,23 @@ use rimloc_domain::DiffOutput;
 use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 
+/// Source strings from a baseline PO keyed by the key part of `msgctxt`
+/// (`key|relpath`, legacy `key|relpath:line`) and of shared keys of compact entries;
+/// the first occurrence wins.
+fn baseline_sources(po: &Path) -> Result<HashMap<String, String>> {
+    let po = rimloc_po::PoFile::read(po)?;
+    let mut base: HashMap<String, String> = HashMap::new();
+    for m in po.active() {
+        for ctx in m.covered_contexts() {
+            let (key, _) = rimloc_po::split_msgctxt(ctx);
+            if !key.is_empty() && !m.msgid.is_empty() {
+                base.entry(key.to_string()).or_insert_with(|| m.msgid.clone());
+            }
+        }
+    }
+    Ok(base)
+}
+
 /// Compute presence/changed diffs between source and target language data.
 pub fn diff_xml(
     root: &Path,
@@ -48,91 +65,7 @@ pub fn diff_xml(
 
     let mut changed: Vec<(String, String)> = Vec::new();
     if let Some(po) = baseline_po {
-        // Parse PO header+entries with msgctxt support to extract original key from context
-        let file = std::fs::File::open(po)?;
-        use std::io::{BufRead, BufReader};
-        let rdr = BufReader::new(file);
-        let mut base: HashMap<String, String> = HashMap::new();
-        let mut ctx: Option<String> = None;
-        let mut id = String::new();
-        let mut strv = String::new();
-        enum Mode {
-            None,
-            InId,
-            InStr,
-        }
-        let mut mode = Mode::None;
-        fn unq(s: &str) -> String {
-            let mut out = String::new();
-            let raw = s.trim().trim_start_matches('"').trim_end_matches('"');
-            let mut it = raw.chars().peekable();
-            while let Some(c) = it.next() {
-                if c == '\\' {
-                    if let Some(n) = it.next() {
-                        out.push(match n {
-                            'n' => '\n',
-                            't' => '\t',
-                            'r' => '\r',
-                            '\\' => '\\',
-                            '"' => '"',
-                            x => x,
-                        });
-                    } else {
-                        out.push('\\');
-                    }
-                } else {
-                    out.push(c);
-                }
-            }
-            out
-        }
-        let mut push = |ctx: &mut Option<String>, id: &mut String, strv: &mut String| {
-            if !id.is_empty() {
-                // ctx format: key|relpath[:line]? — take key before '|'
-                if let Some(c) = ctx.as_deref() {
-                    let key = c.split('|').next().unwrap_or("").trim().to_string();
-                    if !key.is_empty() {
-                        base.entry(key).or_insert(std::mem::take(id));
-                    }
-                }
-                *ctx = None;
-                *strv = String::new();
-            }
-        };
-        for line in rdr.lines() {
-            let t = line?.trim().to_string();
-            if t.is_empty() {
-                push(&mut ctx, &mut id, &mut strv);
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgctxt ") {
-                push(&mut ctx, &mut id, &mut strv);
-                ctx = Some(unq(rest));
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgid ") {
-                push(&mut ctx, &mut id, &mut strv);
-                id = unq(rest);
-                mode = Mode::InId;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgstr ") {
-                strv = unq(rest);
-                mode = Mode::InStr;
-                continue;
-            }
-            if matches!(mode, Mode::InId | Mode::InStr) && t.starts_with('"') {
-                let chunk = unq(&t);
-                match mode {
-                    Mode::InId => id.push_str(&chunk),
-                    Mode::InStr => strv.push_str(&chunk),
-                    Mode::None => {}
-                }
-            }
-        }
-        push(&mut ctx, &mut id, &mut strv);
+        let base = baseline_sources(po)?;
         for (k, new_src) in &src_map {
             if let Some(old_src) = base.get(k) {
                 if old_src != new_src {
@@ -191,91 +124,7 @@ pub fn diff_xml_with_defs(
 
     let mut changed: Vec<(String, String)> = Vec::new();
     if let Some(po) = baseline_po {
-        // Parse PO header+entries with msgctxt support to extract original key from context
-        let file = std::fs::File::open(po)?;
-        use std::io::{BufRead, BufReader};
-        let rdr = BufReader::new(file);
-        let mut base: HashMap<String, String> = HashMap::new();
-        let mut ctx: Option<String> = None;
-        let mut id = String::new();
-        let mut strv = String::new();
-        enum Mode {
-            None,
-            InId,
-            InStr,
-        }
-        let mut mode = Mode::None;
-        fn unq(s: &str) -> String {
-            let mut out = String::new();
-            let raw = s.trim().trim_start_matches('"').trim_end_matches('"');
-            let mut it = raw.chars().peekable();
-            while let Some(c) = it.next() {
-                if c == '\\' {
-                    if let Some(n) = it.next() {
-                        out.push(match n {
-                            'n' => '\n',
-                            't' => '\t',
-                            'r' => '\r',
-                            '\\' => '\\',
-                            '"' => '"',
-                            x => x,
-                        });
-                    } else {
-                        out.push('\\');
-                    }
-                } else {
-                    out.push(c);
-                }
-            }
-            out
-        }
-        let mut push = |ctx: &mut Option<String>, id: &mut String, strv: &mut String| {
-            if !id.is_empty() {
-                // ctx format: key|relpath[:line]? — take key before '|'
-                if let Some(c) = ctx.as_deref() {
-                    let key = c.split('|').next().unwrap_or("").trim().to_string();
-                    if !key.is_empty() {
-                        base.entry(key).or_insert(std::mem::take(id));
-                    }
-                }
-                *ctx = None;
-                *strv = String::new();
-            }
-        };
-        for line in rdr.lines() {
-            let t = line?.trim().to_string();
-            if t.is_empty() {
-                push(&mut ctx, &mut id, &mut strv);
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgctxt ") {
-                push(&mut ctx, &mut id, &mut strv);
-                ctx = Some(unq(rest));
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgid ") {
-                push(&mut ctx, &mut id, &mut strv);
-                id = unq(rest);
-                mode = Mode::InId;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgstr ") {
-                strv = unq(rest);
-                mode = Mode::InStr;
-                continue;
-            }
-            if matches!(mode, Mode::InId | Mode::InStr) && t.starts_with('"') {
-                let chunk = unq(&t);
-                match mode {
-                    Mode::InId => id.push_str(&chunk),
-                    Mode::InStr => strv.push_str(&chunk),
-                    Mode::None => {}
-                }
-            }
-        }
-        push(&mut ctx, &mut id, &mut strv);
+        let base = baseline_sources(po)?;
         for (k, new_src) in &src_map {
             if let Some(old_src) = base.get(k) {
                 if old_src != new_src {
@@ -334,89 +183,7 @@ pub fn diff_xml_with_defs_and_fields(
 
     let mut changed: Vec<(String, String)> = Vec::new();
     if let Some(po) = baseline_po {
-        let file = std::fs::File::open(po)?;
-        use std::io::{BufRead, BufReader};
-        let rdr = BufReader::new(file);
-        let mut base: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-        let mut ctx: Option<String> = None;
-        let mut id = String::new();
-        let mut strv = String::new();
-        enum Mode {
-            None,
-            InId,
-            InStr,
-        }
-        let mut mode = Mode::None;
-        fn unq(s: &str) -> String {
-            let mut out = String::new();
-            let raw = s.trim().trim_start_matches('"').trim_end_matches('"');
-            let mut it = raw.chars().peekable();
-            while let Some(c) = it.next() {
-                if c == '\\' {
-                    if let Some(n) = it.next() {
-                        out.push(match n {
-                            'n' => '\n',
-                            't' => '\t',
-                            'r' => '\r',
-                            '\\' => '\\',
-                            '"' => '"',
-                            x => x,
-                        });
-                    } else {
-                        out.push('\\');
-                    }
-                } else {
-                    out.push(c);
-                }
-            }
-            out
-        }
-        let mut push = |ctx: &mut Option<String>, id: &mut String, strv: &mut String| {
-            if !id.is_empty() {
-                if let Some(c) = ctx.as_deref() {
-                    let key = c.split('|').next().unwrap_or("").trim().to_string();
-                    if !key.is_empty() {
-                        base.entry(key).or_insert(std::mem::take(id));
-                    }
-                }
-                *ctx = None;
-                *strv = String::new();
-            }
-        };
-        for line in rdr.lines() {
-            let t = line?.trim().to_string();
-            if t.is_empty() {
-                push(&mut ctx, &mut id, &mut strv);
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgctxt ") {
-                push(&mut ctx, &mut id, &mut strv);
-                ctx = Some(unq(rest));
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgid ") {
-                push(&mut ctx, &mut id, &mut strv);
-                id = unq(rest);
-                mode = Mode::InId;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgstr ") {
-                strv = unq(rest);
-                mode = Mode::InStr;
-                continue;
-            }
-            if matches!(mode, Mode::InId | Mode::InStr) && t.starts_with('"') {
-                let chunk = unq(&t);
-                match mode {
-                    Mode::InId => id.push_str(&chunk),
-                    Mode::InStr => strv.push_str(&chunk),
-                    Mode::None => {}
-                }
-            }
-        }
-        push(&mut ctx, &mut id, &mut strv);
+        let base = baseline_sources(po)?;
         for (k, new_src) in &src_map {
             if let Some(old_src) = base.get(k) {
                 if old_src != new_src {
@@ -478,89 +245,7 @@ pub fn diff_xml_with_defs_and_dict(
 
     let mut changed: Vec<(String, String)> = Vec::new();
     if let Some(po) = baseline_po {
-        let file = std::fs::File::open(po)?;
-        use std::io::{BufRead, BufReader};
-        let rdr = BufReader::new(file);
-        let mut base: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-        let mut ctx: Option<String> = None;
-        let mut id = String::new();
-        let mut strv = String::new();
-        enum Mode {
-            None,
-            InId,
-            InStr,
-        }
-        let mut mode = Mode::None;
-        fn unq(s: &str) -> String {
-            let mut out = String::new();
-            let raw = s.trim().trim_start_matches('"').trim_end_matches('"');
-            let mut it = raw.chars().peekable();
-            while let Some(c) = it.next() {
-                if c == '\\' {
-                    if let Some(n) = it.next() {
-                        out.push(match n {
-                            'n' => '\n',
-                            't' => '\t',
-                            'r' => '\r',
-                            '\\' => '\\',
-                            '"' => '"',
-                            x => x,
-                        });
-                    } else {
-                        out.push('\\');
-                    }
-                } else {
-                    out.push(c);
-                }
-            }
-            out
-        }
-        let mut push = |ctx: &mut Option<String>, id: &mut String, strv: &mut String| {
-            if !id.is_empty() {
-                if let Some(c) = ctx.as_deref() {
-                    let key = c.split('|').next().unwrap_or("").trim().to_string();
-                    if !key.is_empty() {
-                        base.entry(key).or_insert(std::mem::take(id));
-                    }
-                }
-                *ctx = None;
-                *strv = String::new();
-            }
-        };
-        for line in rdr.lines() {
-            let t = line?.trim().to_string();
-            if t.is_empty() {
-                push(&mut ctx, &mut id, &mut strv);
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgctxt ") {
-                push(&mut ctx, &mut id, &mut strv);
-                ctx = Some(unq(rest));
-                mode = Mode::None;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgid ") {
-                push(&mut ctx, &mut id, &mut strv);
-                id = unq(rest);
-                mode = Mode::InId;
-                continue;
-            }
-            if let Some(rest) = t.strip_prefix("msgstr ") {
-                strv = unq(rest);
-                mode = Mode::InStr;
-                continue;
-            }
-            if matches!(mode, Mode::InId | Mode::InStr) && t.starts_with('"') {
-                let chunk = unq(&t);
-                match mode {
-                    Mode::InId => id.push_str(&chunk),
-                    Mode::InStr => strv.push_str(&chunk),
-                    Mode::None => {}
-                }
-            }
-        }
-        push(&mut ctx, &mut id, &mut strv);
+        let base = baseline_sources(po)?;
         for (k, new_src) in &src_map {
             if let Some(old_src) = base.get(k) {
                 if old_src != new_src {
@@ -624,7 +309,7 @@ pub fn apply_diff_flags(root: &Path, target_lang_dir: &str, diff: &DiffOutput, b
     for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
         let p = entry.path();
         if !p.is_file() { continue; }
-        if p.extension().and_then(|e| e.to_str()).map_or(true, |ext| !ext.eq_ignore_ascii_case("xml")) { continue; }
+        if p.extension().and_then(|e| e.to_str()).is_none_or(|ext| !ext.eq_ignore_ascii_case("xml")) { continue; }
         // Under Languages/<target>/Keyed or DefInjected
         let s = p.to_string_lossy();
         if !(s.contains(&format!("/Languages/{target_lang}/", target_lang = target_lang_dir)) || s.contains(&format!("\\Languages\\{target_lang}\\", target_lang = target_lang_dir))) { continue; }
@@ -1,12 +1,21 @@
 use crate::Result;
 use quick_xml::{events::Event, Reader};
+use regex::Regex;
 use rimloc_domain::{ImportFileStat as DFileStat, ImportSummary as DSummary};
+use rimloc_core::PoEntry;
+use rimloc_import_po::FuzzyPolicy;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone)]
 pub struct ImportPlan {
     pub files: Vec<(PathBuf, usize)>,
     pub total_keys: usize,
+    pub fuzzy_skipped: usize,
+    pub fuzzy_imported: usize,
+    /// Keys given more than once for the same file; the last value wins
+    pub duplicates: Vec<(String, PathBuf)>,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +29,92 @@ pub struct FileStat {
 
 pub type ImportSummary = DSummary;
 
+/// Pairs for one target file: a repeated key keeps its first position and takes
+/// the last value (as the game would); the repeated keys are returned too.
+fn dedupe_pairs(
+    pairs: impl IntoIterator<Item = (String, String)>,
+) -> (Vec<(String, String)>, Vec<String>) {
+    let mut out: Vec<(String, String)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut repeated = Vec::new();
+    for (k, v) in pairs {
+        if let Some(&i) = index.get(&k) {
+            out[i].1 = v;
+            repeated.push(k);
+        } else {
+            index.insert(k.clone(), out.len());
+            out.push((k, v));
+        }
+    }
+    (out, repeated)
+}
+
+/// `(key, value)` pairs per target XML file.
+type FileGroups = BTreeMap<PathBuf, Vec<(String, String)>>;
+
+/// Group entries into target files under `root/Languages/<lang_folder>` by their references
+/// (`Keyed/_Imported.xml` without one). A reference that starts with a version folder
+/// (`1.4/Languages/English/...`, as written by `export-po --split version`) goes into that
+/// version folder of the mod; references are then taken as mod-root relative, so the ones
+/// without a version go to `Languages/` of the mod root even when `root` is a version folder.
+/// Returns the files with deduplicated pairs and the `(key, file)` duplicates.
+fn group_entries_for_mod_tree(
+    entries: Vec<PoEntry>,
+    root: &Path,
+    lang_folder: &str,
+) -> (FileGroups, Vec<(String, PathBuf)>) {
+    static REF_RE: OnceLock<Regex> = OnceLock::new();
+    let re = REF_RE.get_or_init(|| {
+        Regex::new(
+            r"(?:^|[/\\])(?:(?P<ver>[vV]?\d+\.\d+)[/\\])?Languages[/\\][^/\\]+[/\\](?P<rel>.+?)(?::\d+)?$",
+        )
+        .unwrap()
+    });
+    let placed: Vec<(Option<String>, PathBuf, PoEntry)> = entries
+        .into_iter()
+        .map(|e| {
+            let caps = e.reference.as_deref().and_then(|r| re.captures(r));
+            let ver = caps
+                .as_ref()
+                .and_then(|c| c.name("ver"))
+                .map(|m| m.as_str().to_string());
+            let rel = caps
+                .as_ref()
+                .and_then(|c| c.name("rel"))
+                .map(|m| PathBuf::from(m.as_str()))
+                .unwrap_or_else(|| PathBuf::from("Keyed/_Imported.xml"));
+            (ver, rel, e)
+        })
+        .collect();
+
+    let mod_relative = placed.iter().any(|(ver, _, _)| ver.is_some());
+    let mod_root = root
+        .file_name()
+        .filter(|n| rimloc_core::is_version_folder(&n.to_string_lossy()))
+        .and_then(|_| root.parent())
+        .unwrap_or(root);
+    let mut grouped = FileGroups::new();
+    for (ver, rel, e) in placed {
+        let base = match ver {
+            Some(ver) => mod_root.join(ver),
+            None if mod_relative => mod_root.to_path_buf(),
+            None => root.to_path_buf(),
+        };
+        grouped
+            .entry(base.join("Languages").join(lang_folder).join(rel))
+            .or_default()
+            .push((e.key, e.value));
+    }
+
+    let mut duplicates = Vec::new();
+    for (path, items) in grouped.iter_mut() {
+        let (pairs, repeated) = dedupe_pairs(std::mem::take(items));
+        *items = pairs;
+        duplicates.extend(repeated.into_iter().map(|k| (k, path.clone())));
+    }
+    (grouped, duplicates)
+}
+
 fn parse_language_file_keys(
     path: &Path,
 ) -> std::io::Result<std::collections::HashMap<String, String>> {
@@ -45,26 +140,20 @@ fn parse_language_file_keys(
                 }
                 stack.pop();
             }
-            Ok(Event::Text(t)) => {
-                if stack.len() == 2 {
-                    if let Some(k) = key.as_ref() {
-                        let v = t
-                            .unescape()
-                            .unwrap_or_else(|_| {
-                                std::borrow::Cow::Owned(
-                                    String::from_utf8_lossy(t.as_ref()).into_owned(),
-                                )
-                            })
-                            .to_string();
-                        acc.insert(k.clone(), v);
-                    }
+            Ok(Event::Text(t)) if stack.len() == 2 => {
+                if let Some(k) = key.as_ref() {
+                    let v = t
+                        .unescape()
+                        .unwrap_or_else(|_| {
+                            std::borrow::Cow::Owned(String::from_utf8_lossy(t.as_ref()).into_owned())
+                        })
+                        .to_string();
+                    acc.insert(k.clone(), v);
                 }
             }
-            Ok(Event::Empty(e)) => {
-                if stack.len() == 1 {
-                    let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
-                    acc.insert(name, String::new());
-                }
+            Ok(Event::Empty(e)) if stack.len() == 1 => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                acc.insert(name, String::new());
             }
             Ok(Event::Eof) => break,
             Err(_) => break,
@@ -80,13 +169,33 @@ pub fn import_po_to_file(
     po: &Path,
     out_xml: &Path,
     keep_empty: bool,
+    fuzzy: FuzzyPolicy,
+    dry_run: bool,
+    backup: bool,
+) -> Result<ImportSummary> {
+    let entries = rimloc_import_po::read_po_entries(po)?;
+    import_entries_to_file(entries, out_xml, keep_empty, fuzzy, dry_run, backup)
+}
+
+/// Write already read entries (PO, XLIFF) into a single XML file at `out_xml`.
+pub fn import_entries_to_file(
+    mut entries: Vec<PoEntry>,
+    out_xml: &Path,
+    keep_empty: bool,
+    fuzzy: FuzzyPolicy,
     dry_run: bool,
     backup: bool,
 ) -> Result<ImportSummary> {
-    let mut entries = rimloc_import_po::read_po_entries(po)?;
     if !keep_empty {
         entries.retain(|e| !e.value.trim().is_empty());
     }
+    let fz = rimloc_import_po::apply_fuzzy_policy(&mut entries, fuzzy);
+    let marked = rimloc_import_po::fuzzy_marked_keys(&entries, fuzzy);
+    let (pairs, repeated) = dedupe_pairs(entries.into_iter().map(|e| (e.key, e.value)));
+    let duplicates: Vec<(String, String)> = repeated
+        .into_iter()
+        .map(|k| (k, out_xml.display().to_string()))
+        .collect();
 
     if dry_run {
         return Ok(ImportSummary {
@@ -94,14 +203,17 @@ pub fn import_po_to_file(
             created: if out_xml.exists() { 0 } else { 1 },
             updated: if out_xml.exists() { 1 } else { 0 },
             skipped: 0,
-            keys: entries.len(),
+            keys: pairs.len(),
             files: vec![DFileStat {
                 path: out_xml.display().to_string(),
-                keys: entries.len(),
+                keys: pairs.len(),
                 status: "planned".into(),
                 added: Vec::new(),
                 changed: Vec::new(),
             }],
+            fuzzy_skipped: fz.skipped,
+            fuzzy_imported: fz.imported,
+            duplicates,
         });
     }
 
@@ -109,8 +221,7 @@ pub fn import_po_to_file(
         let bak = out_xml.with_extension("xml.bak");
         std::fs::copy(out_xml, &bak)?;
     }
-    let pairs: Vec<(String, String)> = entries.into_iter().map(|e| (e.key, e.value)).collect();
-    rimloc_import_po::write_language_data_xml(out_xml, &pairs)?;
+    rimloc_import_po::write_language_data_xml_marked(out_xml, &pairs, &marked)?;
 
     Ok(ImportSummary {
         mode: "import".into(),
@@ -125,6 +236,9 @@ pub fn import_po_to_file(
             added: Vec::new(),
             changed: Vec::new(),
         }],
+        fuzzy_skipped: fz.skipped,
+        fuzzy_imported: fz.imported,
+        duplicates,
     })
 }
 
@@ -137,6 +251,39 @@ pub fn import_po_to_mod_tree(
     root: &Path,
     lang_folder: &str,
     keep_empty: bool,
+    fuzzy: FuzzyPolicy,
+    dry_run: bool,
+    backup: bool,
+    single_file: bool,
+    incremental: bool,
+    only_diff: bool,
+    report: bool,
+) -> Result<(Option<ImportPlan>, Option<ImportSummary>)> {
+    let entries = rimloc_import_po::read_po_entries(po)?;
+    import_entries_to_mod_tree(
+        entries,
+        root,
+        lang_folder,
+        keep_empty,
+        fuzzy,
+        dry_run,
+        backup,
+        single_file,
+        incremental,
+        only_diff,
+        report,
+    )
+}
+
+/// Place already read entries (PO, XLIFF) into `root/Languages/<lang_folder>`
+/// by their references; see [`import_po_to_mod_tree`].
+#[allow(clippy::too_many_arguments)]
+pub fn import_entries_to_mod_tree(
+    mut entries: Vec<PoEntry>,
+    root: &Path,
+    lang_folder: &str,
+    keep_empty: bool,
+    fuzzy: FuzzyPolicy,
     dry_run: bool,
     backup: bool,
     single_file: bool,
@@ -144,23 +291,26 @@ pub fn import_po_to_mod_tree(
     only_diff: bool,
     report: bool,
 ) -> Result<(Option<ImportPlan>, Option<ImportSummary>)> {
-    use std::collections::HashMap;
-    let mut entries = rimloc_import_po::read_po_entries(po)?;
     if !keep_empty {
         entries.retain(|e| !e.value.trim().is_empty());
-        if entries.is_empty() {
-            return Ok((
-                None,
-                Some(ImportSummary {
-                    mode: "import".into(),
-                    created: 0,
-                    updated: 0,
-                    skipped: 0,
-                    keys: 0,
-                    files: vec![],
-                }),
-            ));
-        }
+    }
+    let fz = rimloc_import_po::apply_fuzzy_policy(&mut entries, fuzzy);
+    let marked = rimloc_import_po::fuzzy_marked_keys(&entries, fuzzy);
+    if !keep_empty && entries.is_empty() {
+        return Ok((
+            None,
+            Some(ImportSummary {
+                mode: "import".into(),
+                created: 0,
+                updated: 0,
+                skipped: 0,
+                keys: 0,
+                files: vec![],
+                fuzzy_skipped: fz.skipped,
+                fuzzy_imported: fz.imported,
+                duplicates: vec![],
+            }),
+        ));
     }
 
     if single_file {
@@ -169,11 +319,15 @@ pub fn import_po_to_mod_tree(
             .join(lang_folder)
             .join("Keyed")
             .join("_Imported.xml");
+        let (pairs, repeated) = dedupe_pairs(entries.into_iter().map(|e| (e.key, e.value)));
         if dry_run {
             return Ok((
                 Some(ImportPlan {
-                    files: vec![(out.clone(), entries.len())],
-                    total_keys: entries.len(),
+                    files: vec![(out.clone(), pairs.len())],
+                    total_keys: pairs.len(),
+                    fuzzy_skipped: fz.skipped,
+                    fuzzy_imported: fz.imported,
+                    duplicates: repeated.into_iter().map(|k| (k, out.clone())).collect(),
                 }),
                 None,
             ));
@@ -181,8 +335,7 @@ pub fn import_po_to_mod_tree(
         if backup && out.exists() {
             let _ = std::fs::copy(&out, out.with_extension("xml.bak"));
         }
-        let pairs: Vec<(String, String)> = entries.into_iter().map(|e| (e.key, e.value)).collect();
-        let bytes = rimloc_import_po::render_language_data_xml_bytes(&pairs)?;
+        let bytes = rimloc_import_po::render_language_data_xml_bytes_marked(&pairs, &marked)?;
         crate::util::write_atomic(&out, &bytes)?;
         return Ok((
             None,
@@ -199,38 +352,32 @@ pub fn import_po_to_mod_tree(
                     added: vec![],
                     changed: vec![],
                 }],
+                fuzzy_skipped: fz.skipped,
+                fuzzy_imported: fz.imported,
+                duplicates: repeated
+                    .into_iter()
+                    .map(|k| (k, out.display().to_string()))
+                    .collect(),
             }),
         ));
     }
 
-    // Group by relative path from Languages/*
-    let re = regex::Regex::new(r"(?:^|[/\\])Languages[/\\]([^/\\]+)[/\\](?P<rel>.+?)(?::\d+)?$")
-        .unwrap();
-    let mut grouped: HashMap<PathBuf, Vec<(String, String)>> = HashMap::new();
-    for e in entries {
-        let rel = e
-            .reference
-            .as_ref()
-            .and_then(|r| re.captures(r))
-            .and_then(|c| c.name("rel").map(|m| PathBuf::from(m.as_str())))
-            .unwrap_or_else(|| PathBuf::from("Keyed/_Imported.xml"));
-        grouped.entry(rel).or_default().push((e.key, e.value));
-    }
+    let (grouped, duplicates) = group_entries_for_mod_tree(entries, root, lang_folder);
 
     if dry_run {
         let mut files = Vec::new();
         let mut total = 0usize;
-        let mut keys: Vec<_> = grouped.keys().cloned().collect();
-        keys.sort();
-        for rel in keys.into_iter() {
-            let n = grouped.get(&rel).map(|v| v.len()).unwrap_or(0);
-            total += n;
-            files.push((root.join("Languages").join(lang_folder).join(rel), n));
+        for (out_path, items) in &grouped {
+            total += items.len();
+            files.push((out_path.clone(), items.len()));
         }
         return Ok((
             Some(ImportPlan {
                 files,
                 total_keys: total,
+                fuzzy_skipped: fz.skipped,
+                fuzzy_imported: fz.imported,
+                duplicates,
             }),
             None,
         ));
@@ -242,8 +389,7 @@ pub fn import_po_to_mod_tree(
     let mut keys_written = 0usize;
     let mut files_stat: Vec<DFileStat> = Vec::new();
 
-    for (rel, mut items) in grouped {
-        let out_path = root.join("Languages").join(lang_folder).join(&rel);
+    for (out_path, mut items) in grouped {
         if backup && out_path.exists() {
             let _ = std::fs::copy(&out_path, out_path.with_extension("xml.bak"));
         }
@@ -270,7 +416,7 @@ pub fn import_po_to_mod_tree(
         };
 
         if incremental && out_path.exists() {
-            let new_bytes = rimloc_import_po::render_language_data_xml_bytes(&items)?;
+            let new_bytes = rimloc_import_po::render_language_data_xml_bytes_marked(&items, &marked)?;
             let old_bytes = std::fs::read(&out_path).unwrap_or_default();
             if old_bytes == new_bytes {
                 skipped_files += 1;
@@ -302,7 +448,7 @@ pub fn import_po_to_mod_tree(
             }
         }
 
-        let bytes = rimloc_import_po::render_language_data_xml_bytes(&items)?;
+        let bytes = rimloc_import_po::render_language_data_xml_bytes_marked(&items, &marked)?;
         crate::util::write_atomic(&out_path, &bytes)?;
         keys_written += items.len();
         if existed {
@@ -335,6 +481,12 @@ pub fn import_po_to_mod_tree(
             skipped: skipped_files,
             keys: keys_written,
             files: files_stat,
+            fuzzy_skipped: fz.skipped,
+            fuzzy_imported: fz.imported,
+            duplicates: duplicates
+                .into_iter()
+                .map(|(k, path)| (k, path.display().to_string()))
+                .collect(),
         }),
     ))
 }
@@ -346,6 +498,7 @@ pub fn import_po_to_mod_tree_with_progress(
     root: &Path,
     lang_folder: &str,
     keep_empty: bool,
+    fuzzy: FuzzyPolicy,
     backup: bool,
     single_file: bool,
     incremental: bool,
@@ -353,20 +506,24 @@ pub fn import_po_to_mod_tree_with_progress(
     report: bool,
     mut progress: impl FnMut(usize, usize, &Path),
 ) -> Result<ImportSummary> {
-    use std::collections::HashMap;
     let mut entries = rimloc_import_po::read_po_entries(po)?;
     if !keep_empty {
         entries.retain(|e| !e.value.trim().is_empty());
-        if entries.is_empty() {
-            return Ok(ImportSummary {
-                mode: "import".into(),
-                created: 0,
-                updated: 0,
-                skipped: 0,
-                keys: 0,
-                files: vec![],
-            });
-        }
+    }
+    let fz = rimloc_import_po::apply_fuzzy_policy(&mut entries, fuzzy);
+    let marked = rimloc_import_po::fuzzy_marked_keys(&entries, fuzzy);
+    if !keep_empty && entries.is_empty() {
+        return Ok(ImportSummary {
+            mode: "import".into(),
+            created: 0,
+            updated: 0,
+            skipped: 0,
+            keys: 0,
+            files: vec![],
+            fuzzy_skipped: fz.skipped,
+            fuzzy_imported: fz.imported,
+            duplicates: vec![],
+        });
     }
 
     if single_file {
@@ -378,8 +535,8 @@ pub fn import_po_to_mod_tree_with_progress(
         if backup && out.exists() {
             let _ = std::fs::copy(&out, out.with_extension("xml.bak"));
         }
-        let pairs: Vec<(String, String)> = entries.into_iter().map(|e| (e.key, e.value)).collect();
-        let bytes = rimloc_import_po::render_language_data_xml_bytes(&pairs)?;
+        let (pairs, repeated) = dedupe_pairs(entries.into_iter().map(|e| (e.key, e.value)));
+        let bytes = rimloc_import_po::render_language_data_xml_bytes_marked(&pairs, &marked)?;
         crate::util::write_atomic(&out, &bytes)?;
         progress(1, 1, &out);
         return Ok(ImportSummary {
@@ -395,22 +552,16 @@ pub fn import_po_to_mod_tree_with_progress(
                 added: vec![],
                 changed: vec![],
             }],
+            fuzzy_skipped: fz.skipped,
+            fuzzy_imported: fz.imported,
+            duplicates: repeated
+                .into_iter()
+                .map(|k| (k, out.display().to_string()))
+                .collect(),
         });
     }
 
-    // Group by relative path from Languages/*
-    let re = regex::Regex::new(r"(?:^|[/\\])Languages[/\\]([^/\\]+)[/\\](?P<rel>.+?)(?::\d+)?$")
-        .unwrap();
-    let mut grouped: HashMap<PathBuf, Vec<(String, String)>> = HashMap::new();
-    for e in entries {
-        let rel = e
-            .reference
-            .as_ref()
-            .and_then(|r| re.captures(r))
-            .and_then(|c| c.name("rel").map(|m| PathBuf::from(m.as_str())))
-            .unwrap_or_else(|| PathBuf::from("Keyed/_Imported.xml"));
-        grouped.entry(rel).or_default().push((e.key, e.value));
-    }
+    let (grouped, duplicates) = group_entries_for_mod_tree(entries, root, lang_folder);
 
     let total_files = grouped.len();
     let mut idx = 0usize;
@@ -421,8 +572,7 @@ pub fn import_po_to_mod_tree_with_progress(
     let mut keys_written = 0usize;
     let mut files_stat: Vec<DFileStat> = Vec::new();
 
-    for (rel, mut items) in grouped {
-        let out_path = root.join("Languages").join(lang_folder).join(&rel);
+    for (out_path, mut items) in grouped {
         if backup && out_path.exists() {
             let _ = std::fs::copy(&out_path, out_path.with_extension("xml.bak"));
         }
@@ -449,7 +599,7 @@ pub fn import_po_to_mod_tree_with_progress(
         };
 
         if incremental && out_path.exists() {
-            let new_bytes = rimloc_import_po::render_language_data_xml_bytes(&items)?;
+            let new_bytes = rimloc_import_po::render_language_data_xml_bytes_marked(&items, &marked)?;
             let old_bytes = std::fs::read(&out_path).unwrap_or_default();
             if old_bytes == new_bytes {
                 skipped_files += 1;
@@ -485,7 +635,7 @@ pub fn import_po_to_mod_tree_with_progress(
             }
         }
 
-        let bytes = rimloc_import_po::render_language_data_xml_bytes(&items)?;
+        let bytes = rimloc_import_po::render_language_data_xml_bytes_marked(&items, &marked)?;
         crate::util::write_atomic(&out_path, &bytes)?;
         keys_written += items.len();
         if existed {
@@ -518,5 +668,11 @@ pub fn import_po_to_mod_tree_with_progress(
         skipped: skipped_files,
         keys: keys_written,
         files: files_stat,
+        fuzzy_skipped: fz.skipped,
+        fuzzy_imported: fz.imported,
+        duplicates: duplicates
+            .into_iter()
+            .map(|(k, path)| (k, path.display().to_string()))
+            .collect(),
     })
 }
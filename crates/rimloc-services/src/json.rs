@@ -0,0 +1,343 @@
+use crate::{
+    export::{collect_export_units, collect_translations, source_dir},
+    Result,
+};
+use rimloc_core::PoEntry;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Stats for [`export_json`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonExportStats {
+    /// JSON files written per language
+    pub files: usize,
+    pub keys: usize,
+    /// Keys prefilled from existing `Languages/<lang>` translations
+    pub translated: usize,
+}
+
+/// Export source strings (same units as PO export) as key/value JSON mirroring the
+/// mod layout: `<out_dir>/<SourceDir>/Keyed/A.json`, and the same files under
+/// `<out_dir>/<TargetDir>/` with current translations (empty when missing) when
+/// `lang` is given. With `nested`, dotted keys (`Beer.label`) become nested objects.
+pub fn export_json(
+    scan_root: &Path,
+    out_dir: &Path,
+    lang: Option<&str>,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+    nested: bool,
+) -> Result<JsonExportStats> {
+    let units = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let existing: HashMap<String, String> = match lang {
+        Some(code) => collect_translations(scan_root, code)?,
+        None => HashMap::new(),
+    };
+
+    let mut files: BTreeMap<PathBuf, Vec<(String, String)>> = BTreeMap::new();
+    for u in units {
+        let rel = language_relative(&u.path.to_string_lossy())
+            .unwrap_or_else(|| PathBuf::from("Keyed/_Imported.xml"))
+            .with_extension("json");
+        files
+            .entry(rel)
+            .or_default()
+            .push((u.key, u.source.unwrap_or_default()));
+    }
+
+    let src_dir = source_dir(source_lang, source_lang_dir);
+    let target_dir = lang.map(rimloc_import_po::rimworld_lang_dir);
+    let mut stats = JsonExportStats {
+        files: files.len(),
+        ..Default::default()
+    };
+    for (rel, pairs) in files {
+        stats.keys += pairs.len();
+        write_json(&out_dir.join(&src_dir).join(&rel), &pairs, nested)?;
+        if let Some(target_dir) = &target_dir {
+            let translated: Vec<(String, String)> = pairs
+                .into_iter()
+                .map(|(key, _)| {
+                    let value = existing.get(&key).cloned().unwrap_or_default();
+                    if !value.is_empty() {
+                        stats.translated += 1;
+                    }
+                    (key, value)
+                })
+                .collect();
+            write_json(&out_dir.join(target_dir).join(&rel), &translated, nested)?;
+        }
+    }
+    Ok(stats)
+}
+
+/// Path below `Languages/<lang>/`, e.g. `Keyed/A.xml`.
+fn language_relative(path: &str) -> Option<PathBuf> {
+    let parts: Vec<&str> = path.split(['/', '\\']).collect();
+    let i = parts.iter().rposition(|p| *p == "Languages")?;
+    (i + 2 < parts.len()).then(|| parts[i + 2..].iter().collect())
+}
+
+fn write_json(path: &Path, pairs: &[(String, String)], nested: bool) -> Result<()> {
+    let mut root = Map::new();
+    for (key, value) in pairs {
+        if nested {
+            insert_nested(&mut root, key, value.clone());
+        } else {
+            root.insert(key.clone(), Value::String(value.clone()));
+        }
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut text = serde_json::to_string_pretty(&Value::Object(root))?;
+    text.push('\n');
+    crate::util::write_atomic(path, text.as_bytes())?;
+    Ok(())
+}
+
+/// Insert `key` split on dots. When a part is already taken by a value of the
+/// other kind (`A` and `A.b` both exist), the rest of the key is kept as one
+/// dotted name at that level, which flattens back to the same key.
+fn insert_nested(obj: &mut Map<String, Value>, key: &str, value: String) {
+    let Some((head, rest)) = key.split_once('.') else {
+        if !matches!(obj.get(key), Some(Value::Object(_))) {
+            obj.insert(key.to_string(), Value::String(value));
+            return;
+        }
+        // `key` is already an object: keep the value next to it under its full name
+        obj.insert(format!("{key}."), Value::String(value));
+        return;
+    };
+    if head.is_empty() || rest.is_empty() {
+        obj.insert(key.to_string(), Value::String(value));
+        return;
+    }
+    match obj
+        .entry(head.to_string())
+        .or_insert_with(|| Value::Object(Map::new()))
+    {
+        Value::Object(child) => insert_nested(child, rest, value),
+        _ => {
+            obj.insert(key.to_string(), Value::String(value));
+        }
+    }
+}
+
+/// Read key/value JSON (flat or nested, as written by [`export_json`]) from a file or
+/// every `.json` file under a directory. Nested objects are joined into dotted keys;
+/// a file under `…/Keyed/` or `…/DefInjected/` is placed into the matching XML file.
+///
+/// A directory must hold one language only (`<out_dir>/Russian`, not `<out_dir>` with the
+/// source folder next to it), and a key may be given once per XML file.
+pub fn read_json_entries(path: &Path) -> Result<Vec<PoEntry>> {
+    let mut files: Vec<PathBuf> = if path.is_dir() {
+        walkdir::WalkDir::new(path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| {
+                p.extension()
+                    .map(|x| x.eq_ignore_ascii_case("json"))
+                    .unwrap_or(false)
+            })
+            .collect()
+    } else {
+        vec![path.to_path_buf()]
+    };
+    files.sort();
+    if path.is_dir() {
+        check_single_language(path, &files)?;
+    }
+
+    let mut entries = Vec::new();
+    let mut seen: HashMap<(Option<String>, String), PathBuf> = HashMap::new();
+    for file in files {
+        let text = std::fs::read_to_string(&file)?;
+        let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+        let value: Value = serde_json::from_str(text)
+            .map_err(|e| color_eyre::eyre::eyre!("failed to parse {}: {e}", file.display()))?;
+        let Value::Object(obj) = value else {
+            return Err(color_eyre::eyre::eyre!(
+                "{}: expected a JSON object",
+                file.display()
+            ));
+        };
+        let reference = json_reference(&file);
+        let mut pairs = Vec::new();
+        flatten(&obj, "", &mut pairs).map_err(|key| {
+            color_eyre::eyre::eyre!("{}: '{key}' is an array, expected text", file.display())
+        })?;
+        for (key, value) in pairs {
+            if let Some(first) = seen.insert((reference.clone(), key.clone()), file.clone()) {
+                return Err(color_eyre::eyre::eyre!(
+                    "key '{key}' is given twice for {} ({} and {})",
+                    reference.as_deref().unwrap_or("Keyed/_Imported.xml"),
+                    first.display(),
+                    file.display()
+                ));
+            }
+            entries.push(PoEntry {
+                key,
+                value,
+                reference: reference.clone(),
+                fuzzy: false,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Fail when `files` come from more than one language folder (the folders holding
+/// `Keyed/` or `DefInjected/`), e.g. the whole `export-json` output with `English/`.
+fn check_single_language(dir: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut folders: Vec<PathBuf> = files
+        .iter()
+        .filter_map(|file| {
+            file.ancestors()
+                .find(|a| {
+                    a.file_name()
+                        .is_some_and(|n| n == "Keyed" || n == "DefInjected")
+                })
+                .and_then(Path::parent)
+                .map(Path::to_path_buf)
+        })
+        .collect();
+    folders.dedup();
+    if folders.len() > 1 {
+        let names: Vec<String> = folders
+            .iter()
+            .map(|f| crate::util::rel_slash_path(f, dir))
+            .collect();
+        return Err(color_eyre::eyre::eyre!(
+            "{} holds several language folders ({}); pass the folder of the language to import",
+            dir.display(),
+            names.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Import reference for a JSON file: `Languages/_/Keyed/A.xml` for `…/Keyed/A.json`.
+fn json_reference(file: &Path) -> Option<String> {
+    let parts: Vec<String> = file
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let i = parts
+        .iter()
+        .rposition(|p| p == "Keyed" || p == "DefInjected")?;
+    let rel = Path::new(&parts[i..].join("/")).with_extension("xml");
+    Some(format!("Languages/_/{}", rel.to_string_lossy()))
+}
+
+/// Collect `(dotted key, text)` pairs; numbers and booleans become text, `null`
+/// is skipped and an array is `Err(key)`.
+fn flatten(
+    obj: &Map<String, Value>,
+    prefix: &str,
+    out: &mut Vec<(String, String)>,
+) -> std::result::Result<(), String> {
+    for (k, v) in obj {
+        let key = if prefix.is_empty() {
+            k.trim_end_matches('.').to_string()
+        } else {
+            format!("{prefix}.{}", k.trim_end_matches('.'))
+        };
+        match v {
+            Value::Object(child) => flatten(child, &key, out)?,
+            Value::String(s) => out.push((key, s.clone())),
+            Value::Null => {}
+            Value::Number(n) => out.push((key, n.to_string())),
+            Value::Bool(b) => out.push((key, b.to_string())),
+            Value::Array(_) => return Err(key),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_keys_flatten_back() {
+        let mut obj = Map::new();
+        for key in [
+            "Beer.label",
+            "Beer.description",
+            "Menu",
+            "Menu.Open",
+            "Plain",
+        ] {
+            insert_nested(&mut obj, key, key.to_lowercase());
+        }
+        assert_eq!(obj["Beer"]["label"], "beer.label");
+        let mut pairs = Vec::new();
+        flatten(&obj, "", &mut pairs).unwrap();
+        pairs.sort();
+        let keys: Vec<_> = pairs.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "Beer.description",
+                "Beer.label",
+                "Menu",
+                "Menu.Open",
+                "Plain"
+            ]
+        );
+        assert!(pairs.iter().all(|(k, v)| k.to_lowercase() == *v));
+    }
+
+    #[test]
+    fn export_then_read_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        crate::util::write_greeting_mod(root);
+        let out = root.join("json");
+        let stats = export_json(root, &out, Some("ru"), None, None, false).unwrap();
+        assert_eq!((stats.files, stats.keys, stats.translated), (1, 2, 1));
+        let source: Value = serde_json::from_str(
+            &std::fs::read_to_string(out.join("English/Keyed/A.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(source["Bye"], "Bye");
+
+        let entries = read_json_entries(&out.join("Russian")).unwrap();
+        let greeting = entries.iter().find(|e| e.key == "Greeting").unwrap();
+        assert_eq!(greeting.value, "Привет, {0}");
+        assert_eq!(
+            greeting.reference.as_deref(),
+            Some("Languages/_/Keyed/A.xml")
+        );
+        let bye = entries.iter().find(|e| e.key == "Bye").unwrap();
+        assert!(bye.value.is_empty());
+
+        // the whole output also holds the English source folder
+        let err = read_json_entries(&out).unwrap_err().to_string();
+        assert!(
+            err.contains("several language folders (English, Russian)"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn key_given_twice_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("Keyed/A.json");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &file,
+            r#"{ "Beer.label": "пиво", "Beer": { "label": "эль" } }"#,
+        )
+        .unwrap();
+        let err = read_json_entries(dir.path()).unwrap_err().to_string();
+        assert!(
+            err.contains("key 'Beer.label' is given twice for Languages/_/Keyed/A.xml"),
+            "{err}"
+        );
+    }
+}
@@ -73,16 +73,12 @@ pub fn scan_keyed_from_defs_special(root: &Path) -> Result<Vec<(String, String,
                             let nm = child.tag_name().name();
                             match nm {
                                 "tKey" => tkey = child.text().map(|s| s.trim().to_string()),
-                                "label" | "text" => {
-                                    if tkey_text.is_none() {
-                                        tkey_text = child.text().map(|s| s.trim().to_string())
-                                    }
+                                "label" | "text" if tkey_text.is_none() => {
+                                    tkey_text = child.text().map(|s| s.trim().to_string())
                                 }
                                 "tKeyTip" => tkey_tip = child.text().map(|s| s.trim().to_string()),
-                                "tooltip" => {
-                                    if tkey_tip_text.is_none() {
-                                        tkey_tip_text = child.text().map(|s| s.trim().to_string())
-                                    }
+                                "tooltip" if tkey_tip_text.is_none() => {
+                                    tkey_tip_text = child.text().map(|s| s.trim().to_string())
                                 }
                                 _ => {}
                             }
@@ -1,25 +1,39 @@
 //! High-level orchestration layer over lower-level crates.
 //! Thin public surface re-exporting operations from per-feature modules.
 
-pub use rimloc_core::{Result, TransUnit};
-pub use rimloc_export_po::PoStats as ExportPoStats;
-pub use rimloc_validate::ValidationMessage;
+pub use rimloc_core::{Result, TransUnit, UnitKind};
+pub use rimloc_export_po::{PoSplit, PoStats as ExportPoStats};
+pub use rimloc_import_po::FuzzyPolicy;
+pub use rimloc_validate::{Severity, ValidationMessage};
 
 pub mod build;
+pub mod csv;
 pub mod export;
 pub mod extras;
 pub mod import;
+pub mod json;
 pub mod learn;
+pub mod mt;
+pub mod po_lint;
+pub mod po_merge;
+pub mod po_meta;
+pub mod po_update;
 pub mod scan;
+pub mod terms;
 mod util;
+pub mod tm;
+pub mod tm_build;
+pub mod tmx;
 pub mod validate;
+pub mod xliff;
+pub mod xlsx;
 pub mod plugins;
 
 pub use build::{
     build_from_po_dry_run, build_from_po_execute, build_from_po_with_progress, build_from_root,
     build_from_root_with_progress, BuildPlan,
 };
-pub use export::export_po_with_tm;
+pub use export::{export_po_split, export_po_with_tm, ExportPoOptions};
 pub use extras::annotate::{
     annotate as annotate_apply, annotate_dry_run_plan, AnnotateFilePlan, AnnotatePlan,
     AnnotateSummary,
@@ -33,12 +47,23 @@ pub use extras::lang_update::{lang_update, LangUpdatePlan, LangUpdateSummary};
 pub use extras::morph::{generate as morph_generate, MorphOptions, MorphProvider, MorphResult};
 pub use extras::xml_health::xml_health_scan;
 pub use import::{
-    import_po_to_file, import_po_to_mod_tree, import_po_to_mod_tree_with_progress, FileStat,
-    ImportPlan, ImportSummary,
+    import_entries_to_file, import_entries_to_mod_tree, import_po_to_file, import_po_to_mod_tree,
+    import_po_to_mod_tree_with_progress, FileStat, ImportPlan, ImportSummary,
+};
+pub use mt::{
+    mt_fill, DummyTranslator, LibreTranslate, MtOptions, TranslationProvider, DEFAULT_MT_BATCH_SIZE,
+    MT_COMMENT,
+};
+pub use po_lint::{lint_po, lint_po_file, lint_po_with_glossary};
+pub use po_merge::{merge_po_files, po_merge};
+pub use po_meta::{check_po_identity, read_mod_about, source_fingerprint, ModAbout, PoMismatch};
+pub use po_update::{merge_po, po_update, DEFAULT_MIN_SIMILARITY};
+pub use rimloc_domain::{
+    DiffOutput, HealthIssue, HealthReport, PoIssue, PoLintReport, PoMergeSummary, PoUpdateSummary,
+    MtFillSummary, TermCandidate, TmBuildPair, TmBuildSummary,
 };
-pub use rimloc_domain::{DiffOutput, HealthIssue, HealthReport};
 pub use scan::{
-    autodiscover_defs_context, scan_defs_with_meta, scan_units, scan_units_auto,
+    annotate_context, autodiscover_defs_context, scan_defs_with_meta, scan_units, scan_units_auto,
     scan_units_with_defs, scan_units_with_defs_and_dict, scan_units_with_defs_and_fields,
     AutoDefsContext,
 };
@@ -47,4 +72,20 @@ pub use validate::{
     validate_under_root, validate_under_root_with_defs, validate_under_root_with_defs_and_dict,
     validate_under_root_with_defs_and_fields,
 };
-pub use validate::validate_placeholders_cross_language;
+pub use validate::{
+    validate_glossary_cross_language, validate_grammar_cross_language,
+    validate_keyed_duplicates_with_root, validate_literals_cross_language,
+    validate_placeholders_cross_language, validate_rich_text_cross_language,
+};
+pub use rimloc_glossary::{Glossary, Term as GlossaryTerm, TermIssue};
+pub use rimloc_tm::{TmEntry, TmIndex, TmMatch, TmMatchKind, DEFAULT_MIN_SCORE as DEFAULT_TM_MIN_SCORE};
+pub use terms::{extract_terms, extract_terms_under_root, terms_to_glossary, TermExtractOptions};
+pub use json::{export_json, read_json_entries, JsonExportStats};
+pub use tm::{load_tm_index, TmOptions};
+pub use tm_build::build_tm;
+pub use tmx::{export_tmx, read_tmx_entries, TmxExportStats};
+pub use csv::{export_csv, read_csv_entries, CsvExportStats};
+pub use rimloc_export_csv::{parse_delimiter as parse_csv_delimiter, CsvOptions, RowStatus as CsvRowStatus};
+pub use rimloc_xliff::{XliffState, XliffVersion};
+pub use xliff::{export_xliff, read_xliff_entries, XliffExportStats};
+pub use xlsx::{export_xlsx, read_xlsx_entries, XlsxExportStats};
@@ -0,0 +1,506 @@
+use crate::{util::write_atomic, Result};
+use color_eyre::eyre::eyre;
+use regex::Regex;
+use rimloc_domain::MtFillSummary;
+use rimloc_po::PoFile;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Prefix of the `#.` comment on machine-translated entries (`MT libretranslate`)
+pub const MT_COMMENT: &str = "MT";
+
+/// Default number of strings sent in one provider request.
+pub const DEFAULT_MT_BATCH_SIZE: usize = 50;
+
+/// Times a rate-limited (HTTP 429) request is retried.
+const MAX_RETRIES: usize = 3;
+
+/// A machine translation engine.
+pub trait TranslationProvider {
+    /// Short name, written to the `#. MT` comment and used to name the cache file.
+    fn name(&self) -> &str;
+    /// Translate a batch of texts; returns one translation per text, in order.
+    /// Texts are HTML fragments in which `<x id="N"/>` stands for masked markup.
+    fn translate(
+        &mut self,
+        texts: &[String],
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<Vec<String>>;
+}
+
+/// Returns every text unchanged; for trying the pipeline without a server.
+pub struct DummyTranslator;
+
+impl TranslationProvider for DummyTranslator {
+    fn name(&self) -> &str {
+        "dummy"
+    }
+
+    fn translate(&mut self, texts: &[String], _: &str, _: &str) -> Result<Vec<String>> {
+        Ok(texts.to_vec())
+    }
+}
+
+/// Client of a LibreTranslate-compatible `/translate` endpoint (HTML format, batched `q`).
+pub struct LibreTranslate {
+    url: String,
+    api_key: Option<String>,
+    client: reqwest::blocking::Client,
+}
+
+impl LibreTranslate {
+    pub fn new(url: &str, api_key: Option<String>, timeout_ms: u64) -> Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_millis(timeout_ms))
+            .build()?;
+        Ok(Self {
+            url: format!("{}/translate", url.trim_end_matches('/')),
+            api_key,
+            client,
+        })
+    }
+}
+
+impl TranslationProvider for LibreTranslate {
+    fn name(&self) -> &str {
+        "libretranslate"
+    }
+
+    fn translate(
+        &mut self,
+        texts: &[String],
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<Vec<String>> {
+        #[derive(serde::Serialize)]
+        struct In<'a> {
+            q: &'a [String],
+            source: String,
+            target: String,
+            format: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            api_key: Option<&'a str>,
+        }
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Text {
+            Many(Vec<String>),
+            One(String),
+        }
+        #[derive(serde::Deserialize)]
+        struct Out {
+            #[serde(rename = "translatedText")]
+            translated_text: Text,
+        }
+        let body = In {
+            q: texts,
+            source: libretranslate_code(source_lang),
+            target: libretranslate_code(target_lang),
+            format: "html",
+            api_key: self.api_key.as_deref(),
+        };
+        let mut attempt = 0;
+        let resp = loop {
+            let resp = self.client.post(&self.url).json(&body).send()?;
+            if resp.status() != reqwest::StatusCode::TOO_MANY_REQUESTS || attempt == MAX_RETRIES {
+                break resp;
+            }
+            attempt += 1;
+            let wait = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok()?.parse().ok())
+                .unwrap_or(attempt as u64 * 2);
+            std::thread::sleep(Duration::from_secs(wait));
+        };
+        let out: Out = resp.error_for_status()?.json()?;
+        let translated = match out.translated_text {
+            Text::Many(v) => v,
+            Text::One(s) => vec![s],
+        };
+        if translated.len() != texts.len() {
+            return Err(eyre!(
+                "{} returned {} translations for {} texts",
+                self.url,
+                translated.len(),
+                texts.len()
+            ));
+        }
+        Ok(translated)
+    }
+}
+
+/// LibreTranslate language code: the primary subtag, `zt` for traditional Chinese.
+fn libretranslate_code(lang: &str) -> String {
+    let lower = lang.to_ascii_lowercase().replace('_', "-");
+    if matches!(lower.as_str(), "zh-hant" | "zh-tw" | "zh-hk") {
+        return "zt".to_string();
+    }
+    lower.split('-').next().unwrap_or(&lower).to_string()
+}
+
+/// Settings for [`mt_fill`].
+#[derive(Debug, Clone)]
+pub struct MtOptions<'a> {
+    /// Source language; defaults to the PO `X-RimLoc-Source-Lang` header, then `en`
+    pub source_lang: Option<&'a str>,
+    /// Target language; defaults to the PO `Language` header
+    pub target_lang: Option<&'a str>,
+    /// Strings per provider request
+    pub batch_size: usize,
+    /// Upper bound of provider requests per minute
+    pub requests_per_minute: Option<u32>,
+    /// Folder of the translation cache; `None` disables it
+    pub cache_dir: Option<&'a Path>,
+}
+
+impl Default for MtOptions<'_> {
+    fn default() -> Self {
+        Self {
+            source_lang: None,
+            target_lang: None,
+            batch_size: DEFAULT_MT_BATCH_SIZE,
+            requests_per_minute: None,
+            cache_dir: None,
+        }
+    }
+}
+
+/// Fill untranslated entries of `po_path` with machine translations. Filled entries
+/// are marked `fuzzy` and get a `#. MT <provider>` comment so they are never taken
+/// for reviewed translations. Placeholders, `[tags]`, rich-text tags and line breaks
+/// are masked before sending; a translation that loses any of them is dropped.
+/// Results are cached per provider and language pair, and the cache is saved after
+/// every request, so an interrupted run resumes where it stopped.
+pub fn mt_fill(
+    po_path: &Path,
+    out_po: Option<&Path>,
+    provider: &mut dyn TranslationProvider,
+    opts: &MtOptions,
+    dry_run: bool,
+) -> Result<MtFillSummary> {
+    let mut po = PoFile::read(po_path)?;
+    let source_lang = rimloc_import_po::rimworld_lang_code(
+        opts.source_lang
+            .or_else(|| po.header_field(rimloc_export_po::HEADER_SOURCE_LANG))
+            .filter(|l| !l.is_empty())
+            .unwrap_or("en"),
+    );
+    let target_lang = opts
+        .target_lang
+        .or_else(|| po.header_field("Language"))
+        .filter(|l| !l.is_empty())
+        .map(rimloc_import_po::rimworld_lang_code)
+        .ok_or_else(|| eyre!("{}: no target language (set --lang)", po_path.display()))?;
+
+    let mut summary = MtFillSummary {
+        provider: provider.name().to_string(),
+        source_lang: source_lang.clone(),
+        target_lang: target_lang.clone(),
+        ..Default::default()
+    };
+    summary.total = po.messages.iter().filter(|m| !m.obsolete).count();
+    let mut pending: Vec<usize> = po
+        .messages
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| !m.obsolete && !m.is_plural() && m.msgstr.is_empty())
+        .map(|(i, _)| i)
+        .collect();
+    summary.untranslated = pending.len();
+
+    let masked: HashMap<String, Masked> = pending
+        .iter()
+        .map(|&i| &po.messages[i].msgid)
+        .map(|text| (text.clone(), mask(text)))
+        .collect();
+    // strings that are only placeholders have nothing to translate
+    pending.retain(|&i| !masked[&po.messages[i].msgid].text.is_empty());
+    let mut cache = MtCache::open(opts.cache_dir, provider.name(), &source_lang, &target_lang);
+    let mut todo: Vec<String> = masked
+        .values()
+        .map(|m| m.text.clone())
+        .filter(|t| !t.is_empty() && !cache.entries.contains_key(t))
+        .collect();
+    todo.sort();
+    todo.dedup();
+    let interval = opts
+        .requests_per_minute
+        .filter(|&n| n > 0)
+        .map(|n| Duration::from_secs(60) / n);
+    let mut last: Option<Instant> = None;
+    for batch in todo.chunks(opts.batch_size.max(1)) {
+        if let (Some(interval), Some(last)) = (interval, last) {
+            std::thread::sleep(interval.saturating_sub(last.elapsed()));
+        }
+        last = Some(Instant::now());
+        let translated = provider.translate(batch, &source_lang, &target_lang)?;
+        summary.requests += 1;
+        for (src, tgt) in batch.iter().zip(translated) {
+            cache.entries.insert(src.clone(), tgt);
+        }
+        cache.save();
+    }
+
+    let comment = format!("{MT_COMMENT} {}", provider.name());
+    for i in pending {
+        let msg = &mut po.messages[i];
+        let m = &masked[&msg.msgid];
+        let result = cache.entries.get(&m.text).and_then(|t| m.unmask(t));
+        let Some(text) = result.filter(|t| !t.trim().is_empty()) else {
+            summary.failed += 1;
+            continue;
+        };
+        if !todo.contains(&m.text) {
+            summary.cached += 1;
+        }
+        msg.msgstr = text;
+        msg.set_fuzzy(true);
+        msg.extracted_comments
+            .retain(|c| c != MT_COMMENT && !c.starts_with(&format!("{MT_COMMENT} ")));
+        msg.extracted_comments.push(comment.clone());
+        summary.translated += 1;
+    }
+    if !dry_run {
+        write_atomic(out_po.unwrap_or(po_path), po.to_string().as_bytes())?;
+    }
+    Ok(summary)
+}
+
+/// Source text with markup replaced by `<x id="N"/>` and the rest HTML-escaped.
+#[derive(Debug, Clone)]
+struct Masked {
+    text: String,
+    tokens: Vec<String>,
+    /// Whitespace around the text, which engines do not keep
+    lead: String,
+    trail: String,
+}
+
+/// Placeholders (`{0}`, `{PAWN_label}`, `%1$s`), RimWorld `[tags]` and `(*Tag)`,
+/// rich-text tags (`<color=#fff>`), escaped and real line breaks.
+fn markup_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"</?[A-Za-z][^<>]*>|\((?:\*|/)[A-Za-z_]+\)|\[[^\[\]]+\]|\{[^{}]+\}|%(?:\d+\$)?[-+0#]*\d*(?:\.\d+)?[sdifxXc]|\\n|\r?\n",
+        )
+        .unwrap()
+    })
+}
+
+fn token_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"<x\s+id="?(\d+)"?\s*/?>(?:\s*</x>)?"#).unwrap())
+}
+
+fn mask(text: &str) -> Masked {
+    let trimmed = text.trim();
+    let lead = text[..text.len() - text.trim_start().len()].to_string();
+    let trail = text[text.trim_end().len()..].to_string();
+    let mut out = String::new();
+    let mut tokens = Vec::new();
+    let mut has_text = false;
+    let mut pos = 0;
+    for m in markup_re().find_iter(trimmed) {
+        let between = &trimmed[pos..m.start()];
+        has_text |= between.chars().any(char::is_alphabetic);
+        out.push_str(&escape_html(between));
+        out.push_str(&format!("<x id=\"{}\"/>", tokens.len()));
+        tokens.push(m.as_str().to_string());
+        pos = m.end();
+    }
+    has_text |= trimmed[pos..].chars().any(char::is_alphabetic);
+    out.push_str(&escape_html(&trimmed[pos..]));
+    // nothing to translate between the tokens
+    if !has_text {
+        out.clear();
+    }
+    Masked {
+        text: out,
+        tokens,
+        lead,
+        trail,
+    }
+}
+
+impl Masked {
+    /// Put the markup back into a translation; `None` when a token is missing or repeated.
+    fn unmask(&self, translated: &str) -> Option<String> {
+        let mut seen = vec![false; self.tokens.len()];
+        let mut out = self.lead.clone();
+        let mut pos = 0;
+        for cap in token_re().captures_iter(translated) {
+            let whole = cap.get(0)?;
+            let id: usize = cap[1].parse().ok()?;
+            if std::mem::replace(seen.get_mut(id)?, true) {
+                return None;
+            }
+            out.push_str(&unescape_html(&translated[pos..whole.start()]));
+            out.push_str(&self.tokens[id]);
+            pos = whole.end();
+        }
+        if !seen.iter().all(|s| *s) {
+            return None;
+        }
+        out.push_str(&unescape_html(&translated[pos..]));
+        out.push_str(&self.trail);
+        Some(out)
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape_html(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", "\u{a0}")
+        .replace("&amp;", "&")
+}
+
+/// Masked source -> masked translation for one provider and language pair.
+struct MtCache {
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, String>,
+}
+
+impl MtCache {
+    fn open(dir: Option<&Path>, provider: &str, source: &str, target: &str) -> Self {
+        let path = dir.map(|d| d.join(format!("{provider}.{source}-{target}.json")));
+        // an unreadable cache only costs new requests
+        let entries = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|t| serde_json::from_str(&t).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    fn save(&self) {
+        if let Some(path) = &self.path {
+            if let Ok(json) = serde_json::to_string(&self.entries) {
+                let _ = write_atomic(path, json.as_bytes());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Upper-cases text outside tokens and moves the first token to the end.
+    struct Shouty {
+        batches: Vec<usize>,
+    }
+
+    impl TranslationProvider for Shouty {
+        fn name(&self) -> &str {
+            "shouty"
+        }
+
+        fn translate(&mut self, texts: &[String], _: &str, target: &str) -> Result<Vec<String>> {
+            assert_eq!(target, "ru");
+            self.batches.push(texts.len());
+            Ok(texts
+                .iter()
+                .map(|t| match token_re().find(t) {
+                    Some(m) if t.starts_with(m.as_str()) => {
+                        format!("{} {}", t[m.end()..].trim().to_uppercase(), m.as_str())
+                    }
+                    _ if t.contains("LOSE") || t.contains("lose") => "lost".to_string(),
+                    _ => t.to_uppercase().replace("<X ID=", "<x id="),
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn masks_markup_and_restores_it() {
+        let m = mask(
+            "  <color=#ff0000>{PAWN_label}</color> has 5% & [THING_label]\\n(*Threat)x(/Threat) ",
+        );
+        assert_eq!(
+            m.text,
+            "<x id=\"0\"/><x id=\"1\"/><x id=\"2\"/> has 5% &amp; <x id=\"3\"/><x id=\"4\"/><x id=\"5\"/>x<x id=\"6\"/>"
+        );
+        let back = m
+            .unmask("<x id=\"3\"></x> &amp; <x id=\"0\"/><x id=\"1\"/><x id=\"2\"/> 5%<x id=\"4\"/><x id=\"5\"/>y<x id=\"6\"/>")
+            .unwrap();
+        assert_eq!(
+            back,
+            "  [THING_label] & <color=#ff0000>{PAWN_label}</color> 5%\\n(*Threat)y(/Threat) "
+        );
+        assert!(m.unmask("<x id=\"0\"/> lost the rest").is_none());
+        assert!(mask("{0}: %d").text.is_empty());
+    }
+
+    #[test]
+    fn fills_untranslated_entries_in_batches_with_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let po_path = dir.path().join("ru.po");
+        std::fs::write(
+            &po_path,
+            "msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n\n\
+             msgctxt \"A\"\nmsgid \"{0} arrived\"\nmsgstr \"\"\n\n\
+             msgctxt \"B\"\nmsgid \"Hello\"\nmsgstr \"Привет\"\n\n\
+             msgctxt \"C\"\nmsgid \"{0} arrived\"\nmsgstr \"\"\n\n\
+             msgctxt \"D\"\nmsgid \"Raid\"\nmsgstr \"\"\n\n\
+             msgctxt \"E\"\nmsgid \"lose {0}\"\nmsgstr \"\"\n",
+        )
+        .unwrap();
+        let cache = dir.path().join("cache");
+        let opts = MtOptions {
+            batch_size: 2,
+            cache_dir: Some(&cache),
+            ..Default::default()
+        };
+        let mut provider = Shouty { batches: vec![] };
+        let summary = mt_fill(&po_path, None, &mut provider, &opts, false).unwrap();
+        assert_eq!(provider.batches, vec![2, 1]);
+        assert_eq!(
+            (
+                summary.total,
+                summary.untranslated,
+                summary.translated,
+                summary.failed
+            ),
+            (5, 4, 3, 1)
+        );
+        assert_eq!((summary.requests, summary.cached), (2, 0));
+        assert_eq!(summary.source_lang, "en");
+
+        let po = PoFile::read(&po_path).unwrap();
+        let a = &po.messages[0];
+        assert_eq!(a.msgstr, "ARRIVED {0}");
+        assert!(a.is_fuzzy());
+        assert_eq!(a.extracted_comments, vec!["MT shouty"]);
+        assert_eq!(po.messages[1].msgstr, "Привет");
+        assert!(!po.messages[1].is_fuzzy());
+        assert_eq!(po.messages[3].msgstr, "RAID");
+        // the translation lost the placeholder
+        assert_eq!(po.messages[4].msgstr, "");
+
+        // filled entries are left alone; a new file is served from the cache
+        let other = dir.path().join("other.po");
+        std::fs::write(
+            &other,
+            "msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n\nmsgid \"Raid\"\nmsgstr \"\"\n",
+        )
+        .unwrap();
+        let mut provider = Shouty { batches: vec![] };
+        let summary = mt_fill(&other, None, &mut provider, &opts, true).unwrap();
+        assert!(provider.batches.is_empty());
+        assert_eq!((summary.translated, summary.cached), (1, 1));
+    }
+}
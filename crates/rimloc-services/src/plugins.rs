@@ -2,7 +2,7 @@
 //! Public trait lives in `rimloc-plugin-api`. This module handles registration
 //! and dynamic loading for scan plugins.
 
-use crate::{Result, TransUnit};
+use crate::{Result, TransUnit, UnitKind};
 use once_cell::sync::Lazy;
 use rimloc_plugin_api::{ScanJsonFn, ScanPlugin, SCAN_JSON_SYMBOL};
 use std::ffi::{CStr, CString};
@@ -106,6 +106,9 @@ pub fn run_scan_plugins(root: &Path) -> Result<Vec<TransUnit>> {
         for p in guard.iter() {
             if p.matches(root) {
                 let mut units = p.scan(root)?;
+                for u in &mut units {
+                    u.kind = UnitKind::Plugin;
+                }
                 out.append(&mut units);
             }
         }
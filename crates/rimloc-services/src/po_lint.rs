@@ -0,0 +1,393 @@
+use crate::Result;
+use regex::Regex;
+use rimloc_domain::{PoIssue, PoLintReport};
+use rimloc_po::{split_msgctxt, PoFile, PoMessage};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Lint a PO file: placeholders, duplicate contexts, `Language` header, whitespace,
+/// newline, rich-text tag and grammar rule parity, characters that cannot be written to XML and keys that are not valid
+/// XML element names. Header and obsolete (`#~`) entries are not checked.
+pub fn lint_po(po_path: &Path) -> Result<PoLintReport> {
+    let po = PoFile::read(po_path)?;
+    Ok(lint_po_file(&po, po_path))
+}
+
+/// [`lint_po`] plus glossary checks (kind `glossary`): translations of msgids using a
+/// glossary term must contain an approved form and no forbidden one. The glossary's
+/// target language is taken from the PO `Language` header.
+pub fn lint_po_with_glossary(po_path: &Path, glossary_path: &Path) -> Result<PoLintReport> {
+    let po = PoFile::read(po_path)?;
+    let mut report = lint_po_file(&po, po_path);
+    let lang = report
+        .language
+        .as_deref()
+        .map(rimloc_import_po::rimworld_lang_code);
+    let glossary = rimloc_glossary::Glossary::load(glossary_path, lang.as_deref())?;
+    report.issues.extend(glossary_issues(&po, &glossary));
+    Ok(report)
+}
+
+/// Same as [`lint_po`] for an already parsed file.
+pub fn lint_po_file(po: &PoFile, po_path: &Path) -> PoLintReport {
+    let language = po
+        .header_field("Language")
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string);
+    let mut report = PoLintReport {
+        path: po_path.display().to_string(),
+        language: language.clone(),
+        ..Default::default()
+    };
+
+    match language.as_deref() {
+        None => report
+            .issues
+            .push(header_issue("Missing Language header".into())),
+        Some(lang) if !is_language_code(lang) => report.issues.push(header_issue(format!(
+            "Language header is not a language code: {lang}"
+        ))),
+        Some(_) => {}
+    }
+
+    let mut seen_ctx: HashSet<&str> = HashSet::new();
+    for m in po.active() {
+        report.total += 1;
+        if !m.has_translation() {
+            report.untranslated += 1;
+        }
+        if m.is_fuzzy() {
+            report.fuzzy += 1;
+        }
+
+        if let Some(ctx) = m.msgctxt.as_deref() {
+            if !seen_ctx.insert(ctx) {
+                report.issues.push(issue(
+                    m,
+                    "duplicate-msgctxt",
+                    &m.msgstr,
+                    "Duplicate msgctxt".into(),
+                ));
+            }
+            let (key, _) = split_msgctxt(ctx);
+            if !is_xml_name(key) {
+                report.issues.push(issue(
+                    m,
+                    "invalid-key",
+                    &m.msgstr,
+                    format!("Key is not a valid XML element name: {key}"),
+                ));
+            }
+        }
+
+        let forms: Vec<&String> = if m.is_plural() {
+            m.msgstr_plural.iter().collect()
+        } else {
+            vec![&m.msgstr]
+        };
+        let src_ph = rimloc_validate::format_args::usage(&m.msgid);
+        let src_tags = rimloc_validate::rich_text::sequence(&m.msgid);
+        let is_rules = m
+            .msgctxt
+            .as_deref()
+            .map(|ctx| rimloc_validate::grammar::is_rules_key(split_msgctxt(ctx).0))
+            .unwrap_or(false);
+        for msgstr in forms {
+            // пустые переводы не проверяем
+            if msgstr.trim().is_empty() {
+                continue;
+            }
+            report.checked += 1;
+
+            let dst_ph = rimloc_validate::format_args::usage(msgstr);
+            if src_ph != dst_ph {
+                let mut it = issue(m, "placeholder", msgstr, "Placeholder mismatch".into());
+                it.expected_placeholders = src_ph.iter().cloned().collect();
+                it.got_placeholders = dst_ph.into_iter().collect();
+                report.issues.push(it);
+            }
+            if leading_ws(&m.msgid) != leading_ws(msgstr)
+                || trailing_ws(&m.msgid) != trailing_ws(msgstr)
+            {
+                report.issues.push(issue(
+                    m,
+                    "whitespace",
+                    msgstr,
+                    "Leading/trailing whitespace differs from msgid".into(),
+                ));
+            }
+            let (src_nl, dst_nl) = (m.msgid.matches('\n').count(), msgstr.matches('\n').count());
+            if src_nl != dst_nl {
+                report.issues.push(issue(
+                    m,
+                    "newlines",
+                    msgstr,
+                    format!("Newline count differs: msgid {src_nl}, msgstr {dst_nl}"),
+                ));
+            }
+            let dst_tags = rimloc_validate::rich_text::sequence(msgstr);
+            if src_tags != dst_tags {
+                report.issues.push(issue(
+                    m,
+                    "rich-text",
+                    msgstr,
+                    format!(
+                        "Rich-text tags differ from msgid: expected [{}], got [{}]",
+                        src_tags.join(" "),
+                        dst_tags.join(" ")
+                    ),
+                ));
+            }
+            if is_rules {
+                let mut problems = rimloc_validate::grammar::problems(msgstr);
+                problems.extend(rimloc_validate::grammar::compare(&m.msgid, msgstr));
+                for problem in problems {
+                    report.issues.push(issue(m, "grammar", msgstr, problem));
+                }
+            }
+            if let Some(c) = msgstr.chars().find(|c| !is_xml_char(*c)) {
+                report.issues.push(issue(
+                    m,
+                    "xml-unsafe",
+                    msgstr,
+                    format!("Character U+{:04X} is not allowed in XML", c as u32),
+                ));
+            }
+        }
+    }
+    report
+}
+
+fn glossary_issues(po: &PoFile, glossary: &rimloc_glossary::Glossary) -> Vec<PoIssue> {
+    let mut issues = Vec::new();
+    for m in po.active() {
+        let forms: Vec<&String> = if m.is_plural() {
+            m.msgstr_plural.iter().collect()
+        } else {
+            vec![&m.msgstr]
+        };
+        for msgstr in forms {
+            for found in glossary.check(&m.msgid, msgstr) {
+                issues.push(issue(m, "glossary", msgstr, found.to_string()));
+            }
+        }
+    }
+    issues
+}
+
+fn issue(m: &PoMessage, kind: &str, msgstr: &str, message: String) -> PoIssue {
+    PoIssue {
+        kind: kind.to_string(),
+        context: m.msgctxt.clone(),
+        reference: m.references.first().cloned(),
+        msgid: m.msgid.clone(),
+        msgstr: msgstr.to_string(),
+        message,
+        expected_placeholders: Vec::new(),
+        got_placeholders: Vec::new(),
+    }
+}
+
+fn header_issue(message: String) -> PoIssue {
+    PoIssue {
+        kind: "language-header".into(),
+        context: None,
+        reference: None,
+        msgid: String::new(),
+        msgstr: String::new(),
+        message,
+        expected_placeholders: Vec::new(),
+        got_placeholders: Vec::new(),
+    }
+}
+
+/// gettext-style code: `ru`, `pt_BR`, `zh-Hans`, `sr@latin`.
+fn is_language_code(s: &str) -> bool {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)^[a-z]{2,3}(?:[_-][a-z0-9]{2,8})*(?:@[a-z]+)?$").unwrap())
+        .is_match(s)
+}
+
+/// Element name accepted by the LanguageData writer (XML `Name` without namespaces).
+fn is_xml_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// XML 1.0 `Char` production.
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r') || (c >= ' ' && !matches!(c, '\u{FFFE}' | '\u{FFFF}'))
+}
+
+fn leading_ws(s: &str) -> &str {
+    &s[..s.len() - s.trim_start().len()]
+}
+
+fn trailing_ws(s: &str) -> &str {
+    &s[s.trim_end().len()..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(report: &PoLintReport) -> Vec<&str> {
+        report.issues.iter().map(|i| i.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn reports_each_check_and_counts() {
+        let po = PoFile::parse(concat!(
+            "msgid \"\"\nmsgstr \"\"\n\"Language: Russian\\n\"\n\n",
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"Hi {NAME}\"\nmsgstr \"Привет\"\n\n",
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"Bye \"\nmsgstr \"Пока\"\n\n",
+            "msgctxt \"1Bad|Keyed/A.xml\"\nmsgid \"One\\nTwo\"\nmsgstr \"Раз\"\n\n",
+            "#, fuzzy\nmsgctxt \"Ok|Keyed/A.xml\"\nmsgid \"Ok\"\nmsgstr \"Ок\"\n\n",
+            "msgctxt \"Empty|Keyed/A.xml\"\nmsgid \"Empty\"\nmsgstr \"\"\n",
+        ))
+        .unwrap();
+        let report = lint_po_file(&po, Path::new("ru.po"));
+        assert_eq!(report.language.as_deref(), Some("Russian"));
+        assert_eq!((report.total, report.untranslated, report.fuzzy), (5, 1, 1));
+        assert_eq!(report.checked, 4);
+        assert_eq!(
+            kinds(&report),
+            vec![
+                "language-header",
+                "placeholder",
+                "duplicate-msgctxt",
+                "whitespace",
+                "invalid-key",
+                "newlines",
+            ]
+        );
+        assert_eq!(report.issues[1].expected_placeholders, vec!["{NAME}"]);
+    }
+
+    #[test]
+    fn glossary_violations_are_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        let po = dir.path().join("ru.po");
+        std::fs::write(
+            &po,
+            concat!(
+                "msgid \"\"\nmsgstr \"Language: ru\\n\"\n\n",
+                "msgctxt \"A|Keyed/A.xml\"\nmsgid \"A colonist\"\nmsgstr \"Поселенец\"\n\n",
+                "msgctxt \"B|Keyed/A.xml\"\nmsgid \"Colonists\"\nmsgstr \"Колонисты\"\n",
+            ),
+        )
+        .unwrap();
+        let glossary = dir.path().join("terms.csv");
+        std::fs::write(
+            &glossary,
+            "source,target,forbidden\nColonist,Колонист,Поселенец\n",
+        )
+        .unwrap();
+        let report = lint_po_with_glossary(&po, &glossary).unwrap();
+        assert_eq!(kinds(&report), vec!["glossary"]);
+        assert_eq!(report.issues[0].context.as_deref(), Some("A|Keyed/A.xml"));
+    }
+
+    #[test]
+    fn rich_text_tags_must_match_msgid() {
+        let po = PoFile::parse(concat!(
+            "msgid \"\"\nmsgstr \"Language: ru\\n\"\n\n",
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"<color=#ff0000>Danger</color> ahead\"\nmsgstr \"<color=#ff0000>Опасность</color> впереди\"\n\n",
+            "msgctxt \"B|Keyed/A.xml\"\nmsgid \"<b>Bold</b>\"\nmsgstr \"&lt;i&gt;Жирный&lt;/i&gt;\"\n",
+        ))
+        .unwrap();
+        let report = lint_po_file(&po, Path::new("ru.po"));
+        assert_eq!(kinds(&report), vec!["rich-text"]);
+        assert_eq!(report.issues[0].context.as_deref(), Some("B|Keyed/A.xml"));
+        assert!(report.issues[0]
+            .message
+            .ends_with("expected [<b> </b>], got [<i> </i>]"));
+    }
+
+    #[test]
+    fn arguments_compare_by_usage_not_suffix() {
+        let po = PoFile::parse(concat!(
+            "msgid \"\"\nmsgstr \"Language: ru\\n\"\n\n",
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"{PAWN_labelShort} lost {PAWN_possessive} hat\"\nmsgstr \"{PAWN_labelShort} потерял{PAWN_gender ? : а} шляпу\"\n\n",
+            "msgctxt \"B|Keyed/A.xml\"\nmsgid \"{0_label} gave {1} to {2}\"\nmsgstr \"{0_label} отдал {1}\"\n",
+        ))
+        .unwrap();
+        let report = lint_po_file(&po, Path::new("ru.po"));
+        assert_eq!(kinds(&report), vec!["placeholder"]);
+        assert_eq!(report.issues[0].context.as_deref(), Some("B|Keyed/A.xml"));
+        assert_eq!(
+            report.issues[0].expected_placeholders,
+            vec!["{0}", "{1}", "{2}"]
+        );
+
+        let (args, problems) = rimloc_validate::format_args::parse(
+            "{PAWN_gender ? him : her} {0:F1} {PAWN_customTitle} {X_gender ? a}",
+        );
+        assert_eq!(args.len(), 3);
+        assert_eq!(args[0].options, vec!["him", "her"]);
+        assert_eq!(args[1].format.as_deref(), Some("F1"));
+        // a suffix the game does not know is a mod resolver: kept as a plain argument
+        assert_eq!(
+            (args[2].arg.as_str(), args[2].suffix.as_deref()),
+            ("PAWN_customTitle", None)
+        );
+        assert_eq!(
+            problems,
+            vec!["Gender switch {X_gender ? a} needs 2 or 3 options separated by ':'"]
+        );
+    }
+
+    #[test]
+    fn grammar_rules_keep_syntax_and_symbols() {
+        let po = PoFile::parse(concat!(
+            "msgid \"\"\nmsgstr \"Language: ru\\n\"\n\n",
+            "msgctxt \"MyPack.rulePack.rulesStrings|DefInjected/RulePackDef/R.xml\"\n",
+            "msgid \"r_logentry->[INITIATOR_definite] [hitverb] [RECIPIENT_definite].\\nhitverb->smacked\"\n",
+            "msgstr \"r_logentry->[INITIATOR_definite] [hitverb] [TARGET_definite].\\nhitverb->врезал\\nextra->лишнее\\nbroken-[x\"\n",
+        ))
+        .unwrap();
+        let report = lint_po_file(&po, Path::new("ru.po"));
+        // the extra rules also change the line count
+        assert_eq!(kinds(&report)[0], "newlines");
+        let messages: Vec<&str> = report.issues[1..]
+            .iter()
+            .map(|i| i.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Rule 'broken-[x' has no '->' separator",
+                "Symbol [RECIPIENT_definite] from source is not used",
+                "Unknown symbol [TARGET_definite]: not in source and not defined",
+                "Rule 'extra' is not in source and no rule references it",
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_control_characters_and_accepts_clean_file() {
+        let po = PoFile {
+            header: Some(PoMessage::new(None, "", "Language: pt_BR\n")),
+            messages: vec![PoMessage::new(
+                Some("Beer.label|DefInjected/ThingDef/A.xml".into()),
+                "beer",
+                "cerveja\u{7}",
+            )],
+        };
+        let report = lint_po_file(&po, Path::new("pt.po"));
+        assert_eq!(kinds(&report), vec!["xml-unsafe"]);
+        assert!(report.issues[0].message.contains("U+0007"));
+
+        let clean = PoFile::parse(
+            "msgid \"\"\nmsgstr \"Language: ru\\n\"\n\nmsgctxt \"Beer.label|DefInjected/ThingDef/A.xml\"\nmsgid \" %d beer\\n\"\nmsgstr \" %d пива\\n\"\n",
+        )
+        .unwrap();
+        assert!(lint_po_file(&clean, Path::new("ru.po")).issues.is_empty());
+    }
+}
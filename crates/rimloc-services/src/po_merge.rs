@@ -0,0 +1,131 @@
+use crate::{po_meta::source_fingerprint, util::write_atomic, Result, TransUnit};
+use rimloc_domain::PoMergeSummary;
+use rimloc_export_po::{HEADER_GAME_VERSION, HEADER_SOURCE_FINGERPRINT};
+use rimloc_po::{split_msgctxt, PoFile};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Merge PO files (or directories of PO files, e.g. a split export) into `out_po`.
+pub fn po_merge(inputs: &[PathBuf], out_po: &Path) -> Result<PoMergeSummary> {
+    let mut files = Vec::new();
+    for input in inputs {
+        for f in rimloc_import_po::collect_po_files(input)? {
+            if !files.contains(&f) {
+                files.push(f);
+            }
+        }
+    }
+    let parts = files
+        .iter()
+        .map(|f| PoFile::read(f))
+        .collect::<Result<Vec<_>>>()?;
+    let (merged, summary) = merge_po_files(&parts);
+    write_atomic(out_po, merged.to_string().as_bytes())?;
+    Ok(summary)
+}
+
+/// Combine parsed PO files in order. The header comes from the first file that has one;
+/// the game version is kept only when all parts agree and the source fingerprint is
+/// recomputed over the merged entries. For repeated msgctxt/msgid the first entry wins,
+/// unless a later one is active or translated where the first is not.
+pub fn merge_po_files(parts: &[PoFile]) -> (PoFile, PoMergeSummary) {
+    let mut out = PoFile {
+        header: parts.iter().find_map(|p| p.header.clone()),
+        messages: Vec::new(),
+    };
+    let mut summary = PoMergeSummary {
+        files: parts.len(),
+        ..Default::default()
+    };
+
+    let mut index: HashMap<(Option<String>, String), usize> = HashMap::new();
+    for m in parts.iter().flat_map(|p| p.messages.iter()) {
+        let id = (m.msgctxt.clone(), m.msgid.clone());
+        match index.get(&id) {
+            Some(&i) => {
+                summary.duplicates += 1;
+                let kept = &out.messages[i];
+                let better = (kept.obsolete && !m.obsolete)
+                    || (kept.obsolete == m.obsolete
+                        && !kept.has_translation()
+                        && m.has_translation());
+                if better {
+                    out.messages[i] = m.clone();
+                }
+            }
+            None => {
+                index.insert(id, out.messages.len());
+                out.messages.push(m.clone());
+            }
+        }
+    }
+    summary.entries = out.messages.len();
+
+    let mut versions = parts.iter().map(|p| p.header_field(HEADER_GAME_VERSION));
+    let first_version = versions.next().flatten();
+    if versions.any(|v| v != first_version) {
+        out.remove_header_field(HEADER_GAME_VERSION);
+    }
+    if out.header_field(HEADER_SOURCE_FINGERPRINT).is_some() {
+        let units: Vec<TransUnit> = out
+            .active()
+            .map(|m| TransUnit {
+                key: m
+                    .msgctxt
+                    .as_deref()
+                    .map(|c| split_msgctxt(c).0.to_string())
+                    .unwrap_or_default(),
+                source: Some(m.msgid.clone()),
+                ..Default::default()
+            })
+            .collect();
+        out.set_header_field(HEADER_SOURCE_FINGERPRINT, &source_fingerprint(&units));
+    }
+    (out, summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_parts_and_resolves_duplicates() {
+        let a = PoFile::parse(concat!(
+            "msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n\"X-RimLoc-Game-Version: 1.4\\n\"\n",
+            "\"X-RimLoc-Source-Fingerprint: sha256:aaaa\\n\"\n\n",
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"a\"\nmsgstr \"\"\n",
+        ))
+        .unwrap();
+        let b = PoFile::parse(concat!(
+            "msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n\"X-RimLoc-Game-Version: 1.5\\n\"\n\n",
+            "msgctxt \"A|Keyed/A.xml\"\nmsgid \"a\"\nmsgstr \"а\"\n\n",
+            "msgctxt \"B|Keyed/B.xml\"\nmsgid \"b\"\nmsgstr \"б\"\n",
+        ))
+        .unwrap();
+        let (merged, summary) = merge_po_files(&[a, b]);
+        assert_eq!(
+            (summary.files, summary.entries, summary.duplicates),
+            (2, 2, 1)
+        );
+        assert_eq!(merged.messages[0].msgstr, "а");
+        assert_eq!(merged.header_field("Language"), Some("ru"));
+        assert_eq!(merged.header_field(HEADER_GAME_VERSION), None);
+
+        let expected = source_fingerprint(&[
+            TransUnit {
+                key: "A".into(),
+                source: Some("a".into()),
+                ..Default::default()
+            },
+            TransUnit {
+                key: "B".into(),
+                source: Some("b".into()),
+                ..Default::default()
+            },
+        ]);
+        assert_eq!(
+            merged.header_field(HEADER_SOURCE_FINGERPRINT),
+            Some(expected.as_str())
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{Result, TransUnit};
+use rimloc_export_po::{PoHeaderMeta, HEADER_GAME_VERSION, HEADER_PACKAGE_ID};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Mod identity from `About/About.xml`.
+#[derive(Debug, Clone, Default)]
+pub struct ModAbout {
+    pub package_id: Option<String>,
+    pub name: Option<String>,
+    pub mod_version: Option<String>,
+    pub supported_versions: Vec<String>,
+    /// packageIds from `modDependencies`, `loadAfter` and `forceLoadAfter`
+    pub dependencies: Vec<String>,
+}
+
+/// Read `About/About.xml` under `root`, falling back to the parent folder so a
+/// version subfolder (e.g. `<mod>/1.5`) still resolves to the mod's About.
+pub fn read_mod_about(root: &Path) -> Option<ModAbout> {
+    let path = [Some(root), root.parent()]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("About").join("About.xml"))
+        .find(|p| p.is_file())?;
+    let content = std::fs::read_to_string(path).ok()?;
+    let doc = roxmltree::Document::parse(&content).ok()?;
+    let root_el = doc.root_element();
+    let text = |tag: &str| {
+        root_el
+            .children()
+            .find(|c| c.is_element() && c.tag_name().name().eq_ignore_ascii_case(tag))
+            .and_then(|n| n.text())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let supported_versions = root_el
+        .children()
+        .find(|c| c.is_element() && c.tag_name().name() == "supportedVersions")
+        .map(|n| {
+            n.children()
+                .filter(|c| c.is_element() && c.tag_name().name() == "li")
+                .filter_map(|c| c.text().map(str::trim).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut dependencies: Vec<String> = Vec::new();
+    for list in root_el.children().filter(|c| {
+        c.is_element()
+            && matches!(
+                c.tag_name().name(),
+                "modDependencies" | "loadAfter" | "forceLoadAfter"
+            )
+    }) {
+        for li in list
+            .children()
+            .filter(|c| c.is_element() && c.tag_name().name() == "li")
+        {
+            // modDependencies items nest <packageId>, load order lists hold it directly
+            let id = li
+                .children()
+                .find(|c| c.is_element() && c.tag_name().name() == "packageId")
+                .unwrap_or(li)
+                .text()
+                .map(str::trim)
+                .filter(|s| !s.is_empty());
+            if let Some(id) = id {
+                if !dependencies.iter().any(|d| d.eq_ignore_ascii_case(id)) {
+                    dependencies.push(id.to_string());
+                }
+            }
+        }
+    }
+    Some(ModAbout {
+        package_id: text("packageId"),
+        name: text("name"),
+        mod_version: text("modVersion"),
+        supported_versions,
+        dependencies,
+    })
+}
+
+/// Stable fingerprint of exported source units (keys and source strings, order-independent).
+pub fn source_fingerprint(units: &[TransUnit]) -> String {
+    let mut pairs: Vec<(&str, &str)> = units
+        .iter()
+        .map(|u| (u.key.as_str(), u.source.as_deref().unwrap_or("")))
+        .collect();
+    pairs.sort_unstable();
+    let mut hasher = Sha256::new();
+    for (key, source) in pairs {
+        hasher.update(key.as_bytes());
+        hasher.update([0]);
+        hasher.update(source.as_bytes());
+        hasher.update([0]);
+    }
+    let digest = hasher.finalize();
+    let hex: String = digest[..8].iter().map(|b| format!("{b:02x}")).collect();
+    format!("sha256:{hex}")
+}
+
+/// PO header metadata for an export of `units` from `scan_root`.
+pub fn export_header_meta(
+    scan_root: &Path,
+    source_lang_dir: &str,
+    game_version: Option<&str>,
+    units: &[TransUnit],
+) -> PoHeaderMeta {
+    let about = read_mod_about(scan_root).unwrap_or_default();
+    PoHeaderMeta {
+        package_id: about.package_id,
+        mod_name: about.name,
+        mod_version: about.mod_version,
+        game_version: game_version.map(str::to_string),
+        source_lang: Some(source_lang_dir.to_string()),
+        source_fingerprint: Some(source_fingerprint(units)),
+        date: Some(chrono::Local::now().format("%Y-%m-%d %H:%M%z").to_string()),
+    }
+}
+
+/// A PO header value that does not match the mod it is used with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoMismatch {
+    /// `package_id` or `game_version`
+    pub field: &'static str,
+    /// Value recorded in the PO header
+    pub po: String,
+    /// Value of the mod / selected game version
+    pub expected: String,
+}
+
+/// Compare the PO's `X-RimLoc-Package-Id`/`X-RimLoc-Game-Version` headers with the
+/// mod at `mod_root` and the selected game version. Missing values are not reported.
+pub fn check_po_identity(
+    po_path: &Path,
+    mod_root: Option<&Path>,
+    game_version: Option<&str>,
+) -> Result<Vec<PoMismatch>> {
+    let po = rimloc_po::PoFile::read(po_path)?;
+    let header = |name: &str| {
+        po.header_field(name)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let mut out = Vec::new();
+    let mod_package = mod_root
+        .and_then(read_mod_about)
+        .and_then(|a| a.package_id);
+    if let (Some(po_pkg), Some(mod_pkg)) = (header(HEADER_PACKAGE_ID), mod_package) {
+        if !po_pkg.eq_ignore_ascii_case(&mod_pkg) {
+            out.push(PoMismatch {
+                field: "package_id",
+                po: po_pkg,
+                expected: mod_pkg,
+            });
+        }
+    }
+    if let (Some(po_ver), Some(ver)) = (header(HEADER_GAME_VERSION), game_version) {
+        let norm = |v: &str| v.trim().trim_start_matches(['v', 'V']).to_string();
+        if norm(&po_ver) != norm(ver) {
+            out.push(PoMismatch {
+                field: "game_version",
+                po: po_ver,
+                expected: ver.to_string(),
+            });
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reads_about_from_version_subfolder_and_detects_mismatch() -> Result<()> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("About"))?;
+        fs::create_dir_all(dir.path().join("1.5"))?;
+        fs::write(
+            dir.path().join("About/About.xml"),
+            "<ModMetaData><packageId>author.mymod</packageId><name>My Mod</name>\
+             <supportedVersions><li>1.5</li></supportedVersions></ModMetaData>",
+        )?;
+        let about = read_mod_about(&dir.path().join("1.5")).expect("about");
+        assert_eq!(about.package_id.as_deref(), Some("author.mymod"));
+        assert_eq!(about.supported_versions, vec!["1.5"]);
+
+        let po = dir.path().join("ru.po");
+        fs::write(
+            &po,
+            "msgid \"\"\nmsgstr \"\"\n\"X-RimLoc-Package-Id: other.mod\\n\"\n\"X-RimLoc-Game-Version: 1.4\\n\"\n",
+        )?;
+        let found = check_po_identity(&po, Some(dir.path()), Some("v1.5"))?;
+        assert_eq!(
+            found.iter().map(|m| m.field).collect::<Vec<_>>(),
+            vec!["package_id", "game_version"]
+        );
+        assert!(check_po_identity(&po, None, None)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn fingerprint_ignores_order_but_tracks_text() {
+        let u = |k: &str, s: &str| TransUnit {
+            key: k.into(),
+            source: Some(s.into()),
+            ..Default::default()
+        };
+        let a = source_fingerprint(&[u("A", "x"), u("B", "y")]);
+        assert_eq!(a, source_fingerprint(&[u("B", "y"), u("A", "x")]));
+        assert_ne!(a, source_fingerprint(&[u("A", "x"), u("B", "z")]));
+        assert!(a.starts_with("sha256:"));
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{export::collect_export_units, po_meta::export_header_meta, util::write_atomic, Result};
+use rimloc_domain::PoUpdateSummary;
+use rimloc_po::{split_msgctxt, PoFile, PoMessage};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Default minimal similarity (0..1) for pre-filling new entries from existing translations.
+pub const DEFAULT_MIN_SIMILARITY: f64 = 0.75;
+
+/// Update an existing translated PO against the current mod sources (msgmerge-style).
+/// Writes to `out_po` (or in place) unless `dry_run` is set.
+pub fn po_update(
+    scan_root: &Path,
+    po_path: &Path,
+    out_po: Option<&Path>,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+    min_similarity: f64,
+    dry_run: bool,
+) -> Result<PoUpdateSummary> {
+    let old = PoFile::read(po_path)?;
+    let units = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let lang = old
+        .header_field("Language")
+        .filter(|l| !l.is_empty())
+        .map(str::to_string);
+    let game_version = old
+        .header_field(rimloc_export_po::HEADER_GAME_VERSION)
+        .map(str::to_string);
+    let meta = export_header_meta(
+        scan_root,
+        source_lang_dir
+            .map(str::to_string)
+            .or_else(|| source_lang.map(rimloc_import_po::rimworld_lang_dir))
+            .as_deref()
+            .unwrap_or("English"),
+        game_version.as_deref(),
+        &units,
+    );
+    // a compact export folds keys with the same source into `#. rimloc-key:` comments
+    let compact = old.active().any(|m| m.covered_contexts().len() > 1);
+    let (template, _) = rimloc_export_po::build_po(
+        &units,
+        &rimloc_export_po::PoWriteOptions {
+            lang: lang.as_deref(),
+            tm: None,
+            tm_index: None,
+            tm_min_score: None,
+            mod_root: Some(scan_root),
+            meta: Some(&meta),
+            compact,
+            glossary: None,
+        },
+    );
+    let (merged, summary) = merge_po(&old, template, min_similarity);
+    if !dry_run {
+        write_atomic(out_po.unwrap_or(po_path), merged.to_string().as_bytes())?;
+    }
+    Ok(summary)
+}
+
+/// Merge translations from `old` into a freshly exported `template`.
+///
+/// Entries are matched by msgctxt (key + path, then key only; obsolete entries
+/// can be revived). Unchanged sources keep their translation; changed sources
+/// keep it as fuzzy with the old msgid in `#|`; unmatched new entries are
+/// pre-filled from the most similar translated source when the similarity is
+/// at least `min_similarity`. Old translated entries that were not matched
+/// become obsolete (`#~`).
+pub fn merge_po(old: &PoFile, template: PoFile, min_similarity: f64) -> (PoFile, PoUpdateSummary) {
+    let mut by_ctx: HashMap<(&str, Option<&str>), Vec<usize>> = HashMap::new();
+    let mut by_key: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, m) in old.messages.iter().enumerate() {
+        if let Some(ctx) = m.msgctxt.as_deref() {
+            let (key, rel) = split_msgctxt(ctx);
+            by_ctx.entry((key, rel)).or_default().push(i);
+            by_key.entry(key).or_default().push(i);
+        }
+    }
+    // Candidates for similarity pre-fill: unique translated sources
+    let mut seen_src = std::collections::HashSet::new();
+    let similar_pool: Vec<&PoMessage> = old
+        .active()
+        .filter(|m| !m.msgid.is_empty() && m.has_translation() && !m.is_plural())
+        .filter(|m| seen_src.insert(m.msgid.as_str()))
+        .collect();
+
+    let mut used = vec![false; old.messages.len()];
+    let mut summary = PoUpdateSummary::default();
+    let mut out = PoFile {
+        header: old.header.clone(),
+        messages: Vec::with_capacity(template.messages.len()),
+    };
+    // Keep the translator's header, refresh source provenance from the template
+    let fresh: Vec<(String, String)> = template
+        .header_fields()
+        .into_iter()
+        .filter(|(name, value)| {
+            !value.is_empty() && (name == "POT-Creation-Date" || name.starts_with("X-RimLoc-"))
+        })
+        .collect();
+    if out.header.is_none() {
+        out.header = template.header;
+    } else {
+        for (name, value) in &fresh {
+            out.set_header_field(name, value);
+        }
+    }
+
+    for mut new in template.messages {
+        let found = new.msgctxt.as_deref().and_then(|ctx| {
+            let (key, rel) = split_msgctxt(ctx);
+            let pick = |ids: Option<&Vec<usize>>, obsolete: bool| {
+                ids.and_then(|ids| {
+                    ids.iter()
+                        .copied()
+                        .find(|&i| !used[i] && old.messages[i].obsolete == obsolete)
+                })
+            };
+            pick(by_ctx.get(&(key, rel)), false)
+                .or_else(|| pick(by_key.get(key), false))
+                .or_else(|| pick(by_key.get(key), true))
+        });
+
+        if let Some(i) = found {
+            used[i] = true;
+            let o = &old.messages[i];
+            new.translator_comments = o.translator_comments.clone();
+            if o.msgid == new.msgid && !o.obsolete {
+                new.flags = o.flags.clone();
+                new.previous_msgctxt = o.previous_msgctxt.clone();
+                new.previous_msgid = o.previous_msgid.clone();
+                new.previous_msgid_plural = o.previous_msgid_plural.clone();
+                new.msgstr = o.msgstr.clone();
+                new.msgstr_plural = o.msgstr_plural.clone();
+                summary.kept += 1;
+            } else if o.has_translation() {
+                new.msgstr = o.msgstr.clone();
+                new.msgstr_plural = o.msgstr_plural.clone();
+                new.set_fuzzy(true);
+                if o.msgid != new.msgid {
+                    new.previous_msgid = Some(o.msgid.clone());
+                }
+                if o.msgctxt != new.msgctxt {
+                    new.previous_msgctxt = o.msgctxt.clone();
+                }
+                summary.fuzzy += 1;
+            } else {
+                summary.added += 1;
+            }
+        } else if let Some(best) = most_similar(&new.msgid, &similar_pool, min_similarity) {
+            new.msgstr = best.msgstr.clone();
+            new.set_fuzzy(true);
+            new.previous_msgid = Some(best.msgid.clone());
+            summary.prefilled += 1;
+        } else {
+            summary.added += 1;
+        }
+        out.messages.push(new);
+    }
+    summary.total = out.messages.len();
+
+    // Keep previously obsolete entries, then retire translated entries that vanished.
+    for (i, m) in old.messages.iter().enumerate() {
+        if !used[i] && m.obsolete {
+            out.messages.push(m.clone());
+        }
+    }
+    for (i, m) in old.messages.iter().enumerate() {
+        if !used[i] && !m.obsolete && m.has_translation() {
+            let mut gone = m.clone();
+            gone.obsolete = true;
+            gone.references.clear();
+            out.messages.push(gone);
+            summary.obsolete += 1;
+        }
+    }
+    (out, summary)
+}
+
+/// Best translated candidate by normalized Levenshtein similarity of source texts.
+fn most_similar<'a>(src: &str, pool: &[&'a PoMessage], min: f64) -> Option<&'a PoMessage> {
+    if src.trim().is_empty() {
+        return None;
+    }
+    let src_len = src.chars().count();
+    let mut best: Option<(f64, &PoMessage)> = None;
+    for m in pool {
+        // Similarity cannot exceed shorter/longer length ratio: cheap pre-filter.
+        let len = m.msgid.chars().count();
+        let ratio = src_len.min(len) as f64 / src_len.max(len).max(1) as f64;
+        if ratio < min || best.is_some_and(|(b, _)| ratio <= b) {
+            continue;
+        }
+        let score = strsim::normalized_levenshtein(src, &m.msgid);
+        if score >= min && best.is_none_or(|(b, _)| score > b) {
+            best = Some((score, m));
+        }
+    }
+    best.map(|(_, m)| m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ctx: &str, id: &str, s: &str) -> PoMessage {
+        PoMessage::new(Some(ctx.to_string()), id, s)
+    }
+
+    #[test]
+    fn merges_kept_fuzzy_prefilled_added_and_obsolete() {
+        let old = PoFile {
+            header: None,
+            messages: vec![
+                entry("Same|Keyed/A.xml:3", "Hello", "Привет"),
+                entry("Changed|Keyed/A.xml", "Open door", "Открыть дверь"),
+                entry("Gone|Keyed/A.xml", "Removed text", "Удалённый текст"),
+                entry("GoneEmpty|Keyed/A.xml", "Nothing", ""),
+            ],
+        };
+        let template = PoFile {
+            header: None,
+            messages: vec![
+                entry("Same|Keyed/A.xml", "Hello", ""),
+                entry("Changed|Keyed/A.xml", "Open the door", ""),
+                entry("New|Keyed/B.xml", "Removed texts", ""),
+                entry("Fresh|Keyed/B.xml", "Completely different", ""),
+            ],
+        };
+        let (po, sum) = merge_po(&old, template, DEFAULT_MIN_SIMILARITY);
+        assert_eq!(
+            (sum.kept, sum.fuzzy, sum.prefilled, sum.added, sum.obsolete, sum.total),
+            (1, 1, 1, 1, 1, 4)
+        );
+
+        let same = &po.messages[0];
+        assert_eq!(same.msgstr, "Привет");
+        assert!(same.is_translated());
+
+        let changed = &po.messages[1];
+        assert!(changed.is_fuzzy());
+        assert_eq!(changed.msgstr, "Открыть дверь");
+        assert_eq!(changed.previous_msgid.as_deref(), Some("Open door"));
+
+        let prefilled = &po.messages[2];
+        assert!(prefilled.is_fuzzy());
+        assert_eq!(prefilled.msgstr, "Удалённый текст");
+
+        assert!(po.messages[3].msgstr.is_empty());
+
+        let gone = &po.messages[4];
+        assert!(gone.obsolete);
+        assert_eq!(gone.msgctxt.as_deref(), Some("Gone|Keyed/A.xml"));
+        assert_eq!(po.messages.len(), 5);
+    }
+
+    #[test]
+    fn revives_obsolete_entry_with_same_key() {
+        let mut back = entry("Back|Keyed/A.xml", "Welcome", "Добро пожаловать");
+        back.obsolete = true;
+        let old = PoFile {
+            header: None,
+            messages: vec![back],
+        };
+        let template = PoFile {
+            header: None,
+            messages: vec![entry("Back|Keyed/A.xml", "Welcome", "")],
+        };
+        let (po, sum) = merge_po(&old, template, DEFAULT_MIN_SIMILARITY);
+        assert_eq!((sum.fuzzy, sum.obsolete), (1, 0));
+        assert_eq!(po.messages.len(), 1);
+        assert!(!po.messages[0].obsolete && po.messages[0].is_fuzzy());
+    }
+
+    #[test]
+    fn keeps_compact_po_compact() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let keyed = root.join("Languages/English/Keyed");
+        std::fs::create_dir_all(&keyed).unwrap();
+        std::fs::write(
+            keyed.join("A.xml"),
+            "<LanguageData><Yes>OK</Yes><Confirm>OK</Confirm><Bye>Bye</Bye></LanguageData>",
+        )
+        .unwrap();
+        let units = collect_export_units(root, None, None).unwrap();
+        let (mut old, _) = rimloc_export_po::build_po(
+            &units,
+            &rimloc_export_po::PoWriteOptions {
+                lang: Some("ru"),
+                compact: true,
+                ..Default::default()
+            },
+        );
+        for m in old.messages.iter_mut().filter(|m| m.msgid == "OK") {
+            m.msgstr = "Да".into();
+        }
+        let po_path = root.join("ru.po");
+        std::fs::write(&po_path, old.to_string()).unwrap();
+
+        let sum = po_update(
+            root,
+            &po_path,
+            None,
+            None,
+            None,
+            DEFAULT_MIN_SIMILARITY,
+            false,
+        )
+        .unwrap();
+        assert_eq!((sum.kept, sum.added, sum.obsolete), (2, 0, 0));
+        let po = PoFile::read(&po_path).unwrap();
+        assert_eq!(po.messages.len(), 2);
+        let ok = po.messages.iter().find(|m| m.msgid == "OK").unwrap();
+        assert_eq!(ok.msgstr, "Да");
+        assert_eq!(ok.covered_contexts().len(), 2);
+    }
+}
@@ -30,8 +30,7 @@ fn merge_defs_units(
             continue;
         }
         let target_path = def_injected_target_path(scan_root, lang_dir, &meta.def_type, &unit.path);
-        unit.path = target_path;
-        unit.line = None;
+        unit.retarget(target_path);
         let key = seen_key(&unit.path, &unit.key);
         if seen.insert(key) {
             units.push(unit);
@@ -39,6 +38,66 @@ fn merge_defs_units(
     }
 }
 
+/// Fill `comments` with translator context: def type, the owning def's label
+/// (for non-label fields and list entries) and placeholders found in the source.
+/// Labels are looked up among `units` of the same language (`<defName>.label`).
+pub fn annotate_context(units: &mut [TransUnit]) {
+    fn lang_of(path: &Path) -> String {
+        let mut comps = path.components().map(|c| c.as_os_str().to_string_lossy());
+        comps
+            .by_ref()
+            .find(|c| c.eq_ignore_ascii_case("Languages"))
+            .and_then(|_| comps.next())
+            .map(|c| c.into_owned())
+            .unwrap_or_default()
+    }
+    let mut labels: HashMap<(String, String, String), String> = HashMap::new();
+    for u in units.iter() {
+        if let (Some(t), Some(n), Some("label"), Some(src)) = (
+            u.def_type.as_ref(),
+            u.def_name.as_ref(),
+            u.field_path.as_deref(),
+            u.source.as_deref(),
+        ) {
+            if !src.trim().is_empty() {
+                labels
+                    .entry((lang_of(&u.path), t.clone(), n.clone()))
+                    .or_insert_with(|| src.trim().to_string());
+            }
+        }
+    }
+    for u in units.iter_mut() {
+        let mut comments = Vec::new();
+        if let (Some(def_type), Some(def_name)) = (u.def_type.as_ref(), u.def_name.as_ref()) {
+            comments.push(format!("{def_type}: {def_name}"));
+            let field = u.field_path.as_deref().unwrap_or("");
+            if field != "label" {
+                let label_key = (lang_of(&u.path), def_type.clone(), def_name.clone());
+                if let Some(label) = labels.get(&label_key) {
+                    let in_list = field
+                        .split('.')
+                        .any(|seg| seg == "li" || seg.chars().all(|c| c.is_ascii_digit()));
+                    if in_list {
+                        comments.push(format!("Parent def label: {label}"));
+                    } else {
+                        comments.push(format!("Def label: {label}"));
+                    }
+                }
+            }
+        }
+        if let Some(src) = u.source.as_deref() {
+            let ph = rimloc_core::placeholders::extract(src);
+            if !ph.is_empty() {
+                comments.push(format!(
+                    "Placeholders: {}",
+                    ph.into_iter().collect::<Vec<_>>().join(" ")
+                ));
+            }
+        }
+        u.comments = comments;
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct AutoDefsContext {
     pub dict: HashMap<String, Vec<String>>,
@@ -220,6 +279,7 @@ pub fn scan_units_auto(root: &Path) -> Result<Vec<TransUnit>> {
         DEFAULT_SOURCE_LANG_DIR,
         defs_meta,
     );
+    annotate_context(&mut units);
     Ok(units)
 }
 
@@ -270,6 +330,7 @@ pub fn scan_units_with_defs_and_dict(
         DEFAULT_SOURCE_LANG_DIR,
         defs_meta,
     );
+    annotate_context(&mut units);
     Ok(units)
 }
 
@@ -316,4 +377,69 @@ mod tests {
             .unwrap_or(false));
         Ok(())
     }
+
+    #[test]
+    fn defs_units_keep_origin_next_to_def_injected_target() -> Result<()> {
+        let dir = tempdir()?;
+        let defs = dir.path().join("Defs").join("ThingDefs");
+        fs::create_dir_all(&defs)?;
+        fs::write(
+            defs.join("Drinks.xml"),
+            "<Defs>\n  <ThingDef>\n    <defName>Beer</defName>\n    <label>beer</label>\n  </ThingDef>\n</Defs>\n",
+        )?;
+
+        let units = scan_units(dir.path())?;
+        let beer = units
+            .iter()
+            .find(|u| u.key == "Beer.label")
+            .expect("Beer.label unit");
+        assert_eq!(beer.kind, crate::UnitKind::Defs);
+        assert_eq!(beer.def_type.as_deref(), Some("ThingDef"));
+        assert_eq!(beer.def_name.as_deref(), Some("Beer"));
+        assert_eq!(beer.field_path.as_deref(), Some("label"));
+        assert!(beer
+            .path
+            .ends_with("Languages/English/DefInjected/ThingDef/Drinks.xml"));
+        assert_eq!(beer.line, None);
+        assert_eq!(beer.origin_path.as_deref(), Some(defs.join("Drinks.xml").as_path()));
+        assert!(beer.origin_line.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn annotate_context_adds_def_label_and_placeholders() {
+        let def_unit = |lang: &str, field: &str, src: &str| TransUnit {
+            key: format!("Beer.{field}"),
+            source: Some(src.to_string()),
+            path: PathBuf::from(format!("Mod/Languages/{lang}/DefInjected/ThingDef/Drinks.xml")),
+            def_type: Some("ThingDef".into()),
+            def_name: Some("Beer".into()),
+            field_path: Some(field.to_string()),
+            ..Default::default()
+        };
+        let mut units = vec![
+            def_unit("English", "label", "beer"),
+            def_unit("English", "description", "Makes {PAWN_label} drunk."),
+            def_unit("English", "stages.0.label", "tipsy"),
+            def_unit("Russian", "label", "пиво"),
+            TransUnit {
+                key: "Greeting".into(),
+                source: Some("Hello".into()),
+                ..Default::default()
+            },
+        ];
+        annotate_context(&mut units);
+
+        assert_eq!(units[0].comments, vec!["ThingDef: Beer"]);
+        assert_eq!(
+            units[1].comments,
+            vec![
+                "ThingDef: Beer",
+                "Def label: beer",
+                "Placeholders: {PAWN_label}"
+            ]
+        );
+        assert_eq!(units[2].comments[1], "Parent def label: beer");
+        assert!(units[4].comments.is_empty());
+    }
 }
@@ -0,0 +1,439 @@
+use crate::{export::collect_export_units, Result};
+use regex::Regex;
+use rimloc_core::TransUnit;
+use rimloc_domain::TermCandidate;
+use rimloc_glossary::{Glossary, Term};
+use rust_stemmers::{Algorithm, Stemmer};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Longest run of words taken as one term.
+const MAX_WORDS: usize = 4;
+
+/// Shortest single word taken as a term.
+const MIN_WORD_CHARS: usize = 4;
+
+/// Function words and generic verbs/adjectives that never start or end a term.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "again", "against", "all", "also", "an", "and", "any", "are", "as",
+    "at", "be", "because", "been", "before", "being", "between", "both", "but", "by", "can",
+    "cannot", "could", "did", "do", "does", "doing", "done", "down", "during", "each", "either",
+    "enough", "even", "ever", "every", "few", "for", "from", "further", "get", "gets", "give",
+    "gives", "good", "great", "had", "has", "have", "having", "he", "her", "here", "hers", "him",
+    "his", "how", "however", "if", "in", "into", "is", "it", "its", "itself", "just", "less",
+    "like", "made", "make", "makes", "many", "may", "might", "more", "most", "much", "must",
+    "need", "needs", "never", "new", "no", "nor", "not", "now", "of", "off", "often", "on", "once",
+    "one", "only", "or", "other", "others", "our", "out", "over", "own", "per", "quite", "rather",
+    "same", "she", "should", "since", "so", "some", "such", "than", "that", "the", "their", "them",
+    "then", "there", "these", "they", "this", "those", "through", "thus", "to", "too", "under",
+    "until", "up", "upon", "use", "used", "uses", "using", "very", "was", "way", "we", "well",
+    "were", "what", "when", "where", "whether", "which", "while", "who", "whom", "whose", "why",
+    "will", "with", "within", "without", "would", "yet", "you", "your",
+];
+
+/// Settings for [`extract_terms`].
+#[derive(Debug, Clone)]
+pub struct TermExtractOptions {
+    /// Terms found fewer times are left out
+    pub min_count: usize,
+    /// Keep only this many of the best-ranked terms
+    pub limit: Option<usize>,
+    /// Example keys listed per term
+    pub examples: usize,
+}
+
+impl Default for TermExtractOptions {
+    fn default() -> Self {
+        Self {
+            min_count: 2,
+            limit: None,
+            examples: 3,
+        }
+    }
+}
+
+/// Kinds in order of precedence when the same words are found as several kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    DefLabel,
+    Name,
+    Phrase,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::DefLabel => "def-label",
+            Kind::Name => "name",
+            Kind::Phrase => "phrase",
+        }
+    }
+
+    fn weight(self) -> f64 {
+        match self {
+            Kind::DefLabel => 2.0,
+            Kind::Name => 1.5,
+            Kind::Phrase => 1.0,
+        }
+    }
+}
+
+struct Token {
+    surface: String,
+    lower: String,
+    stem: String,
+}
+
+struct Label {
+    text: String,
+    def: String,
+    unit_key: String,
+}
+
+struct Tally {
+    kind: Kind,
+    words: usize,
+    count: usize,
+    files: BTreeSet<PathBuf>,
+    examples: Vec<String>,
+    spellings: HashMap<String, usize>,
+}
+
+/// Extract recurring terms from the source texts under `scan_root`
+/// (the same units `export-po` writes).
+pub fn extract_terms_under_root(
+    scan_root: &Path,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+    opts: &TermExtractOptions,
+) -> Result<Vec<TermCandidate>> {
+    let units = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let lang = source_lang_dir.or(source_lang).unwrap_or("en");
+    Ok(extract_terms(
+        &units,
+        &rimloc_import_po::rimworld_lang_code(lang),
+        opts,
+    ))
+}
+
+/// Candidate glossary terms found in the source texts of `units`, best first:
+/// def labels mentioned in other strings, runs of capitalized words (`Mech Hive`)
+/// and recurring word sequences without stop words. Words are compared by stem
+/// (`source_lang` picks the stemmer), so `raid` and `raids` count as one term.
+/// The score grows with the number of uses and of files they are spread over; a
+/// term is dropped when a longer one containing it is used as often.
+pub fn extract_terms(
+    units: &[TransUnit],
+    source_lang: &str,
+    opts: &TermExtractOptions,
+) -> Vec<TermCandidate> {
+    let stemmer = stemmer(source_lang);
+    let labels = def_labels(units, stemmer.as_ref());
+
+    let mut tallies: HashMap<String, Tally> = HashMap::new();
+    for unit in units {
+        let Some(text) = unit.source.as_deref() else {
+            continue;
+        };
+        let file = unit.origin_path.as_ref().unwrap_or(&unit.path);
+        for segment in segments(text, stemmer.as_ref()) {
+            for n in 1..=MAX_WORDS.min(segment.len()) {
+                for window in segment.windows(n) {
+                    let key = join(window.iter().map(|t| t.stem.as_str()));
+                    let kind = match labels.get(&key) {
+                        // a label does not count as a mention of itself
+                        Some(label) if label.unit_key == unit.key => continue,
+                        Some(_) => Kind::DefLabel,
+                        None if is_name(window) => Kind::Name,
+                        None if is_phrase(window) => Kind::Phrase,
+                        None => continue,
+                    };
+                    let spelling = match kind {
+                        Kind::Phrase => join(window.iter().map(|t| t.lower.as_str())),
+                        _ => join(window.iter().map(|t| t.surface.as_str())),
+                    };
+                    let tally = tallies.entry(key).or_insert_with(|| Tally {
+                        kind,
+                        words: n,
+                        count: 0,
+                        files: BTreeSet::new(),
+                        examples: Vec::new(),
+                        spellings: HashMap::new(),
+                    });
+                    tally.kind = tally.kind.min(kind);
+                    tally.count += 1;
+                    tally.files.insert(file.clone());
+                    if tally.examples.len() < opts.examples && !tally.examples.contains(&unit.key) {
+                        tally.examples.push(unit.key.clone());
+                    }
+                    *tally.spellings.entry(spelling).or_default() += 1;
+                }
+            }
+        }
+    }
+    tallies.retain(|_, t| t.count >= opts.min_count.max(1));
+
+    // highest use count of a longer term containing each shorter one
+    let mut covered: HashMap<String, usize> = HashMap::new();
+    for (key, tally) in &tallies {
+        let stems: Vec<&str> = key.split(' ').collect();
+        for n in 1..stems.len() {
+            for window in stems.windows(n) {
+                let slot = covered.entry(window.join(" ")).or_default();
+                *slot = (*slot).max(tally.count);
+            }
+        }
+    }
+
+    let mut out: Vec<TermCandidate> = tallies
+        .into_iter()
+        .filter(|(key, t)| {
+            t.kind == Kind::DefLabel || covered.get(key).is_none_or(|&c| c < t.count)
+        })
+        .map(|(key, t)| {
+            let label = labels.get(&key).filter(|_| t.kind == Kind::DefLabel);
+            let term = match label {
+                Some(label) => label.text.clone(),
+                None => t
+                    .spellings
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                    .map(|(s, _)| s.clone())
+                    .unwrap_or_default(),
+            };
+            let spread = 1.0 + (t.files.len() as f64).ln();
+            let score =
+                t.count as f64 * spread * t.kind.weight() * (1.0 + 0.25 * (t.words - 1) as f64);
+            TermCandidate {
+                term,
+                kind: t.kind.as_str().to_string(),
+                def: label.map(|l| l.def.clone()),
+                count: t.count,
+                files: t.files.len(),
+                score: (score * 100.0).round() / 100.0,
+                examples: t.examples,
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.term.to_lowercase().cmp(&b.term.to_lowercase()))
+    });
+    if let Some(limit) = opts.limit {
+        out.truncate(limit);
+    }
+    out
+}
+
+/// Glossary skeleton with one term per candidate and no translations yet; the note
+/// says where the term was found.
+pub fn terms_to_glossary(
+    candidates: &[TermCandidate],
+    source_lang: &str,
+    target_lang: Option<&str>,
+) -> Glossary {
+    let terms = candidates
+        .iter()
+        .map(|c| {
+            let mut note = format!("{}: {} uses in {} files", c.kind, c.count, c.files);
+            if let Some(def) = &c.def {
+                note = format!("{note}; {def}");
+            }
+            if !c.examples.is_empty() {
+                note = format!("{note}; e.g. {}", c.examples.join(", "));
+            }
+            Term {
+                source: c.term.clone(),
+                note: Some(note),
+                ..Default::default()
+            }
+        })
+        .collect();
+    Glossary::new(source_lang, target_lang, terms)
+}
+
+/// `label` texts of defs (`ThingDef/Steel`) by their stemmed words.
+fn def_labels(units: &[TransUnit], stemmer: Option<&Stemmer>) -> HashMap<String, Label> {
+    let mut labels = HashMap::new();
+    for unit in units {
+        let (Some(text), Some(def_type), Some(def_name)) = (
+            unit.source.as_deref(),
+            unit.def_type.as_deref(),
+            unit.def_name.as_deref(),
+        ) else {
+            continue;
+        };
+        if unit.field_path.as_deref() != Some("label") {
+            continue;
+        }
+        let segments = segments(text, stemmer);
+        let [words] = &segments[..] else {
+            continue;
+        };
+        if words.len() > MAX_WORDS || words.len() != text.split_whitespace().count() {
+            continue;
+        }
+        let key = join(words.iter().map(|t| t.stem.as_str()));
+        labels.entry(key).or_insert_with(|| Label {
+            text: text.trim().to_string(),
+            def: format!("{def_type}/{def_name}"),
+            unit_key: unit.key.clone(),
+        });
+    }
+    labels
+}
+
+/// Runs of words in `text` not broken by punctuation, digits, placeholders
+/// (`{0}`, `[PAWN_nameDef]`, `%s`) or markup tags.
+fn segments(text: &str, stemmer: Option<&Stemmer>) -> Vec<Vec<Token>> {
+    static MARKUP: OnceLock<Regex> = OnceLock::new();
+    let re = MARKUP.get_or_init(|| {
+        Regex::new(r"\{[^}]*\}|\[[^\]]*\]|<[^>]*>|%\d*\$?[-+ 0#]*\d*(?:\.\d+)?[a-zA-Z]").unwrap()
+    });
+    let cleaned = re.replace_all(text, " . ");
+    let mut out = Vec::new();
+    let mut current = Vec::new();
+    for piece in cleaned
+        .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '-' || c.is_whitespace()))
+    {
+        for raw in piece.split_whitespace() {
+            let word = raw.strip_suffix("'s").unwrap_or(raw);
+            let word = word.trim_matches(|c| c == '\'' || c == '-');
+            if word.is_empty() || word.chars().any(|c| c.is_numeric()) {
+                out.push(std::mem::take(&mut current));
+                continue;
+            }
+            let lower = word.to_lowercase();
+            let stem = match stemmer {
+                Some(s) => s.stem(&lower).into_owned(),
+                None => lower.clone(),
+            };
+            current.push(Token {
+                surface: word.to_string(),
+                lower,
+                stem,
+            });
+        }
+        out.push(std::mem::take(&mut current));
+    }
+    out.push(current);
+    out.retain(|s| !s.is_empty());
+    out
+}
+
+fn is_stop_word(t: &Token) -> bool {
+    STOP_WORDS.binary_search(&t.lower.as_str()).is_ok()
+}
+
+/// Two or more capitalized words, not starting or ending with a stop word.
+fn is_name(window: &[Token]) -> bool {
+    window.len() >= 2
+        && window
+            .iter()
+            .all(|t| t.surface.chars().next().is_some_and(char::is_uppercase))
+        && !is_stop_word(&window[0])
+        && !is_stop_word(&window[window.len() - 1])
+}
+
+/// Words without stop words; single words need [`MIN_WORD_CHARS`] letters.
+fn is_phrase(window: &[Token]) -> bool {
+    window.iter().all(|t| !is_stop_word(t))
+        && (window.len() > 1 || window[0].lower.chars().count() >= MIN_WORD_CHARS)
+}
+
+fn join<'a>(words: impl Iterator<Item = &'a str>) -> String {
+    words.collect::<Vec<_>>().join(" ")
+}
+
+fn stemmer(lang: &str) -> Option<Stemmer> {
+    let primary = lang.split(['-', '_']).next().unwrap_or(lang);
+    primary
+        .eq_ignore_ascii_case("en")
+        .then(|| Stemmer::create(Algorithm::English))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rimloc_core::UnitKind;
+
+    fn unit(key: &str, file: &str, text: &str) -> TransUnit {
+        TransUnit {
+            key: key.into(),
+            source: Some(text.into()),
+            path: PathBuf::from(file),
+            line: None,
+            kind: UnitKind::Keyed,
+            def_type: None,
+            def_name: None,
+            field_path: None,
+            origin_path: None,
+            origin_line: None,
+            comments: Vec::new(),
+        }
+    }
+
+    fn label(def_name: &str, text: &str) -> TransUnit {
+        TransUnit {
+            def_type: Some("ThingDef".into()),
+            def_name: Some(def_name.into()),
+            field_path: Some("label".into()),
+            kind: UnitKind::Defs,
+            ..unit(&format!("{def_name}.label"), "Defs/Things.xml", text)
+        }
+    }
+
+    #[test]
+    fn stop_words_are_sorted() {
+        assert!(STOP_WORDS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn ranks_def_labels_names_and_phrases() {
+        let units = vec![
+            label("Plasteel", "plasteel"),
+            unit(
+                "A",
+                "Keyed/A.xml",
+                "Made of plasteel. The Mech Hive sends {0} mechanoids.",
+            ),
+            unit(
+                "B",
+                "Keyed/B.xml",
+                "Plasteel walls stop the Mech Hive raids.",
+            ),
+            unit(
+                "C",
+                "Keyed/C.xml",
+                "Mechanoid raids hit [PAWN_nameDef]'s base.",
+            ),
+            unit("D", "Keyed/D.xml", "Once"),
+        ];
+        let terms = extract_terms(&units, "en", &TermExtractOptions::default());
+        let find = |t: &str| terms.iter().find(|c| c.term == t);
+
+        let plasteel = find("plasteel").unwrap();
+        assert_eq!(plasteel.kind, "def-label");
+        assert_eq!(plasteel.def.as_deref(), Some("ThingDef/Plasteel"));
+        assert_eq!((plasteel.count, plasteel.files), (2, 2));
+        assert_eq!(plasteel.examples, vec!["A", "B"]);
+
+        let hive = find("Mech Hive").unwrap();
+        assert_eq!(hive.kind, "name");
+        // used only as part of "Mech Hive"
+        assert!(find("hive").is_none());
+        // "mechanoids" and "Mechanoid" share a stem
+        assert_eq!(find("mechanoid").map(|c| c.count), Some(2));
+        assert_eq!(find("raids").map(|c| c.kind.as_str()), Some("phrase"));
+        assert_eq!(terms[0].term, "plasteel");
+
+        let g = terms_to_glossary(&terms, "en", Some("ru"));
+        assert_eq!(g.len(), terms.len());
+        assert_eq!(
+            g.terms()[0].note.as_deref(),
+            Some("def-label: 2 uses in 2 files; ThingDef/Plasteel; e.g. A, B")
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{export::collect_export_units, util::is_under_languages_dir, Result};
+use rimloc_tm::{TmEntry, TmIndex};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Bump when the way roots are turned into entries changes, to invalidate caches.
+const CACHE_FORMAT: &str = "rimloc-tm-cache-1";
+
+/// Translation memory sources for PO export.
+#[derive(Debug, Clone, Copy)]
+pub struct TmOptions<'a> {
+    /// `Languages/<lang>` folders, mod roots, `.tmx` files or saved TM files (`.json`)
+    pub roots: &'a [PathBuf],
+    /// Minimum fuzzy score (0..1) for a candidate to be suggested
+    pub min_score: f64,
+    /// Cache for scanned folders; a root is rescanned only when its XML files change
+    pub cache_dir: Option<&'a Path>,
+}
+
+impl Default for TmOptions<'_> {
+    fn default() -> Self {
+        Self {
+            roots: &[],
+            min_score: rimloc_tm::DEFAULT_MIN_SCORE,
+            cache_dir: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedRoot {
+    fingerprint: String,
+    entries: Vec<TmEntry>,
+}
+
+/// Build a TM index from all roots. `lang` selects the translation language in mod
+/// roots and TMX files; `source_dir` is the source language folder (`English`).
+pub fn load_tm_index(opts: &TmOptions, lang: Option<&str>, source_dir: &str) -> Result<TmIndex> {
+    let mut index = TmIndex::new();
+    for root in opts.roots {
+        let ext = root
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("tmx") if root.is_file() => {
+                index.extend(crate::tmx::read_tmx_entries(root, source_dir, lang)?);
+            }
+            Some("json") if root.is_file() => {
+                index.extend(TmIndex::load(root)?.entries().iter().cloned());
+            }
+            _ => index.extend(cached_root_entries(root, lang, source_dir, opts.cache_dir)?),
+        }
+    }
+    Ok(index)
+}
+
+fn cached_root_entries(
+    root: &Path,
+    lang: Option<&str>,
+    source_dir: &str,
+    cache_dir: Option<&Path>,
+) -> Result<Vec<TmEntry>> {
+    let Some(cache_dir) = cache_dir else {
+        return scan_root_entries(root, lang, source_dir);
+    };
+    let (mod_root, lang_dir) = resolve_root(root, lang);
+    let abs = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let id = hex(&Sha256::digest(
+        format!(
+            "{}\0{}\0{source_dir}",
+            abs.display(),
+            lang_dir.as_deref().unwrap_or("")
+        )
+        .as_bytes(),
+    ));
+    let cache_file = cache_dir.join(format!("{id}.json"));
+    let fingerprint = fingerprint(&mod_root);
+    if let Some(cached) = std::fs::read_to_string(&cache_file)
+        .ok()
+        .and_then(|t| serde_json::from_str::<CachedRoot>(&t).ok())
+        .filter(|c| c.fingerprint == fingerprint)
+    {
+        return Ok(cached.entries);
+    }
+    let entries = scan_root_entries(root, lang, source_dir)?;
+    let cached = CachedRoot {
+        fingerprint,
+        entries,
+    };
+    // a cache that cannot be written only costs a rescan next time
+    if std::fs::create_dir_all(cache_dir).is_ok() {
+        if let Ok(json) = serde_json::to_string(&cached) {
+            let _ = crate::util::write_atomic(&cache_file, json.as_bytes());
+        }
+    }
+    Ok(cached.entries)
+}
+
+/// Mod root and translation folder for a TM root: `<mod>/Languages/<X>` points at
+/// `<mod>` and `X`; anything else is a mod root translated into `lang`.
+fn resolve_root(root: &Path, lang: Option<&str>) -> (PathBuf, Option<String>) {
+    let parent = root.parent();
+    if parent
+        .and_then(|p| p.file_name())
+        .is_some_and(|n| n.eq_ignore_ascii_case("Languages"))
+    {
+        if let (Some(mod_root), Some(name)) = (parent.and_then(Path::parent), root.file_name()) {
+            return (
+                mod_root.to_path_buf(),
+                Some(name.to_string_lossy().into_owned()),
+            );
+        }
+    }
+    (
+        root.to_path_buf(),
+        lang.map(rimloc_import_po::rimworld_lang_dir),
+    )
+}
+
+/// Pair translations under `Languages/<lang>` with the mod's source texts by key.
+/// Folders without such translations (plain Keyed dumps, glossaries) give key-only
+/// entries from every non-source unit.
+fn scan_root_entries(root: &Path, lang: Option<&str>, source_dir: &str) -> Result<Vec<TmEntry>> {
+    let (mod_root, lang_dir) = resolve_root(root, lang);
+    // unreadable roots are skipped, as with the key-only TM
+    let Ok(units) = rimloc_parsers_xml::scan_keyed_xml(&mod_root) else {
+        return Ok(Vec::new());
+    };
+    let origin = Some(mod_root.display().to_string());
+    let mut translations: BTreeMap<String, String> = BTreeMap::new();
+    if let Some(dir) = lang_dir.as_deref() {
+        for u in units
+            .iter()
+            .filter(|u| is_under_languages_dir(&u.path, dir))
+        {
+            if let Some(v) = u.source.as_deref().filter(|v| !v.trim().is_empty()) {
+                translations.insert(u.key.clone(), v.to_string());
+            }
+        }
+    }
+    if translations.is_empty() {
+        // last wins across files, as before source-text matching existed
+        for u in units
+            .into_iter()
+            .filter(|u| !is_under_languages_dir(&u.path, source_dir))
+        {
+            if let Some(v) = u.source.filter(|v| !v.trim().is_empty()) {
+                translations.insert(u.key, v);
+            }
+        }
+        return Ok(translations
+            .into_iter()
+            .map(|(key, target)| TmEntry {
+                key: Some(key),
+                target,
+                origin: origin.clone(),
+                ..Default::default()
+            })
+            .collect());
+    }
+
+    let sources: BTreeMap<String, String> = collect_export_units(&mod_root, None, Some(source_dir))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|u| Some((u.key, u.source?)))
+        .collect();
+    Ok(translations
+        .into_iter()
+        .map(|(key, target)| TmEntry {
+            source: sources.get(&key).cloned().unwrap_or_default(),
+            key: Some(key),
+            target,
+            origin: origin.clone(),
+        })
+        .collect())
+}
+
+/// Hash of XML file paths, sizes and modification times under `root`.
+fn fingerprint(root: &Path) -> String {
+    let mut files: Vec<(String, u64, u128)> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file()
+                && e.path()
+                    .extension()
+                    .is_some_and(|x| x.eq_ignore_ascii_case("xml"))
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let mtime = meta
+                .modified()
+                .ok()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()?
+                .as_nanos();
+            Some((e.path().display().to_string(), meta.len(), mtime))
+        })
+        .collect();
+    files.sort();
+    let mut hasher = Sha256::new();
+    hasher.update(CACHE_FORMAT.as_bytes());
+    for (path, len, mtime) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(len.to_le_bytes());
+        hasher.update(mtime.to_le_bytes());
+    }
+    hex(&hasher.finalize())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes[..16].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, body: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, format!("<LanguageData>{body}</LanguageData>")).unwrap();
+    }
+
+    #[test]
+    fn pairs_translations_with_sources_and_caches_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("OldMod");
+        write(
+            &old.join("Languages/English/Keyed/A.xml"),
+            "<Door>Open the heavy door now</Door><Bye>Bye</Bye>",
+        );
+        write(
+            &old.join("Languages/Russian/Keyed/A.xml"),
+            "<Door>Откройте тяжёлую дверь</Door>",
+        );
+        let cache = dir.path().join("cache");
+        let roots = [old.join("Languages/Russian")];
+        let opts = TmOptions {
+            roots: &roots,
+            cache_dir: Some(&cache),
+            ..Default::default()
+        };
+        let index = load_tm_index(&opts, Some("ru"), "English").unwrap();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.entries()[0].source, "Open the heavy door now");
+        let m = index.lookup(Some("NewDoor"), "Open the heavy door", 0.75, 3);
+        assert_eq!(m[0].entry.target, "Откройте тяжёлую дверь");
+
+        // served from cache while files are unchanged
+        let cached: Vec<_> = std::fs::read_dir(&cache).unwrap().collect();
+        assert_eq!(cached.len(), 1);
+        let cache_file = cached[0].as_ref().unwrap().path();
+        let mut doc: CachedRoot =
+            serde_json::from_str(&std::fs::read_to_string(&cache_file).unwrap()).unwrap();
+        doc.entries[0].target = "из кеша".into();
+        std::fs::write(&cache_file, serde_json::to_string(&doc).unwrap()).unwrap();
+        let index = load_tm_index(&opts, Some("ru"), "English").unwrap();
+        assert_eq!(index.entries()[0].target, "из кеша");
+
+        // a changed file invalidates the cache
+        write(
+            &old.join("Languages/Russian/Keyed/A.xml"),
+            "<Door>Откройте дверь</Door><Bye>Пока</Bye>",
+        );
+        let index = load_tm_index(&opts, Some("ru"), "English").unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.entries()[1].target, "Откройте дверь");
+    }
+
+    #[test]
+    fn roots_without_sources_give_key_only_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            &dir.path().join("Glossary/Languages/Russian/Keyed/G.xml"),
+            "<Bye>Пока</Bye>",
+        );
+        let roots = [dir.path().join("Glossary")];
+        let opts = TmOptions {
+            roots: &roots,
+            ..Default::default()
+        };
+        // no language given: every non-source unit counts
+        let index = load_tm_index(&opts, None, "English").unwrap();
+        let m = index.lookup(Some("Bye"), "Bye", 0.75, 3);
+        assert_eq!(m[0].kind, rimloc_tm::TmMatchKind::Key);
+        assert_eq!(m[0].entry.target, "Пока");
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{export::collect_export_units, po_meta::read_mod_about, Result};
+use rimloc_domain::{TmBuildPair, TmBuildSummary};
+use rimloc_tm::{TmEntry, TmIndex};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// A mod is taken as the source of a translation by key overlap when it shares at
+/// least this share of the smaller of the two key sets.
+const MIN_KEY_OVERLAP: f64 = 0.5;
+
+/// Deep enough for `Mods/<mod>/About` and `workshop/content/294100/<id>/About`.
+const MAX_MOD_DEPTH: usize = 4;
+
+struct ModDir {
+    root: PathBuf,
+    package_id: Option<String>,
+    dependencies: Vec<String>,
+    /// key -> text under `Languages/<lang>`
+    translations: BTreeMap<String, String>,
+}
+
+impl ModDir {
+    fn label(&self) -> String {
+        self.package_id
+            .clone()
+            .unwrap_or_else(|| self.root.display().to_string())
+    }
+}
+
+/// Build a TM from every `Languages/<lang>` tree found in the mods under `roots`
+/// (a Mods folder, a Steam workshop content folder or single mods).
+///
+/// Each tree is paired with source texts of the mod it translates: the mod itself,
+/// mods named by packageId in its About.xml (`modDependencies`, `loadAfter`), then
+/// any other mod sharing enough keys. Translations without a source are left out.
+pub fn build_tm(
+    roots: &[PathBuf],
+    lang: &str,
+    source_lang_dir: &str,
+) -> Result<(TmIndex, TmBuildSummary)> {
+    let lang_dir = rimloc_import_po::rimworld_lang_dir(lang);
+    let mods: Vec<ModDir> = find_mods(roots)
+        .into_iter()
+        .map(|root| {
+            let about = read_mod_about(&root).unwrap_or_default();
+            // unreadable mods simply contribute nothing
+            let translations = rimloc_parsers_xml::scan_keyed_xml(&root)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|u| lang_folder(&u.path).is_some_and(|d| matches_lang(d, &lang_dir)))
+                .filter_map(|u| Some((u.key, u.source.filter(|v| !v.trim().is_empty())?)))
+                .collect();
+            ModDir {
+                root,
+                package_id: about.package_id,
+                dependencies: about.dependencies,
+                translations,
+            }
+        })
+        .collect();
+    let by_package: HashMap<String, usize> = mods
+        .iter()
+        .enumerate()
+        .filter_map(|(i, m)| Some((m.package_id.as_ref()?.to_ascii_lowercase(), i)))
+        .collect();
+
+    // source texts are only collected for mods that are actually needed
+    let mut sources: Vec<Option<BTreeMap<String, String>>> = vec![None; mods.len()];
+    let mut index = TmIndex::new();
+    let mut summary = TmBuildSummary {
+        mods: mods.len(),
+        ..Default::default()
+    };
+    for (t, tr_mod) in mods.iter().enumerate() {
+        if tr_mod.translations.is_empty() {
+            continue;
+        }
+        let mut pairing = Pairing {
+            remaining: tr_mod.translations.clone(),
+            origin: tr_mod.label(),
+            report: TmBuildPair {
+                translation: tr_mod.root.display().to_string(),
+                package_id: tr_mod.package_id.clone(),
+                ..Default::default()
+            },
+            methods: Vec::new(),
+        };
+
+        let own = load_sources(&mut sources, &mods, source_lang_dir, t);
+        pairing.take(&mut index, own, tr_mod.label(), "self");
+
+        for dep in &tr_mod.dependencies {
+            match by_package.get(&dep.to_ascii_lowercase()) {
+                Some(&s) if s != t => {
+                    let src = load_sources(&mut sources, &mods, source_lang_dir, s);
+                    pairing.take(&mut index, src, mods[s].label(), "package-id");
+                }
+                _ => {}
+            }
+        }
+
+        let mut used: Vec<usize> = vec![t];
+        while !pairing.remaining.is_empty() {
+            let mut best: Option<(usize, usize)> = None;
+            for s in (0..mods.len()).filter(|s| !used.contains(s)) {
+                let src = load_sources(&mut sources, &mods, source_lang_dir, s);
+                let common = pairing.common_keys(src);
+                let needed = pairing.remaining.len().min(src.len()) as f64 * MIN_KEY_OVERLAP;
+                if common > 0 && common as f64 >= needed && best.is_none_or(|(_, c)| common > c) {
+                    best = Some((s, common));
+                }
+            }
+            let Some((s, _)) = best else { break };
+            used.push(s);
+            let src = load_sources(&mut sources, &mods, source_lang_dir, s);
+            pairing.take(&mut index, src, mods[s].label(), "key-overlap");
+        }
+
+        let mut report = pairing.report;
+        report.matched_by = pairing.methods.join(",");
+        report.unpaired = pairing.remaining.len();
+        summary.translations.push(report);
+    }
+    summary.entries = index.len();
+    Ok((index, summary))
+}
+
+/// Translations of one mod still waiting for a source text.
+struct Pairing {
+    remaining: BTreeMap<String, String>,
+    origin: String,
+    report: TmBuildPair,
+    methods: Vec<&'static str>,
+}
+
+impl Pairing {
+    fn common_keys(&self, src: &BTreeMap<String, String>) -> usize {
+        self.remaining
+            .keys()
+            .filter(|k| src.contains_key(*k))
+            .count()
+    }
+
+    /// Move every remaining translation that has a source in `src` into the index.
+    fn take(
+        &mut self,
+        index: &mut TmIndex,
+        src: &BTreeMap<String, String>,
+        source_mod: String,
+        method: &'static str,
+    ) {
+        let keys: Vec<String> = self
+            .remaining
+            .keys()
+            .filter(|k| src.contains_key(*k))
+            .cloned()
+            .collect();
+        if keys.is_empty() {
+            return;
+        }
+        for key in keys {
+            let target = self.remaining.remove(&key).unwrap_or_default();
+            index.insert(TmEntry {
+                source: src[&key].clone(),
+                key: Some(key),
+                target,
+                origin: Some(self.origin.clone()),
+            });
+            self.report.paired += 1;
+        }
+        self.report.sources.push(source_mod);
+        if !self.methods.contains(&method) {
+            self.methods.push(method);
+        }
+    }
+}
+
+/// Source texts (key -> text) of mod `i`, collected on first use.
+fn load_sources<'a>(
+    cache: &'a mut [Option<BTreeMap<String, String>>],
+    mods: &[ModDir],
+    source_lang_dir: &str,
+    i: usize,
+) -> &'a BTreeMap<String, String> {
+    cache[i].get_or_insert_with(|| {
+        collect_export_units(&mods[i].root, None, Some(source_lang_dir))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|u| Some((u.key, u.source.filter(|s| !s.trim().is_empty())?)))
+            .collect()
+    })
+}
+
+/// Mod folders (those with `About/About.xml`) under `roots`, sorted and deduplicated.
+fn find_mods(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut mods: Vec<PathBuf> = roots
+        .iter()
+        .flat_map(|root| {
+            WalkDir::new(root)
+                .max_depth(MAX_MOD_DEPTH)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    e.file_type().is_file()
+                        && e.file_name().eq_ignore_ascii_case("About.xml")
+                        && e.path()
+                            .parent()
+                            .and_then(Path::file_name)
+                            .is_some_and(|n| n.eq_ignore_ascii_case("About"))
+                })
+                .filter_map(|e| Some(e.path().parent()?.parent()?.to_path_buf()))
+        })
+        .collect();
+    mods.sort();
+    mods.dedup();
+    mods
+}
+
+/// Name of the folder right below `Languages` in `path`.
+fn lang_folder(path: &Path) -> Option<&str> {
+    let mut comps = path.components();
+    while let Some(c) = comps.next() {
+        if c.as_os_str().eq_ignore_ascii_case("Languages") {
+            return match comps.next() {
+                Some(Component::Normal(name)) => name.to_str(),
+                _ => None,
+            };
+        }
+    }
+    None
+}
+
+/// Workshop translations often name the folder `Russian (Русский)`.
+fn matches_lang(folder: &str, lang_dir: &str) -> bool {
+    folder.eq_ignore_ascii_case(lang_dir)
+        || folder
+            .get(..lang_dir.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(lang_dir))
+            && folder[lang_dir.len()..].starts_with(" (")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, body: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, body).unwrap();
+    }
+
+    fn about(dir: &Path, package_id: &str, extra: &str) {
+        write(
+            &dir.join("About/About.xml"),
+            &format!("<ModMetaData><packageId>{package_id}</packageId>{extra}</ModMetaData>"),
+        );
+    }
+
+    fn keyed(path: &Path, body: &str) {
+        write(path, &format!("<LanguageData>{body}</LanguageData>"));
+    }
+
+    #[test]
+    fn pairs_translation_mods_by_package_id_and_key_overlap() {
+        let dir = tempfile::tempdir().unwrap();
+        let mods = dir.path().join("Mods");
+        about(&mods.join("Doors"), "author.doors", "");
+        keyed(
+            &mods.join("Doors/Languages/English/Keyed/D.xml"),
+            "<Door>Open the door</Door><Lock>Lock it</Lock>",
+        );
+        about(&mods.join("Hats"), "author.hats", "");
+        keyed(
+            &mods.join("Hats/1.5/Languages/English/Keyed/H.xml"),
+            "<Hat>Nice hat</Hat><Cap>Cap</Cap>",
+        );
+        // ships its own translation
+        keyed(
+            &mods.join("Hats/1.5/Languages/Russian/Keyed/H.xml"),
+            "<Hat>Шляпа</Hat>",
+        );
+        about(
+            &mods.join("DoorsRu"),
+            "fan.doors.ru",
+            "<modDependencies><li><packageId>Author.Doors</packageId></li></modDependencies>",
+        );
+        keyed(
+            &mods.join("DoorsRu/Languages/Russian (Русский)/Keyed/D.xml"),
+            "<Door>Откройте дверь</Door><Gone>Нет</Gone>",
+        );
+        // no About reference: found through shared keys
+        about(&mods.join("HatsRu"), "fan.hats.ru", "");
+        keyed(
+            &mods.join("HatsRu/Languages/Russian/Keyed/H.xml"),
+            "<Cap>Кепка</Cap>",
+        );
+
+        let (index, summary) = build_tm(&[mods], "ru", "English").unwrap();
+        assert_eq!(summary.mods, 4);
+        assert_eq!(summary.entries, 3);
+        let by_id = |id: &str| {
+            summary
+                .translations
+                .iter()
+                .find(|p| p.package_id.as_deref() == Some(id))
+                .unwrap()
+        };
+        let doors = by_id("fan.doors.ru");
+        assert_eq!(doors.matched_by, "package-id");
+        assert_eq!(doors.sources, vec!["author.doors"]);
+        assert_eq!((doors.paired, doors.unpaired), (1, 1));
+        assert_eq!(by_id("author.hats").matched_by, "self");
+        assert_eq!(by_id("fan.hats.ru").matched_by, "key-overlap");
+
+        let m = index.lookup(None, "Open the door", 0.75, 1);
+        assert_eq!(m[0].entry.target, "Откройте дверь");
+        assert_eq!(m[0].entry.origin.as_deref(), Some("fan.doors.ru"));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    export::{collect_export_units, collect_translations, source_dir},
+    po_meta::read_mod_about,
+    Result,
+};
+use rimloc_tm::TmEntry;
+use rimloc_tmx::{TmxDoc, TmxUnit, PROP_GAME_VERSION, PROP_KEY, PROP_PACKAGE_ID};
+use std::path::Path;
+
+/// Stats for [`export_tmx`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TmxExportStats {
+    /// Source units scanned
+    pub total: usize,
+    /// Source/target pairs written
+    pub exported: usize,
+}
+
+/// Export a TMX of source/target pairs harvested from a translated mod tree: every
+/// source unit (same set as PO export) whose key is translated under `Languages/<lang>`.
+/// Units carry the key, the mod `packageId` and `game_version` as properties.
+pub fn export_tmx(
+    scan_root: &Path,
+    out: &Path,
+    lang: &str,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+    game_version: Option<&str>,
+) -> Result<TmxExportStats> {
+    let units = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let translations = collect_translations(scan_root, lang)?;
+    let package_id = read_mod_about(scan_root).and_then(|a| a.package_id);
+    let src_code = rimloc_import_po::rimworld_lang_code(&source_dir(source_lang, source_lang_dir));
+    let trg_code = rimloc_import_po::rimworld_lang_code(lang);
+
+    let mut stats = TmxExportStats {
+        total: units.len(),
+        ..Default::default()
+    };
+    let mut doc = TmxDoc {
+        source_lang: src_code.clone(),
+        units: Vec::new(),
+    };
+    for u in units {
+        let Some(source) = u.source.filter(|s| !s.trim().is_empty()) else {
+            continue;
+        };
+        let Some(target) = translations.get(&u.key) else {
+            continue;
+        };
+        let mut props = vec![(PROP_KEY.to_string(), u.key)];
+        if let Some(id) = &package_id {
+            props.push((PROP_PACKAGE_ID.to_string(), id.clone()));
+        }
+        if let Some(ver) = game_version {
+            props.push((PROP_GAME_VERSION.to_string(), ver.to_string()));
+        }
+        doc.units.push(TmxUnit {
+            tuid: None,
+            props,
+            variants: vec![
+                (src_code.clone(), source),
+                (trg_code.clone(), target.clone()),
+            ],
+        });
+        stats.exported += 1;
+    }
+    crate::util::write_atomic(out, doc.to_string().as_bytes())?;
+    Ok(stats)
+}
+
+/// TM entries from a TMX file: key from `x-rimloc-key` (or `tuid`), origin from
+/// `x-rimloc-package-id` (or the file). `source_lang` is used when the header has no
+/// concrete `srclang`; without `lang` the first non-source variant is taken.
+pub fn read_tmx_entries(
+    path: &Path,
+    source_lang: &str,
+    lang: Option<&str>,
+) -> Result<Vec<TmEntry>> {
+    let doc = TmxDoc::read(path)?;
+    let src = match doc.source_lang.as_str() {
+        "" | "*all*" => rimloc_import_po::rimworld_lang_code(source_lang),
+        code => code.to_string(),
+    };
+    let trg = lang.map(rimloc_import_po::rimworld_lang_code);
+    let mut out = Vec::new();
+    for u in &doc.units {
+        let Some(source) = u.text(&src).filter(|s| !s.trim().is_empty()) else {
+            continue;
+        };
+        let target = match &trg {
+            Some(code) => u.text(code),
+            None => u
+                .variants
+                .iter()
+                .find(|(l, _)| !same_language(l, &src))
+                .map(|(_, t)| t.as_str()),
+        };
+        if let Some(t) = target.filter(|t| !t.trim().is_empty()) {
+            out.push(TmEntry {
+                key: u.key().map(str::to_string),
+                source: source.to_string(),
+                target: t.to_string(),
+                origin: Some(
+                    u.prop(PROP_PACKAGE_ID)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| path.display().to_string()),
+                ),
+            });
+        }
+    }
+    Ok(out)
+}
+
+fn same_language(a: &str, b: &str) -> bool {
+    let primary = |t: &str| t.split(['-', '_']).next().unwrap_or(t).to_ascii_lowercase();
+    primary(a) == primary(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_then_read_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("About")).unwrap();
+        std::fs::write(
+            root.join("About/About.xml"),
+            "<ModMetaData><packageId>author.sample</packageId></ModMetaData>",
+        )
+        .unwrap();
+        crate::util::write_greeting_mod(root);
+        let out = root.join("mod.tmx");
+        let stats = export_tmx(root, &out, "ru", None, None, Some("1.5")).unwrap();
+        assert_eq!((stats.total, stats.exported), (2, 1));
+
+        let doc = TmxDoc::read(&out).unwrap();
+        assert_eq!(doc.source_lang, "en");
+        let tu = &doc.units[0];
+        assert_eq!(tu.key(), Some("Greeting"));
+        assert_eq!(tu.prop(PROP_PACKAGE_ID), Some("author.sample"));
+        assert_eq!(tu.prop(PROP_GAME_VERSION), Some("1.5"));
+
+        let entries = read_tmx_entries(&out, "en", Some("Russian")).unwrap();
+        assert_eq!(
+            entries,
+            vec![TmEntry {
+                key: Some("Greeting".into()),
+                source: "Hello, {0}".into(),
+                target: "Привет, {0}".into(),
+                origin: Some("author.sample".into()),
+            }]
+        );
+        assert_eq!(read_tmx_entries(&out, "en", None).unwrap(), entries);
+        assert!(read_tmx_entries(&out, "en", Some("de")).unwrap().is_empty());
+    }
+}
@@ -54,6 +54,19 @@ pub fn def_injected_target_path(
         .join(file_name)
 }
 
+/// Path of `path` relative to `root` with `/` separators (as written into exchange
+/// files); the full path when it is outside `root`.
+pub fn rel_slash_path(path: &Path, root: &Path) -> String {
+    match path.strip_prefix(root) {
+        Ok(rel) => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.display().to_string(),
+    }
+}
+
 pub fn write_atomic(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()> {
     use std::fs;
     use std::io::Write;
@@ -69,3 +82,21 @@ pub fn write_atomic(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()>
     fs::rename(&tmp, path)?;
     Ok(())
 }
+
+/// Test mod whose `Keyed/A.xml` has `Greeting` in English and Russian and `Bye` in
+/// English only.
+#[cfg(test)]
+pub(crate) fn write_greeting_mod(root: &Path) {
+    for (lang, body) in [
+        ("English", "<Greeting>Hello, {0}</Greeting><Bye>Bye</Bye>"),
+        ("Russian", "<Greeting>Привет, {0}</Greeting>"),
+    ] {
+        let keyed = root.join("Languages").join(lang).join("Keyed");
+        std::fs::create_dir_all(&keyed).unwrap();
+        std::fs::write(
+            keyed.join("A.xml"),
+            format!("<LanguageData>{body}</LanguageData>"),
+        )
+        .unwrap();
+    }
+}
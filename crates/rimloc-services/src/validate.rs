@@ -1,4 +1,5 @@
 use crate::{util::is_source_for_lang_dir, Result, ValidationMessage};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Validate scanned units under a root with optional filtering by language folder/code.
@@ -78,33 +79,51 @@ pub fn validate_under_root_with_defs_and_dict(
     Ok(msgs)
 }
 
-/// Compare placeholders between source (English) and a target language by matching on keys.
-/// This is stricter than the per-string `validate` and similar in spirit to `validate-po`.
-/// Not wired to CLI by default; GUI or advanced flows can opt-in.
-pub fn validate_placeholders_cross_language(
+/// Cross-file Keyed duplicates between a version folder picked for validation and
+/// the mod root's `Languages`, which the game loads together with every version.
+/// Only clashes involving a root file are returned; the rest are already reported
+/// by validating the version folder on its own.
+pub fn validate_keyed_duplicates_with_root(
+    mod_root: &Path,
+    version_root: &Path,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+) -> Result<Vec<ValidationMessage>> {
+    let shared = mod_root.join("Languages");
+    if !shared.is_dir() || version_root == mod_root {
+        return Ok(Vec::new());
+    }
+    let mut units = rimloc_parsers_xml::scan_keyed_xml(version_root)?;
+    units.extend(rimloc_parsers_xml::scan_keyed_xml(&shared)?);
+    let lang_dir = source_lang_dir
+        .map(str::to_string)
+        .or_else(|| source_lang.map(rimloc_import_po::rimworld_lang_dir));
+    if let Some(dir) = lang_dir.as_deref() {
+        units.retain(|u| is_source_for_lang_dir(&u.path, dir));
+    }
+    let mut msgs = rimloc_validate::keyed_duplicates_across_files(&units);
+    let shared = shared.to_string_lossy().to_string();
+    let with_root: std::collections::HashSet<String> = msgs
+        .iter()
+        .filter(|m| m.path.starts_with(&shared))
+        .map(|m| m.key.clone())
+        .collect();
+    msgs.retain(|m| with_root.contains(&m.key));
+    Ok(msgs)
+}
+
+/// Source texts and target translations (text, path, line) by key.
+type KeyedPairs = (
+    std::collections::HashMap<String, String>,
+    std::collections::HashMap<String, (String, String, Option<usize>)>,
+);
+
+fn source_and_target_by_key(
     scan_root: &Path,
     source_lang_dir: &str,
     target_lang_dir: &str,
     defs_root: Option<&Path>,
-) -> Result<Vec<ValidationMessage>> {
-    fn extract_placeholders_like_cli(text: &str) -> std::collections::BTreeSet<String> {
-        use regex::Regex;
-        use std::sync::OnceLock;
-        static RE_PCT: OnceLock<Regex> = OnceLock::new();
-        static RE_BRACE: OnceLock<Regex> = OnceLock::new();
-        let re_pct = RE_PCT.get_or_init(|| Regex::new(r"%(\d+\$)?0?\d*[sdif]").unwrap());
-        let re_brace = RE_BRACE.get_or_init(|| Regex::new(r"\{\s*([^{}\s]+)\s*\}").unwrap());
-        let mut out: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
-        for m in re_pct.find_iter(text) {
-            out.insert(m.as_str().to_string());
-        }
-        for cap in re_brace.captures_iter(text) {
-            if let Some(name) = cap.get(1) {
-                out.insert(format!("{{{}}}", name.as_str()));
-            }
-        }
-        out
-    }
+) -> Result<KeyedPairs> {
     // Scan everything, then filter per language
     let mut units = if let Some(defs) = defs_root {
         rimloc_parsers_xml::scan_all_units_with_defs(scan_root, Some(defs))?
@@ -132,20 +151,46 @@ pub fn validate_placeholders_cross_language(
             }
         }
     }
+    Ok((src_map, tgt_map))
+}
+
+/// Compare placeholders between source (English) and a target language by matching on keys.
+/// Brace arguments are compared by the argument they use, so suffixes and gender
+/// switches may differ (see [`rimloc_validate::format_args::usage`]).
+/// This is stricter than the per-string `validate` and similar in spirit to `validate-po`.
+/// Not wired to CLI by default; GUI or advanced flows can opt-in.
+pub fn validate_placeholders_cross_language(
+    scan_root: &Path,
+    source_lang_dir: &str,
+    target_lang_dir: &str,
+    defs_root: Option<&Path>,
+) -> Result<Vec<ValidationMessage>> {
+    let (src_map, tgt_map) =
+        source_and_target_by_key(scan_root, source_lang_dir, target_lang_dir, defs_root)?;
 
     // Compare placeholder sets
     let mut msgs = Vec::new();
     for (key, (tgt, path, line)) in tgt_map.into_iter() {
         if let Some(src) = src_map.get(&key) {
-            let src_ph = extract_placeholders_like_cli(src);
-            let tgt_ph = extract_placeholders_like_cli(&tgt);
+            // argument usage: `{PAWN_pronoun}` may become `{PAWN_gender ? он : она}`
+            let src_ph = rimloc_validate::format_args::usage(src);
+            let tgt_ph = rimloc_validate::format_args::usage(&tgt);
             if src_ph != tgt_ph {
+                let missing: Vec<&str> = src_ph.difference(&tgt_ph).map(String::as_str).collect();
+                let extra: Vec<&str> = tgt_ph.difference(&src_ph).map(String::as_str).collect();
+                let mut message = "Placeholder mismatch vs source".to_string();
+                if !missing.is_empty() {
+                    message.push_str(&format!("; missing {}", missing.join(" ")));
+                }
+                if !extra.is_empty() {
+                    message.push_str(&format!("; not in source {}", extra.join(" ")));
+                }
                 msgs.push(ValidationMessage {
                     kind: "placeholder-check".into(),
                     key,
                     path,
                     line,
-                    message: "Placeholder mismatch vs source".into(),
+                    message,
                 });
             }
         }
@@ -153,3 +198,229 @@ pub fn validate_placeholders_cross_language(
 
     Ok(msgs)
 }
+
+/// Compare rich-text tags (`<b>`, `<color=…>`) between source and target by key
+/// (kind `rich-text`): the translation must use the same tags in the same order.
+/// Tag-like text the game does not render is reported as `rich-text-unknown` only
+/// when the source does not have it.
+pub fn validate_rich_text_cross_language(
+    scan_root: &Path,
+    source_lang_dir: &str,
+    target_lang_dir: &str,
+    defs_root: Option<&Path>,
+) -> Result<Vec<ValidationMessage>> {
+    let (src_map, tgt_map) =
+        source_and_target_by_key(scan_root, source_lang_dir, target_lang_dir, defs_root)?;
+
+    let mut msgs = Vec::new();
+    for (key, (tgt, path, line)) in tgt_map {
+        let Some(src) = src_map.get(&key) else {
+            continue;
+        };
+        if tgt.trim().is_empty() {
+            continue;
+        }
+        let src_tags = rimloc_validate::rich_text::sequence(src);
+        let tgt_tags = rimloc_validate::rich_text::sequence(&tgt);
+        if src_tags != tgt_tags {
+            msgs.push(ValidationMessage {
+                kind: "rich-text".into(),
+                key: key.clone(),
+                path: path.clone(),
+                line,
+                message: format!(
+                    "Rich-text tags differ from source: expected [{}], got [{}]",
+                    src_tags.join(" "),
+                    tgt_tags.join(" ")
+                ),
+            });
+        }
+        let src_unknown = rimloc_validate::rich_text::unknown(src);
+        for name in rimloc_validate::rich_text::unknown(&tgt).difference(&src_unknown) {
+            msgs.push(ValidationMessage {
+                kind: "rich-text-unknown".into(),
+                key: key.clone(),
+                path: path.clone(),
+                line,
+                message: format!("Unknown rich-text tag <{name}> is not in source"),
+            });
+        }
+    }
+    msgs.sort_by(|a, b| (&a.path, a.line, &a.key).cmp(&(&b.path, b.line, &b.key)));
+    Ok(msgs)
+}
+
+/// Compare literal tokens between source and target by key, one kind per token
+/// type: `number-mismatch`, `percent-mismatch`, `url-mismatch`, `hotkey-mismatch`
+/// and `newline-mismatch` (`\n` escapes). See [`rimloc_validate::literals`].
+pub fn validate_literals_cross_language(
+    scan_root: &Path,
+    source_lang_dir: &str,
+    target_lang_dir: &str,
+    defs_root: Option<&Path>,
+) -> Result<Vec<ValidationMessage>> {
+    use rimloc_validate::literals::{extract, missing};
+
+    let (src_map, tgt_map) =
+        source_and_target_by_key(scan_root, source_lang_dir, target_lang_dir, defs_root)?;
+
+    let mut msgs = Vec::new();
+    for (key, (tgt, path, line)) in tgt_map {
+        let Some(src) = src_map.get(&key) else {
+            continue;
+        };
+        if tgt.trim().is_empty() {
+            continue;
+        }
+        let (src, tgt) = (extract(src), extract(&tgt));
+        let mut push = |kind: &str, message: String| {
+            msgs.push(ValidationMessage {
+                kind: kind.into(),
+                key: key.clone(),
+                path: path.clone(),
+                line,
+                message,
+            })
+        };
+        for (kind, what, suffix, src_items, tgt_items) in [
+            ("number-mismatch", "Numbers", "", &src.numbers, &tgt.numbers),
+            ("percent-mismatch", "Percent values", "%", &src.percents, &tgt.percents),
+            ("url-mismatch", "URLs", "", &src.urls, &tgt.urls),
+            ("hotkey-mismatch", "Hotkeys", "", &src.hotkeys, &tgt.hotkeys),
+        ] {
+            if src_items == tgt_items {
+                continue;
+            }
+            let list = |items: Vec<&str>| {
+                items
+                    .iter()
+                    .map(|i| format!("{i}{suffix}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            let mut message = format!("{what} differ from source");
+            let (gone, added) = (missing(src_items, tgt_items), missing(tgt_items, src_items));
+            if !gone.is_empty() {
+                message.push_str(&format!("; missing {}", list(gone)));
+            }
+            if !added.is_empty() {
+                message.push_str(&format!("; not in source {}", list(added)));
+            }
+            push(kind, message);
+        }
+        if src.newline_escapes != tgt.newline_escapes {
+            push(
+                "newline-mismatch",
+                format!(
+                    "\\n escapes differ from source: {} in source, {} in translation",
+                    src.newline_escapes, tgt.newline_escapes
+                ),
+            );
+        }
+    }
+    msgs.sort_by(|a, b| (&a.path, a.line, &a.key).cmp(&(&b.path, b.line, &b.key)));
+    Ok(msgs)
+}
+
+/// Compare grammar rules (`rulesStrings`) between source and target by def (kind
+/// `grammar`): see [`rimloc_validate::grammar::compare`]. Items of one list
+/// (`Pack.rulePack.rulesStrings.0`, `.1`, ...) are checked together.
+pub fn validate_grammar_cross_language(
+    scan_root: &Path,
+    source_lang_dir: &str,
+    target_lang_dir: &str,
+    defs_root: Option<&Path>,
+) -> Result<Vec<ValidationMessage>> {
+    let (src_map, tgt_map) =
+        source_and_target_by_key(scan_root, source_lang_dir, target_lang_dir, defs_root)?;
+
+    let mut src_rules: BTreeMap<String, Vec<(String, &str)>> = BTreeMap::new();
+    for (key, text) in &src_map {
+        if let Some(list) = rules_list_key(key) {
+            src_rules.entry(list).or_default().push((key.clone(), text));
+        }
+    }
+    let mut tgt_rules: BTreeMap<String, Vec<(String, &str)>> = BTreeMap::new();
+    let mut tgt_loc: BTreeMap<String, (String, Option<usize>)> = BTreeMap::new();
+    for (key, (text, path, line)) in &tgt_map {
+        if let Some(list) = rules_list_key(key) {
+            tgt_rules
+                .entry(list.clone())
+                .or_default()
+                .push((key.clone(), text));
+            let loc = tgt_loc.entry(list).or_insert_with(|| (path.clone(), *line));
+            if (path, *line) < (&loc.0, loc.1) {
+                *loc = (path.clone(), *line);
+            }
+        }
+    }
+
+    let join = |items: &mut Vec<(String, &str)>| {
+        items.sort();
+        items
+            .iter()
+            .map(|(_, text)| *text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let mut msgs = Vec::new();
+    for (list, mut items) in tgt_rules {
+        let Some(src_items) = src_rules.get_mut(&list) else {
+            continue;
+        };
+        let translation = join(&mut items);
+        if translation.trim().is_empty() {
+            continue;
+        }
+        let (path, line) = tgt_loc.remove(&list).unwrap_or_default();
+        for message in rimloc_validate::grammar::compare(&join(src_items), &translation) {
+            msgs.push(ValidationMessage {
+                kind: "grammar".into(),
+                key: list.clone(),
+                path: path.clone(),
+                line,
+                message,
+            });
+        }
+    }
+    Ok(msgs)
+}
+
+/// `Pack.rulePack.rulesStrings` for `Pack.rulePack.rulesStrings.3` and itself.
+fn rules_list_key(key: &str) -> Option<String> {
+    let parts: Vec<&str> = key.split('.').collect();
+    let i = parts.iter().rposition(|p| *p == "rulesStrings")?;
+    Some(parts[..=i].join("."))
+}
+
+/// Check translations under `target_lang_dir` against a glossary (kind `glossary`):
+/// when the source text of a key uses a term, the translation must contain one of
+/// its approved forms and none of its forbidden ones. Sorted by path and line.
+pub fn validate_glossary_cross_language(
+    scan_root: &Path,
+    source_lang_dir: &str,
+    target_lang_dir: &str,
+    defs_root: Option<&Path>,
+    glossary: &rimloc_glossary::Glossary,
+) -> Result<Vec<ValidationMessage>> {
+    let (src_map, tgt_map) =
+        source_and_target_by_key(scan_root, source_lang_dir, target_lang_dir, defs_root)?;
+
+    let mut msgs = Vec::new();
+    for (key, (tgt, path, line)) in tgt_map {
+        let Some(src) = src_map.get(&key) else {
+            continue;
+        };
+        for found in glossary.check(src, &tgt) {
+            msgs.push(ValidationMessage {
+                kind: "glossary".into(),
+                key: key.clone(),
+                path: path.clone(),
+                line,
+                message: found.to_string(),
+            });
+        }
+    }
+    msgs.sort_by(|a, b| (&a.path, a.line, &a.key).cmp(&(&b.path, b.line, &b.key)));
+    Ok(msgs)
+}
@@ -0,0 +1,111 @@
+use crate::{
+    export::{collect_export_units, collect_translations},
+    util::rel_slash_path,
+    Result,
+};
+use rimloc_core::PoEntry;
+use rimloc_xliff::{XliffDoc, XliffState, XliffUnit, XliffVersion};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Stats for [`export_xliff`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XliffExportStats {
+    pub total: usize,
+    /// Units prefilled from existing `Languages/<lang>` translations
+    pub translated: usize,
+}
+
+/// Export source strings (same units as PO export) to XLIFF. Keys already translated
+/// under `Languages/<lang>` are written as targets with state `translated`.
+pub fn export_xliff(
+    scan_root: &Path,
+    out: &Path,
+    version: XliffVersion,
+    lang: Option<&str>,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+) -> Result<XliffExportStats> {
+    let units = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let existing: HashMap<String, String> = match lang {
+        Some(code) => collect_translations(scan_root, code)?,
+        None => HashMap::new(),
+    };
+
+    let mut stats = XliffExportStats::default();
+    let mut doc = XliffDoc {
+        version,
+        source_lang: rimloc_import_po::rimworld_lang_code(source_lang.unwrap_or("en")),
+        target_lang: lang.map(rimloc_import_po::rimworld_lang_code),
+        units: Vec::with_capacity(units.len()),
+    };
+    for u in units {
+        let file = rel_slash_path(&u.path, scan_root);
+        let target = existing.get(&u.key).cloned();
+        stats.total += 1;
+        if target.is_some() {
+            stats.translated += 1;
+        }
+        doc.units.push(XliffUnit {
+            state: if target.is_some() {
+                XliffState::Translated
+            } else {
+                XliffState::New
+            },
+            key: u.key,
+            file,
+            source: u.source.unwrap_or_default(),
+            target,
+            notes: u.comments,
+        });
+    }
+    crate::util::write_atomic(out, doc.to_string().as_bytes())?;
+    Ok(stats)
+}
+
+/// Read XLIFF units as import entries: the unit's `file` is the reference used for
+/// placement, and filled targets still in state `new` count as fuzzy.
+pub fn read_xliff_entries(path: &Path) -> Result<Vec<PoEntry>> {
+    let doc = XliffDoc::read(path)?;
+    Ok(doc
+        .units
+        .into_iter()
+        .filter(|u| !u.key.is_empty())
+        .map(|u| {
+            let value = u.target.unwrap_or_default();
+            PoEntry {
+                fuzzy: u.state == XliffState::New && !value.is_empty(),
+                key: u.key,
+                value,
+                reference: Some(u.file).filter(|f| !f.is_empty()),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_then_read_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        crate::util::write_greeting_mod(root);
+        let out = root.join("mod.xlf");
+        let stats =
+            export_xliff(root, &out, XliffVersion::V2_0, Some("ru"), None, None).unwrap();
+        assert_eq!((stats.total, stats.translated), (2, 1));
+
+        let entries = read_xliff_entries(&out).unwrap();
+        let greeting = entries.iter().find(|e| e.key == "Greeting").unwrap();
+        assert_eq!(greeting.value, "Привет, {0}");
+        assert_eq!(
+            greeting.reference.as_deref(),
+            Some("Languages/English/Keyed/A.xml")
+        );
+        assert!(!greeting.fuzzy);
+        let bye = entries.iter().find(|e| e.key == "Bye").unwrap();
+        assert!(bye.value.is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{
+    export::{collect_export_units, collect_translations},
+    util::rel_slash_path,
+    Result,
+};
+use rimloc_core::PoEntry;
+use rimloc_xlsx::{source_hash, XlsxBook, XlsxRow, XlsxSheet};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Mismatched rows listed in the import error before it is cut short.
+const MAX_LISTED_MISMATCHES: usize = 10;
+
+/// Stats for [`export_xlsx`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XlsxExportStats {
+    pub total: usize,
+    /// Rows prefilled from existing `Languages/<lang>` translations
+    pub translated: usize,
+    pub sheets: usize,
+}
+
+/// Export source strings (same units as PO export) to an XLSX workbook with one
+/// sheet per Keyed file or DefInjected def type. Keys already translated under
+/// `Languages/<lang>` are prefilled.
+pub fn export_xlsx(
+    scan_root: &Path,
+    out: &Path,
+    lang: Option<&str>,
+    source_lang: Option<&str>,
+    source_lang_dir: Option<&str>,
+) -> Result<XlsxExportStats> {
+    let units = collect_export_units(scan_root, source_lang, source_lang_dir)?;
+    let existing: HashMap<String, String> = match lang {
+        Some(code) => collect_translations(scan_root, code)?,
+        None => HashMap::new(),
+    };
+
+    let mut stats = XlsxExportStats::default();
+    let mut book = XlsxBook::default();
+    let mut sheet_of: HashMap<String, usize> = HashMap::new();
+    let mut taken = HashSet::new();
+    for u in units {
+        let file = rel_slash_path(&u.path, scan_root);
+        let group = sheet_group(&file);
+        let idx = *sheet_of.entry(group.clone()).or_insert_with(|| {
+            book.sheets.push(XlsxSheet {
+                name: rimloc_xlsx::sheet_name(&group, &mut taken),
+                rows: Vec::new(),
+            });
+            book.sheets.len() - 1
+        });
+        let source = u.source.unwrap_or_default();
+        let translation = existing.get(&u.key).cloned().unwrap_or_default();
+        stats.total += 1;
+        if !translation.is_empty() {
+            stats.translated += 1;
+        }
+        book.sheets[idx].rows.push(XlsxRow {
+            hash: source_hash(&u.key, &source),
+            key: u.key,
+            source,
+            translation,
+            notes: u.comments.join("\n"),
+            file,
+        });
+    }
+    stats.sheets = book.sheets.len();
+    crate::util::write_atomic(out, &book.to_bytes()?)?;
+    Ok(stats)
+}
+
+/// Sheet a file belongs to: the def type for `DefInjected/<DefType>/…`, the file
+/// name without extension otherwise.
+fn sheet_group(file: &str) -> String {
+    let parts: Vec<&str> = file.split('/').collect();
+    if let Some(i) = parts.iter().position(|p| *p == "DefInjected") {
+        if i + 2 < parts.len() {
+            return parts[i + 1].to_string();
+        }
+    }
+    let name = parts.last().copied().unwrap_or(file);
+    name.rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Read XLSX rows as import entries. Fails without returning anything when a
+/// translator changed a key or source text (the row no longer matches its hash),
+/// listing the affected cells.
+pub fn read_xlsx_entries(path: &Path) -> Result<Vec<PoEntry>> {
+    let book = XlsxBook::read(path)?;
+    let mut entries = Vec::new();
+    let mut mismatches = Vec::new();
+    for sheet in book.sheets {
+        for (i, r) in sheet.rows.into_iter().enumerate() {
+            if !r.is_intact() {
+                // header is row 1
+                mismatches.push(format!("'{}'!A{} ({})", sheet.name, i + 2, r.key));
+                continue;
+            }
+            entries.push(PoEntry {
+                key: r.key,
+                value: r.translation,
+                reference: Some(r.file).filter(|f| !f.is_empty()),
+                fuzzy: false,
+            });
+        }
+    }
+    if !mismatches.is_empty() {
+        let more = mismatches.len().saturating_sub(MAX_LISTED_MISMATCHES);
+        mismatches.truncate(MAX_LISTED_MISMATCHES);
+        let mut list = mismatches.join(", ");
+        if more > 0 {
+            list.push_str(&format!(" and {more} more"));
+        }
+        color_eyre::eyre::bail!(
+            "{}: key or source text was changed in {list}; restore them from a fresh export-xlsx",
+            path.display()
+        );
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_mod(root: &Path) {
+        crate::util::write_greeting_mod(root);
+        let defs = root.join("Defs");
+        std::fs::create_dir_all(&defs).unwrap();
+        std::fs::write(
+            defs.join("Things.xml"),
+            "<Defs><ThingDef><defName>Beer</defName><label>beer</label></ThingDef></Defs>",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn export_groups_sheets_then_reads_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_mod(root);
+        let out = root.join("mod.xlsx");
+        let stats = export_xlsx(root, &out, Some("ru"), None, None).unwrap();
+        assert_eq!((stats.total, stats.translated, stats.sheets), (3, 1, 2));
+
+        let book = XlsxBook::read(&out).unwrap();
+        let names: Vec<_> = book.sheets.iter().map(|s| s.name.as_str()).collect();
+        assert!(
+            names.contains(&"A") && names.contains(&"ThingDef"),
+            "{names:?}"
+        );
+
+        let entries = read_xlsx_entries(&out).unwrap();
+        let greeting = entries.iter().find(|e| e.key == "Greeting").unwrap();
+        assert_eq!(greeting.value, "Привет, {0}");
+        assert_eq!(
+            greeting.reference.as_deref(),
+            Some("Languages/English/Keyed/A.xml")
+        );
+        let beer = entries.iter().find(|e| e.key == "Beer.label").unwrap();
+        assert!(beer
+            .reference
+            .as_deref()
+            .unwrap()
+            .contains("DefInjected/ThingDef/"));
+    }
+
+    #[test]
+    fn read_rejects_edited_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_mod(root);
+        let out = root.join("mod.xlsx");
+        export_xlsx(root, &out, Some("ru"), None, None).unwrap();
+
+        let mut book = XlsxBook::read(&out).unwrap();
+        let row = book
+            .sheets
+            .iter_mut()
+            .flat_map(|s| s.rows.iter_mut())
+            .find(|r| r.key == "Bye")
+            .unwrap();
+        row.source = "Goodbye".into();
+        row.translation = "До свидания".into();
+        book.save(&out).unwrap();
+
+        let err = read_xlsx_entries(&out).unwrap_err().to_string();
+        assert!(err.contains("(Bye)"), "{err}");
+    }
+}
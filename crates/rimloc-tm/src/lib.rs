@@ -0,0 +1,410 @@
+//! Translation memory index with fuzzy source-text matching.
+//!
+//! Entries are source/target pairs (optionally with the RimWorld key they came
+//! from). [`TmIndex::lookup`] returns the best candidates for a unit: the same key
+//! with the same source, the same source under another key, the same key with an
+//! edited source, and similar sources scored by token-level edit distance
+//! (see [`similarity`]). Placeholders such as `{0}` count as single tokens.
+
+use color_eyre::eyre::{eyre, Result};
+use rimloc_core::placeholders;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Default minimum similarity for fuzzy candidates.
+pub const DEFAULT_MIN_SCORE: f64 = 0.75;
+/// Default number of candidates returned per lookup.
+pub const DEFAULT_CANDIDATES: usize = 3;
+
+const FILE_VERSION: u32 = 1;
+
+/// One source/target pair.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TmEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Source text; empty when only the key is known
+    #[serde(default)]
+    pub source: String,
+    pub target: String,
+    /// Where the pair came from (TM root, file, packageId)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+}
+
+/// How a candidate relates to the looked-up unit, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TmMatchKind {
+    /// Same key, same source
+    Exact,
+    /// Same source, different (or no) key
+    Source,
+    /// Same key, different or unknown source
+    Key,
+    /// Similar source
+    Fuzzy,
+}
+
+impl TmMatchKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TmMatchKind::Exact => "exact",
+            TmMatchKind::Source => "source",
+            TmMatchKind::Key => "key",
+            TmMatchKind::Fuzzy => "fuzzy",
+        }
+    }
+}
+
+/// A lookup candidate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TmMatch<'a> {
+    pub entry: &'a TmEntry,
+    /// Source similarity in `0.0..=1.0` (0 for key matches without a known source)
+    pub score: f64,
+    pub kind: TmMatchKind,
+}
+
+impl TmMatch<'_> {
+    /// Score as a whole percentage, rounded down so only identical sources show 100.
+    pub fn percent(&self) -> u32 {
+        (self.score * 100.0).floor() as u32
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TmFile {
+    version: u32,
+    entries: Vec<TmEntry>,
+}
+
+/// In-memory TM with key, exact-source and token indexes.
+#[derive(Debug, Clone, Default)]
+pub struct TmIndex {
+    entries: Vec<TmEntry>,
+    /// Token ids of each entry's source
+    tokens: Vec<Vec<u32>>,
+    vocab: HashMap<String, u32>,
+    /// Token id -> entries containing it
+    postings: HashMap<u32, Vec<u32>>,
+    by_key: HashMap<String, Vec<u32>>,
+    by_source: HashMap<String, Vec<u32>>,
+    seen: HashSet<TmEntry>,
+}
+
+impl TmIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[TmEntry] {
+        &self.entries
+    }
+
+    /// Add an entry. Entries with an empty target and exact duplicates are skipped;
+    /// returns whether the entry was added.
+    pub fn insert(&mut self, mut entry: TmEntry) -> bool {
+        entry.source = entry.source.trim().to_string();
+        entry.target = entry.target.trim().to_string();
+        if entry.target.is_empty() || entry.source.is_empty() && entry.key.is_none() {
+            return false;
+        }
+        if !self.seen.insert(entry.clone()) {
+            return false;
+        }
+        let id = self.entries.len() as u32;
+        let mut ids = Vec::new();
+        for tok in tokenize(&entry.source) {
+            let next = self.vocab.len() as u32;
+            let tid = *self.vocab.entry(tok).or_insert(next);
+            let posting = self.postings.entry(tid).or_default();
+            if posting.last() != Some(&id) {
+                posting.push(id);
+            }
+            ids.push(tid);
+        }
+        if let Some(key) = &entry.key {
+            self.by_key.entry(key.clone()).or_default().push(id);
+        }
+        if !entry.source.is_empty() {
+            self.by_source
+                .entry(entry.source.clone())
+                .or_default()
+                .push(id);
+        }
+        self.tokens.push(ids);
+        self.entries.push(entry);
+        true
+    }
+
+    /// Best candidates for a unit, highest score first (ties: exact, source, key,
+    /// fuzzy), at most one per distinct target. Candidates with a known, different
+    /// source need a score of at least `min_score`; key matches without a known
+    /// source are always returned.
+    pub fn lookup(
+        &self,
+        key: Option<&str>,
+        source: &str,
+        min_score: f64,
+        limit: usize,
+    ) -> Vec<TmMatch<'_>> {
+        let source = source.trim();
+        let query: Vec<Option<u32>> = tokenize(source)
+            .iter()
+            .map(|t| self.vocab.get(t).copied())
+            .collect();
+        let mut found: HashMap<u32, (f64, TmMatchKind)> = HashMap::new();
+
+        for &id in key.and_then(|k| self.by_key.get(k)).into_iter().flatten() {
+            let e = &self.entries[id as usize];
+            let hit = if e.source == source {
+                (1.0, TmMatchKind::Exact)
+            } else if e.source.is_empty() {
+                (0.0, TmMatchKind::Key)
+            } else {
+                match self.score(&query, id) {
+                    score if score >= min_score => (score, TmMatchKind::Key),
+                    _ => continue,
+                }
+            };
+            found.insert(id, hit);
+        }
+        if !source.is_empty() {
+            for &id in self.by_source.get(source).into_iter().flatten() {
+                found.entry(id).or_insert((1.0, TmMatchKind::Source));
+            }
+            let mut candidates: HashSet<u32> = HashSet::new();
+            for tid in query.iter().flatten() {
+                candidates.extend(self.postings.get(tid).into_iter().flatten());
+            }
+            for id in candidates {
+                if found.contains_key(&id) {
+                    continue;
+                }
+                // Similarity cannot exceed the shorter/longer token count ratio.
+                let (a, b) = (query.len(), self.tokens[id as usize].len());
+                if (a.min(b) as f64) < min_score * a.max(b) as f64 {
+                    continue;
+                }
+                let score = self.score(&query, id);
+                if score >= min_score {
+                    found.insert(id, (score, TmMatchKind::Fuzzy));
+                }
+            }
+        }
+
+        let mut out: Vec<(u32, f64, TmMatchKind)> =
+            found.into_iter().map(|(id, (s, k))| (id, s, k)).collect();
+        out.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.2.cmp(&b.2)).then(a.0.cmp(&b.0)));
+        let mut targets: HashSet<&str> = HashSet::new();
+        out.into_iter()
+            .filter(|(id, _, _)| targets.insert(&self.entries[*id as usize].target))
+            .take(limit)
+            .map(|(id, score, kind)| TmMatch {
+                entry: &self.entries[id as usize],
+                score,
+                kind,
+            })
+            .collect()
+    }
+
+    fn score(&self, query: &[Option<u32>], id: u32) -> f64 {
+        let other = &self.tokens[id as usize];
+        let max = query.len().max(other.len());
+        if max == 0 {
+            return 1.0;
+        }
+        // unknown query tokens never match anything
+        let dist = levenshtein(query, other, |q, t| *q == Some(*t));
+        1.0 - dist as f64 / max as f64
+    }
+
+    /// Load a TM saved with [`TmIndex::save`].
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let file: TmFile =
+            serde_json::from_str(&text).map_err(|e| eyre!("{}: {e}", path.display()))?;
+        if file.version > FILE_VERSION {
+            return Err(eyre!(
+                "{}: unsupported TM file version {}",
+                path.display(),
+                file.version
+            ));
+        }
+        let mut index = Self::new();
+        index.extend(file.entries);
+        Ok(index)
+    }
+
+    /// Save entries as JSON (`{"version":1,"entries":[...]}`); indexes are rebuilt on load.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let file = TmFile {
+            version: FILE_VERSION,
+            entries: self.entries.clone(),
+        };
+        std::fs::write(path, serde_json::to_string(&file)?)?;
+        Ok(())
+    }
+}
+
+impl Extend<TmEntry> for TmIndex {
+    fn extend<I: IntoIterator<Item = TmEntry>>(&mut self, iter: I) {
+        for e in iter {
+            self.insert(e);
+        }
+    }
+}
+
+impl FromIterator<TmEntry> for TmIndex {
+    fn from_iter<I: IntoIterator<Item = TmEntry>>(iter: I) -> Self {
+        let mut index = Self::new();
+        index.extend(iter);
+        index
+    }
+}
+
+/// Lowercased words, placeholders (`{0}`, `%s`) and single punctuation marks.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = 0;
+    let push_plain = |s: &str, out: &mut Vec<String>| {
+        let mut word = String::new();
+        for ch in s.chars() {
+            if ch.is_alphanumeric() {
+                word.extend(ch.to_lowercase());
+                continue;
+            }
+            if !word.is_empty() {
+                out.push(std::mem::take(&mut word));
+            }
+            if !ch.is_whitespace() {
+                out.push(ch.to_string());
+            }
+        }
+        if !word.is_empty() {
+            out.push(word);
+        }
+    };
+    for span in placeholders::spans(text) {
+        push_plain(&text[rest..span.start], &mut out);
+        out.push(text[span.clone()].to_string());
+        rest = span.end;
+    }
+    push_plain(&text[rest..], &mut out);
+    out
+}
+
+/// Token-level similarity: `1 - edit_distance / max(token counts)`.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (tokenize(a), tokenize(b));
+    let max = a.len().max(b.len());
+    if max == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b, |x, y| x == y) as f64 / max as f64
+}
+
+fn levenshtein<A, B>(a: &[A], b: &[B], eq: impl Fn(&A, &B) -> bool) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let cost = usize::from(!eq(x, y));
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, source: &str, target: &str) -> TmEntry {
+        TmEntry {
+            key: Some(key.into()),
+            source: source.into(),
+            target: target.into(),
+            origin: None,
+        }
+    }
+
+    #[test]
+    fn tokens_keep_placeholders_whole() {
+        assert_eq!(
+            tokenize("Hello, {PAWN_nameDef}! 50%d"),
+            vec!["hello", ",", "{PAWN_nameDef}", "!", "50", "%d"]
+        );
+        assert_eq!(similarity("Hello, {0}!", "hello, {0}!"), 1.0);
+        assert!((similarity("Open the door", "Close the door") - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lookup_ranks_exact_source_key_and_fuzzy() {
+        let index: TmIndex = [
+            entry("Greeting", "Hello, {0}!", "Привет, {0}!"),
+            entry("Welcome", "Hello, {0}!", "Здравствуй, {0}!"),
+            entry("Bye", "Goodbye, see you soon", "Пока, до скорого"),
+            entry("Door", "Open the heavy door now", "Откройте тяжёлую дверь"),
+            TmEntry {
+                key: Some("Legacy".into()),
+                target: "Старое".into(),
+                ..Default::default()
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        let m = index.lookup(Some("Greeting"), "Hello, {0}!", 0.75, 5);
+        assert_eq!(m[0].kind, TmMatchKind::Exact);
+        assert_eq!(m[1].kind, TmMatchKind::Source);
+        assert_eq!(m[1].entry.target, "Здравствуй, {0}!");
+
+        // renamed key, same text
+        let m = index.lookup(Some("Renamed"), "Goodbye, see you soon", 0.75, 5);
+        assert_eq!((m[0].kind, m[0].percent()), (TmMatchKind::Source, 100));
+
+        // edited sentence
+        let m = index.lookup(None, "Open the heavy door", 0.75, 5);
+        assert_eq!(m.len(), 1);
+        assert_eq!((m[0].kind, m[0].percent()), (TmMatchKind::Fuzzy, 80));
+        assert!(index.lookup(None, "Open the heavy door", 0.9, 5).is_empty());
+
+        // same key, but the source changed too much
+        assert!(index.lookup(Some("Door"), "Close it", 0.75, 5).is_empty());
+
+        // key-only entries still match by key
+        let m = index.lookup(Some("Legacy"), "Something new", 0.75, 5);
+        assert_eq!((m[0].kind, m[0].score), (TmMatchKind::Key, 0.0));
+    }
+
+    #[test]
+    fn saves_and_loads() {
+        let dir = std::env::temp_dir().join(format!("rimloc-tm-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tm.json");
+        let mut index = TmIndex::new();
+        assert!(index.insert(entry("A", "Apple", "Яблоко")));
+        assert!(!index.insert(entry("A", "Apple", "Яблоко")));
+        assert!(!index.insert(entry("B", "Pear", " ")));
+        index.save(&path).unwrap();
+        let back = TmIndex::load(&path).unwrap();
+        assert_eq!(back.entries(), index.entries());
+        assert_eq!(
+            back.lookup(None, "apple", 0.75, 1)[0].entry.target,
+            "Яблоко"
+        );
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
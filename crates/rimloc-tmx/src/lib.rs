@@ -0,0 +1,250 @@
+//! TMX 1.4 reader/writer for exchanging RimLoc translation memories with other
+//! teams and CAT tools.
+//!
+//! Each `<tu>` holds one source/target pair. RimLoc writes the key, the mod
+//! `packageId` and the game version as `<prop>` elements (see [`PROP_KEY`] and
+//! friends); reading accepts any number of `<tuv>` variants per unit, inline codes
+//! (`<ph>`, `<bpt>`, `<hi>`…) are flattened to their text.
+
+use color_eyre::eyre::{eyre, Result};
+use std::fmt;
+use std::path::Path;
+
+/// `<prop type>` carrying the RimWorld key.
+pub const PROP_KEY: &str = "x-rimloc-key";
+/// `<prop type>` carrying the mod `packageId` from About.xml.
+pub const PROP_PACKAGE_ID: &str = "x-rimloc-package-id";
+/// `<prop type>` carrying the game version folder the pair was harvested from.
+pub const PROP_GAME_VERSION: &str = "x-rimloc-game-version";
+
+const XML_NS: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// One translation unit: properties plus language variants.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TmxUnit {
+    pub tuid: Option<String>,
+    /// `(type, value)` pairs in document order
+    pub props: Vec<(String, String)>,
+    /// `(language, text)` pairs, one per `<tuv>`
+    pub variants: Vec<(String, String)>,
+}
+
+impl TmxUnit {
+    /// Value of the first `<prop>` of the given type.
+    pub fn prop(&self, kind: &str) -> Option<&str> {
+        self.props
+            .iter()
+            .find(|(k, _)| k == kind)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// RimWorld key: the [`PROP_KEY`] property, falling back to `tuid`.
+    pub fn key(&self) -> Option<&str> {
+        self.prop(PROP_KEY).or(self.tuid.as_deref())
+    }
+
+    /// Text of the variant in `lang`. An exact tag match wins over a match on the
+    /// primary subtag only (`ru` finds `ru-RU` and the other way round).
+    pub fn text(&self, lang: &str) -> Option<&str> {
+        self.variants
+            .iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(lang))
+            .or_else(|| {
+                self.variants
+                    .iter()
+                    .find(|(l, _)| primary(l).eq_ignore_ascii_case(primary(lang)))
+            })
+            .map(|(_, t)| t.as_str())
+    }
+}
+
+/// TMX document: header source language plus units.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TmxDoc {
+    /// `srclang` of the header (`en`; `*all*` in some memories)
+    pub source_lang: String,
+    pub units: Vec<TmxUnit>,
+}
+
+impl TmxDoc {
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| eyre!("{}: {e}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let xml = roxmltree::Document::parse(text)?;
+        let root = xml.root_element();
+        if root.tag_name().name() != "tmx" {
+            return Err(eyre!(
+                "root element is <{}>, expected <tmx>",
+                root.tag_name().name()
+            ));
+        }
+        let mut doc = TmxDoc {
+            source_lang: children(root, "header")
+                .next()
+                .and_then(|h| h.attribute("srclang"))
+                .unwrap_or("")
+                .to_string(),
+            units: Vec::new(),
+        };
+        for body in children(root, "body") {
+            for tu in children(body, "tu") {
+                let props = children(tu, "prop")
+                    .filter_map(|p| {
+                        let kind = p.attribute("type")?;
+                        Some((kind.to_string(), p.text().unwrap_or("").to_string()))
+                    })
+                    .collect();
+                let variants = children(tu, "tuv")
+                    .filter_map(|tuv| {
+                        // TMX 1.4 uses xml:lang, 1.1 a plain `lang`
+                        let lang = tuv.attribute((XML_NS, "lang")).or(tuv.attribute("lang"))?;
+                        let seg = children(tuv, "seg").next()?;
+                        Some((lang.to_string(), seg_text(seg)))
+                    })
+                    .collect();
+                doc.units.push(TmxUnit {
+                    tuid: tu.attribute("tuid").map(str::to_string),
+                    props,
+                    variants,
+                });
+            }
+        }
+        Ok(doc)
+    }
+}
+
+impl fmt::Display for TmxDoc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(f, r#"<tmx version="1.4">"#)?;
+        writeln!(
+            f,
+            r#"  <header creationtool="RimLoc" creationtoolversion="{}" segtype="sentence" o-tmf="RimLoc" adminlang="en" srclang="{}" datatype="plaintext"/>"#,
+            env!("CARGO_PKG_VERSION"),
+            escape(&self.source_lang)
+        )?;
+        writeln!(f, "  <body>")?;
+        for u in &self.units {
+            match &u.tuid {
+                Some(id) => writeln!(f, r#"    <tu tuid="{}">"#, escape(id))?,
+                None => writeln!(f, "    <tu>")?,
+            }
+            for (kind, value) in &u.props {
+                writeln!(
+                    f,
+                    r#"      <prop type="{}">{}</prop>"#,
+                    escape(kind),
+                    escape(value)
+                )?;
+            }
+            for (lang, text) in &u.variants {
+                writeln!(
+                    f,
+                    r#"      <tuv xml:lang="{}"><seg>{}</seg></tuv>"#,
+                    escape(lang),
+                    escape(text)
+                )?;
+            }
+            writeln!(f, "    </tu>")?;
+        }
+        writeln!(f, "  </body>")?;
+        writeln!(f, "</tmx>")
+    }
+}
+
+fn primary(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn children<'a, 'i>(
+    node: roxmltree::Node<'a, 'i>,
+    name: &'static str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'i>> {
+    node.children()
+        .filter(move |c| c.is_element() && c.tag_name().name() == name)
+}
+
+/// Segment text; inline elements keep their content (native code for `ph`/`bpt`/`ept`,
+/// translatable text for `hi`/`sub`).
+fn seg_text(node: roxmltree::Node) -> String {
+    node.descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_units_with_props() {
+        let doc = TmxDoc {
+            source_lang: "en".into(),
+            units: vec![TmxUnit {
+                tuid: Some("Greeting".into()),
+                props: vec![
+                    (PROP_KEY.into(), "Greeting".into()),
+                    (PROP_PACKAGE_ID.into(), "author.mod".into()),
+                    (PROP_GAME_VERSION.into(), "1.5".into()),
+                ],
+                variants: vec![
+                    ("en".into(), "Hello, {0} & <b>".into()),
+                    ("ru".into(), "Привет, {0} и <b>".into()),
+                ],
+            }],
+        };
+        let text = doc.to_string();
+        assert!(text.contains(r#"<prop type="x-rimloc-package-id">author.mod</prop>"#));
+        assert!(text.contains(r#"<tuv xml:lang="en"><seg>Hello, {0} &amp; &lt;b&gt;</seg></tuv>"#));
+        let back = TmxDoc::parse(&text).unwrap();
+        assert_eq!(back, doc);
+        assert_eq!(back.units[0].key(), Some("Greeting"));
+    }
+
+    #[test]
+    fn reads_foreign_memories() {
+        let text = r#"<?xml version="1.0"?>
+<tmx version="1.4">
+  <header srclang="EN-US" segtype="sentence" o-tmf="x" adminlang="en" datatype="plaintext" creationtool="x" creationtoolversion="1"/>
+  <body>
+    <tu>
+      <tuv xml:lang="EN-US"><seg>Hello, <ph x="1">{0}</ph>!</seg></tuv>
+      <tuv xml:lang="ru-RU"><seg><hi>Привет</hi>, <ph x="1">{0}</ph>!</seg></tuv>
+    </tu>
+    <tu tuid="Bye"><tuv lang="en"><seg>Bye</seg></tuv></tu>
+  </body>
+</tmx>"#;
+        let doc = TmxDoc::parse(text).unwrap();
+        assert_eq!(doc.source_lang, "EN-US");
+        let tu = &doc.units[0];
+        assert_eq!(tu.key(), None);
+        assert_eq!(tu.text("en"), Some("Hello, {0}!"));
+        assert_eq!(tu.text("ru"), Some("Привет, {0}!"));
+        assert_eq!(tu.text("de"), None);
+        assert_eq!(doc.units[1].key(), Some("Bye"));
+        assert_eq!(doc.units[1].text("en"), Some("Bye"));
+    }
+}
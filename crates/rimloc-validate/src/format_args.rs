@@ -0,0 +1,239 @@
+//! RimWorld format arguments in braces: positional `{0}`, C# formats `{0:F1}`,
+//! named resolvers `{PAWN_labelShort}` / `{0_pronoun}` and gender switches
+//! `{PAWN_gender ? him : her}`.
+
+use std::collections::BTreeSet;
+
+/// Suffixes the game's grammar resolver understands after `ARG_`. Other lower-case
+/// suffixes (resolvers added by mods) keep the whole name as a plain argument.
+pub const KNOWN_SUFFIXES: &[&str] = &[
+    "label",
+    "labelShort",
+    "labelPlural",
+    "labelPluralDef",
+    "labelPluralIndef",
+    "definite",
+    "indefinite",
+    "pronoun",
+    "possessive",
+    "objective",
+    "gender",
+    "humanlike",
+    "name",
+    "nameDef",
+    "nameIndef",
+    "nameFull",
+    "nameFullDef",
+    "kind",
+    "kindDef",
+    "kindIndef",
+    "kindPlural",
+    "kindPluralDef",
+    "kindPluralIndef",
+    "kindBase",
+    "kindBaseDef",
+    "kindBaseIndef",
+    "kindBasePlural",
+    "kindBasePluralDef",
+    "kindBasePluralIndef",
+    "factionName",
+    "factionPawnSingular",
+    "factionPawnSingularDef",
+    "factionPawnSingularIndef",
+    "factionPawnsPlural",
+    "factionPawnsPluralDef",
+    "factionPawnsPluralIndef",
+    "factionRoyalFavorLabel",
+    "pawnSingular",
+    "pawnsPlural",
+    "leaderTitle",
+    "lifeStage",
+    "lifeStageDef",
+    "lifeStageIndef",
+    "lifeStageAdjective",
+    "title",
+    "titleDef",
+    "titleIndef",
+    "bestRoyalTitle",
+    "bestRoyalTitleDef",
+    "bestRoyalTitleIndef",
+    "royalTitleInCurrentFaction",
+    "royalTitleInCurrentFactionDef",
+    "royalTitleInCurrentFactionIndef",
+    "relationInfo",
+    "relationInfoInParentheses",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatArg {
+    /// Text between the braces
+    pub raw: String,
+    /// `PAWN` in `{PAWN_labelShort}`, `0` in `{0}`
+    pub arg: String,
+    /// `labelShort` in `{PAWN_labelShort}`
+    pub suffix: Option<String>,
+    /// `F1` in `{0:F1}`
+    pub format: Option<String>,
+    /// Options of a gender switch, in order (male, female[, neuter])
+    pub options: Vec<String>,
+}
+
+/// Tokenize braces in `text`. Returns the arguments that parsed and a problem
+/// description for each one that did not.
+pub fn parse(text: &str) -> (Vec<FormatArg>, Vec<String>) {
+    let mut args = Vec::new();
+    let mut problems = Vec::new();
+    let mut rest = text;
+    while let Some(i) = rest.find(['{', '}']) {
+        if rest[i..].starts_with('}') {
+            problems.push("Unmatched closing brace".to_string());
+            rest = &rest[i + 1..];
+            continue;
+        }
+        let after = &rest[i + 1..];
+        let Some(end) = after.find(['{', '}']) else {
+            problems.push("Unmatched opening brace".to_string());
+            break;
+        };
+        if after[end..].starts_with('{') {
+            problems.push("Nested braces".to_string());
+            rest = &after[end..];
+            continue;
+        }
+        match parse_inner(&after[..end]) {
+            Ok(arg) => args.push(arg),
+            Err(problem) => problems.push(problem),
+        }
+        rest = &after[end + 1..];
+    }
+    (args, problems)
+}
+
+/// Problems of [`parse`] only.
+pub fn problems(text: &str) -> Vec<String> {
+    parse(text).1
+}
+
+/// Arguments a text refers to, as `{ARG}` regardless of suffix or switch, plus
+/// printf-style placeholders as written. A translation may pick other suffixes
+/// (or a gender switch) but should use the same set.
+pub fn usage(text: &str) -> BTreeSet<String> {
+    let mut out: BTreeSet<String> = rimloc_core::placeholders::extract(text)
+        .into_iter()
+        .filter(|p| p.starts_with('%'))
+        .collect();
+    out.extend(parse(text).0.into_iter().map(|a| format!("{{{}}}", a.arg)));
+    out
+}
+
+fn parse_inner(raw: &str) -> Result<FormatArg, String> {
+    let inner = raw.trim();
+    if inner.is_empty() {
+        return Err("Empty brace placeholder".to_string());
+    }
+    if let Some((head, options)) = inner.split_once('?') {
+        let mut arg = parse_name(raw, head.trim())?;
+        if arg.suffix.as_deref() != Some("gender") {
+            return Err(format!("Only _gender switches are supported: {{{raw}}}"));
+        }
+        arg.options = options.split(':').map(|o| o.trim().to_string()).collect();
+        if !(2..=3).contains(&arg.options.len()) {
+            return Err(format!(
+                "Gender switch {{{raw}}} needs 2 or 3 options separated by ':'"
+            ));
+        }
+        return Ok(arg);
+    }
+    let (name, format) = match inner.split_once(':') {
+        Some((name, format)) => (name.trim(), Some(format.trim().to_string())),
+        None => (inner, None),
+    };
+    let mut arg = parse_name(raw, name)?;
+    arg.format = format;
+    Ok(arg)
+}
+
+fn parse_name(raw: &str, name: &str) -> Result<FormatArg, String> {
+    let bare = name.strip_prefix('$').unwrap_or(name);
+    if bare.is_empty() || !bare.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid brace placeholder {{{raw}}}"));
+    }
+    // named arguments are `0` or upper case (`PAWN`, `ROYAL_TITLE`) followed by a
+    // lower-case suffix; `{name_1}` and `{MY_KEY}` are plain names
+    let split = bare.rsplit_once('_').filter(|(arg, suffix)| {
+        !arg.is_empty()
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase() || c == '_')
+            && suffix.starts_with(|c: char| c.is_ascii_lowercase())
+    });
+    let (arg, suffix) = match split {
+        Some((arg, suffix)) if KNOWN_SUFFIXES.contains(&suffix) => {
+            (arg.to_string(), Some(suffix.to_string()))
+        }
+        _ => (bare.to_string(), None),
+    };
+    Ok(FormatArg {
+        raw: raw.to_string(),
+        arg,
+        suffix,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_splits_known_suffixes_only() {
+        let (args, problems) = parse("{PAWN_labelShort} {0:F1} {PAWN_customTitle} {MY_KEY}");
+        assert!(problems.is_empty(), "{problems:?}");
+        let got: Vec<(&str, Option<&str>, Option<&str>)> = args
+            .iter()
+            .map(|a| (a.arg.as_str(), a.suffix.as_deref(), a.format.as_deref()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("PAWN", Some("labelShort"), None),
+                ("0", None, Some("F1")),
+                ("PAWN_customTitle", None, None),
+                ("MY_KEY", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_reads_gender_switches() {
+        let (args, problems) = parse("потерял{PAWN_gender ? : а} {0_gender ? он : она : оно}");
+        assert!(problems.is_empty(), "{problems:?}");
+        assert_eq!(args[0].options, vec!["", "а"]);
+        assert_eq!(args[1].options, vec!["он", "она", "оно"]);
+    }
+
+    #[test]
+    fn problems_report_broken_braces() {
+        assert_eq!(
+            problems("{0_gender ? him} {PAWN_label ? a : b} {} {a b}"),
+            vec![
+                "Gender switch {0_gender ? him} needs 2 or 3 options separated by ':'",
+                "Only _gender switches are supported: {PAWN_label ? a : b}",
+                "Empty brace placeholder",
+                "Invalid brace placeholder {a b}",
+            ]
+        );
+        assert_eq!(problems("a } b"), vec!["Unmatched closing brace"]);
+        assert_eq!(problems("{0 {1}"), vec!["Nested braces"]);
+        assert_eq!(problems("{0"), vec!["Unmatched opening brace"]);
+    }
+
+    #[test]
+    fn usage_ignores_suffixes_and_switches() {
+        assert_eq!(
+            usage("{PAWN_labelShort} lost {PAWN_possessive} hat, %d left"),
+            usage("%d: {PAWN_labelShort} потерял{PAWN_gender ? : а} шляпу")
+        );
+        assert_ne!(usage("{0} gave {1} to {2}"), usage("{0} отдал {1}"));
+    }
+}
@@ -0,0 +1,204 @@
+//! RimWorld grammar rules as found in `rulesStrings` lists, one per line:
+//! `r_logentry->[INITIATOR_definite] hit [RECIPIENT_definite]`, optionally with
+//! conditions after the name (`r_logentry(p=2)->...`).
+
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rule {
+    pub name: String,
+    pub body: String,
+    /// `[symbol]` references in the body, in order
+    pub symbols: Vec<String>,
+}
+
+/// Keys holding grammar rules: `Pack.rulePack.rulesStrings` or one of its
+/// `.N` items.
+pub fn is_rules_key(key: &str) -> bool {
+    key.split('.').any(|s| s == "rulesStrings")
+}
+
+/// Parse every non-empty line of `text` as a rule. Returns the rules that parsed
+/// and a problem description for each one that did not.
+pub fn parse(text: &str) -> (Vec<Rule>, Vec<String>) {
+    let mut rules = Vec::new();
+    let mut problems = Vec::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match parse_rule(line) {
+            Ok(rule) => rules.push(rule),
+            Err(problem) => problems.push(problem),
+        }
+    }
+    (rules, problems)
+}
+
+/// Problems of [`parse`] only.
+pub fn problems(text: &str) -> Vec<String> {
+    parse(text).1
+}
+
+/// Differences a translation must not have: a `[symbol]` of the source is not
+/// used, an entry rule of the source (one no other rule references, such as
+/// `r_logentry`) is missing, a symbol is neither in the source nor defined by
+/// the translation, or a rule is neither in the source nor referenced.
+pub fn compare(source: &str, translation: &str) -> Vec<String> {
+    let (src, _) = parse(source);
+    let (tgt, _) = parse(translation);
+    let src_defined: BTreeSet<&str> = src.iter().map(|r| r.name.as_str()).collect();
+    let src_used: BTreeSet<&str> = symbols(&src);
+    let tgt_defined: BTreeSet<&str> = tgt.iter().map(|r| r.name.as_str()).collect();
+    let tgt_used: BTreeSet<&str> = symbols(&tgt);
+
+    let mut out = Vec::new();
+    for s in src_used.difference(&tgt_used) {
+        out.push(format!("Symbol [{s}] from source is not used"));
+    }
+    for name in src_defined.difference(&src_used) {
+        if !tgt_defined.contains(name) {
+            out.push(format!("Rule '{name}' from source is missing"));
+        }
+    }
+    for s in &tgt_used {
+        if !src_used.contains(s) && !src_defined.contains(s) && !tgt_defined.contains(s) {
+            out.push(format!(
+                "Unknown symbol [{s}]: not in source and not defined"
+            ));
+        }
+    }
+    for name in &tgt_defined {
+        if !src_defined.contains(name) && !tgt_used.contains(name) {
+            out.push(format!(
+                "Rule '{name}' is not in source and no rule references it"
+            ));
+        }
+    }
+    out
+}
+
+fn symbols(rules: &[Rule]) -> BTreeSet<&str> {
+    rules
+        .iter()
+        .flat_map(|r| r.symbols.iter().map(String::as_str))
+        .collect()
+}
+
+fn parse_rule(line: &str) -> Result<Rule, String> {
+    let Some((head, body)) = line.split_once("->") else {
+        return Err(format!("Rule '{line}' has no '->' separator"));
+    };
+    let head = head.trim();
+    let name = match head.split_once('(') {
+        Some((name, params)) if params.ends_with(')') => name.trim(),
+        Some(_) => return Err(format!("Unclosed '(' in rule name '{head}'")),
+        None => head,
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid rule name '{head}'"));
+    }
+
+    let mut symbols = Vec::new();
+    let mut rest = body;
+    while let Some(i) = rest.find(['[', ']']) {
+        if rest[i..].starts_with(']') {
+            return Err(format!("Unbalanced ']' in rule '{name}'"));
+        }
+        let after = &rest[i + 1..];
+        let end = match after.find(['[', ']']) {
+            Some(end) if after[end..].starts_with(']') => end,
+            _ => return Err(format!("Unbalanced '[' in rule '{name}'")),
+        };
+        let symbol = after[..end].trim();
+        if symbol.is_empty()
+            || !symbol
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(format!("Invalid symbol [{symbol}] in rule '{name}'"));
+        }
+        symbols.push(symbol.to_string());
+        rest = &after[end + 1..];
+    }
+    Ok(Rule {
+        name: name.to_string(),
+        body: body.to_string(),
+        symbols,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_names_conditions_and_symbols() {
+        let (rules, problems) =
+            parse("r_logentry(p=2)->[INITIATOR_definite] [hitverb] [RECIPIENT_definite].\n\nhitverb->smacked");
+        assert!(problems.is_empty(), "{problems:?}");
+        assert_eq!(rules[0].name, "r_logentry");
+        assert_eq!(
+            rules[0].symbols,
+            vec!["INITIATOR_definite", "hitverb", "RECIPIENT_definite"]
+        );
+        assert_eq!(rules[1].name, "hitverb");
+        assert!(rules[1].symbols.is_empty());
+    }
+
+    #[test]
+    fn problems_report_broken_rules() {
+        assert_eq!(
+            problems(concat!(
+                "r_logentry-[INITIATOR_definite] hit\n",
+                "r_logentry(p=2->x\n",
+                "bad name->x\n",
+                "r_a->[open\n",
+                "r_b->close]\n",
+                "r_c->[two words]",
+            )),
+            vec![
+                "Rule 'r_logentry-[INITIATOR_definite] hit' has no '->' separator",
+                "Unclosed '(' in rule name 'r_logentry(p=2'",
+                "Invalid rule name 'bad name'",
+                "Unbalanced '[' in rule 'r_a'",
+                "Unbalanced ']' in rule 'r_b'",
+                "Invalid symbol [two words] in rule 'r_c'",
+            ]
+        );
+    }
+
+    #[test]
+    fn compare_checks_translation_against_source() {
+        let source = concat!(
+            "r_logentry->[INITIATOR_definite] hit [RECIPIENT_definite].\n",
+            "r_logentry(p=2)->[INITIATOR_definite] [hitverb] [RECIPIENT_definite].\n",
+            "hitverb->smacked",
+        );
+        let renamed = concat!(
+            "r_logentry->[INITIATOR_definite] ударил [RECIPIENT_definite].\n",
+            "r_logentry(p=2)->[INITIATOR_definite] [hitverb] [RECIPIENT_definte].\n",
+            "hitverb->врезал\n",
+            "slang->вмазал",
+        );
+        assert_eq!(
+            compare(source, renamed),
+            vec![
+                "Unknown symbol [RECIPIENT_definte]: not in source and not defined",
+                "Rule 'slang' is not in source and no rule references it",
+            ]
+        );
+        assert_eq!(
+            compare(source, "hitverb->врезал"),
+            vec![
+                "Symbol [INITIATOR_definite] from source is not used",
+                "Symbol [RECIPIENT_definite] from source is not used",
+                "Symbol [hitverb] from source is not used",
+                "Rule 'r_logentry' from source is missing",
+            ]
+        );
+        // new rules are fine as long as something references them
+        assert!(compare(
+            "r_logentry->[A] hit",
+            "r_logentry->[A] [verb]\nverb->ударил"
+        )
+        .is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::OnceLock;
 
@@ -7,6 +7,11 @@ pub use rimloc_core::parse_simple_po as parse_po_string;
 
 use rimloc_core::{Result as CoreResult, TransUnit};
 
+pub mod format_args;
+pub mod grammar;
+pub mod literals;
+pub mod rich_text;
+
 #[derive(Debug, Clone)]
 pub struct ValidationMessage {
     pub kind: String,
@@ -16,12 +21,46 @@ pub struct ValidationMessage {
     pub message: String,
 }
 
-/// Validator that reports duplicate keys per file using scanned TransUnits.
+/// How serious a message kind is. Every kind has one fixed severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// Severity of a message kind: things the game renders broken are errors, likely
+/// translation slips are warnings, layout hints are info. Kinds without their own
+/// grade (`duplicate`, `empty`, `placeholder-check`, ...) are warnings.
+pub fn severity_of(kind: &str) -> Severity {
+    match kind {
+        "rich-text" | "grammar" | "url-mismatch" => Severity::Error,
+        "newline-mismatch" | "rich-text-unknown" => Severity::Info,
+        _ => Severity::Warning,
+    }
+}
+
+impl ValidationMessage {
+    pub fn severity(&self) -> Severity {
+        severity_of(&self.kind)
+    }
+}
+
+/// Validator that reports duplicate keys, empty values, placeholder, grammar rule and
+/// rich-text tag problems using scanned TransUnits.
 pub fn validate(units: &[TransUnit]) -> CoreResult<Vec<ValidationMessage>> {
     static RE_PCT: OnceLock<Regex> = OnceLock::new();
-    static RE_BRACE_INNER: OnceLock<Regex> = OnceLock::new();
     let _re_pct = RE_PCT.get_or_init(|| Regex::new(r"%(\d+\$)?0?\d*[sdif]").unwrap());
-    let re_brace_inner = RE_BRACE_INNER.get_or_init(|| Regex::new(r"^\$?[A-Za-z0-9_]+$").unwrap());
 
     let mut by_file_key: HashMap<(String, String), Vec<Option<usize>>> = HashMap::new();
     for u in units {
@@ -61,57 +100,14 @@ pub fn validate(units: &[TransUnit]) -> CoreResult<Vec<ValidationMessage>> {
                     placeholder_msg_emitted = true;
                 }
 
-                // 2) Brace-style placeholders: ensure balanced braces and non-empty names like {NAME} / {0}
-                let mut depth = 0usize;
-                let mut last_open: Option<usize> = None;
-                let mut brace_error: Option<&'static str> = None;
-                for (i, ch) in text.char_indices() {
-                    match ch {
-                        '{' => {
-                            if depth == 0 {
-                                last_open = Some(i);
-                            }
-                            depth += 1;
-                            // very naive: we don't allow nested braces for our use case
-                            if depth > 1 {
-                                brace_error = Some("Nested braces");
-                                break;
-                            }
-                        }
-                        '}' => {
-                            if depth == 0 {
-                                brace_error = Some("Unmatched closing brace");
-                                break;
-                            }
-                            if depth == 1 {
-                                if let Some(lo) = last_open {
-                                    let inner = text[lo + 1..i].trim();
-                                    if inner.is_empty() {
-                                        brace_error = Some("Empty brace placeholder");
-                                        break;
-                                    }
-                                    // Only allow {$var}, {VAR}, {0}, {name_1}
-                                    if !re_brace_inner.is_match(inner) {
-                                        brace_error = Some("Invalid brace placeholder");
-                                        break;
-                                    }
-                                }
-                            }
-                            depth -= 1;
-                        }
-                        _ => {}
-                    }
-                }
-                if brace_error.is_none() && depth > 0 {
-                    brace_error = Some("Unmatched opening brace");
-                }
-                if let Some(msg) = brace_error {
+                // 2) Brace-style arguments: {0}, {0:F1}, {PAWN_labelShort}, {PAWN_gender ? he : she}
+                for problem in format_args::problems(text) {
                     msgs.push(ValidationMessage {
                         kind: "placeholder-check".to_string(),
                         key: u.key.clone(),
                         path: u.path.to_string_lossy().to_string(),
                         line: u.line,
-                        message: msg.to_string(),
+                        message: problem,
                     });
                     placeholder_msg_emitted = true;
                 }
@@ -128,12 +124,40 @@ pub fn validate(units: &[TransUnit]) -> CoreResult<Vec<ValidationMessage>> {
                         message: "Placeholders present".to_string(),
                     });
                 }
+
+                if grammar::is_rules_key(&u.key) {
+                    for problem in grammar::problems(text) {
+                        msgs.push(ValidationMessage {
+                            kind: "grammar".to_string(),
+                            key: u.key.clone(),
+                            path: u.path.to_string_lossy().to_string(),
+                            line: u.line,
+                            message: problem,
+                        });
+                    }
+                }
+                for problem in rich_text::problems(text) {
+                    msgs.push(ValidationMessage {
+                        kind: "rich-text".to_string(),
+                        key: u.key.clone(),
+                        path: u.path.to_string_lossy().to_string(),
+                        line: u.line,
+                        message: problem,
+                    });
+                }
             }
         }
     }
 
+    // keys repeated in one file and also found in another are reported by the
+    // cross-file pass, with every location
+    let across = keyed_duplicates_across_files(units);
+    let across_files: HashSet<(&str, &str)> = across
+        .iter()
+        .map(|m| (m.path.as_str(), m.key.as_str()))
+        .collect();
     for ((path, key), lines) in by_file_key {
-        if lines.len() > 1 {
+        if lines.len() > 1 && !across_files.contains(&(path.as_str(), key.as_str())) {
             // duplicate detected in the same file
             let line = lines.into_iter().flatten().next();
             msgs.push(ValidationMessage {
@@ -145,10 +169,122 @@ pub fn validate(units: &[TransUnit]) -> CoreResult<Vec<ValidationMessage>> {
             });
         }
     }
+    msgs.extend(across);
 
     Ok(msgs)
 }
 
+/// Where a Keyed file sits: language folder and the version folder it loads from.
+struct KeyedLocation<'a> {
+    unit: &'a TransUnit,
+    path: String,
+    lang: String,
+    /// `Some("1.5")` for `1.5/Languages/...`; `None` for folders loaded with every version
+    version: Option<String>,
+}
+
+fn keyed_location(unit: &TransUnit) -> Option<KeyedLocation<'_>> {
+    let path = unit.path.to_string_lossy().to_string();
+    let parts: Vec<&str> = path.split(['/', '\\']).collect();
+    let i = parts.iter().rposition(|p| *p == "Languages")?;
+    if parts.get(i + 2) != Some(&"Keyed") {
+        return None;
+    }
+    let version = i
+        .checked_sub(1)
+        .map(|j| parts[j])
+        .filter(|p| rimloc_core::is_version_folder(p))
+        .map(|p| p.trim_start_matches(['v', 'V']).to_string());
+    Some(KeyedLocation {
+        unit,
+        lang: parts[i + 1].to_string(),
+        path,
+        version,
+    })
+}
+
+/// Keyed keys defined in more than one file of the same language. RimWorld loads
+/// version folders before the mod root and keeps the first value it sees, logging
+/// "Duplicate keyed translation key" for the rest; a key in `1.4/` and `1.5/` only
+/// clashes with the root, not with each other. Every location is reported.
+pub fn keyed_duplicates_across_files(units: &[TransUnit]) -> Vec<ValidationMessage> {
+    let mut by_lang_key: HashMap<(String, String), Vec<KeyedLocation>> = HashMap::new();
+    for loc in units.iter().filter_map(keyed_location) {
+        by_lang_key
+            .entry((loc.lang.clone(), loc.unit.key.clone()))
+            .or_default()
+            .push(loc);
+    }
+
+    let mut msgs = Vec::new();
+    let mut groups: Vec<_> = by_lang_key.into_iter().collect();
+    groups.sort_by(|a, b| a.0.cmp(&b.0));
+    for ((lang, key), mut locs) in groups {
+        // load order: version folder first, then shared folders; files by path
+        locs.sort_by(|a, b| {
+            (a.version.is_none(), &a.path, a.unit.line).cmp(&(
+                b.version.is_none(),
+                &b.path,
+                b.unit.line,
+            ))
+        });
+        let mut versions: Vec<Option<&str>> = locs
+            .iter()
+            .filter_map(|l| l.version.as_deref())
+            .map(Some)
+            .collect();
+        versions.sort();
+        versions.dedup();
+        if versions.is_empty() {
+            versions.push(None);
+        }
+
+        let mut reported: Vec<Vec<usize>> = Vec::new();
+        for version in versions {
+            let loaded: Vec<usize> = (0..locs.len())
+                .filter(|&i| locs[i].version.is_none() || locs[i].version.as_deref() == version)
+                .collect();
+            let first = &locs[loaded[0]];
+            if loaded.iter().all(|&i| locs[i].path == first.path) || reported.contains(&loaded) {
+                continue;
+            }
+            let scope = match version {
+                Some(v) => format!("{lang} ({v})"),
+                None => lang.clone(),
+            };
+            let kept = format!(
+                "{}:{} \"{}\"",
+                first.path,
+                first.unit.line.unwrap_or(0),
+                first.unit.source.as_deref().unwrap_or_default()
+            );
+            for (n, &i) in loaded.iter().enumerate() {
+                let loc = &locs[i];
+                let message = if n == 0 {
+                    format!(
+                        "Duplicate keyed key in {} places of {scope}; the game keeps this value",
+                        loaded.len()
+                    )
+                } else {
+                    format!(
+                        "Duplicate keyed key in {} places of {scope}; ignored, the game keeps {kept}",
+                        loaded.len()
+                    )
+                };
+                msgs.push(ValidationMessage {
+                    kind: "duplicate".to_string(),
+                    key: key.clone(),
+                    path: loc.path.clone(),
+                    line: loc.unit.line,
+                    message,
+                });
+            }
+            reported.push(loaded);
+        }
+    }
+    msgs
+}
+
 /// Temporary minimalist scanner used by CLI integration tests that only
 /// assert CSV headers; returns an empty list of units.
 /// TODO: implement full XML scan or integrate with validate crate.
@@ -0,0 +1,183 @@
+//! Literal tokens a translation should carry over unchanged: numbers, percent
+//! values, URLs, hotkeys and `\n` escapes. Placeholders, `{…}` arguments and rich-text tags
+//! are skipped, so `%1$d`, `{0}` and `<size=20>` do not count as numbers.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Literals {
+    /// Normalized: grouping separators dropped, decimal comma as dot (`1 000` → `1000`, `0,5` → `0.5`)
+    pub numbers: Vec<String>,
+    /// Percent values without the sign, normalized like numbers (`25%` → `25`)
+    pub percents: Vec<String>,
+    pub urls: Vec<String>,
+    /// Key combinations, lowercased with modifiers sorted (`Shift + Ctrl+S` → `ctrl+shift+s`)
+    pub hotkeys: Vec<String>,
+    /// Count of the two-character `\n` escape
+    pub newline_escapes: usize,
+}
+
+static RE_SKIP: OnceLock<Regex> = OnceLock::new();
+static RE_URL: OnceLock<Regex> = OnceLock::new();
+static RE_HOTKEY: OnceLock<Regex> = OnceLock::new();
+static RE_PERCENT: OnceLock<Regex> = OnceLock::new();
+static RE_NUMBER: OnceLock<Regex> = OnceLock::new();
+
+const NUMBER: &str = r"\d{1,3}(?:[ \u{a0}\u{202f},.]\d{3})+\b|\d+(?:[.,]\d+)?";
+
+fn re_skip() -> &'static Regex {
+    RE_SKIP.get_or_init(|| {
+        Regex::new(r"\{[^{}]*\}|%(\d+\$)?0?\d*[sdif]|(?:<|&lt;)/?[A-Za-z][^<>&]*?(?:>|&gt;)")
+            .unwrap()
+    })
+}
+
+fn re_url() -> &'static Regex {
+    RE_URL.get_or_init(|| Regex::new(r#"(?i)\b(?:https?://|www\.)[^\s<>"'\]\)]+"#).unwrap())
+}
+
+fn re_hotkey() -> &'static Regex {
+    // a modifier chain and one key; mouse buttons (`Ctrl+click`) are left to the
+    // translator
+    RE_HOTKEY.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b((?:(?:ctrl|control|shift|alt|cmd|command)\s*\+\s*)+)(f(?:1[0-2]|[1-9])|tab|enter|return|space|esc|escape|delete|del|insert|home|end|backspace|pageup|pagedown|up|down|left|right|[a-z0-9])\b",
+        )
+        .unwrap()
+    })
+}
+
+fn normalize_hotkey(modifiers: &str, key: &str) -> String {
+    let mut parts: Vec<String> = modifiers
+        .split('+')
+        .map(|m| m.trim().to_ascii_lowercase())
+        .filter(|m| !m.is_empty())
+        .map(|m| match m.as_str() {
+            "control" => "ctrl".to_string(),
+            "command" => "cmd".to_string(),
+            _ => m,
+        })
+        .collect();
+    parts.sort();
+    parts.dedup();
+    parts.push(key.to_ascii_lowercase());
+    parts.join("+")
+}
+
+fn re_percent() -> &'static Regex {
+    RE_PERCENT.get_or_init(|| Regex::new(&format!(r"({NUMBER})[ \u{{a0}}\u{{202f}}]?%")).unwrap())
+}
+
+fn re_number() -> &'static Regex {
+    RE_NUMBER.get_or_init(|| Regex::new(NUMBER).unwrap())
+}
+
+fn normalize_number(raw: &str) -> String {
+    let grouped = raw.len() > 4
+        && raw
+            .split([' ', '\u{a0}', '\u{202f}', ',', '.'])
+            .skip(1)
+            .all(|g| g.len() == 3)
+        && raw.contains([' ', '\u{a0}', '\u{202f}', ',', '.']);
+    if grouped {
+        raw.chars().filter(char::is_ascii_digit).collect()
+    } else {
+        raw.replace(',', ".")
+    }
+}
+
+pub fn extract(text: &str) -> Literals {
+    let mut out = Literals {
+        newline_escapes: text.matches("\\n").count(),
+        ..Default::default()
+    };
+    let text = text.replace("\\n", " ");
+    let mut rest = re_skip().replace_all(&text, " ").into_owned();
+
+    for m in re_url().find_iter(&rest) {
+        let url = m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        out.urls.push(url.to_string());
+    }
+    rest = re_url().replace_all(&rest, " ").into_owned();
+
+    for cap in re_hotkey().captures_iter(&rest) {
+        out.hotkeys.push(normalize_hotkey(&cap[1], &cap[2]));
+    }
+    rest = re_hotkey().replace_all(&rest, " ").into_owned();
+
+    for cap in re_percent().captures_iter(&rest) {
+        out.percents.push(normalize_number(&cap[1]));
+    }
+    rest = re_percent().replace_all(&rest, " ").into_owned();
+
+    for m in re_number().find_iter(&rest) {
+        out.numbers.push(normalize_number(m.as_str()));
+    }
+    out.numbers.sort();
+    out.percents.sort();
+    out.urls.sort();
+    out.hotkeys.sort();
+    out
+}
+
+/// Items of `a` not matched in `b`, counting repeats (both sorted).
+pub fn missing<'a>(a: &'a [String], b: &[String]) -> Vec<&'a str> {
+    let mut b = b.to_vec();
+    let mut out = Vec::new();
+    for item in a {
+        match b.iter().position(|x| x == item) {
+            Some(i) => {
+                b.remove(i);
+            }
+            None => out.push(item.as_str()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_normalizes_numbers_and_skips_markup() {
+        let got = extract(
+            "Costs 1,000 silver, {0} left, &lt;size=20&gt;big&lt;/size&gt;, %1$d, half is 0.5",
+        );
+        assert_eq!(got.numbers, vec!["0.5", "1000"]);
+        assert_eq!(
+            extract("Стоит 1 000 серебра, половина — 0,5").numbers,
+            got.numbers
+        );
+        assert!(got.percents.is_empty() && got.urls.is_empty());
+    }
+
+    #[test]
+    fn extract_reads_percents_urls_and_newlines() {
+        let got = extract("25% chance, 10 % more.\\nSee https://example.com/wiki.");
+        assert_eq!(got.percents, vec!["10", "25"]);
+        assert_eq!(got.urls, vec!["https://example.com/wiki"]);
+        assert_eq!(got.newline_escapes, 1);
+        assert!(got.numbers.is_empty(), "{:?}", got.numbers);
+    }
+
+    #[test]
+    fn extract_reads_hotkeys() {
+        let got = extract("Shift + Ctrl+S saves, Alt+F4 quits, Ctrl+click selects, press Ctrl+1");
+        assert_eq!(got.hotkeys, vec!["alt+f4", "ctrl+1", "ctrl+shift+s"]);
+        assert!(got.numbers.is_empty(), "{:?}", got.numbers);
+        assert_eq!(
+            extract("Ctrl+Shift+S сохраняет, Alt + F4 — выход, Control+1").hotkeys,
+            got.hotkeys
+        );
+    }
+
+    #[test]
+    fn missing_counts_repeats() {
+        let a: Vec<String> = ["2", "2", "3"].map(String::from).to_vec();
+        let b: Vec<String> = ["2", "3", "4"].map(String::from).to_vec();
+        assert_eq!(missing(&a, &b), vec!["2"]);
+        assert_eq!(missing(&b, &a), vec!["4"]);
+    }
+}
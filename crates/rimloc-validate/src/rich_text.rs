@@ -0,0 +1,202 @@
+//! Unity rich-text tags (`<color=#ff0000>`, `<b>`, `<i>`, `<size=20>`) as used in
+//! RimWorld strings. Texts may hold them unescaped (as `scan_keyed_xml` returns them)
+//! or still escaped (`&lt;b&gt;`); both forms are understood.
+
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+/// Tags the game renders; `quad` has no closing tag.
+pub const KNOWN_TAGS: [&str; 6] = ["b", "i", "color", "size", "material", "quad"];
+
+static RE_TAG: OnceLock<Regex> = OnceLock::new();
+static RE_COLOR: OnceLock<Regex> = OnceLock::new();
+
+fn re_tag() -> &'static Regex {
+    RE_TAG.get_or_init(|| {
+        Regex::new(
+            r"(?:<|&lt;)(/?)([A-Za-z][A-Za-z0-9_]*)(?:\s*=\s*((?:[^<>&]|&[^lg])*?))?\s*(?:>|&gt;)",
+        )
+        .unwrap()
+    })
+}
+
+fn re_color() -> &'static Regex {
+    RE_COLOR.get_or_init(|| {
+        Regex::new(r"^(?:#(?:[0-9A-Fa-f]{3,4}|[0-9A-Fa-f]{6}|[0-9A-Fa-f]{8})|[A-Za-z]+)$").unwrap()
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// Lowercased tag name
+    pub name: String,
+    /// `#ff0000` in `<color=#ff0000>`, quotes stripped
+    pub value: Option<String>,
+    pub closing: bool,
+}
+
+impl Tag {
+    pub fn is_known(&self) -> bool {
+        KNOWN_TAGS.contains(&self.name.as_str())
+    }
+
+    /// `<b>` / `</b>` / `<color>`: the tag without its value.
+    pub fn token(&self) -> String {
+        if self.closing {
+            format!("</{}>", self.name)
+        } else {
+            format!("<{}>", self.name)
+        }
+    }
+}
+
+/// Tag-like tokens in `text`, known or not, in order.
+pub fn tags(text: &str) -> Vec<Tag> {
+    re_tag()
+        .captures_iter(text)
+        .map(|cap| Tag {
+            name: cap[2].to_ascii_lowercase(),
+            value: cap
+                .get(3)
+                .map(|v| v.as_str().trim().trim_matches(['"', '\'']).to_string()),
+            closing: !cap[1].is_empty(),
+        })
+        .collect()
+}
+
+/// Known tags in order as [`Tag::token`]s, for comparing a translation with its source.
+pub fn sequence(text: &str) -> Vec<String> {
+    tags(text)
+        .iter()
+        .filter(|t| t.is_known())
+        .map(Tag::token)
+        .collect()
+}
+
+/// Names of tag-like tokens the game does not render, sorted and deduplicated.
+/// Plain text such as `Press <Enter>` looks the same, so these are only worth
+/// reporting when a translation has them and its source does not.
+pub fn unknown(text: &str) -> BTreeSet<String> {
+    tags(text)
+        .into_iter()
+        .filter(|t| !t.is_known())
+        .map(|t| t.name)
+        .collect()
+}
+
+/// Problems that make the game show a broken string: missing or malformed
+/// values, and closing tags that do not match the open ones. Unknown tags are
+/// left alone (see [`unknown`]).
+pub fn problems(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut open: Vec<String> = Vec::new();
+    for tag in tags(text).into_iter().filter(Tag::is_known) {
+        if tag.closing {
+            match open.pop() {
+                Some(name) if name == tag.name => {}
+                Some(name) => {
+                    out.push(format!(
+                        "Closing tag {} does not match <{name}>",
+                        tag.token()
+                    ));
+                    open.push(name);
+                }
+                None => out.push(format!(
+                    "Closing tag {} without an opening tag",
+                    tag.token()
+                )),
+            }
+            continue;
+        }
+        match (tag.name.as_str(), tag.value.as_deref()) {
+            ("color" | "size" | "material", None | Some("")) => {
+                out.push(format!("Tag {} needs a value", tag.token()));
+            }
+            ("color", Some(v)) if !re_color().is_match(v) => {
+                out.push(format!("Invalid color value '{v}'"));
+            }
+            ("size", Some(v)) if v.trim_end_matches('%').parse::<f32>().is_err() => {
+                out.push(format!("Invalid size value '{v}'"));
+            }
+            _ => {}
+        }
+        if tag.name != "quad" {
+            open.push(tag.name);
+        }
+    }
+    for name in open {
+        out.push(format!("Unclosed tag <{name}>"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_read_escaped_and_unescaped_forms() {
+        let got = tags("&lt;color=\"#ff0000\"&gt;Danger&lt;/color&gt; <B>x</B>");
+        let got: Vec<(String, Option<&str>, bool)> = got
+            .iter()
+            .map(|t| (t.token(), t.value.as_deref(), t.closing))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("<color>".to_string(), Some("#ff0000"), false),
+                ("</color>".to_string(), None, true),
+                ("<b>".to_string(), None, false),
+                ("</b>".to_string(), None, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn problems_report_broken_strings() {
+        assert!(
+            problems("&lt;color=#ff0000&gt;Danger&lt;/color&gt; <size=20>big</size>").is_empty()
+        );
+        assert!(problems("<quad material=0 size=20/>").is_empty());
+        assert!(problems("Press &lt;Enter&gt; to confirm, use <tool>").is_empty());
+        assert_eq!(problems("<b>Never closed"), vec!["Unclosed tag <b>"]);
+        assert_eq!(
+            problems("<b><i>x</b></i>"),
+            vec!["Closing tag </b> does not match <i>", "Unclosed tag <b>"]
+        );
+        assert_eq!(
+            problems("x</i>"),
+            vec!["Closing tag </i> without an opening tag"]
+        );
+        assert_eq!(
+            problems("<color=reddish1>x</color> <size=big>y</size> <color>z</color>"),
+            vec![
+                "Invalid color value 'reddish1'",
+                "Invalid size value 'big'",
+                "Tag <color> needs a value",
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_lists_tag_names_once() {
+        assert_eq!(
+            unknown("&lt;blink&gt;Hi&lt;/blink&gt; <b>x</b> <Enter>"),
+            BTreeSet::from(["blink".to_string(), "enter".to_string()])
+        );
+    }
+
+    #[test]
+    fn sequence_keeps_known_tags_in_order() {
+        assert_eq!(
+            sequence("&lt;b&gt;Bold&lt;/b&gt; <blink>"),
+            vec!["<b>", "</b>"]
+        );
+        assert_ne!(sequence("<b>Bold</b>"), sequence("<i>Жирный</i>"));
+        assert_eq!(
+            sequence("<color=#ff0000>Danger</color>"),
+            sequence("<color=red>Опасность</color>")
+        );
+    }
+}
@@ -0,0 +1,564 @@
+//! XLIFF 1.2 / 2.0 reader/writer for exchanging RimLoc translations with CAT tools.
+//!
+//! Layout: one `<file>` per target XML file (`original` is the path relative to the
+//! mod root, as in PO `#:` references), one unit per key (`name` in 2.0, `resname`
+//! in 1.2). Def context goes into notes. Placeholders (`{0}`, `%s`) are written as
+//! inline `<ph>` codes and turned back into text when reading.
+
+use color_eyre::eyre::{eyre, Result};
+use rimloc_core::placeholders;
+use std::fmt::{self, Write as _};
+use std::path::Path;
+
+const NS_1_2: &str = "urn:oasis:names:tc:xliff:document:1.2";
+const NS_2_0: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+/// XLIFF dialect to write.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum XliffVersion {
+    V1_2,
+    #[default]
+    V2_0,
+}
+
+impl XliffVersion {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            XliffVersion::V1_2 => "1.2",
+            XliffVersion::V2_0 => "2.0",
+        }
+    }
+}
+
+impl std::str::FromStr for XliffVersion {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "1.2" => Ok(XliffVersion::V1_2),
+            "2" | "2.0" | "2.1" => Ok(XliffVersion::V2_0),
+            other => Err(eyre!(
+                "unknown XLIFF version '{other}' (expected 1.2 or 2.0)"
+            )),
+        }
+    }
+}
+
+/// Translation state of a unit. Written as `initial|translated|reviewed|final`
+/// in 2.0 and `new|translated|signed-off|final` in 1.2.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum XliffState {
+    #[default]
+    New,
+    Translated,
+    Reviewed,
+    Final,
+}
+
+impl XliffState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            XliffState::New => "new",
+            XliffState::Translated => "translated",
+            XliffState::Reviewed => "reviewed",
+            XliffState::Final => "final",
+        }
+    }
+
+    fn attr(self, version: XliffVersion) -> &'static str {
+        match (self, version) {
+            (XliffState::New, XliffVersion::V2_0) => "initial",
+            (XliffState::New, XliffVersion::V1_2) => "new",
+            (XliffState::Translated, _) => "translated",
+            (XliffState::Reviewed, XliffVersion::V2_0) => "reviewed",
+            (XliffState::Reviewed, XliffVersion::V1_2) => "signed-off",
+            (XliffState::Final, _) => "final",
+        }
+    }
+
+    /// Parse a state attribute of either version; 1.2 `needs-*` states count as new.
+    fn from_attr(s: &str) -> Self {
+        match s.trim() {
+            "translated" => XliffState::Translated,
+            "reviewed" | "signed-off" => XliffState::Reviewed,
+            "final" => XliffState::Final,
+            _ => XliffState::New,
+        }
+    }
+}
+
+/// One key with its source text and (optional) translation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XliffUnit {
+    /// RimWorld key (`Greeting`, `Beer.label`)
+    pub key: String,
+    /// Target XML file, relative to the mod root (`Languages/English/Keyed/A.xml`)
+    pub file: String,
+    pub source: String,
+    pub target: Option<String>,
+    pub state: XliffState,
+    /// Notes for the translator (def type/defName, label, placeholders)
+    pub notes: Vec<String>,
+}
+
+/// XLIFF document: languages plus units in file order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XliffDoc {
+    pub version: XliffVersion,
+    /// BCP-47 code of the source language (`en`)
+    pub source_lang: String,
+    pub target_lang: Option<String>,
+    pub units: Vec<XliffUnit>,
+}
+
+impl XliffDoc {
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| eyre!("{}: {e}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Parse XLIFF 1.2 or 2.x (detected from the root `version` attribute).
+    pub fn parse(text: &str) -> Result<Self> {
+        let xml = roxmltree::Document::parse(text)?;
+        let root = xml.root_element();
+        if root.tag_name().name() != "xliff" {
+            return Err(eyre!(
+                "root element is <{}>, expected <xliff>",
+                root.tag_name().name()
+            ));
+        }
+        match root.attribute("version").unwrap_or("") {
+            "1.2" | "1.1" | "1.0" => Ok(parse_1_2(root)),
+            v if v.starts_with('2') => Ok(parse_2_0(root)),
+            other => Err(eyre!("unsupported XLIFF version '{other}'")),
+        }
+    }
+
+    /// Units grouped by `file`, in order of first appearance.
+    fn files(&self) -> Vec<(&str, Vec<&XliffUnit>)> {
+        let mut out: Vec<(&str, Vec<&XliffUnit>)> = Vec::new();
+        for u in &self.units {
+            match out.iter_mut().find(|(f, _)| *f == u.file) {
+                Some((_, units)) => units.push(u),
+                None => out.push((&u.file, vec![u])),
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for XliffDoc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        match self.version {
+            XliffVersion::V2_0 => write_2_0(self, f),
+            XliffVersion::V1_2 => write_1_2(self, f),
+        }
+    }
+}
+
+fn write_2_0(doc: &XliffDoc, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+        f,
+        r#"<xliff xmlns="{NS_2_0}" version="2.0" srcLang="{}""#,
+        escape(&doc.source_lang)
+    )?;
+    if let Some(trg) = &doc.target_lang {
+        write!(f, r#" trgLang="{}""#, escape(trg))?;
+    }
+    writeln!(f, ">")?;
+    let mut n = 0usize;
+    for (i, (file, units)) in doc.files().into_iter().enumerate() {
+        writeln!(f, r#"  <file id="f{}" original="{}">"#, i + 1, escape(file))?;
+        for u in units {
+            n += 1;
+            writeln!(f, r#"    <unit id="u{n}" name="{}">"#, escape(&u.key))?;
+            if !u.notes.is_empty() {
+                writeln!(f, "      <notes>")?;
+                for note in &u.notes {
+                    writeln!(
+                        f,
+                        r#"        <note category="context">{}</note>"#,
+                        escape(note)
+                    )?;
+                }
+                writeln!(f, "      </notes>")?;
+            }
+            // коды плейсхолдеров: исходный текст хранится в <originalData>
+            let mut data = Vec::new();
+            let source = inline(&u.source, XliffVersion::V2_0, &mut data);
+            let target = u
+                .target
+                .as_deref()
+                .map(|t| inline(t, XliffVersion::V2_0, &mut data));
+            if !data.is_empty() {
+                writeln!(f, "      <originalData>")?;
+                for (k, d) in data.iter().enumerate() {
+                    writeln!(f, r#"        <data id="d{}">{}</data>"#, k + 1, escape(d))?;
+                }
+                writeln!(f, "      </originalData>")?;
+            }
+            writeln!(
+                f,
+                r#"      <segment state="{}">"#,
+                u.state.attr(XliffVersion::V2_0)
+            )?;
+            writeln!(f, "        <source>{source}</source>")?;
+            if let Some(target) = target {
+                writeln!(f, "        <target>{target}</target>")?;
+            }
+            writeln!(f, "      </segment>")?;
+            writeln!(f, "    </unit>")?;
+        }
+        writeln!(f, "  </file>")?;
+    }
+    writeln!(f, "</xliff>")
+}
+
+fn write_1_2(doc: &XliffDoc, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, r#"<xliff xmlns="{NS_1_2}" version="1.2">"#)?;
+    let mut n = 0usize;
+    for (file, units) in doc.files() {
+        write!(
+            f,
+            r#"  <file original="{}" source-language="{}""#,
+            escape(file),
+            escape(&doc.source_lang)
+        )?;
+        if let Some(trg) = &doc.target_lang {
+            write!(f, r#" target-language="{}""#, escape(trg))?;
+        }
+        writeln!(f, r#" datatype="xml">"#)?;
+        writeln!(f, "    <body>")?;
+        for u in units {
+            n += 1;
+            writeln!(
+                f,
+                r#"      <trans-unit id="u{n}" resname="{}">"#,
+                escape(&u.key)
+            )?;
+            let mut data = Vec::new();
+            writeln!(
+                f,
+                "        <source>{}</source>",
+                inline(&u.source, XliffVersion::V1_2, &mut data)
+            )?;
+            if let Some(t) = &u.target {
+                writeln!(
+                    f,
+                    r#"        <target state="{}">{}</target>"#,
+                    u.state.attr(XliffVersion::V1_2),
+                    inline(t, XliffVersion::V1_2, &mut data)
+                )?;
+            }
+            for note in &u.notes {
+                writeln!(f, "        <note>{}</note>", escape(note))?;
+            }
+            writeln!(f, "      </trans-unit>")?;
+        }
+        writeln!(f, "    </body>")?;
+        writeln!(f, "  </file>")?;
+    }
+    writeln!(f, "</xliff>")
+}
+
+/// Escaped segment content with placeholders as `<ph>` codes. `data` collects the
+/// distinct placeholder texts of the unit (the same text reuses its id, repeats in a
+/// segment get an `_<n>` suffix so ids stay unique and match between source and target).
+fn inline(text: &str, version: XliffVersion, data: &mut Vec<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut seen: Vec<usize> = Vec::new();
+    let mut pos = 0;
+    for r in placeholders::spans(text) {
+        out.push_str(&escape(&text[pos..r.start]));
+        let ph = &text[r.clone()];
+        let k = match data.iter().position(|d| d == ph) {
+            Some(k) => k,
+            None => {
+                data.push(ph.to_string());
+                data.len() - 1
+            }
+        };
+        seen.push(k);
+        let nth = seen.iter().filter(|&&s| s == k).count();
+        let id = if nth == 1 {
+            format!("{}", k + 1)
+        } else {
+            format!("{}_{nth}", k + 1)
+        };
+        let ph = escape(ph);
+        match version {
+            XliffVersion::V2_0 => {
+                let _ = write!(
+                    out,
+                    r#"<ph id="{id}" dataRef="d{}" equiv="{ph}" disp="{ph}"/>"#,
+                    k + 1
+                );
+            }
+            XliffVersion::V1_2 => {
+                let _ = write!(out, r#"<ph id="{id}">{ph}</ph>"#);
+            }
+        }
+        pos = r.end;
+    }
+    out.push_str(&escape(&text[pos..]));
+    out
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn children<'a, 'i>(
+    node: roxmltree::Node<'a, 'i>,
+    name: &'static str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'i>> {
+    node.children()
+        .filter(move |c| c.is_element() && c.tag_name().name() == name)
+}
+
+/// Text of a segment element with inline codes resolved back to the original text.
+/// `data` maps 2.0 `<originalData>` ids to their content.
+fn inline_text(node: roxmltree::Node, data: &[(String, String)]) -> String {
+    let mut out = String::new();
+    for c in node.children() {
+        if c.is_text() {
+            out.push_str(c.text().unwrap_or(""));
+            continue;
+        }
+        if !c.is_element() {
+            continue;
+        }
+        match c.tag_name().name() {
+            // 2.0: <ph dataRef/equiv/disp/>; 1.2: <ph>native code</ph>
+            "ph" => {
+                let by_ref = c
+                    .attribute("dataRef")
+                    .and_then(|r| data.iter().find(|(id, _)| id == r))
+                    .map(|(_, d)| d.as_str());
+                match by_ref.or(c.attribute("equiv")).or(c.attribute("disp")) {
+                    Some(v) => out.push_str(v),
+                    None => out.push_str(&inline_text(c, data)),
+                }
+            }
+            // paired/marker codes: keep the content, drop the markup
+            "g" | "mrk" | "pc" | "sub" => out.push_str(&inline_text(c, data)),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn parse_2_0(root: roxmltree::Node) -> XliffDoc {
+    let mut doc = XliffDoc {
+        version: XliffVersion::V2_0,
+        source_lang: root.attribute("srcLang").unwrap_or("").to_string(),
+        target_lang: root.attribute("trgLang").map(str::to_string),
+        units: Vec::new(),
+    };
+    for file in children(root, "file") {
+        let original = file.attribute("original").unwrap_or("").to_string();
+        // юниты могут лежать во вложенных <group>
+        for unit in file
+            .descendants()
+            .filter(|n| n.is_element() && n.tag_name().name() == "unit")
+        {
+            let data: Vec<(String, String)> = children(unit, "originalData")
+                .flat_map(|od| children(od, "data"))
+                .map(|d| {
+                    (
+                        d.attribute("id").unwrap_or("").to_string(),
+                        d.text().unwrap_or("").to_string(),
+                    )
+                })
+                .collect();
+            let notes = children(unit, "notes")
+                .flat_map(|ns| children(ns, "note"))
+                .map(|n| n.text().unwrap_or("").to_string())
+                .collect();
+            // CAT-инструмент мог разбить юнит на несколько сегментов — склеиваем
+            let mut source = String::new();
+            let mut target: Option<String> = None;
+            let mut state = None;
+            for seg in unit.children().filter(|c| {
+                c.is_element() && matches!(c.tag_name().name(), "segment" | "ignorable")
+            }) {
+                if seg.tag_name().name() == "segment" && state.is_none() {
+                    state = Some(XliffState::from_attr(seg.attribute("state").unwrap_or("")));
+                }
+                if let Some(s) = children(seg, "source").next() {
+                    source.push_str(&inline_text(s, &data));
+                }
+                if let Some(t) = children(seg, "target").next() {
+                    target
+                        .get_or_insert_with(String::new)
+                        .push_str(&inline_text(t, &data));
+                }
+            }
+            doc.units.push(XliffUnit {
+                key: unit
+                    .attribute("name")
+                    .or(unit.attribute("id"))
+                    .unwrap_or("")
+                    .to_string(),
+                file: original.clone(),
+                source,
+                target,
+                state: state.unwrap_or_default(),
+                notes,
+            });
+        }
+    }
+    doc
+}
+
+fn parse_1_2(root: roxmltree::Node) -> XliffDoc {
+    let mut doc = XliffDoc {
+        version: XliffVersion::V1_2,
+        ..Default::default()
+    };
+    for file in children(root, "file") {
+        if doc.source_lang.is_empty() {
+            doc.source_lang = file.attribute("source-language").unwrap_or("").to_string();
+        }
+        if doc.target_lang.is_none() {
+            doc.target_lang = file.attribute("target-language").map(str::to_string);
+        }
+        let original = file.attribute("original").unwrap_or("").to_string();
+        for tu in file
+            .descendants()
+            .filter(|n| n.is_element() && n.tag_name().name() == "trans-unit")
+        {
+            let source = children(tu, "source")
+                .next()
+                .map(|s| inline_text(s, &[]))
+                .unwrap_or_default();
+            let target_node = children(tu, "target").next();
+            let target = target_node.map(|t| inline_text(t, &[]));
+            let state = match target_node.and_then(|t| t.attribute("state")) {
+                Some(s) => XliffState::from_attr(s),
+                // без state: заполненный target считаем переведённым
+                None if target.as_deref().is_some_and(|t| !t.is_empty()) => XliffState::Translated,
+                None => XliffState::New,
+            };
+            doc.units.push(XliffUnit {
+                key: tu
+                    .attribute("resname")
+                    .or(tu.attribute("id"))
+                    .unwrap_or("")
+                    .to_string(),
+                file: original.clone(),
+                source,
+                target,
+                state,
+                notes: children(tu, "note")
+                    .map(|n| n.text().unwrap_or("").to_string())
+                    .collect(),
+            });
+        }
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(version: XliffVersion) -> XliffDoc {
+        XliffDoc {
+            version,
+            source_lang: "en".into(),
+            target_lang: Some("ru".into()),
+            units: vec![
+                XliffUnit {
+                    key: "Greeting".into(),
+                    file: "Languages/English/Keyed/A.xml".into(),
+                    source: "Hello, {0} & {0}: %s <b>".into(),
+                    target: Some("Привет, {0} и {0}: %s <b>".into()),
+                    state: XliffState::Reviewed,
+                    notes: vec!["Placeholders: {0}, %s".into()],
+                },
+                XliffUnit {
+                    key: "Beer.label".into(),
+                    file: "Languages/English/DefInjected/ThingDef/Drinks.xml".into(),
+                    source: "beer".into(),
+                    notes: vec!["ThingDef: Beer".into()],
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_both_versions() {
+        for version in [XliffVersion::V2_0, XliffVersion::V1_2] {
+            let doc = sample(version);
+            let text = doc.to_string();
+            assert_eq!(XliffDoc::parse(&text).unwrap(), doc, "{text}");
+        }
+    }
+
+    #[test]
+    fn placeholders_become_inline_codes() {
+        let v2 = sample(XliffVersion::V2_0).to_string();
+        assert!(v2.contains(r#"<data id="d1">{0}</data>"#));
+        assert!(v2.contains(
+            r#"<source>Hello, <ph id="1" dataRef="d1" equiv="{0}" disp="{0}"/> &amp; <ph id="1_2" dataRef="d1""#
+        ));
+        assert!(v2.contains(r#"<segment state="reviewed">"#));
+        assert!(v2.contains(r#"<unit id="u2" name="Beer.label">"#));
+        assert!(v2.contains(r#"<segment state="initial">"#));
+
+        let v12 = sample(XliffVersion::V1_2).to_string();
+        assert!(v12.contains(r#"<ph id="2">%s</ph>"#));
+        assert!(v12.contains(r#"<target state="signed-off">"#));
+        assert!(v12.contains(r#"resname="Beer.label""#));
+    }
+
+    #[test]
+    fn reads_cat_tool_output() {
+        let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="de">
+  <file id="f1" original="Languages/English/Keyed/A.xml">
+    <group id="g1">
+      <unit id="Greeting">
+        <originalData><data id="d1">{0}</data></originalData>
+        <segment state="translated"><source>Hello, </source><target>Hallo, </target></segment>
+        <segment><source><ph id="1" dataRef="d1"/>!</source><target><pc id="2"><ph id="1" dataRef="d1"/></pc>!</target></segment>
+      </unit>
+    </group>
+  </file>
+</xliff>"#;
+        let doc = XliffDoc::parse(text).unwrap();
+        let u = &doc.units[0];
+        assert_eq!(u.key, "Greeting");
+        assert_eq!(u.source, "Hello, {0}!");
+        assert_eq!(u.target.as_deref(), Some("Hallo, {0}!"));
+        assert_eq!(u.state, XliffState::Translated);
+        assert_eq!(doc.target_lang.as_deref(), Some("de"));
+
+        let v12 = r#"<xliff version="1.2"><file original="a.xml" source-language="en" datatype="xml"><body>
+            <trans-unit id="1"><source>a</source><target state="needs-review-translation">b</target></trans-unit>
+            <trans-unit id="2"><source>c</source><target>d</target></trans-unit>
+        </body></file></xliff>"#;
+        let doc = XliffDoc::parse(v12).unwrap();
+        assert_eq!(doc.units[0].state, XliffState::New);
+        assert_eq!(doc.units[1].state, XliffState::Translated);
+        assert!(XliffDoc::parse("<root/>").is_err());
+    }
+}
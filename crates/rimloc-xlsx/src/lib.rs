@@ -0,0 +1,346 @@
+//! XLSX workbooks for translating in a spreadsheet: one sheet per source file or
+//! def type, with key and source locked and a hidden hash guarding them.
+
+use calamine::{open_workbook, Data, Reader, Xlsx};
+use color_eyre::eyre::{bail, eyre};
+use rust_xlsxwriter::{
+    ConditionalFormatCell, ConditionalFormatCellRule, Format, FormatBorder, ProtectionOptions,
+    Workbook,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::Path;
+
+pub type Result<T> = color_eyre::eyre::Result<T>;
+
+/// Columns of every sheet, in the order they are written.
+pub const SHEET_HEADER: [&str; 7] = [
+    "key",
+    "source",
+    "translation",
+    "ratio",
+    "notes",
+    "file",
+    "hash",
+];
+
+const COL_KEY: u16 = 0;
+const COL_SOURCE: u16 = 1;
+const COL_TRANSLATION: u16 = 2;
+const COL_RATIO: u16 = 3;
+const COL_NOTES: u16 = 4;
+const COL_FILE: u16 = 5;
+const COL_HASH: u16 = 6;
+
+/// Translation/source length ratio above which the ratio cell turns red.
+pub const RATIO_TOO_LONG: f64 = 1.5;
+/// Ratio below which the ratio cell turns yellow.
+pub const RATIO_TOO_SHORT: f64 = 0.5;
+
+/// Excel limit on sheet name length.
+const MAX_SHEET_NAME: usize = 31;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XlsxRow {
+    pub key: String,
+    pub source: String,
+    pub translation: String,
+    pub notes: String,
+    /// Source file relative to the mod root; used to place the key on import.
+    pub file: String,
+    /// [`source_hash`] of key and source at export time.
+    pub hash: String,
+}
+
+impl XlsxRow {
+    /// Key and source are still what was exported.
+    pub fn is_intact(&self) -> bool {
+        self.hash == source_hash(&self.key, &self.source)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XlsxSheet {
+    pub name: String,
+    pub rows: Vec<XlsxRow>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XlsxBook {
+    pub sheets: Vec<XlsxSheet>,
+}
+
+/// Short hash of a key and its source text, stored next to each row so edits to
+/// either are caught on import.
+pub fn source_hash(key: &str, source: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hasher.update([0]);
+    hasher.update(source.as_bytes());
+    let digest = hasher.finalize();
+    digest[..6].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Make `raw` a valid sheet name not in `taken`: forbidden characters become `_`,
+/// the name is cut to 31 characters and numbered on clashes (compared case-insensitively).
+pub fn sheet_name(raw: &str, taken: &mut HashSet<String>) -> String {
+    let clean: String = raw
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .collect();
+    let clean = clean.trim_matches('\'');
+    let clean = if clean.trim().is_empty() {
+        "Sheet"
+    } else {
+        clean
+    };
+    let mut n = 1;
+    loop {
+        let suffix = if n == 1 {
+            String::new()
+        } else {
+            format!(" ({n})")
+        };
+        let name: String = clean
+            .chars()
+            .take(MAX_SHEET_NAME - suffix.chars().count())
+            .chain(suffix.chars())
+            .collect();
+        if taken.insert(name.to_lowercase()) {
+            return name;
+        }
+        n += 1;
+    }
+}
+
+impl XlsxBook {
+    /// Write the workbook. Sheets are protected: only the translation column is
+    /// editable, `file` and `hash` are hidden, and the ratio column is colored when
+    /// the translation is much longer or shorter than the source.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let bytes = self.to_bytes()?;
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut workbook = Workbook::new();
+        let header = Format::new()
+            .set_bold()
+            .set_border_bottom(FormatBorder::Thin);
+        let locked = Format::new().set_text_wrap();
+        let editable = Format::new()
+            .set_text_wrap()
+            .set_unlocked()
+            .set_background_color("#FFFDE7");
+        let ratio = Format::new().set_num_format("0.00");
+        let too_long = Format::new().set_background_color("#FFC7CE");
+        let too_short = Format::new().set_background_color("#FFEB9C");
+        let protection = ProtectionOptions {
+            format_columns: true,
+            format_rows: true,
+            use_autofilter: true,
+            ..ProtectionOptions::new()
+        };
+
+        for sheet in &self.sheets {
+            let ws = workbook.add_worksheet();
+            ws.set_name(&sheet.name)?;
+            for (col, name) in SHEET_HEADER.iter().enumerate() {
+                ws.write_string_with_format(0, col as u16, *name, &header)?;
+            }
+            ws.set_column_width(COL_KEY, 30)?;
+            ws.set_column_width(COL_SOURCE, 60)?;
+            ws.set_column_width(COL_TRANSLATION, 60)?;
+            ws.set_column_width(COL_RATIO, 8)?;
+            ws.set_column_width(COL_NOTES, 40)?;
+            ws.set_column_format(COL_TRANSLATION, &editable)?;
+            ws.set_column_hidden(COL_FILE)?;
+            ws.set_column_hidden(COL_HASH)?;
+
+            for (i, r) in sheet.rows.iter().enumerate() {
+                let row = i as u32 + 1;
+                let excel_row = row + 1;
+                ws.write_string_with_format(row, COL_KEY, &r.key, &locked)?;
+                ws.write_string_with_format(row, COL_SOURCE, &r.source, &locked)?;
+                ws.write_string_with_format(row, COL_TRANSLATION, &r.translation, &editable)?;
+                ws.write_formula_with_format(
+                    row,
+                    COL_RATIO,
+                    format!(
+                        "=IF(OR(B{excel_row}=\"\",C{excel_row}=\"\"),\"\",LEN(C{excel_row})/LEN(B{excel_row}))"
+                    )
+                    .as_str(),
+                    &ratio,
+                )?;
+                ws.write_string_with_format(row, COL_NOTES, &r.notes, &locked)?;
+                ws.write_string(row, COL_FILE, &r.file)?;
+                ws.write_string(row, COL_HASH, &r.hash)?;
+            }
+
+            let last = sheet.rows.len() as u32;
+            if last > 0 {
+                ws.add_conditional_format(
+                    1,
+                    COL_RATIO,
+                    last,
+                    COL_RATIO,
+                    &ConditionalFormatCell::new()
+                        .set_rule(ConditionalFormatCellRule::GreaterThan(RATIO_TOO_LONG))
+                        .set_format(&too_long),
+                )?;
+                ws.add_conditional_format(
+                    1,
+                    COL_RATIO,
+                    last,
+                    COL_RATIO,
+                    &ConditionalFormatCell::new()
+                        .set_rule(ConditionalFormatCellRule::LessThan(RATIO_TOO_SHORT))
+                        .set_format(&too_short),
+                )?;
+            }
+            ws.autofilter(0, COL_KEY, last, COL_NOTES)?;
+            ws.set_freeze_panes(1, 0)?;
+            ws.protect_with_options(&protection);
+        }
+        if self.sheets.is_empty() {
+            workbook.add_worksheet();
+        }
+        Ok(workbook.save_to_buffer()?)
+    }
+
+    /// Read a workbook. Columns are matched by header name, so they may be
+    /// reordered; `key`, `source`, `translation` and `hash` are required on every
+    /// sheet with data. Rows without a key are skipped.
+    pub fn read(path: &Path) -> Result<Self> {
+        let mut workbook: Xlsx<_> =
+            open_workbook(path).map_err(|e| eyre!("failed to open {}: {e}", path.display()))?;
+        let mut book = XlsxBook::default();
+        for name in workbook.sheet_names() {
+            let range = workbook
+                .worksheet_range(&name)
+                .map_err(|e| eyre!("failed to read sheet '{name}': {e}"))?;
+            let mut rows = range.rows();
+            let Some(header) = rows.next() else {
+                continue;
+            };
+            let header: Vec<String> = header
+                .iter()
+                .map(|c| c.to_string().trim().to_ascii_lowercase())
+                .collect();
+            let col = |name: &str| header.iter().position(|h| h == name);
+            let (Some(key), Some(source), Some(translation), Some(hash)) =
+                (col("key"), col("source"), col("translation"), col("hash"))
+            else {
+                bail!(
+                    "sheet '{name}' must have key, source, translation and hash columns, found: {}",
+                    header.join(", ")
+                );
+            };
+            let (notes, file) = (col("notes"), col("file"));
+
+            let mut sheet = XlsxSheet {
+                name: name.clone(),
+                rows: Vec::new(),
+            };
+            for cells in rows {
+                let cell = |i: Option<usize>| {
+                    i.and_then(|i| cells.get(i))
+                        .map(cell_text)
+                        .unwrap_or_default()
+                };
+                let row = XlsxRow {
+                    key: cell(Some(key)).trim().to_string(),
+                    source: cell(Some(source)),
+                    translation: cell(Some(translation)),
+                    notes: cell(notes),
+                    file: cell(file),
+                    hash: cell(Some(hash)).trim().to_string(),
+                };
+                if !row.key.is_empty() {
+                    sheet.rows.push(row);
+                }
+            }
+            book.sheets.push(sheet);
+        }
+        Ok(book)
+    }
+}
+
+fn cell_text(c: &Data) -> String {
+    match c {
+        Data::Empty => String::new(),
+        Data::String(s) => s.replace("\r\n", "\n"),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(key: &str, source: &str, translation: &str) -> XlsxRow {
+        XlsxRow {
+            key: key.into(),
+            source: source.into(),
+            translation: translation.into(),
+            notes: "Placeholders: {0}".into(),
+            file: "Languages/English/Keyed/A.xml".into(),
+            hash: source_hash(key, source),
+        }
+    }
+
+    #[test]
+    fn write_then_read_round_trip() {
+        let book = XlsxBook {
+            sheets: vec![
+                XlsxSheet {
+                    name: "A".into(),
+                    rows: vec![
+                        row("Greeting", "Hello, {0}", "Привет, {0}"),
+                        row("Letter", "Line one\nLine two", ""),
+                    ],
+                },
+                XlsxSheet {
+                    name: "ThingDef".into(),
+                    rows: vec![row("Beer.label", "beer", "пиво")],
+                },
+            ],
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mod.xlsx");
+        book.save(&path).unwrap();
+        let back = XlsxBook::read(&path).unwrap();
+        assert_eq!(back, book);
+        assert!(back
+            .sheets
+            .iter()
+            .flat_map(|s| &s.rows)
+            .all(|r| r.is_intact()));
+    }
+
+    #[test]
+    fn hash_catches_edited_key_or_source() {
+        let mut r = row("Greeting", "Hello", "Привет");
+        assert!(r.is_intact());
+        r.source = "Hello!".into();
+        assert!(!r.is_intact());
+        let mut r = row("Greeting", "Hello", "Привет");
+        r.key = "Greeting2".into();
+        assert!(!r.is_intact());
+    }
+
+    #[test]
+    fn sheet_names_are_valid_and_unique() {
+        let mut taken = HashSet::new();
+        assert_eq!(sheet_name("Keyed/Misc", &mut taken), "Keyed_Misc");
+        assert_eq!(sheet_name("keyed_misc", &mut taken), "keyed_misc (2)");
+        let long = "A".repeat(40);
+        assert_eq!(sheet_name(&long, &mut taken).chars().count(), 31);
+        let second = sheet_name(&long, &mut taken);
+        assert!(second.ends_with(" (2)") && second.chars().count() == 31);
+    }
+}
@@ -414,6 +414,9 @@ struct ImportPoRequest {
     only_diff: bool,
     #[serde(default)]
     report: bool,
+    /// skip | include | mark
+    #[serde(default)]
+    fuzzy: Option<String>,
     #[serde(default)]
     dry_run: bool,
 }
@@ -428,6 +431,8 @@ struct ImportPoResponse {
     updated: usize,
     skipped: usize,
     keys: usize,
+    fuzzy_skipped: usize,
+    fuzzy_imported: usize,
 }
 
 #[derive(Debug, Deserialize)]
@@ -446,6 +451,9 @@ struct BuildModRequest {
     from_root: Option<String>,
     #[serde(default)]
     from_game_versions: Option<Vec<String>>,
+    /// skip | include | mark
+    #[serde(default)]
+    fuzzy: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -804,6 +812,13 @@ fn write_scan_json(path: &Path, units: &[rimloc_services::TransUnit]) -> Result<
             line: u.line,
             key: u.key.clone(),
             value: u.source.clone(),
+            kind: u.kind.as_str().to_string(),
+            def_type: u.def_type.clone(),
+            def_name: u.def_name.clone(),
+            field_path: u.field_path.clone(),
+            origin_path: u.origin_path.as_ref().map(|p| p.display().to_string()),
+            origin_line: u.origin_line,
+            comments: u.comments.clone(),
         })
         .collect();
     serde_json::to_writer_pretty(file, &payload).map_err(ApiError::from)
@@ -1062,11 +1077,19 @@ fn export_po(window: Window, state: State<LogState>, request: ExportPoRequest) -
     };
 
     // Write PO
-    let stats = rimloc_export_po::write_po_with_tm(
+    let stats = rimloc_export_po::write_po_opts(
         &out_po_path,
         &units,
-        if request.pot { None } else { request.lang.as_deref() },
-        tm_map.as_ref(),
+        &rimloc_export_po::PoWriteOptions {
+            lang: if request.pot { None } else { request.lang.as_deref() },
+            tm: tm_map.as_ref(),
+            tm_index: None,
+            tm_min_score: None,
+            mod_root: Some(scan_root.as_path()),
+            meta: None,
+            compact: false,
+            glossary: None,
+        },
     )
     .wrap_err("export po")?;
 
@@ -1277,9 +1300,10 @@ fn import_po(window: Window, state: State<LogState>, request: ImportPoRequest) -
         .clone()
         .or_else(|| request.lang.clone().map(|c| rimloc_import_po::rimworld_lang_dir(&c)))
         .unwrap_or_else(|| "English".to_string());
+    let fuzzy: rimloc_services::FuzzyPolicy = request.fuzzy.as_deref().unwrap_or("skip").parse()?;
     let summary = if let Some(out_xml) = request.out_xml.as_deref() {
         let outp = make_absolute(&scan_root, Path::new(out_xml));
-        rimloc_services::import_po_to_file(&po_path, &outp, request.keep_empty, request.dry_run, request.backup)
+        rimloc_services::import_po_to_file(&po_path, &outp, request.keep_empty, fuzzy, request.dry_run, request.backup)
             .wrap_err("import po to file")?
     } else if request.dry_run {
         let (_plan, summary) = import_po_to_mod_tree(
@@ -1287,6 +1311,7 @@ fn import_po(window: Window, state: State<LogState>, request: ImportPoRequest) -
             &scan_root,
             &lang_dir,
             request.keep_empty,
+            fuzzy,
             true,
             request.backup,
             request.single_file,
@@ -1294,13 +1319,14 @@ fn import_po(window: Window, state: State<LogState>, request: ImportPoRequest) -
             request.only_diff,
             request.report,
         )?;
-        summary.unwrap_or(rimloc_services::ImportSummary { mode: "dry_run".into(), created: 0, updated: 0, skipped: 0, keys: 0, files: vec![] })
+        summary.unwrap_or(rimloc_services::ImportSummary { mode: "dry_run".into(), created: 0, updated: 0, skipped: 0, keys: 0, files: vec![], fuzzy_skipped: 0, fuzzy_imported: 0, duplicates: vec![] })
     } else {
         import_po_to_mod_tree_with_progress(
             &po_path,
             &scan_root,
             &lang_dir,
             request.keep_empty,
+            fuzzy,
             request.backup,
             request.single_file,
             request.incremental,
@@ -1321,6 +1347,8 @@ fn import_po(window: Window, state: State<LogState>, request: ImportPoRequest) -
         updated: summary.updated,
         skipped: summary.skipped,
         keys: summary.keys,
+        fuzzy_skipped: summary.fuzzy_skipped,
+        fuzzy_imported: summary.fuzzy_imported,
     };
     write_profile(&state, "import_po", t0, serde_json::json!({"created": resp.created, "updated": resp.updated, "skipped": resp.skipped, "keys": resp.keys}));
     Ok(resp)
@@ -1333,6 +1361,7 @@ fn build_mod(window: Window, state: State<LogState>, request: BuildModRequest) -
     emit_log(&window, &state, "info", format!("build_mod from PO: {}", request.po_path));
     emit_progress(&window, &state, "build", "start", Some("Building mod…".to_string()), Some(0));
     let out = PathBuf::from(&request.out_mod);
+    let fuzzy: rimloc_services::FuzzyPolicy = request.fuzzy.as_deref().unwrap_or("skip").parse()?;
     let mut files_count = 0usize;
     let mut total_keys = 0usize;
     if let Some(from_root) = request.from_root.as_deref() {
@@ -1359,6 +1388,7 @@ fn build_mod(window: Window, state: State<LogState>, request: BuildModRequest) -
             &request.package_id,
             &request.rw_version,
             request.dedupe,
+            fuzzy,
         )?;
         files_count = plan.files.len();
         total_keys = plan.total_keys;
@@ -1372,6 +1402,7 @@ fn build_mod(window: Window, state: State<LogState>, request: BuildModRequest) -
             &request.package_id,
             &request.rw_version,
             request.dedupe,
+            fuzzy,
             |cur, total, path| {
                 files_count = total;
                 emit_progress(&window, &state, "build", "file", Some(path.display().to_string()), Some(((cur as f64 / total as f64) * 100.0).round() as u32));
@@ -1493,70 +1524,32 @@ fn extract_placeholders_cli_like(s: &str) -> BTreeSet<String> {
 
 #[tauri::command]
 fn validate_po_gui(_window: Window, _state: State<LogState>, req: ValidatePoRequest) -> Result<ValidatePoResponse, ApiError> {
-    use std::io::BufRead;
-    let file = std::fs::File::open(&req.po_path).map_err(ApiError::from)?;
-    let rdr = std::io::BufReader::new(file);
-    let mut ctx: Option<String> = None;
-    let mut id = String::new();
-    let mut strv = String::new();
-    let mut reference: Option<String> = None;
-    enum Mode { None, InId, InStr }
-    let mut mode = Mode::None;
-    let mut entries: Vec<(Option<String>, String, String, Option<String>)> = Vec::new();
-    let mut push = |ctx: &mut Option<String>, id: &mut String, strv: &mut String, reference: &mut Option<String>, entries: &mut Vec<(Option<String>, String, String, Option<String>)>| {
-        if !id.is_empty() || !strv.is_empty() || ctx.is_some() || reference.is_some() {
-            entries.push((ctx.clone(), std::mem::take(id), std::mem::take(strv), reference.clone()));
-            *ctx = None; *reference = None;
-        }
-    };
-    for line in rdr.lines() {
-        let t = line.map_err(ApiError::from)?.trim().to_string();
-        if t.is_empty() { push(&mut ctx, &mut id, &mut strv, &mut reference, &mut entries); mode = Mode::None; continue; }
-        if let Some(rest) = t.strip_prefix("#:") { reference = Some(rest.trim().to_string()); continue; }
-        if let Some(rest) = t.strip_prefix("msgctxt ") { push(&mut ctx, &mut id, &mut strv, &mut reference, &mut entries); ctx = Some(unquote(&rest)); mode = Mode::None; continue; }
-        if let Some(rest) = t.strip_prefix("msgid ") { push(&mut ctx, &mut id, &mut strv, &mut reference, &mut entries); id = unquote(&rest); mode = Mode::InId; continue; }
-        if let Some(rest) = t.strip_prefix("msgstr ") { strv = unquote(&rest); mode = Mode::InStr; continue; }
-        if matches!(mode, Mode::InId | Mode::InStr) && t.starts_with('"') {
-            let chunk = unquote(&t);
-            match mode { Mode::InId => id.push_str(&chunk), Mode::InStr => strv.push_str(&chunk), Mode::None => {} }
-            continue;
-        }
-    }
-    push(&mut ctx, &mut id, &mut strv, &mut reference, &mut entries);
-
+    let po = rimloc_po::PoFile::read(std::path::Path::new(&req.po_path)).map_err(ApiError::from)?;
     let mut mismatches: Vec<ValidatePoMismatch> = Vec::new();
     let mut checked = 0usize;
-    for (ctx, msgid, msgstr, reference) in entries {
-        if msgid.is_empty() { continue; }
-        if msgstr.trim().is_empty() { continue; }
-        checked += 1;
-        let src_ph = extract_placeholders_cli_like(&msgid);
-        let dst_ph = extract_placeholders_cli_like(&msgstr);
-        if src_ph != dst_ph {
-            mismatches.push(ValidatePoMismatch {
-                context: ctx,
-                reference,
-                msgid,
-                msgstr,
-                expected_placeholders: src_ph.into_iter().collect(),
-                got_placeholders: dst_ph.into_iter().collect(),
-            });
+    for m in po.active() {
+        if m.msgid.is_empty() { continue; }
+        let forms: Vec<&String> = if m.is_plural() { m.msgstr_plural.iter().collect() } else { vec![&m.msgstr] };
+        let src_ph = extract_placeholders_cli_like(&m.msgid);
+        for msgstr in forms {
+            if msgstr.trim().is_empty() { continue; }
+            checked += 1;
+            let dst_ph = extract_placeholders_cli_like(msgstr);
+            if src_ph != dst_ph {
+                mismatches.push(ValidatePoMismatch {
+                    context: m.msgctxt.clone(),
+                    reference: m.references.first().cloned(),
+                    msgid: m.msgid.clone(),
+                    msgstr: msgstr.clone(),
+                    expected_placeholders: src_ph.iter().cloned().collect(),
+                    got_placeholders: dst_ph.into_iter().collect(),
+                });
+            }
         }
     }
     Ok(ValidatePoResponse { checked, mismatches })
 }
 
-fn unquote(s: &str) -> String {
-    let raw = s.trim().trim_start_matches('"').trim_end_matches('"');
-    let mut out = String::new();
-    let mut it = raw.chars().peekable();
-    while let Some(c) = it.next() {
-        if c == '\\' { if let Some(n) = it.next() { out.push(match n { 'n' => '\n', 't' => '\t', 'r' => '\r', '\\' => '\\', '"' => '"', x => x }); } else { out.push('\\'); } }
-        else { out.push(c); }
-    }
-    out
-}
-
 // --- Learn Patches (scan Patches/ texts) ---
 #[derive(Debug, Deserialize)]
 struct LearnPatchesRequest { root: String, #[serde(default)] min_len: Option<usize>, #[serde(default)] out_json: Option<String>, #[serde(default)] game_version: Option<String> }
@@ -35,7 +35,12 @@ mod tests {
     assert!(plan.processed >= 0);
     let dir = tempfile::tempdir().unwrap();
     let po = dir.path().join("mod.po");
-    rimloc_services::export_po_with_tm(root.as_path(), po.as_path(), Some("ru"), Some("en"), None, None).unwrap();
+    let opts = rimloc_services::ExportPoOptions {
+        lang: Some("ru"),
+        source_lang: Some("en"),
+        ..Default::default()
+    };
+    rimloc_services::export_po_with_tm(root.as_path(), po.as_path(), &opts).unwrap();
     let plan2 = crate::api_import_po_dry(po.display().to_string(), root.display().to_string(), Some("ru".into()), None, false, false, None, true).unwrap();
     assert!(plan2.total_keys >= 0);
   }